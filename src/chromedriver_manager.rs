@@ -1,13 +1,100 @@
 use anyhow::{Result, Context};
+use crate::scraper::{wait_until, WAIT_POLL_INTERVAL};
 use std::fs;
 use std::path::PathBuf;
 use std::process::{Child, Command, Stdio};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
+/// Called while ChromeDriver downloads with the bytes received so far and
+/// the total size, if the server sent one
+pub type DownloadProgress<'a> = &'a (dyn Fn(u64, Option<u64>) + Send + Sync);
+
+/// Status line for a running download, e.g. "Downloading ChromeDriver 45%…"
+pub fn download_status(downloaded: u64, total: Option<u64>) -> String {
+    match total.filter(|total| *total > 0) {
+        Some(total) => format!("Downloading ChromeDriver {}%…", (downloaded.min(total) * 100) / total),
+        None => format!("Downloading ChromeDriver {:.1} MB…", downloaded as f64 / 1_000_000.0),
+    }
+}
+
+/// Commands printing the installed Chrome version, tried in order
+#[cfg(windows)]
+const CHROME_VERSION_COMMANDS: &[(&str, &[&str])] = &[
+    ("reg", &["query", r"HKCU\Software\Google\Chrome\BLBeacon", "/v", "version"]),
+    ("reg", &["query", r"HKLM\Software\Google\Chrome\BLBeacon", "/v", "version"]),
+];
+#[cfg(target_os = "macos")]
+const CHROME_VERSION_COMMANDS: &[(&str, &[&str])] = &[
+    ("/Applications/Google Chrome.app/Contents/MacOS/Google Chrome", &["--version"]),
+];
+#[cfg(all(unix, not(target_os = "macos")))]
+const CHROME_VERSION_COMMANDS: &[(&str, &[&str])] = &[
+    ("google-chrome", &["--version"]),
+    ("google-chrome-stable", &["--version"]),
+    ("chromium", &["--version"]),
+    ("chromium-browser", &["--version"]),
+];
+
+/// Platform part of Chrome's user agent
+#[cfg(windows)]
+const USER_AGENT_PLATFORM: &str = "Windows NT 10.0; Win64; x64";
+#[cfg(target_os = "macos")]
+const USER_AGENT_PLATFORM: &str = "Macintosh; Intel Mac OS X 10_15_7";
+#[cfg(all(unix, not(target_os = "macos")))]
+const USER_AGENT_PLATFORM: &str = "X11; Linux x86_64";
+
+/// First dotted version number in `output`, e.g. "131.0.6778.86" in
+/// "Google Chrome 131.0.6778.86" or the `reg query` output
+fn parse_chrome_version(output: &str) -> Option<String> {
+    output
+        .split_whitespace()
+        .find(|word| word.split('.').count() >= 3 && word.split('.').all(|part| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit())))
+        .map(str::to_string)
+}
+
+/// User agent of a regular, not headless, Chrome of `chrome_version`. Like
+/// Chrome itself it only gives the major version.
+pub fn default_user_agent(chrome_version: &str) -> String {
+    let major = chrome_version.split('.').next().unwrap_or(chrome_version);
+    format!(
+        "Mozilla/5.0 ({}) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/{}.0.0.0 Safari/537.36",
+        USER_AGENT_PLATFORM, major
+    )
+}
+
+/// The ChromeDriver at `url`, e.g. "http://localhost:9516", answers its status request
+pub async fn driver_ready(url: &str) -> bool {
+    let status_url = format!("{}/status", url.trim_end_matches('/'));
+    reqwest::Client::new()
+        .get(&status_url)
+        .send()
+        .await
+        .is_ok_and(|response| response.status().is_success())
+}
+
+/// HTTP client builder that goes through `proxy` (host:port, optionally
+/// user:pass@host:port) when one is set
+pub fn proxied_client_builder(proxy: Option<&str>) -> Result<reqwest::ClientBuilder> {
+    let mut builder = reqwest::Client::builder();
+
+    if let Some(proxy) = proxy.filter(|p| !p.trim().is_empty()) {
+        let proxy_url = if proxy.contains("://") {
+            proxy.to_string()
+        } else {
+            format!("http://{}", proxy)
+        };
+        builder = builder.proxy(reqwest::Proxy::all(&proxy_url)
+            .with_context(|| format!("Invalid proxy address: {}", proxy))?);
+    }
+
+    Ok(builder)
+}
+
 pub struct ChromeDriverManager {
     driver_path: PathBuf,
     process: Arc<Mutex<Option<Child>>>,
+    proxy: Arc<Mutex<Option<String>>>,
 }
 
 impl ChromeDriverManager {
@@ -23,13 +110,36 @@ impl ChromeDriverManager {
         Self {
             driver_path,
             process: Arc::new(Mutex::new(None)),
+            proxy: Arc::new(Mutex::new(None)),
         }
     }
 
-    pub async fn ensure_driver_available(&self) -> Result<()> {
+    /// Version of the installed Chrome, None when it can't be found
+    pub fn detect_chrome_version() -> Option<String> {
+        CHROME_VERSION_COMMANDS.iter().find_map(|(program, args)| {
+            let output = Command::new(program)
+                .args(*args)
+                .stdin(Stdio::null())
+                .stderr(Stdio::null())
+                .output()
+                .ok()?;
+            parse_chrome_version(&String::from_utf8_lossy(&output.stdout))
+        })
+    }
+
+    /// Set the HTTP proxy used when downloading ChromeDriver (host:port, optionally user:pass@host:port)
+    pub async fn set_proxy(&self, proxy: Option<String>) {
+        *self.proxy.lock().await = proxy.filter(|p| !p.trim().is_empty());
+    }
+
+    async fn http_client(&self) -> Result<reqwest::Client> {
+        Ok(proxied_client_builder(self.proxy.lock().await.as_deref())?.build()?)
+    }
+
+    pub async fn ensure_driver_available(&self, on_download: DownloadProgress<'_>) -> Result<()> {
         if !self.driver_path.exists() {
             println!("ChromeDriver not found at {:?}, downloading...", self.driver_path);
-            self.download_chromedriver().await
+            self.download_chromedriver(on_download).await
                 .context("Failed to download ChromeDriver. Please check your internet connection.")?;
         } else {
             println!("ChromeDriver found at {:?}", self.driver_path);
@@ -37,15 +147,21 @@ impl ChromeDriverManager {
         Ok(())
     }
 
-    pub async fn start_driver(&self, port: u16) -> Result<()> {
+    /// Start ChromeDriver on `port` unless it's already running, and wait up
+    /// to `timeout` until it answers
+    pub async fn start_driver(&self, port: u16, timeout: tokio::time::Duration, on_download: DownloadProgress<'_>) -> Result<()> {
         // Ensure driver is available
-        self.ensure_driver_available().await?;
+        self.ensure_driver_available(on_download).await?;
 
-        // Check if already running
+        // Check if already running, a driver that has exited is started again
         let mut process_guard = self.process.lock().await;
         if process_guard.is_some() {
-            println!("ChromeDriver is already running on port {}", port);
-            return Ok(());
+            if reap_exited(&mut process_guard) {
+                println!("ChromeDriver has exited, restarting it");
+            } else {
+                println!("ChromeDriver is already running on port {}", port);
+                return Ok(());
+            }
         }
 
         // Start ChromeDriver
@@ -62,9 +178,12 @@ impl ChromeDriverManager {
 
         // Wait for ChromeDriver to be ready to accept connections
         println!("Waiting for ChromeDriver to become ready...");
-        let ready = self.wait_for_readiness(port, 15).await?;
-        if !ready {
-            return Err(anyhow::anyhow!("ChromeDriver failed to become ready within 15 seconds. This might indicate a Chrome installation problem."));
+        let url = format!("http://localhost:{}", port);
+        if !wait_until(timeout, WAIT_POLL_INTERVAL, || driver_ready(&url)).await {
+            return Err(anyhow::anyhow!(
+                "ChromeDriver failed to become ready within {} seconds. This might indicate a Chrome installation problem.",
+                timeout.as_secs()
+            ));
         }
 
         println!("✅ ChromeDriver successfully started on port {}", port);
@@ -81,16 +200,25 @@ impl ChromeDriverManager {
         Ok(())
     }
 
-    pub async fn is_running(&self) -> bool {
-        let process_guard = self.process.lock().await;
-        if let Some(_child) = process_guard.as_ref() {
-            // Check if process is still alive (simple check)
-            return true; // Simplified - in real implementation we'd check process status
+    /// Best effort `stop_driver` for the UI thread, which can't await it. Skipped
+    /// while the process is locked by a driver being started.
+    pub fn kill_driver(&self) {
+        if let Ok(mut process_guard) = self.process.try_lock() {
+            if let Some(mut child) = process_guard.take() {
+                let _ = child.kill();
+                let _ = child.wait();
+            }
         }
-        false
     }
 
-    async fn download_chromedriver(&self) -> Result<()> {
+    /// Whether the started ChromeDriver process is still alive
+    pub async fn is_running(&self) -> bool {
+        let mut process_guard = self.process.lock().await;
+        reap_exited(&mut process_guard);
+        process_guard.is_some()
+    }
+
+    async fn download_chromedriver(&self, on_download: DownloadProgress<'_>) -> Result<()> {
         // Get latest ChromeDriver version
         let version = self.get_latest_version().await?;
         println!("Downloading ChromeDriver version {}", version);
@@ -101,9 +229,15 @@ impl ChromeDriverManager {
             version
         );
 
-        // Download the file
-        let response = reqwest::get(&download_url).await?;
-        let zip_data = response.bytes().await?;
+        // Download the file in chunks to report the progress
+        let mut response = self.http_client().await?.get(&download_url).send().await?.error_for_status()?;
+        let total = response.content_length();
+        let mut zip_data = Vec::with_capacity(total.unwrap_or_default() as usize);
+        on_download(0, total);
+        while let Some(chunk) = response.chunk().await? {
+            zip_data.extend_from_slice(&chunk);
+            on_download(zip_data.len() as u64, total);
+        }
 
         // Save to temp file
         let temp_dir = std::env::temp_dir();
@@ -133,33 +267,12 @@ impl ChromeDriverManager {
         Ok(())
     }
 
-    async fn wait_for_readiness(&self, port: u16, timeout_secs: u64) -> Result<bool> {
-        let client = reqwest::Client::new();
-        let url = format!("http://localhost:{}/status", port);
-        let timeout = tokio::time::Duration::from_secs(timeout_secs);
-        let start = tokio::time::Instant::now();
-
-        while start.elapsed() < timeout {
-            match client.get(&url).send().await {
-                Ok(response) => {
-                    if response.status().is_success() {
-                        return Ok(true);
-                    }
-                }
-                Err(_) => {
-                    // ChromeDriver not ready yet, continue waiting
-                }
-            }
-            tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
-        }
-
-        Ok(false)
-    }
-
     async fn get_latest_version(&self) -> Result<String> {
         // For Chrome 140+, we need to use the new ChromeDriver endpoint
         // Chrome versions 115+ use a different versioning system
-        let response = reqwest::get("https://googlechromelabs.github.io/chrome-for-testing/LATEST_RELEASE_STABLE")
+        let response = self.http_client().await?
+            .get("https://googlechromelabs.github.io/chrome-for-testing/LATEST_RELEASE_STABLE")
+            .send()
             .await?;
         let version = response.text().await?.trim().to_string();
         println!("Latest ChromeDriver version: {}", version);
@@ -167,14 +280,90 @@ impl ChromeDriverManager {
     }
 }
 
+/// Drop the handle of a process that has exited (or can't be queried), true
+/// if one was dropped
+fn reap_exited(process: &mut Option<Child>) -> bool {
+    let exited = match process.as_mut().map(|child| child.try_wait()) {
+        Some(Ok(status)) => status.is_some(),
+        Some(Err(_)) => true,
+        None => false,
+    };
+    if exited {
+        *process = None;
+    }
+    exited
+}
+
+#[cfg(test)]
+impl ChromeDriverManager {
+    /// A manager whose running "ChromeDriver" is the long-running test stand-in
+    pub(crate) async fn with_stand_in() -> Self {
+        let manager = Self::new();
+        *manager.process.lock().await = Some(tests::stand_in(&["--ignored", "--exact", "chromedriver_manager::tests::driver_stand_in"]));
+        manager
+    }
+}
+
 impl Drop for ChromeDriverManager {
     fn drop(&mut self) {
-        // Best effort cleanup
-        if let Ok(mut process_guard) = self.process.try_lock() {
-            if let Some(mut child) = process_guard.take() {
-                let _ = child.kill();
-                let _ = child.wait();
-            }
-        }
+        self.kill_driver();
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_download_status() {
+        assert_eq!(download_status(0, Some(10_000_000)), "Downloading ChromeDriver 0%…");
+        assert_eq!(download_status(4_500_000, Some(10_000_000)), "Downloading ChromeDriver 45%…");
+        assert_eq!(download_status(12_000_000, Some(10_000_000)), "Downloading ChromeDriver 100%…");
+        assert_eq!(download_status(3_200_000, None), "Downloading ChromeDriver 3.2 MB…");
+        assert_eq!(download_status(0, Some(0)), "Downloading ChromeDriver 0.0 MB…");
+    }
+
+    #[test]
+    fn test_chrome_version_and_user_agent() {
+        assert_eq!(parse_chrome_version("Google Chrome 131.0.6778.85 \n").as_deref(), Some("131.0.6778.85"));
+        let reg = "\r\nHKEY_CURRENT_USER\\Software\\Google\\Chrome\\BLBeacon\r\n    version    REG_SZ    131.0.6778.86\r\n";
+        assert_eq!(parse_chrome_version(reg).as_deref(), Some("131.0.6778.86"));
+        assert_eq!(parse_chrome_version("Chromium 1.x"), None);
+        assert_eq!(parse_chrome_version(""), None);
+
+        let user_agent = default_user_agent("131.0.6778.86");
+        assert!(user_agent.starts_with("Mozilla/5.0 ("));
+        assert!(user_agent.ends_with("Chrome/131.0.0.0 Safari/537.36"));
+        assert!(!user_agent.contains("Headless"));
+    }
+
+    /// Stand-in for ChromeDriver: the test binary itself, with `args`
+    pub(super) fn stand_in(args: &[&str]) -> Child {
+        Command::new(std::env::current_exe().unwrap())
+            .args(args)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap()
+    }
+
+    /// Long-running process for `stand_in`, killed by the test that started it
+    #[test]
+    #[ignore]
+    fn driver_stand_in() {
+        std::thread::sleep(std::time::Duration::from_secs(60));
+    }
+
+    #[tokio::test]
+    async fn test_exited_driver_is_not_running() {
+        let manager = ChromeDriverManager::new();
+
+        // Listing the tests exits right away, like a crashed driver
+        let mut child = stand_in(&["--list"]);
+        child.wait().unwrap();
+        *manager.process.lock().await = Some(child);
+
+        assert!(!manager.is_running().await);
+        assert!(manager.process.lock().await.is_none());
+    }
+}
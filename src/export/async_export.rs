@@ -0,0 +1,63 @@
+use std::path::PathBuf;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::task::JoinHandle;
+use crate::models::PlcTable;
+use crate::ui::app::{LogLevel, ProgressUpdate};
+use super::{ExportFilter, Exporter};
+
+/// Run an export of the entries passing `filter` on the blocking thread pool,
+/// so large formatted workbooks don't freeze the UI. The result is logged and
+/// reported with `ProgressUpdate::ExportFinished`.
+pub fn export_async(
+    exporter: impl Exporter + Send + 'static,
+    table: PlcTable,
+    filter: ExportFilter,
+    path: String,
+    tx: UnboundedSender<ProgressUpdate>,
+) -> JoinHandle<()> {
+    tokio::task::spawn_blocking(move || {
+        let ok = match exporter.export_filtered(&table, &filter, &path) {
+            Ok(_) => {
+                let _ = tx.send(ProgressUpdate::Log(format!("Export complete: {}", path), LogLevel::Success));
+                true
+            }
+            Err(e) => {
+                let _ = tx.send(ProgressUpdate::Log(format!("Export to {} failed: {}", path, e), LogLevel::Error));
+                false
+            }
+        };
+        let _ = tx.send(ProgressUpdate::ExportFinished { path: PathBuf::from(path), ok });
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::csv::CsvExporter;
+    use crate::models::PlcEntry;
+
+    #[tokio::test]
+    async fn test_export_async_reports_result() {
+        let mut table = PlcTable::new("Async".to_string());
+        table.add_entry(PlcEntry::new("I0.0".to_string(), "Start".to_string(), "1".to_string()));
+
+        let path = std::env::temp_dir().join(format!("eview_async_export_{}.csv", std::process::id()));
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        export_async(CsvExporter::new(), table, ExportFilter::default(), path.to_string_lossy().to_string(), tx).await.unwrap();
+        let written = std::fs::read_to_string(&path);
+        let _ = std::fs::remove_file(&path);
+
+        assert!(written.unwrap().contains("Start"));
+        assert!(matches!(rx.try_recv(), Ok(ProgressUpdate::Log(_, LogLevel::Success))));
+        assert!(matches!(rx.try_recv(), Ok(ProgressUpdate::ExportFinished { ok: true, .. })));
+
+        // A missing folder fails without panicking
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let missing = std::env::temp_dir().join("eview_missing_dir").join("table.csv");
+        export_async(CsvExporter::new(), PlcTable::new(String::new()), ExportFilter::default(), missing.to_string_lossy().to_string(), tx)
+            .await
+            .unwrap();
+        assert!(matches!(rx.try_recv(), Ok(ProgressUpdate::Log(_, LogLevel::Error))));
+        assert!(matches!(rx.try_recv(), Ok(ProgressUpdate::ExportFinished { ok: false, .. })));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,179 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+use crate::models::{ColumnId, PlcEntry};
+use super::csv::CsvDelimiter;
+use super::report::markdown_cell;
+
+/// Text formats for copying rows to the clipboard
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum ClipboardFormat {
+    #[default]
+    Tsv,      // Pastes into Excel cells
+    Csv,
+    Markdown, // Table for wiki pages and tickets
+    Json,     // Array of objects
+}
+
+impl ClipboardFormat {
+    pub const ALL: [ClipboardFormat; 4] = [Self::Tsv, Self::Csv, Self::Markdown, Self::Json];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Tsv => "TSV",
+            Self::Csv => "CSV",
+            Self::Markdown => "Markdown",
+            Self::Json => "JSON",
+        }
+    }
+}
+
+/// `entries` as text in `format`, with a header row (or keys) for `columns`.
+/// CSV fields are separated by `delimiter`, the one of the CSV export.
+pub fn copy_text(entries: &[PlcEntry], columns: &[ColumnId], format: ClipboardFormat, delimiter: CsvDelimiter) -> String {
+    match format {
+        ClipboardFormat::Tsv => to_tsv(entries, columns),
+        ClipboardFormat::Csv => to_csv(entries, columns, delimiter.as_char()),
+        ClipboardFormat::Markdown => to_markdown(entries, columns),
+        ClipboardFormat::Json => to_json(entries, columns),
+    }
+}
+
+/// Tabs and line breaks in a value would start a new cell or row
+fn to_tsv(entries: &[PlcEntry], columns: &[ColumnId]) -> String {
+    let mut text = String::new();
+    let titles: Vec<&str> = columns.iter().map(|c| c.title()).collect();
+    let _ = writeln!(text, "{}", titles.join("\t"));
+
+    for entry in entries {
+        let cells: Vec<String> = columns
+            .iter()
+            .map(|c| c.value(entry).replace(['\t', '\r', '\n'], " "))
+            .collect();
+        let _ = writeln!(text, "{}", cells.join("\t"));
+    }
+    text
+}
+
+fn to_csv(entries: &[PlcEntry], columns: &[ColumnId], delimiter: char) -> String {
+    let mut text = String::new();
+    let separator = delimiter.to_string();
+    let titles: Vec<String> = columns.iter().map(|c| csv_field(c.title(), delimiter)).collect();
+    let _ = writeln!(text, "{}", titles.join(&separator));
+
+    for entry in entries {
+        let cells: Vec<String> = columns.iter().map(|c| csv_field(&c.value(entry), delimiter)).collect();
+        let _ = writeln!(text, "{}", cells.join(&separator));
+    }
+    text
+}
+
+/// Quote fields holding the delimiter, a quote or a line break, doubling the quotes
+fn csv_field(value: &str, delimiter: char) -> String {
+    if value.contains([delimiter, '"', '\r', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn to_markdown(entries: &[PlcEntry], columns: &[ColumnId]) -> String {
+    let mut text = String::new();
+    let titles: Vec<&str> = columns.iter().map(|c| c.title()).collect();
+    let _ = writeln!(text, "| {} |", titles.join(" | "));
+    let _ = writeln!(text, "|{}", "---|".repeat(columns.len()));
+
+    for entry in entries {
+        let cells: Vec<String> = columns.iter().map(|c| markdown_cell(&c.value(entry))).collect();
+        let _ = writeln!(text, "| {} |", cells.join(" | "));
+    }
+    text
+}
+
+/// One object per line, keys in column order, tags as an array
+fn to_json(entries: &[PlcEntry], columns: &[ColumnId]) -> String {
+    let objects: Vec<String> = entries
+        .iter()
+        .map(|entry| {
+            let fields: Vec<String> = columns
+                .iter()
+                .map(|column| {
+                    let value = match column {
+                        ColumnId::Tags => serde_json::Value::from(entry.tags.clone()),
+                        _ => serde_json::Value::from(column.value(entry)),
+                    };
+                    format!("\"{}\": {}", column.key(), value)
+                })
+                .collect();
+            format!("  {{{}}}", fields.join(", "))
+        })
+        .collect();
+
+    if objects.is_empty() {
+        "[]\n".to_string()
+    } else {
+        format!("[\n{}\n]\n", objects.join(",\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries() -> Vec<PlcEntry> {
+        let mut start = PlcEntry::new("I0.0".to_string(), "Start, green".to_string(), "3".to_string());
+        start.comment = "Says \"go\"\tnow | here".to_string();
+        start.add_tag("Critical");
+        let motor = PlcEntry::new("Q0.0".to_string(), "Motor".to_string(), "4".to_string());
+        vec![start, motor]
+    }
+
+    const COLUMNS: [ColumnId; 4] = [
+        ColumnId::Name,
+        ColumnId::Address,
+        ColumnId::Comment,
+        ColumnId::Tags,
+    ];
+
+    #[test]
+    fn test_tsv_and_csv() {
+        assert_eq!(
+            copy_text(&entries(), &COLUMNS, ClipboardFormat::Tsv, CsvDelimiter::Comma),
+            "Symbol Name\tAddress\tComment\tTags\n\
+             Start, green\tI0.0\tSays \"go\" now | here\tCritical\n\
+             Motor\tQ0.0\t\t\n"
+        );
+        assert_eq!(
+            copy_text(&entries(), &COLUMNS, ClipboardFormat::Csv, CsvDelimiter::Comma),
+            "Symbol Name,Address,Comment,Tags\n\
+             \"Start, green\",I0.0,\"Says \"\"go\"\"\tnow | here\",Critical\n\
+             Motor,Q0.0,,\n"
+        );
+
+        // A comma needs no quotes between semicolons
+        assert_eq!(
+            copy_text(&entries(), &COLUMNS[..2], ClipboardFormat::Csv, CsvDelimiter::Semicolon),
+            "Symbol Name;Address\n\
+             Start, green;I0.0\n\
+             Motor;Q0.0\n"
+        );
+    }
+
+    #[test]
+    fn test_markdown_and_json() {
+        assert_eq!(
+            copy_text(&entries(), &COLUMNS[..3], ClipboardFormat::Markdown, CsvDelimiter::default()),
+            "| Symbol Name | Address | Comment |\n\
+             |---|---|---|\n\
+             | Start, green | I0.0 | Says \"go\"\tnow \\| here |\n\
+             | Motor | Q0.0 |  |\n"
+        );
+
+        let json = copy_text(&entries(), &COLUMNS, ClipboardFormat::Json, CsvDelimiter::default());
+        assert!(json.starts_with("[\n  {\"symbol_name\": \"Start, green\", \"address\": \"I0.0\""));
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed[0]["comment"], "Says \"go\"\tnow | here");
+        assert_eq!(parsed[0]["tags"][0], "Critical");
+        assert_eq!(parsed[1]["tags"].as_array().unwrap().len(), 0);
+        assert_eq!(copy_text(&[], &COLUMNS, ClipboardFormat::Json, CsvDelimiter::default()), "[]\n");
+    }
+}
\ No newline at end of file
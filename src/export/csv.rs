@@ -1,12 +1,40 @@
 use anyhow::Result;
-use csv::Writer;
+use csv::{ReaderBuilder, WriterBuilder};
+use serde::{Deserialize, Serialize};
 use std::fs::File;
-use crate::models::PlcTable;
-use super::Exporter;
+use crate::models::{ColumnId, ColumnSet, PlcTable};
+use super::{file_cells, file_headers, Exporter, ImportColumns, ImportResult, Importer};
+
+/// Field separator of the CSV export and of rows copied as CSV
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum CsvDelimiter {
+    #[default]
+    Semicolon, // German Excel opens these without the import wizard
+    Comma,
+}
+
+impl CsvDelimiter {
+    pub const ALL: [CsvDelimiter; 2] = [Self::Semicolon, Self::Comma];
+
+    pub fn as_char(&self) -> char {
+        match self {
+            Self::Semicolon => ';',
+            Self::Comma => ',',
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Semicolon => "Semicolon (;)",
+            Self::Comma => "Comma (,)",
+        }
+    }
+}
 
 pub struct CsvExporter {
     delimiter: u8,
     with_bom: bool,
+    columns: Vec<ColumnId>,
 }
 
 impl Default for CsvExporter {
@@ -14,6 +42,7 @@ impl Default for CsvExporter {
         Self {
             delimiter: b';',  // Semicolon for German Excel compatibility
             with_bom: true,   // UTF-8 BOM for Excel
+            columns: super::FILE_COLUMNS.to_vec(),
         }
     }
 }
@@ -23,8 +52,8 @@ impl CsvExporter {
         Self::default()
     }
 
-    pub fn with_delimiter(mut self, delimiter: u8) -> Self {
-        self.delimiter = delimiter;
+    pub fn with_delimiter(mut self, delimiter: CsvDelimiter) -> Self {
+        self.delimiter = delimiter.as_char() as u8;
         self
     }
 
@@ -32,6 +61,12 @@ impl CsvExporter {
         self.with_bom = with_bom;
         self
     }
+
+    /// Write the shown columns of `columns` in their order
+    pub fn with_columns(mut self, columns: &ColumnSet) -> Self {
+        self.columns = columns.visible();
+        self
+    }
 }
 
 impl Exporter for CsvExporter {
@@ -44,17 +79,13 @@ impl Exporter for CsvExporter {
             file.write_all(&[0xEF, 0xBB, 0xBF])?;
         }
 
-        let mut writer = Writer::from_writer(file);
-        writer.write_record(&["Address", "Symbol Name", "Type", "Comment", "Page"])?;
+        let mut writer = WriterBuilder::new().delimiter(self.delimiter).from_writer(file);
+        let headers: Vec<&str> = self.columns.iter().flat_map(|column| file_headers(*column)).copied().collect();
+        writer.write_record(&headers)?;
 
         for entry in &table.entries {
-            writer.write_record(&[
-                &entry.address,
-                &entry.symbol_name,
-                &entry.data_type.to_string(),
-                &entry.comment,
-                &entry.page,
-            ])?;
+            let record: Vec<String> = self.columns.iter().flat_map(|column| file_cells(*column, entry)).collect();
+            writer.write_record(&record)?;
         }
 
         writer.flush()?;
@@ -62,6 +93,70 @@ impl Exporter for CsvExporter {
     }
 }
 
+/// Reads CSV files written by `CsvExporter` or edited by hand
+pub struct CsvImporter {
+    delimiter: u8,
+}
+
+impl Default for CsvImporter {
+    fn default() -> Self {
+        Self { delimiter: b';' } // Same default as the exporter
+    }
+}
+
+impl CsvImporter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The configured delimiter, or a comma if the header row only has commas
+    fn detect_delimiter(&self, content: &str) -> u8 {
+        let header = content.lines().next().unwrap_or_default();
+        if !header.contains(self.delimiter as char) && header.contains(',') {
+            b','
+        } else {
+            self.delimiter
+        }
+    }
+
+    fn parse(&self, content: &str, project_name: &str) -> Result<ImportResult> {
+        // A UTF-8 BOM is written by the exporter for Excel
+        let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+
+        let mut reader = ReaderBuilder::new()
+            .delimiter(self.detect_delimiter(content))
+            .flexible(true)
+            .from_reader(content.as_bytes());
+
+        let columns = ImportColumns::from_headers(&reader.headers()?.iter().collect::<Vec<_>>())?;
+        let mut skipped = Vec::new();
+        let mut rows = Vec::new();
+
+        for (offset, record) in reader.records().enumerate() {
+            match record {
+                Ok(record) => rows.push(record.iter().map(str::to_string).collect::<Vec<_>>()),
+                Err(e) => {
+                    skipped.push(super::SkippedRow { row: offset + 2, reason: e.to_string() });
+                    rows.push(Vec::new()); // Keeps the row numbers of later rows
+                }
+            }
+        }
+
+        let mut result = columns.parse_rows(rows, 2, project_name);
+        result.skipped.extend(skipped);
+        result.skipped.sort_by_key(|row| row.row);
+        Ok(result)
+    }
+}
+
+impl Importer for CsvImporter {
+    fn import(&self, path: &str) -> Result<ImportResult> {
+        let bytes = std::fs::read(path)?;
+        let content = String::from_utf8_lossy(&bytes);
+        self.parse(&content, &super::project_name_from_path(path))
+    }
+}
+
 pub fn export_multiple_csv(table: &PlcTable, prefix: &str) -> Result<()> {
     // Export all entries
     let all_exporter = CsvExporter::new();
@@ -76,6 +171,9 @@ pub fn export_multiple_csv(table: &PlcTable, prefix: &str) -> Result<()> {
             .collect(),
         project_name: table.project_name.clone(),
         extraction_date: table.extraction_date,
+        dirty: false,
+        revision: crate::models::next_revision(),
+        deleted: Vec::new(),
     };
 
     if !inputs_only.entries.is_empty() {
@@ -91,6 +189,9 @@ pub fn export_multiple_csv(table: &PlcTable, prefix: &str) -> Result<()> {
             .collect(),
         project_name: table.project_name.clone(),
         extraction_date: table.extraction_date,
+        dirty: false,
+        revision: crate::models::next_revision(),
+        deleted: Vec::new(),
     };
 
     if !outputs_only.entries.is_empty() {
@@ -98,4 +199,71 @@ pub fn export_multiple_csv(table: &PlcTable, prefix: &str) -> Result<()> {
     }
 
     Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::PlcEntry;
+
+    #[test]
+    fn test_export_import_round_trip() {
+        let mut table = PlcTable::new("Round trip".to_string());
+        let mut entry = PlcEntry::new("I0.0".to_string(), "Start; main".to_string(), "3".to_string());
+        entry.comment = "Grüner Taster".to_string();
+        entry.add_tag("Critical");
+        entry.add_tag("Commissioning done");
+        entry.module = Some("ET 200SP IM155-6 PN".to_string());
+        entry.slot = Some(3);
+        table.add_entry(entry);
+        table.add_entry(PlcEntry::new("QW2".to_string(), "Valve".to_string(), "4".to_string()));
+
+        let path = std::env::temp_dir().join(format!("eview_csv_round_trip_{}.csv", std::process::id()));
+        let path = path.to_string_lossy().to_string();
+        CsvExporter::new().export(&table, &path).unwrap();
+        let result = CsvImporter::new().import(&path);
+        let _ = std::fs::remove_file(&path);
+
+        let result = result.unwrap();
+        assert!(result.skipped.is_empty());
+        assert_eq!(result.table.entries.len(), 2);
+        assert_eq!(result.table.entries[0].symbol_name, "Start; main");
+        assert_eq!(result.table.entries[0].comment, "Grüner Taster");
+        assert_eq!(result.table.entries[0].tags, vec!["Critical", "Commissioning done"]);
+        assert_eq!(result.table.entries[0].module_text(), "ET 200SP IM155-6 PN, slot 3");
+        assert_eq!(result.table.entries[1].address, "QW2");
+        assert_eq!((&result.table.entries[1].module, result.table.entries[1].slot), (&None, None));
+        assert_eq!(result.table.project_name, format!("eview_csv_round_trip_{}", std::process::id()));
+    }
+
+    #[test]
+    fn test_export_follows_column_set() {
+        let mut table = PlcTable::new("Columns".to_string());
+        let mut entry = PlcEntry::new("Q0.0".to_string(), "-K1 Motor contactor".to_string(), "4".to_string());
+        entry.module = Some("SM 322".to_string());
+        table.add_entry(entry);
+
+        let columns = ColumnSet::from_visible(&[ColumnId::DeviceTag, ColumnId::FunctionText, ColumnId::Address, ColumnId::Module]);
+        let path = std::env::temp_dir().join(format!("eview_csv_columns_{}.csv", std::process::id()));
+        let path = path.to_string_lossy().to_string();
+        CsvExporter::new().with_bom(false).with_columns(&columns).export(&table, &path).unwrap();
+        let content = std::fs::read_to_string(&path);
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(
+            content.unwrap(),
+            "Device Tag;Function Text;Address;Module;Slot\n-K1;Motor contactor;Q0.0;SM 322;\n"
+        );
+    }
+
+    #[test]
+    fn test_import_comma_separated_file() {
+        let content = "Address,Symbol Name,Comment\nI0.1,Stop,\n,Missing,\n";
+        let result = CsvImporter::new().parse(content, "P").unwrap();
+
+        assert_eq!(result.table.entries.len(), 1);
+        assert_eq!(result.table.entries[0].symbol_name, "Stop");
+        assert_eq!(result.skipped.len(), 1);
+        assert_eq!(result.skipped[0].row, 3);
+    }
 }
\ No newline at end of file
@@ -1,54 +1,94 @@
 use anyhow::Result;
-use rust_xlsxwriter::Workbook;
-use crate::models::{PlcTable, PlcDataType};
-use super::Exporter;
+use calamine::{open_workbook_auto, Reader};
+use rust_xlsxwriter::{
+    Color, ConditionalFormatDuplicate, DocProperties, Format, FormatBorder, Url, Workbook, Worksheet,
+};
+use serde::{Deserialize, Serialize};
+use crate::models::{natural_sort, ColumnId, ColumnSet, PlcEntry, PlcTable, PlcDataType};
+use super::{file_headers, Exporter, ImportColumns, ImportResult, Importer};
 
-pub struct ExcelExporter;
+/// Project title and extraction info above the column headers
+const HEADER_ROW: u32 = 3;
+
+/// Rows searched for the column headers when importing
+const HEADER_SEARCH_ROWS: usize = 10;
+
+/// Sheets written in any case: PLC Table, Inputs, Outputs, Metadata and the Summary
+const FIXED_SHEETS: usize = 5;
+
+/// Sheets we write at most, extra sheets beyond it are left out with a warning
+const MAX_SHEETS: usize = 255;
+
+/// Entries fitting on a sheet below the title and header rows (Excel has 1,048,576 rows)
+const MAX_SHEET_ENTRIES: usize = 1_048_576 - HEADER_ROW as usize - 1;
+
+const MAX_SHEET_NAME_CHARS: usize = 31;
+
+/// Extra sheets of the Excel export, chosen in the export settings
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExcelOptions {
+    pub sheet_per_page: bool,
+    pub sheet_per_device: bool, // One sheet per device tag, the first word of the symbol name
+}
+
+impl ExcelOptions {
+    fn has_extra_sheets(&self) -> bool {
+        self.sheet_per_page || self.sheet_per_device
+    }
+}
+
+pub struct ExcelExporter {
+    options: ExcelOptions,
+    columns: Vec<ColumnId>,
+}
+
+impl Default for ExcelExporter {
+    fn default() -> Self {
+        Self::new(ExcelOptions::default())
+    }
+}
+
+impl ExcelExporter {
+    pub fn new(options: ExcelOptions) -> Self {
+        Self { options, columns: super::FILE_COLUMNS.to_vec() }
+    }
+
+    /// Write the shown columns of `columns` in their order
+    pub fn with_columns(mut self, columns: &ColumnSet) -> Self {
+        self.columns = columns.visible();
+        self
+    }
+}
+
+/// A sheet listing entries
+struct EntrySheet<'a> {
+    name: String,
+    entries: Vec<&'a PlcEntry>,
+    with_type: bool, // Inputs and Outputs leave out the Type column
+}
 
 impl Exporter for ExcelExporter {
     fn export(&self, table: &PlcTable, path: &str) -> Result<()> {
         let mut workbook = Workbook::new();
+        let styles = SheetStyles::new();
+        let (sheets, _) = self.plan_sheets(table);
 
-        // Create worksheet
-        let worksheet = workbook.add_worksheet();
-        worksheet.set_name("PLC Table")?;
-
-
-        // Set column widths
-        worksheet.set_column_width(0, 15)?;  // Address
-        worksheet.set_column_width(1, 30)?;  // Symbol Name
-        worksheet.set_column_width(2, 10)?;  // Type
-        worksheet.set_column_width(3, 40)?;  // Comment
-        worksheet.set_column_width(4, 10)?;  // Page
-
-        // Write headers
-        worksheet.write(0, 0, "Address")?;
-        worksheet.write(0, 1, "Symbol Name")?;
-        worksheet.write(0, 2, "Type")?;
-        worksheet.write(0, 3, "Comment")?;
-        worksheet.write(0, 4, "Page")?;
-
-        // Freeze header row
-        worksheet.set_freeze_panes(1, 0)?;
-
-        // Enable autofilter
-        worksheet.autofilter(0, 0, table.entries.len() as u32, 4)?;
-
-        // Write data
-        for (row_num, entry) in table.entries.iter().enumerate() {
-            let row = (row_num + 1) as u32;
-
-            // Write row data
-            worksheet.write(row, 0, &entry.address)?;
-            worksheet.write(row, 1, &entry.symbol_name)?;
-            worksheet.write(row, 2, entry.data_type.to_string())?;
-            worksheet.write(row, 3, &entry.comment)?;
-            worksheet.write(row, 4, &entry.page)?;
-        }
+        workbook.set_properties(
+            &DocProperties::new()
+                .set_title(project_title(table))
+                .set_subject("PLC I/O list")
+                .set_keywords("EPLAN, eView, PLC")
+                .set_comment(extraction_info(table, table.entries.len())),
+        );
 
-        // Create separate sheets for inputs and outputs
-        self.create_filtered_sheet(&mut workbook, table, PlcDataType::Input, "Inputs")?;
-        self.create_filtered_sheet(&mut workbook, table, PlcDataType::Output, "Outputs")?;
+        // The PLC Table comes first, the importer also finds it by name
+        for (position, sheet) in sheets.iter().enumerate() {
+            write_entry_sheet(workbook.add_worksheet(), &styles, table, sheet, &self.columns)?;
+            if position == 0 && self.options.has_extra_sheets() {
+                write_summary_sheet(workbook.add_worksheet(), &styles, table, &sheets)?;
+            }
+        }
 
         // Add metadata sheet
         let meta_sheet = workbook.add_worksheet();
@@ -65,41 +105,470 @@ impl Exporter for ExcelExporter {
 
         Ok(())
     }
+
+    fn warnings(&self, table: &PlcTable) -> Vec<String> {
+        self.plan_sheets(table).1
+    }
 }
 
 impl ExcelExporter {
-    fn create_filtered_sheet(
-        &self,
-        workbook: &mut Workbook,
-        table: &PlcTable,
-        filter_type: PlcDataType,
-        sheet_name: &str,
-    ) -> Result<()> {
-        let worksheet = workbook.add_worksheet();
-        worksheet.set_name(sheet_name)?;
-
-        // Write headers
-        worksheet.write(0, 0, "Address")?;
-        worksheet.write(0, 1, "Symbol Name")?;
-        worksheet.write(0, 2, "Comment")?;
-        worksheet.write(0, 3, "Page")?;
-
-        // Filter and write entries
-        let filtered: Vec<_> = table.entries
+    /// Entry sheets in workbook order with unique names, cut to Excel's
+    /// sheet and row limits. Returns a warning for everything left out.
+    fn plan_sheets<'a>(&self, table: &'a PlcTable) -> (Vec<EntrySheet<'a>>, Vec<String>) {
+        let mut sheets = vec![
+            EntrySheet { name: "PLC Table".to_string(), entries: table.entries.iter().collect(), with_type: true },
+            self.create_filtered_sheet(table, PlcDataType::Input, "Inputs"),
+            self.create_filtered_sheet(table, PlcDataType::Output, "Outputs"),
+        ];
+        let mut warnings = Vec::new();
+
+        let mut extra = Vec::new();
+        if self.options.sheet_per_page {
+            extra.extend(group_sheets(table, |entry| {
+                let page = entry.page.trim();
+                if page.is_empty() { "No page".to_string() } else { format!("Page {}", page) }
+            }));
+        }
+        if self.options.sheet_per_device {
+            extra.extend(group_sheets(table, |entry| {
+                entry.device_tag().unwrap_or("No device tag").to_string()
+            }));
+        }
+
+        let room = MAX_SHEETS - FIXED_SHEETS;
+        if extra.len() > room {
+            warnings.push(format!(
+                "Only {} of {} page and device sheets were written, Excel workbooks are limited to {} sheets here",
+                room,
+                extra.len(),
+                MAX_SHEETS
+            ));
+            extra.truncate(room);
+        }
+
+        let mut taken: Vec<String> = ["PLC Table", "Inputs", "Outputs", "Metadata", "Summary"]
             .iter()
-            .filter(|e| e.data_type == filter_type)
+            .map(|name| name.to_string())
             .collect();
+        for mut sheet in extra {
+            sheet.name = unique_sheet_name(&sheet.name, &taken);
+            taken.push(sheet.name.clone());
+            sheets.push(sheet);
+        }
 
-        for (row_num, entry) in filtered.iter().enumerate() {
-            let row = (row_num + 1) as u32;
-            worksheet.write(row, 0, &entry.address)?;
-            worksheet.write(row, 1, &entry.symbol_name)?;
-            worksheet.write(row, 2, &entry.comment)?;
-            worksheet.write(row, 3, &entry.page)?;
+        for sheet in &mut sheets {
+            if sheet.entries.len() > MAX_SHEET_ENTRIES {
+                warnings.push(format!(
+                    "Sheet '{}' has {} entries, only the first {} fit into an Excel sheet",
+                    sheet.name,
+                    sheet.entries.len(),
+                    MAX_SHEET_ENTRIES
+                ));
+                sheet.entries.truncate(MAX_SHEET_ENTRIES);
+            }
         }
 
-        worksheet.autofilter(0, 0, filtered.len() as u32, 3)?;
+        (sheets, warnings)
+    }
+
+    fn create_filtered_sheet<'a>(&self, table: &'a PlcTable, filter_type: PlcDataType, sheet_name: &str) -> EntrySheet<'a> {
+        EntrySheet {
+            name: sheet_name.to_string(),
+            entries: table.entries.iter().filter(|e| e.data_type == filter_type).collect(),
+            with_type: false,
+        }
+    }
+}
 
+/// One sheet per value of `key`, in natural order of the values
+fn group_sheets<'a>(table: &'a PlcTable, key: impl Fn(&PlcEntry) -> String) -> Vec<EntrySheet<'a>> {
+    let mut groups: Vec<(String, Vec<&'a PlcEntry>)> = Vec::new();
+    for entry in &table.entries {
+        let name = key(entry);
+        match groups.iter_mut().find(|(group, _)| *group == name) {
+            Some((_, entries)) => entries.push(entry),
+            None => groups.push((name, vec![entry])),
+        }
+    }
+    groups.sort_by(|a, b| natural_sort(&a.0, &b.0));
+
+    groups
+        .into_iter()
+        .map(|(name, entries)| EntrySheet { name, entries, with_type: true })
+        .collect()
+}
+
+/// Valid sheet name that differs from `taken` (case-insensitive, like Excel):
+/// no `[]:*?/\`, no apostrophe at the ends, at most 31 characters, and
+/// " (2)", " (3)", ... appended on a collision
+fn unique_sheet_name(name: &str, taken: &[String]) -> String {
+    let cleaned: String = name
+        .chars()
+        .map(|c| if "[]:*?/\\".contains(c) || c.is_control() { '_' } else { c })
+        .collect();
+    let cleaned = cleaned.trim().trim_matches('\'').trim();
+    let cleaned = if cleaned.is_empty() { "Sheet" } else { cleaned };
+
+    let is_taken = |candidate: &str| taken.iter().any(|name| name.to_lowercase() == candidate.to_lowercase());
+    let truncate = |text: &str, chars: usize| text.chars().take(chars).collect::<String>().trim_end().to_string();
+
+    let first = truncate(cleaned, MAX_SHEET_NAME_CHARS);
+    if !is_taken(&first) {
+        return first;
+    }
+    (2..)
+        .map(|number| {
+            let suffix = format!(" ({})", number);
+            format!("{}{}", truncate(cleaned, MAX_SHEET_NAME_CHARS - suffix.len()), suffix)
+        })
+        .find(|candidate| !is_taken(candidate))
+        .unwrap_or(first)
+}
+
+fn write_entry_sheet(
+    worksheet: &mut Worksheet,
+    styles: &SheetStyles,
+    table: &PlcTable,
+    sheet: &EntrySheet,
+    columns: &[ColumnId],
+) -> Result<()> {
+    worksheet.set_name(&sheet.name)?;
+
+    let columns: Vec<ColumnId> = columns
+        .iter()
+        .copied()
+        .filter(|column| sheet.with_type || *column != ColumnId::Type)
+        .collect();
+    let headers: Vec<&str> = columns.iter().flat_map(|column| file_headers(*column)).copied().collect();
+    for (col, header) in headers.iter().enumerate() {
+        worksheet.set_column_width(col as u16, column_width(header))?;
+    }
+
+    styles.write_title(worksheet, table, sheet.entries.len())?;
+    styles.write_headers(worksheet, &headers)?;
+
+    for (index, entry) in sheet.entries.iter().enumerate() {
+        let row = HEADER_ROW + 1 + index as u32;
+        let cell = styles.cell(index);
+        let mut col = 0;
+
+        for column in &columns {
+            match column {
+                ColumnId::Type => {
+                    worksheet.write_with_format(row, col, entry.data_type.to_string(), &styles.type_cell(&entry.data_type, index))?;
+                }
+                ColumnId::Module => {
+                    worksheet.write_with_format(row, col, entry.module.as_deref().unwrap_or_default(), cell)?;
+                    col += 1;
+                    match entry.slot {
+                        Some(slot) => worksheet.write_number_with_format(row, col, slot, cell).map(|_| ())?,
+                        None => worksheet.write_blank(row, col, cell).map(|_| ())?,
+                    }
+                }
+                column => {
+                    worksheet.write_with_format(row, col, column.value(entry), cell)?;
+                }
+            }
+            col += 1;
+        }
+    }
+
+    let address_col = headers.iter().position(|header| *header == "Address").unwrap_or_default();
+    styles.finish_table(worksheet, sheet.entries.len(), headers.len() as u16 - 1, address_col as u16)?;
+    Ok(())
+}
+
+/// Width of an export column in characters
+fn column_width(header: &str) -> f64 {
+    match header {
+        "Address" | "Device Tag" => 15.0,
+        "Symbol Name" | "Module" | "Function Text" => 30.0,
+        "Comment" => 40.0,
+        "Tags" => 25.0,
+        "Slot" => 8.0,
+        _ => 10.0,
+    }
+}
+
+/// Every entry sheet with its row count and a link to it
+fn write_summary_sheet(worksheet: &mut Worksheet, styles: &SheetStyles, table: &PlcTable, sheets: &[EntrySheet]) -> Result<()> {
+    worksheet.set_name("Summary")?;
+    worksheet.set_column_width(0, 35)?;
+    worksheet.set_column_width(1, 10)?;
+
+    styles.write_title(worksheet, table, table.entries.len())?;
+    styles.write_headers(worksheet, &["Sheet", "Entries"])?;
+
+    for (index, sheet) in sheets.iter().enumerate() {
+        let row = HEADER_ROW + 1 + index as u32;
+        let link = format!("internal:'{}'!A1", sheet.name.replace('\'', "''"));
+        worksheet.write_url(row, 0, Url::new(link).set_text(&sheet.name))?;
+        worksheet.write_with_format(row, 1, sheet.entries.len() as f64, styles.cell(index))?;
+    }
+
+    worksheet.set_freeze_panes(HEADER_ROW + 1, 0)?;
+    Ok(())
+}
+
+fn project_title(table: &PlcTable) -> &str {
+    if table.project_name.trim().is_empty() { "PLC Table" } else { table.project_name.trim() }
+}
+
+/// "Extracted 2024-03-05 14:07 · 12 entries"
+fn extraction_info(table: &PlcTable, entries: usize) -> String {
+    format!("Extracted {} · {} entries", table.extraction_date.format("%Y-%m-%d %H:%M"), entries)
+}
+
+/// Excel color from the palette used in the app and the reports
+fn excel_color(data_type: &PlcDataType) -> Color {
+    let color = data_type.standard_color();
+    Color::RGB(((color.r() as u32) << 16) | ((color.g() as u32) << 8) | color.b() as u32)
+}
+
+/// Formats shared by all data sheets, so every sheet looks the same
+struct SheetStyles {
+    title: Format,
+    info: Format,
+    header: Format,
+    rows: [Format; 2],   // Alternating row shading
+    duplicate: Format,   // Conditional format for repeated addresses
+}
+
+impl SheetStyles {
+    fn new() -> Self {
+        let cell = Format::new().set_border(FormatBorder::Thin).set_border_color(Color::RGB(0xDADCE0));
+        Self {
+            title: Format::new().set_bold().set_font_size(14),
+            info: Format::new().set_italic().set_font_color(Color::RGB(0x5F6368)),
+            header: cell.clone().set_bold().set_background_color(Color::RGB(0xD9E1F2)),
+            rows: [cell.clone(), cell.set_background_color(Color::RGB(0xF5F7FA))],
+            duplicate: Format::new().set_background_color(Color::RGB(0xFFC7CE)).set_font_color(Color::RGB(0x9C0006)),
+        }
+    }
+
+    /// Format of a data cell in the `index`-th row
+    fn cell(&self, index: usize) -> &Format {
+        &self.rows[index % 2]
+    }
+
+    /// Type cell in the color of its data type
+    fn type_cell(&self, data_type: &PlcDataType, index: usize) -> Format {
+        self.cell(index).clone().set_bold().set_font_color(excel_color(data_type))
+    }
+
+    /// Project name and extraction info in the rows above the headers
+    fn write_title(&self, worksheet: &mut Worksheet, table: &PlcTable, entries: usize) -> Result<()> {
+        worksheet.write_with_format(0, 0, project_title(table), &self.title)?;
+        worksheet.write_with_format(1, 0, extraction_info(table, entries), &self.info)?;
         Ok(())
     }
+
+    fn write_headers(&self, worksheet: &mut Worksheet, headers: &[&str]) -> Result<()> {
+        for (col, header) in headers.iter().enumerate() {
+            worksheet.write_with_format(HEADER_ROW, col as u16, *header, &self.header)?;
+        }
+        Ok(())
+    }
+
+    /// Freeze the headers, add the autofilter and highlight repeated addresses
+    /// in `address_col`
+    fn finish_table(&self, worksheet: &mut Worksheet, entries: usize, last_col: u16, address_col: u16) -> Result<()> {
+        let last_row = HEADER_ROW + entries as u32;
+        worksheet.set_freeze_panes(HEADER_ROW + 1, 0)?;
+        worksheet.autofilter(HEADER_ROW, 0, last_row, last_col)?;
+        if entries > 1 {
+            worksheet.add_conditional_format(
+                HEADER_ROW + 1,
+                address_col,
+                last_row,
+                address_col,
+                &ConditionalFormatDuplicate::new().set_format(&self.duplicate),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Reads the first sheet of a workbook, or the "PLC Table" sheet of our own exports
+pub struct ExcelImporter;
+
+impl Importer for ExcelImporter {
+    fn import(&self, path: &str) -> Result<ImportResult> {
+        let mut workbook = open_workbook_auto(path)?;
+
+        let range = match workbook.worksheet_range("PLC Table") {
+            Ok(range) => range,
+            Err(_) => workbook
+                .worksheet_range_at(0)
+                .ok_or_else(|| anyhow::anyhow!("The workbook has no sheets"))??,
+        };
+
+        let rows: Vec<Vec<String>> = range
+            .rows()
+            .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+            .collect();
+
+        let first = rows.first().ok_or_else(|| anyhow::anyhow!("The sheet is empty"))?;
+
+        // Our own exports have the project title above the headers
+        let (header_index, columns) = rows
+            .iter()
+            .take(HEADER_SEARCH_ROWS)
+            .enumerate()
+            .find_map(|(index, row)| ImportColumns::from_headers(row).ok().map(|columns| (index, columns)))
+            .map(Ok)
+            .unwrap_or_else(|| ImportColumns::from_headers(first).map(|columns| (0, columns)))?;
+
+        // Rows are numbered from the sheet's first used row
+        let first_row = range.start().map(|(row, _)| row as usize + 2).unwrap_or(2) + header_index;
+        Ok(columns.parse_rows(rows.into_iter().skip(header_index + 1), first_row, &super::project_name_from_path(path)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::PlcEntry;
+
+    #[test]
+    fn test_export_import_round_trip() {
+        let mut table = PlcTable::new("Round trip".to_string());
+        let mut entry = PlcEntry::new("I0.0".to_string(), "Start".to_string(), "12".to_string());
+        entry.comment = "Main panel".to_string();
+        entry.add_tag("Critical");
+        entry.module = Some("SM 321".to_string());
+        entry.slot = Some(4);
+        table.add_entry(entry);
+        table.add_entry(PlcEntry::new("Q0.0".to_string(), "Motor".to_string(), "13".to_string()));
+
+        let path = std::env::temp_dir().join(format!("eview_xlsx_round_trip_{}.xlsx", std::process::id()));
+        let path = path.to_string_lossy().to_string();
+        ExcelExporter::default().export(&table, &path).unwrap();
+        let result = ExcelImporter.import(&path);
+        let _ = std::fs::remove_file(&path);
+
+        let result = result.unwrap();
+        assert!(result.skipped.is_empty());
+        assert_eq!(result.table.entries.len(), 2); // Only the main sheet, not Inputs/Outputs
+        assert_eq!(result.table.entries[0].comment, "Main panel");
+        assert_eq!(result.table.entries[0].tags, vec!["Critical"]);
+        assert_eq!(result.table.entries[0].module_text(), "SM 321, slot 4");
+        assert_eq!(result.table.entries[1].page, "13");
+        assert_eq!(result.table.entries[1].slot, None);
+    }
+
+    #[test]
+    fn test_sheets_start_with_project_info() {
+        let mut table = PlcTable::new("P-100".to_string());
+        table.add_entry(PlcEntry::new("I0.0".to_string(), "Start".to_string(), "12".to_string()));
+        table.add_entry(PlcEntry::new("I0.0".to_string(), "Start copy".to_string(), "12".to_string()));
+
+        let path = std::env::temp_dir().join(format!("eview_xlsx_title_{}.xlsx", std::process::id()));
+        let path = path.to_string_lossy().to_string();
+        ExcelExporter::default().export(&table, &path).unwrap();
+        let mut workbook = open_workbook_auto(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        for sheet in ["PLC Table", "Inputs"] {
+            let range = workbook.worksheet_range(sheet).unwrap();
+            assert_eq!(range.get_value((0, 0)).unwrap().to_string(), "P-100");
+            assert!(range.get_value((1, 0)).unwrap().to_string().ends_with("· 2 entries"));
+            assert_eq!(range.get_value((HEADER_ROW, 0)).unwrap().to_string(), "Address");
+            assert_eq!(range.get_value((HEADER_ROW + 2, 1)).unwrap().to_string(), "Start copy");
+        }
+    }
+
+    #[test]
+    fn test_columns_follow_column_set() {
+        let mut table = PlcTable::new("P-100".to_string());
+        let mut entry = PlcEntry::new("I0.0".to_string(), "-S1 Start".to_string(), "12".to_string());
+        entry.comment = "Hidden".to_string();
+        table.add_entry(entry);
+
+        let columns = ColumnSet::from_visible(&[ColumnId::DeviceTag, ColumnId::Type, ColumnId::Address, ColumnId::FunctionText]);
+        let path = std::env::temp_dir().join(format!("eview_xlsx_columns_{}.xlsx", std::process::id()));
+        let path = path.to_string_lossy().to_string();
+        ExcelExporter::default().with_columns(&columns).export(&table, &path).unwrap();
+        let mut workbook = open_workbook_auto(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        let mut cells = |sheet: &str, row: u32| -> Vec<String> {
+            let range = workbook.worksheet_range(sheet).unwrap();
+            (0..4).map(|col| range.get_value((row, col)).map(|value| value.to_string()).unwrap_or_default()).collect()
+        };
+        assert_eq!(cells("PLC Table", HEADER_ROW), ["Device Tag", "Type", "Address", "Function Text"]);
+        assert_eq!(cells("PLC Table", HEADER_ROW + 1), ["-S1", "Input", "I0.0", "Start"]);
+        assert_eq!(cells("Inputs", HEADER_ROW), ["Device Tag", "Address", "Function Text", ""]);
+    }
+
+    #[test]
+    fn test_sheets_per_page_and_device() {
+        let mut table = PlcTable::new("P-100".to_string());
+        for (address, name, page) in [
+            ("I0.0", "-S1 Start", "10"),
+            ("I0.1", "-S2 Stop", "2"),
+            ("Q0.0", "-K1 Motor", "2"),
+            ("Q0.1", "Lamp", ""),
+        ] {
+            table.add_entry(PlcEntry::new(address.to_string(), name.to_string(), page.to_string()));
+        }
+
+        let exporter = ExcelExporter::new(ExcelOptions { sheet_per_page: true, sheet_per_device: true });
+        let path = std::env::temp_dir().join(format!("eview_xlsx_sheets_{}.xlsx", std::process::id()));
+        let path = path.to_string_lossy().to_string();
+        exporter.export(&table, &path).unwrap();
+        let mut workbook = open_workbook_auto(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(workbook.sheet_names(), [
+            "PLC Table", "Summary", "Inputs", "Outputs",
+            "No page", "Page 2", "Page 10",
+            "-K1", "-S1", "-S2", "No device tag",
+            "Metadata",
+        ]);
+
+        let summary = workbook.worksheet_range("Summary").unwrap();
+        assert_eq!(summary.get_value((HEADER_ROW + 6, 0)).unwrap().to_string(), "Page 10");
+        assert_eq!(summary.get_value((HEADER_ROW + 6, 1)).unwrap().to_string(), "1");
+        let page_2 = workbook.worksheet_range("Page 2").unwrap();
+        assert_eq!(page_2.get_value((HEADER_ROW + 2, 0)).unwrap().to_string(), "Q0.0");
+        assert!(exporter.warnings(&table).is_empty());
+    }
+
+    #[test]
+    fn test_unique_sheet_names() {
+        let taken = vec!["PLC Table".to_string(), "Page 1".to_string()];
+
+        assert_eq!(unique_sheet_name("Page 1/2 [A]", &taken), "Page 1_2 _A_");
+        assert_eq!(unique_sheet_name("page 1", &taken), "page 1 (2)");
+        assert_eq!(unique_sheet_name("'quoted'", &taken), "quoted");
+        assert_eq!(unique_sheet_name(" ", &taken), "Sheet");
+
+        let long = "=PLANT+CABINET1-K1000.Main.contactor";
+        let first = unique_sheet_name(long, &taken);
+        assert_eq!(first.chars().count(), MAX_SHEET_NAME_CHARS);
+        let second = unique_sheet_name(long, std::slice::from_ref(&first));
+        assert_eq!(second.chars().count(), MAX_SHEET_NAME_CHARS);
+        assert!(second.ends_with(" (2)"));
+    }
+
+    #[test]
+    fn test_sheet_limit_warns() {
+        let mut table = PlcTable::new("P-100".to_string());
+        for page in 0..300 {
+            table.add_entry(PlcEntry::new(format!("I{}.0", page), String::new(), page.to_string()));
+        }
+
+        let exporter = ExcelExporter::new(ExcelOptions { sheet_per_page: true, sheet_per_device: false });
+        let (sheets, warnings) = exporter.plan_sheets(&table);
+        assert_eq!(sheets.len() + 2, MAX_SHEETS); // With Summary and Metadata
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("Only 250 of 300"));
+    }
+
+    #[test]
+    fn test_type_colors_match_app() {
+        assert_eq!(excel_color(&PlcDataType::Input), Color::RGB(0x2E7D32));
+        assert_eq!(excel_color(&PlcDataType::Output), Color::RGB(0x2196F3));
+    }
 }
\ No newline at end of file
@@ -1,17 +1,116 @@
 use anyhow::Result;
+use serde::{Deserialize, Serialize};
 use serde_json;
 use std::fs::File;
 use std::io::Write;
-use crate::models::PlcTable;
+use std::path::Path;
+use crate::models::{natural_sort, AddressWidth, PlcDataType, PlcEntry, PlcTable};
 use super::Exporter;
 
+/// Version of the documented JSON export schema. Raise it when a field is
+/// renamed or removed, adding a field keeps the version.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Layouts of the JSON export
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum JsonLayout {
+    #[default]
+    Flat,    // Versioned document with an `entries` array
+    ByPage,  // Versioned document with the entries grouped in `pages`
+    Ndjson,  // One entry object per line, for streaming consumers
+    Legacy,  // The table as the app stores it, including UI state like `selected`
+}
+
+impl JsonLayout {
+    pub const ALL: [JsonLayout; 4] = [Self::Flat, Self::ByPage, Self::Ndjson, Self::Legacy];
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Ndjson => "ndjson",
+            _ => "json",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Flat => "Flat entries",
+            Self::ByPage => "Grouped by page",
+            Self::Ndjson => "NDJSON (one entry per line)",
+            Self::Legacy => "Legacy (app format)",
+        }
+    }
+}
+
+/// One entry in the versioned schema, without UI state
+#[derive(Debug, Serialize, Deserialize)]
+struct SchemaEntry {
+    address: String,
+    symbol_name: String,
+    data_type: PlcDataType,
+    comment: String,
+    page: String,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    module: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    slot: Option<u32>,
+}
+
+impl From<&PlcEntry> for SchemaEntry {
+    fn from(entry: &PlcEntry) -> Self {
+        Self {
+            address: entry.address.clone(),
+            symbol_name: entry.symbol_name.clone(),
+            data_type: entry.data_type.clone(),
+            comment: entry.comment.clone(),
+            page: entry.page.clone(),
+            tags: entry.tags.clone(),
+            module: entry.module.clone(),
+            slot: entry.slot,
+        }
+    }
+}
+
+impl From<SchemaEntry> for PlcEntry {
+    fn from(entry: SchemaEntry) -> Self {
+        let mut plc_entry = PlcEntry::new(entry.address, entry.symbol_name, entry.page);
+        plc_entry.data_type = entry.data_type;
+        plc_entry.comment = entry.comment;
+        plc_entry.tags = entry.tags;
+        plc_entry.module = entry.module;
+        plc_entry.slot = entry.slot;
+        plc_entry
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SchemaPage {
+    page: String,
+    entries: Vec<SchemaEntry>,
+}
+
+/// Header of the flat and grouped layouts, exactly one of `entries` and `pages` is set
+#[derive(Debug, Serialize, Deserialize)]
+struct SchemaDocument {
+    schema_version: u32,
+    project: String,
+    extracted: chrono::DateTime<chrono::Local>,
+    entry_count: usize,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    entries: Option<Vec<SchemaEntry>>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pages: Option<Vec<SchemaPage>>,
+}
+
 pub struct JsonExporter {
     pretty: bool,
+    layout: JsonLayout,
 }
 
 impl Default for JsonExporter {
     fn default() -> Self {
-        Self { pretty: true }
+        Self { pretty: true, layout: JsonLayout::default() }
     }
 }
 
@@ -24,15 +123,48 @@ impl JsonExporter {
         self.pretty = pretty;
         self
     }
+
+    pub fn layout(mut self, layout: JsonLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// File contents in the chosen layout
+    pub fn contents(&self, table: &PlcTable) -> Result<String> {
+        let document = match self.layout {
+            JsonLayout::Legacy => return self.to_json(table),
+            JsonLayout::Ndjson => {
+                let mut text = String::new();
+                for entry in &table.entries {
+                    text.push_str(&serde_json::to_string(&SchemaEntry::from(entry))?);
+                    text.push('\n');
+                }
+                return Ok(text);
+            }
+            JsonLayout::Flat => SchemaDocument {
+                entries: Some(table.entries.iter().map(SchemaEntry::from).collect()),
+                ..document_header(table)
+            },
+            JsonLayout::ByPage => SchemaDocument {
+                pages: Some(pages(table)),
+                ..document_header(table)
+            },
+        };
+        self.to_json(&document)
+    }
+
+    fn to_json<T: Serialize>(&self, value: &T) -> Result<String> {
+        Ok(if self.pretty {
+            serde_json::to_string_pretty(value)?
+        } else {
+            serde_json::to_string(value)?
+        })
+    }
 }
 
 impl Exporter for JsonExporter {
     fn export(&self, table: &PlcTable, path: &str) -> Result<()> {
-        let json = if self.pretty {
-            serde_json::to_string_pretty(table)?
-        } else {
-            serde_json::to_string(table)?
-        };
+        let json = self.contents(table)?;
 
         let mut file = File::create(path)?;
         file.write_all(json.as_bytes())?;
@@ -41,6 +173,91 @@ impl Exporter for JsonExporter {
     }
 }
 
+fn document_header(table: &PlcTable) -> SchemaDocument {
+    SchemaDocument {
+        schema_version: SCHEMA_VERSION,
+        project: table.project_name.clone(),
+        extracted: table.extraction_date,
+        entry_count: table.entries.len(),
+        entries: None,
+        pages: None,
+    }
+}
+
+/// Entries per page in natural page order, entries keep their table order
+fn pages(table: &PlcTable) -> Vec<SchemaPage> {
+    let mut pages: Vec<&str> = Vec::new();
+    for entry in &table.entries {
+        if !pages.contains(&entry.page.trim()) {
+            pages.push(entry.page.trim());
+        }
+    }
+    pages.sort_by(|a, b| natural_sort(a, b));
+
+    pages
+        .into_iter()
+        .map(|page| SchemaPage {
+            page: page.to_string(),
+            entries: table
+                .entries
+                .iter()
+                .filter(|entry| entry.page.trim() == page)
+                .map(SchemaEntry::from)
+                .collect(),
+        })
+        .collect()
+}
+
+/// Read a JSON export in any layout. NDJSON has no header, its table is named `fallback_name`.
+pub fn parse_table(content: &str, fallback_name: &str) -> Result<PlcTable> {
+    let value: serde_json::Value = match serde_json::from_str(content) {
+        Ok(value) => value,
+        Err(e) => {
+            // Several values, one entry per line
+            let entries = content
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(|line| serde_json::from_str::<SchemaEntry>(line).map(PlcEntry::from))
+                .collect::<std::result::Result<Vec<_>, _>>()
+                .map_err(|_| e)?;
+            let mut table = PlcTable::new(fallback_name.to_string());
+            table.entries = entries;
+            return Ok(table);
+        }
+    };
+
+    let Some(version) = value.get("schema_version").and_then(|v| v.as_u64()) else {
+        if value.get("address").is_some() {
+            // NDJSON with a single entry
+            let mut table = PlcTable::new(fallback_name.to_string());
+            table.add_entry(serde_json::from_value::<SchemaEntry>(value)?.into());
+            return Ok(table);
+        }
+        return Ok(serde_json::from_value(value)?);
+    };
+    if version > SCHEMA_VERSION as u64 {
+        anyhow::bail!("Schema version {} is newer than this version of the app supports ({})", version, SCHEMA_VERSION);
+    }
+
+    let document: SchemaDocument = serde_json::from_value(value)?;
+    let mut table = PlcTable::new(document.project);
+    table.extraction_date = document.extracted;
+    let entries = document
+        .entries
+        .unwrap_or_default()
+        .into_iter()
+        .chain(document.pages.unwrap_or_default().into_iter().flat_map(|page| page.entries));
+    table.entries = entries.map(PlcEntry::from).collect();
+    Ok(table)
+}
+
+/// Read a file written by `JsonExporter`
+pub fn read_table(path: &Path) -> Result<PlcTable> {
+    let content = std::fs::read_to_string(path)?;
+    let fallback_name = path.file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or_default();
+    parse_table(&content, &fallback_name)
+}
+
 pub fn export_for_tia_portal(table: &PlcTable) -> Result<String> {
     // Special format for future TIA Portal integration
     #[derive(serde::Serialize)]
@@ -59,26 +276,103 @@ pub fn export_for_tia_portal(table: &PlcTable) -> Result<String> {
         .map(|entry| TiaTag {
             name: entry.symbol_name.clone(),
             address: entry.address.clone(),
-            data_type: map_to_tia_type(&entry.address),
+            data_type: map_to_tia_type(&entry.data_type, &entry.address).to_string(),
             comment: entry.comment.clone(),
             retain: false,
             accessible: true,
-            writable: matches!(entry.data_type, crate::models::PlcDataType::Output),
+            writable: matches!(entry.data_type, PlcDataType::Output),
         })
         .collect();
 
     Ok(serde_json::to_string_pretty(&tia_tags)?)
 }
 
-fn map_to_tia_type(address: &str) -> String {
-    // Map EPLAN address format to TIA Portal data types
-    if address.contains('.') {
-        "Bool".to_string()
-    } else if address.contains("W") {
-        "Word".to_string()
-    } else if address.contains("D") {
-        "DWord".to_string()
-    } else {
-        "Bool".to_string()
+/// TIA Portal data type for an address. Input and output words are analog
+/// channels and signed, memory words and double words are plain bit strings.
+fn map_to_tia_type(data_type: &PlcDataType, address: &str) -> &'static str {
+    let analog = matches!(data_type, PlcDataType::Input | PlcDataType::Output);
+
+    match AddressWidth::from_address(address) {
+        AddressWidth::Bit => "Bool",
+        AddressWidth::Byte => "Byte",
+        AddressWidth::Word if analog => "Int",
+        AddressWidth::Word => "Word",
+        AddressWidth::DWord if analog => "DInt",
+        AddressWidth::DWord => "DWord",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn tia_type(address: &str) -> &'static str {
+        map_to_tia_type(&PlcDataType::from_address(address), address)
+    }
+
+    fn table() -> PlcTable {
+        let mut table = PlcTable::new("P-100".to_string());
+        table.extraction_date = chrono::Utc.with_ymd_and_hms(2024, 3, 5, 13, 7, 0).unwrap().with_timezone(&chrono::Local);
+        for (address, name, comment, page) in [
+            ("I0.0", "Start", "Green button", "10"),
+            ("Q0.0", "Motor \"M1\"", "", "2"),
+            ("IW64", "Pressure", "", "10"),
+        ] {
+            let mut entry = PlcEntry::new(address.to_string(), name.to_string(), page.to_string());
+            entry.comment = comment.to_string();
+            table.add_entry(entry);
+        }
+        table.entries[0].selected = true;
+        table.entries[1].add_tag("Critical");
+        table
+    }
+
+    /// Export in `layout`, with the local extraction time replaced by a placeholder
+    fn contents(layout: JsonLayout) -> String {
+        let table = table();
+        let extracted = serde_json::to_string(&table.extraction_date).unwrap();
+        JsonExporter::new()
+            .layout(layout)
+            .contents(&table)
+            .unwrap()
+            .replace(&extracted, "\"{extracted}\"")
+    }
+
+    #[test]
+    fn test_layouts_match_golden_files() {
+        assert_eq!(contents(JsonLayout::Flat), include_str!("testdata/json_flat.json"));
+        assert_eq!(contents(JsonLayout::ByPage), include_str!("testdata/json_by_page.json"));
+        assert_eq!(contents(JsonLayout::Ndjson), include_str!("testdata/json_entries.ndjson"));
+        assert_eq!(contents(JsonLayout::Legacy), include_str!("testdata/json_legacy.json"));
+    }
+
+    #[test]
+    fn test_every_layout_reads_back() {
+        for layout in JsonLayout::ALL {
+            let json = JsonExporter::new().layout(layout).contents(&table()).unwrap();
+            let loaded = parse_table(&json, "export").unwrap();
+
+            let mut addresses: Vec<&str> = loaded.entries.iter().map(|e| e.address.as_str()).collect();
+            addresses.sort();
+            assert_eq!(addresses, ["I0.0", "IW64", "Q0.0"], "{:?}", layout);
+            let motor = loaded.entries.iter().find(|e| e.address == "Q0.0").unwrap();
+            assert_eq!(motor.tags, ["Critical"]);
+
+            let expected_name = if layout == JsonLayout::Ndjson { "export" } else { "P-100" };
+            assert_eq!(loaded.project_name, expected_name);
+        }
+
+        let newer = r#"{"schema_version": 99, "project": "P", "extracted": "2024-03-05T13:07:00Z", "entry_count": 0}"#;
+        assert!(parse_table(newer, "export").is_err());
+    }
+
+    #[test]
+    fn test_map_to_tia_type() {
+        assert_eq!(tia_type("I0.0"), "Bool");
+        assert_eq!(tia_type("IW64"), "Int");
+        assert_eq!(tia_type("MD20"), "DWord");
+        assert_eq!(tia_type("QW2"), "Int");
+        assert_eq!(tia_type("I0"), "Byte");
     }
 }
\ No newline at end of file
@@ -1,31 +1,592 @@
+pub mod async_export;
+pub mod clipboard;
 pub mod excel;
 pub mod csv;
 pub mod json;
+pub mod opcua;
+pub mod report;
+pub mod step7;
+pub mod twincat;
 
 use anyhow::Result;
-use crate::models::PlcTable;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use crate::models::{is_plc_address, next_revision, ColumnId, ColumnSet, PlcEntry, PlcTable};
+use crate::t;
 
 pub trait Exporter {
     fn export(&self, table: &PlcTable, path: &str) -> Result<()>;
+
+    /// Data the format can't hold as-is, e.g. names that get shortened
+    fn warnings(&self, _table: &PlcTable) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Export only the entries passing `filter`
+    fn export_filtered(&self, table: &PlcTable, filter: &ExportFilter, path: &str) -> Result<()> {
+        self.export(&filter.apply(table), path)
+    }
+}
+
+impl<E: Exporter + ?Sized> Exporter for Box<E> {
+    fn export(&self, table: &PlcTable, path: &str) -> Result<()> {
+        (**self).export(table, path)
+    }
+
+    fn warnings(&self, table: &PlcTable) -> Vec<String> {
+        (**self).warnings(table)
+    }
+
+    fn export_filtered(&self, table: &PlcTable, filter: &ExportFilter, path: &str) -> Result<()> {
+        (**self).export_filtered(table, filter, path)
+    }
+}
+
+/// Part of the table to export, e.g. the pages a sub-supplier works on.
+/// Unset options keep all entries.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExportFilter {
+    pub pages: Option<Vec<String>>,
+    pub address_prefix: Option<String>, // Case-insensitive, e.g. "Q" or "IW"
+    pub selected_only: bool,
+}
+
+impl ExportFilter {
+    pub fn matches(&self, entry: &PlcEntry) -> bool {
+        if self.selected_only && !entry.selected {
+            return false;
+        }
+        if let Some(pages) = &self.pages {
+            if !pages.iter().any(|page| page == entry.page.trim()) {
+                return false;
+            }
+        }
+        if let Some(prefix) = &self.address_prefix {
+            if !entry.address.trim().to_uppercase().starts_with(&prefix.trim().to_uppercase()) {
+                return false;
+            }
+        }
+        true
+    }
+
+    pub fn apply(&self, table: &PlcTable) -> PlcTable {
+        PlcTable {
+            entries: table.entries.iter().filter(|e| self.matches(e)).cloned().collect(),
+            project_name: table.project_name.clone(),
+            extraction_date: table.extraction_date,
+            dirty: false,
+            revision: next_revision(),
+            deleted: Vec::new(),
+        }
+    }
+
+    /// Pages from text like "3, 5-7, =A1", ranges of page numbers are expanded.
+    /// `None` if the text names no pages.
+    pub fn parse_pages(text: &str) -> Option<Vec<String>> {
+        let mut pages = Vec::new();
+        for item in text.split(',').map(str::trim).filter(|item| !item.is_empty()) {
+            let range = item
+                .split_once('-')
+                .and_then(|(from, to)| Some((from.trim().parse::<u32>().ok()?, to.trim().parse::<u32>().ok()?)))
+                .filter(|(from, to)| from <= to);
+
+            match range {
+                Some((from, to)) => pages.extend((from..=to).map(|page| page.to_string())),
+                None => pages.push(item.to_string()),
+            }
+        }
+        (!pages.is_empty()).then_some(pages)
+    }
+}
+
+/// Columns of the CSV and Excel exports when no `ColumnSet` is given, the
+/// layout before the columns could be configured
+pub const FILE_COLUMNS: [ColumnId; 7] = [
+    ColumnId::Address,
+    ColumnId::Name,
+    ColumnId::Type,
+    ColumnId::Comment,
+    ColumnId::Page,
+    ColumnId::Tags,
+    ColumnId::Module,
+];
+
+/// Headers of `column` in the CSV and Excel exports. The module and its slot
+/// get a column each, so the importers can read them back.
+pub fn file_headers(column: ColumnId) -> &'static [&'static str] {
+    match column {
+        ColumnId::Address => &["Address"],
+        ColumnId::Name => &["Symbol Name"],
+        ColumnId::Type => &["Type"],
+        ColumnId::Comment => &["Comment"],
+        ColumnId::Page => &["Page"],
+        ColumnId::Tags => &["Tags"],
+        ColumnId::Module => &["Module", "Slot"],
+        ColumnId::DeviceTag => &["Device Tag"],
+        ColumnId::FunctionText => &["Function Text"],
+        ColumnId::Confidence => &["Confidence"],
+    }
+}
+
+/// Cells of `column` for one entry, one per header of `file_headers`
+pub fn file_cells(column: ColumnId, entry: &PlcEntry) -> Vec<String> {
+    match column {
+        ColumnId::Module => vec![
+            entry.module.clone().unwrap_or_default(),
+            entry.slot.map(|slot| slot.to_string()).unwrap_or_default(),
+        ],
+        column => vec![column.value(entry)],
+    }
+}
+
+/// Reads a table back from a file. Rows that can't be parsed are reported in
+/// `ImportResult::skipped` instead of failing the whole import.
+pub trait Importer {
+    fn import(&self, path: &str) -> Result<ImportResult>;
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportResult {
+    pub table: PlcTable,
+    pub skipped: Vec<SkippedRow>,
+}
+
+/// A data row left out of an import, `row` is the 1-based row in the file
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkippedRow {
+    pub row: usize,
+    pub reason: String,
+}
+
+/// Positions of the known columns in an imported header row. Headers are
+/// matched case-insensitively in English and German, only Address and
+/// Symbol Name are required. Type is derived from the address, Tags are
+/// comma-separated and a Slot that isn't a number is left empty.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportColumns {
+    address: usize,
+    symbol_name: usize,
+    comment: Option<usize>,
+    page: Option<usize>,
+    tags: Option<usize>,
+    module: Option<usize>,
+    slot: Option<usize>,
+    confidence: Option<usize>,
 }
 
-pub fn export_to_clipboard(table: &PlcTable) -> Result<String> {
-    let mut output = String::new();
+impl ImportColumns {
+    pub fn from_headers<S: AsRef<str>>(headers: &[S]) -> Result<Self> {
+        let find = |names: &[&str]| {
+            headers.iter().position(|header| {
+                let header = header.as_ref().trim().trim_start_matches('\u{feff}').to_lowercase();
+                names.contains(&header.as_str())
+            })
+        };
+
+        let address = find(&["address", "adresse", "operand"])
+            .ok_or_else(|| anyhow::anyhow!("No Address column found"))?;
+        let symbol_name = find(&["symbol name", "symbol", "name", "symbolname", "symbolischer name"])
+            .ok_or_else(|| anyhow::anyhow!("No Symbol Name column found"))?;
+
+        Ok(Self {
+            address,
+            symbol_name,
+            comment: find(&["comment", "kommentar"]),
+            page: find(&["page", "seite"]),
+            tags: find(&["tags", "tag"]),
+            module: find(&["module", "baugruppe"]),
+            slot: find(&["slot", "steckplatz"]),
+            confidence: find(&["confidence", "zuverlässigkeit"]),
+        })
+    }
+
+    /// Entry for one data row, `None` for a blank row
+    pub fn parse_row<S: AsRef<str>>(&self, cells: &[S]) -> Option<Result<PlcEntry>> {
+        let cell = |index: Option<usize>| {
+            index
+                .and_then(|index| cells.get(index))
+                .map(|value| value.as_ref().trim().to_string())
+                .unwrap_or_default()
+        };
+
+        if cells.iter().all(|value| value.as_ref().trim().is_empty()) {
+            return None;
+        }
+
+        let address = cell(Some(self.address));
+        if address.is_empty() {
+            return Some(Err(anyhow::anyhow!("Address is empty")));
+        }
+        if !is_plc_address(&address) {
+            return Some(Err(anyhow::anyhow!("'{}' is not a valid address", address)));
+        }
+
+        let mut entry = PlcEntry::new(address, cell(Some(self.symbol_name)), cell(self.page));
+        entry.comment = cell(self.comment);
+        for tag in cell(self.tags).split(',') {
+            entry.add_tag(tag);
+        }
+        entry.module = Some(cell(self.module)).filter(|module| !module.is_empty());
+        entry.slot = cell(self.slot).parse().ok();
+        if let Some(confidence) = cell(self.confidence).parse().ok().filter(|value| (0.0..=1.0).contains(value)) {
+            entry.confidence = confidence;
+        }
+        Some(Ok(entry))
+    }
+
+    /// Parse the data rows following the header, `first_row` is the file row of `rows[0]`
+    pub fn parse_rows<R, S>(&self, rows: R, first_row: usize, project_name: &str) -> ImportResult
+    where
+        R: IntoIterator<Item = Vec<S>>,
+        S: AsRef<str>,
+    {
+        let mut table = PlcTable::new(project_name.to_string());
+        let mut skipped = Vec::new();
 
-    // Header
-    output.push_str("Address\tSymbol Name\tType\tComment\tPage\n");
+        for (offset, cells) in rows.into_iter().enumerate() {
+            match self.parse_row(&cells) {
+                Some(Ok(entry)) => table.add_entry(entry),
+                Some(Err(e)) => skipped.push(SkippedRow { row: first_row + offset, reason: e.to_string() }),
+                None => {}
+            }
+        }
 
-    // Data rows
-    for entry in &table.entries {
-        output.push_str(&format!(
-            "{}\t{}\t{}\t{}\t{}\n",
-            entry.address,
-            entry.symbol_name,
-            entry.data_type,
-            entry.comment,
-            entry.page
-        ));
+        ImportResult { table, skipped }
     }
+}
+
+/// Importer for a file chosen by the user, picked by its extension
+pub fn importer_for(path: &str) -> Result<Box<dyn Importer>> {
+    let extension = std::path::Path::new(path)
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    match extension.as_str() {
+        "csv" | "txt" => Ok(Box::new(csv::CsvImporter::new())),
+        "xlsx" | "xlsm" | "xls" | "ods" => Ok(Box::new(excel::ExcelImporter)),
+        _ => Err(anyhow::anyhow!("Unsupported file type '{}'", extension)),
+    }
+}
+
+/// Project name for an imported file, its name without the extension
+fn project_name_from_path(path: &str) -> String {
+    std::path::Path::new(path)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    Excel(excel::ExcelOptions, ColumnSet),
+    Csv(ColumnSet, csv::CsvDelimiter),
+    Json(json::JsonLayout),
+    TwinCat,
+    Step7(step7::Step7SymbolFormat),
+    Markdown(report::ReportOptions),
+    Html(report::ReportOptions),
+    OpcUa,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Excel(..) => "xlsx",
+            ExportFormat::Csv(..) => "csv",
+            ExportFormat::Json(layout) => layout.extension(),
+            ExportFormat::TwinCat => "tmc",
+            ExportFormat::Step7(format) => format.extension(),
+            ExportFormat::Markdown(_) => "md",
+            ExportFormat::Html(_) => "html",
+            ExportFormat::OpcUa => "xml",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ExportFormat::Excel(..) => "Excel",
+            ExportFormat::Csv(..) => "CSV",
+            ExportFormat::Json(_) => "JSON",
+            ExportFormat::TwinCat => "TwinCAT",
+            ExportFormat::Step7(_) => "STEP 7",
+            ExportFormat::Markdown(_) => "Markdown",
+            ExportFormat::Html(_) => "HTML",
+            ExportFormat::OpcUa => "OPC UA",
+        }
+    }
+
+    pub fn exporter(&self) -> Box<dyn Exporter + Send> {
+        match self {
+            ExportFormat::Excel(options, columns) => Box::new(excel::ExcelExporter::new(*options).with_columns(columns)),
+            ExportFormat::Csv(columns, delimiter) => Box::new(csv::CsvExporter::new().with_columns(columns).with_delimiter(*delimiter)),
+            ExportFormat::Json(layout) => Box::new(json::JsonExporter::new().layout(*layout)),
+            ExportFormat::TwinCat => Box::new(twincat::TwinCatExporter),
+            ExportFormat::Step7(format) => Box::new(step7::Step7SymbolExporter::new(*format)),
+            ExportFormat::Markdown(options) => Box::new(report::MarkdownExporter::new(*options)),
+            ExportFormat::Html(options) => Box::new(report::HtmlExporter::new(*options)),
+            ExportFormat::OpcUa => Box::new(opcua::OpcUaExporter),
+        }
+    }
+}
+
+/// Placeholders a file name template may use
+pub const FILENAME_PLACEHOLDERS: [&str; 5] = ["project", "date", "time", "format", "entries"];
 
-    Ok(output)
+/// Gives names like `P-100_20240305_1407.xlsx`
+pub const DEFAULT_FILENAME_TEMPLATE: &str = "{project}_{date}_{time}";
+
+/// Characters Windows doesn't allow in file names
+const INVALID_FILENAME_CHARS: [char; 9] = ['/', '\\', ':', '*', '?', '"', '<', '>', '|'];
+
+enum TemplatePart<'a> {
+    Text(&'a str),
+    Placeholder(&'a str),
+}
+
+/// Split a template into text and placeholder names, unknown placeholders
+/// and unmatched braces are errors
+fn parse_template(template: &str) -> Result<Vec<TemplatePart<'_>>> {
+    let mut parts = Vec::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find(['{', '}']) {
+        if rest[start..].starts_with('}') {
+            anyhow::bail!(t!("Unmatched '}' in file name template"));
+        }
+        let end = rest[start..]
+            .find('}')
+            .map(|end| start + end)
+            .ok_or_else(|| anyhow::anyhow!(t!("Unclosed '{' in file name template")))?;
+
+        let name = &rest[start + 1..end];
+        if !FILENAME_PLACEHOLDERS.contains(&name) {
+            anyhow::bail!(t!(
+                "Unknown placeholder {} in file name template, use {}",
+                format!("{{{}}}", name),
+                FILENAME_PLACEHOLDERS.map(|p| format!("{{{}}}", p)).join(", ")
+            ));
+        }
+
+        parts.push(TemplatePart::Text(&rest[..start]));
+        parts.push(TemplatePart::Placeholder(name));
+        rest = &rest[end + 1..];
+    }
+
+    parts.push(TemplatePart::Text(rest));
+    Ok(parts)
+}
+
+/// Check a file name template before it's saved
+pub fn validate_filename_template(template: &str) -> Result<()> {
+    if template.trim().is_empty() {
+        anyhow::bail!(t!("The file name template is empty"));
+    }
+    if let Some(c) = template.chars().find(|c| INVALID_FILENAME_CHARS.contains(c)) {
+        anyhow::bail!(t!("'{}' is not allowed in file names", c));
+    }
+    parse_template(template).map(|_| ())
+}
+
+/// File name from `template` plus the format's extension. Characters other
+/// than letters, digits, `-` and `_` in the project name become `_`. An
+/// invalid template (e.g. a hand-edited config) falls back to the default.
+pub fn export_file_name(
+    template: &str,
+    project_name: &str,
+    format: ExportFormat,
+    entries: usize,
+    time: chrono::DateTime<chrono::Local>,
+) -> String {
+    let file_safe = |text: &str| text.replace(|c: char| !c.is_alphanumeric() && c != '-' && c != '_', "_");
+    let parts = validate_filename_template(template)
+        .and_then(|_| parse_template(template))
+        .or_else(|_| parse_template(DEFAULT_FILENAME_TEMPLATE))
+        .unwrap_or_default();
+
+    let mut name = String::new();
+    for part in parts {
+        match part {
+            TemplatePart::Text(text) => name.push_str(text),
+            TemplatePart::Placeholder("project") if project_name.is_empty() => name.push_str("plc_table"),
+            TemplatePart::Placeholder("project") => name.push_str(&file_safe(project_name)),
+            TemplatePart::Placeholder("date") => name.push_str(&time.format("%Y%m%d").to_string()),
+            TemplatePart::Placeholder("time") => name.push_str(&time.format("%H%M").to_string()),
+            TemplatePart::Placeholder("format") => name.push_str(&file_safe(format.name())),
+            TemplatePart::Placeholder(_) => name.push_str(&entries.to_string()),
+        }
+    }
+    format!("{}.{}", name.trim(), format.extension())
+}
+
+/// What an export does when its file name is already taken
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum ExistingFilePolicy {
+    Overwrite,
+    #[default]
+    UniqueSuffix, // "P-100_20240305_1407_2.xlsx"
+}
+
+impl ExistingFilePolicy {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ExistingFilePolicy::Overwrite => "Overwrite",
+            ExistingFilePolicy::UniqueSuffix => "Add a number (_2, _3, …)",
+        }
+    }
+}
+
+/// `path`, or with `UniqueSuffix` the first of `name_2.ext`, `name_3.ext`, …
+/// that doesn't exist yet
+pub fn available_path(path: PathBuf, policy: ExistingFilePolicy) -> PathBuf {
+    if policy == ExistingFilePolicy::Overwrite || !path.exists() {
+        return path;
+    }
+
+    let stem = path.file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or_default();
+    let extension = path.extension().map(|ext| format!(".{}", ext.to_string_lossy())).unwrap_or_default();
+    (2..)
+        .map(|n| path.with_file_name(format!("{}_{}{}", stem, n, extension)))
+        .find(|candidate| !candidate.exists())
+        .expect("unbounded range")
+}
+
+/// Which entries an export includes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportScope {
+    All,
+    Filtered, // Only entries matching the current table filters
+}
+
+impl ExportScope {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ExportScope::All => "All entries",
+            ExportScope::Filtered => "Filtered entries",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_import_columns_from_localized_headers() {
+        let columns = ImportColumns::from_headers(&["\u{feff}Seite", "Adresse", "Symbolname"]).unwrap();
+        assert_eq!(columns, ImportColumns { address: 1, symbol_name: 2, comment: None, page: Some(0), tags: None, module: None, slot: None, confidence: None });
+
+        assert!(ImportColumns::from_headers(&["Symbol Name", "Comment"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_rows_reports_bad_rows() {
+        let columns = ImportColumns::from_headers(&["Address", "Symbol Name", "Type", "Comment", "Page"]).unwrap();
+        let rows = vec![
+            vec!["I0.0", "Start", "Input", "Green button", "3"],
+            vec!["", "", "", "", ""],
+            vec!["", "Orphan", "", "", ""],
+            vec!["Q0.0", "Motor"], // Missing optional columns
+            vec!["Address", "Symbol Name", "", "", ""], // Repeated header
+        ];
+
+        let result = columns.parse_rows(rows, 2, "Import");
+        assert_eq!(result.table.entries.len(), 2);
+        assert_eq!(result.table.entries[0].comment, "Green button");
+        assert_eq!(result.table.entries[0].page, "3");
+        assert_eq!(result.table.entries[1].page, "");
+        assert_eq!(
+            result.skipped,
+            vec![
+                SkippedRow { row: 4, reason: "Address is empty".to_string() },
+                SkippedRow { row: 6, reason: "'Address' is not a valid address".to_string() },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_rows_reads_the_confidence() {
+        let columns = ImportColumns::from_headers(&["Address", "Symbol Name", "Confidence"]).unwrap();
+        let rows = vec![vec!["I0.0", "Start", "0.30"], vec!["I0.1", "Stop", ""], vec!["I0.2", "Reset", "7"]];
+
+        let confidences: Vec<f32> = columns.parse_rows(rows, 2, "Import").table.entries.iter().map(|e| e.confidence).collect();
+        assert_eq!(confidences, [0.3, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn test_export_filter() {
+        let mut table = PlcTable::new("Filter".to_string());
+        for (address, page) in [("I0.0", "3"), ("Q0.0", "5"), ("QW4", "6"), ("Q0.1", "12")] {
+            table.add_entry(PlcEntry::new(address.to_string(), String::new(), page.to_string()));
+        }
+        table.entries[2].selected = true;
+
+        let filter = ExportFilter {
+            pages: ExportFilter::parse_pages("3, 5-7"),
+            address_prefix: Some("q".to_string()),
+            selected_only: false,
+        };
+        let addresses: Vec<String> = filter.apply(&table).entries.into_iter().map(|e| e.address).collect();
+        assert_eq!(addresses, ["Q0.0", "QW4"]);
+
+        let selected = ExportFilter { selected_only: true, ..ExportFilter::default() };
+        assert_eq!(selected.apply(&table).entries.len(), 1);
+        assert_eq!(ExportFilter::default().apply(&table).entries.len(), 4);
+    }
+
+    #[test]
+    fn test_parse_pages() {
+        assert_eq!(ExportFilter::parse_pages("3, 5-7"), Some(vec!["3".into(), "5".into(), "6".into(), "7".into()]));
+        assert_eq!(ExportFilter::parse_pages("=A1-2, 9"), Some(vec!["=A1-2".into(), "9".into()]));
+        assert_eq!(ExportFilter::parse_pages(" , "), None);
+    }
+
+    #[test]
+    fn test_export_file_name() {
+        use chrono::TimeZone;
+        let time = chrono::Local.with_ymd_and_hms(2024, 3, 5, 14, 7, 9).unwrap();
+
+        let name = |template: &str, project: &str, format: ExportFormat| export_file_name(template, project, format, 42, time);
+
+        assert_eq!(name(DEFAULT_FILENAME_TEMPLATE, "P-100 Line/2", ExportFormat::Csv(ColumnSet::default(), csv::CsvDelimiter::default())), "P-100_Line_2_20240305_1407.csv");
+        assert_eq!(name(DEFAULT_FILENAME_TEMPLATE, "", ExportFormat::Excel(excel::ExcelOptions::default(), ColumnSet::default())), "plc_table_20240305_1407.xlsx");
+        assert_eq!(
+            name("E-Plan_IO_{project}_{format}_{date}-{time}_{entries}", "P-100", ExportFormat::Step7(step7::Step7SymbolFormat::Sdf)),
+            "E-Plan_IO_P-100_STEP_7_20240305-1407_42.sdf"
+        );
+
+        // Invalid templates never end up in a file name
+        assert_eq!(name("IO_{rev}", "P-100", ExportFormat::Csv(ColumnSet::default(), csv::CsvDelimiter::default())), "P-100_20240305_1407.csv");
+    }
+
+    #[test]
+    fn test_validate_filename_template() {
+        assert!(validate_filename_template(DEFAULT_FILENAME_TEMPLATE).is_ok());
+        assert!(validate_filename_template("IO list {project}").is_ok());
+
+        let error = validate_filename_template("E-Plan_IO_{project}_{rev}").unwrap_err().to_string();
+        assert!(error.starts_with("Unknown placeholder {rev}"), "{}", error);
+        assert!(validate_filename_template("{project").is_err());
+        assert!(validate_filename_template("project}").is_err());
+        assert!(validate_filename_template("{project}/{date}").is_err());
+        assert!(validate_filename_template("  ").is_err());
+    }
+
+    #[test]
+    fn test_available_path() {
+        let dir = std::env::temp_dir().join(format!("eview_available_path_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("P-100_20240305_1407.csv");
+
+        assert_eq!(available_path(path.clone(), ExistingFilePolicy::UniqueSuffix), path);
+
+        std::fs::write(&path, "").unwrap();
+        std::fs::write(dir.join("P-100_20240305_1407_2.csv"), "").unwrap();
+        let unique = available_path(path.clone(), ExistingFilePolicy::UniqueSuffix);
+        let overwrite = available_path(path.clone(), ExistingFilePolicy::Overwrite);
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(unique, dir.join("P-100_20240305_1407_3.csv"));
+        assert_eq!(overwrite, path);
+    }
 }
\ No newline at end of file
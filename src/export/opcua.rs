@@ -0,0 +1,300 @@
+use anyhow::Result;
+use quick_xml::events::{BytesDecl, BytesText, Event};
+use quick_xml::Writer;
+use std::collections::HashSet;
+use std::io;
+use crate::models::{AddressWidth, PlcDataType, PlcEntry, PlcTable};
+use super::Exporter;
+
+/// Namespace of the exported nodes, index 1 in the NodeSet
+pub const NAMESPACE_URI: &str = "opc.tcp://eview-scraper";
+
+const NODESET_XMLNS: &str = "http://opcfoundation.org/UA/2011/03/UANodeSet.xsd";
+
+/// Folder under Objects holding one variable per entry
+const FOLDER_NODE_ID: &str = "ns=1;i=1000";
+
+/// Aliases for the standard nodes referenced below (namespace 0)
+const ALIASES: [(&str, &str); 8] = [
+    ("Boolean", "i=1"),
+    ("Byte", "i=3"),
+    ("UInt16", "i=5"),
+    ("UInt32", "i=7"),
+    ("Organizes", "i=35"),
+    ("HasTypeDefinition", "i=40"),
+    ("HasComponent", "i=47"),
+    ("BaseDataVariableType", "i=63"),
+];
+
+const OBJECTS_FOLDER: &str = "i=85";
+const FOLDER_TYPE: &str = "i=61";
+
+/// OPC UA information model (NodeSet2 XML) with a folder named after the
+/// project and one `UAVariable` per entry. Node ids are `ns=1;s=<address>`,
+/// so clients can find a signal by its PLC address.
+pub struct OpcUaExporter;
+
+impl Exporter for OpcUaExporter {
+    fn export(&self, table: &PlcTable, path: &str) -> Result<()> {
+        std::fs::write(path, to_nodeset(table)?)?;
+        Ok(())
+    }
+}
+
+/// Built-in data type for the width of an address
+fn data_type(address: &str) -> &'static str {
+    match AddressWidth::from_address(address) {
+        AddressWidth::Bit => "Boolean",
+        AddressWidth::Byte => "Byte",
+        AddressWidth::Word => "UInt16",
+        AddressWidth::DWord => "UInt32",
+    }
+}
+
+/// AccessLevel bits: 1 = CurrentRead, 2 = CurrentWrite. Inputs come from the
+/// field and are read-only, outputs and memory may be written.
+fn access_level(data_type: &PlcDataType) -> &'static str {
+    match data_type {
+        PlcDataType::Output | PlcDataType::Memory => "3",
+        PlcDataType::Input | PlcDataType::Unknown => "1",
+    }
+}
+
+fn to_nodeset(table: &PlcTable) -> Result<Vec<u8>> {
+    let mut writer = Writer::new_with_indent(Vec::new(), b' ', 2);
+    let folder_name = if table.project_name.trim().is_empty() { "PLC Table" } else { table.project_name.trim() };
+    let modified = table.extraction_date.to_utc().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+
+    writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("utf-8"), None)))?;
+    writer
+        .create_element("UANodeSet")
+        .with_attributes([
+            ("xmlns:xsi", "http://www.w3.org/2001/XMLSchema-instance"),
+            ("xmlns:xsd", "http://www.w3.org/2001/XMLSchema"),
+            ("xmlns", NODESET_XMLNS),
+            ("LastModified", modified.as_str()),
+        ])
+        .write_inner_content(|writer| {
+            writer
+                .create_element("NamespaceUris")
+                .write_inner_content(|writer| text_element(writer, "Uri", NAMESPACE_URI))?;
+
+            writer.create_element("Models").write_inner_content(|writer| {
+                writer
+                    .create_element("Model")
+                    .with_attributes([("ModelUri", NAMESPACE_URI), ("PublicationDate", modified.as_str())])
+                    .write_inner_content(|writer| {
+                        writer
+                            .create_element("RequiredModel")
+                            .with_attributes([("ModelUri", "http://opcfoundation.org/UA/"), ("Version", "1.04")])
+                            .write_empty()?;
+                        Ok(())
+                    })?;
+                Ok(())
+            })?;
+
+            writer.create_element("Aliases").write_inner_content(|writer| {
+                for (alias, node_id) in ALIASES {
+                    writer
+                        .create_element("Alias")
+                        .with_attribute(("Alias", alias))
+                        .write_text_content(BytesText::new(node_id))?;
+                }
+                Ok(())
+            })?;
+
+            let browse_name = format!("1:{}", folder_name);
+            writer
+                .create_element("UAObject")
+                .with_attributes([("NodeId", FOLDER_NODE_ID), ("BrowseName", browse_name.as_str())])
+                .write_inner_content(|writer| {
+                    text_element(writer, "DisplayName", folder_name)?;
+                    writer.create_element("References").write_inner_content(|writer| {
+                        reference(writer, "Organizes", false, OBJECTS_FOLDER)?;
+                        reference(writer, "HasTypeDefinition", true, FOLDER_TYPE)
+                    })?;
+                    Ok(())
+                })?;
+
+            // Node ids must be unique, a repeated address gets a suffix
+            let mut node_ids = HashSet::new();
+            for entry in &table.entries {
+                let address = entry.address.trim();
+                let mut node_id = format!("ns=1;s={}", address);
+                let mut suffix = 2;
+                while !node_ids.insert(node_id.clone()) {
+                    node_id = format!("ns=1;s={}_{}", address, suffix);
+                    suffix += 1;
+                }
+                write_variable(writer, entry, &node_id)?;
+            }
+            Ok(())
+        })?;
+
+    Ok(writer.into_inner())
+}
+
+fn write_variable(writer: &mut Writer<Vec<u8>>, entry: &PlcEntry, node_id: &str) -> io::Result<()> {
+    let address = entry.address.trim();
+    let browse_name = format!("1:{}", address);
+    let display_name = if entry.symbol_name.trim().is_empty() { address } else { entry.symbol_name.trim() };
+    let access_level = access_level(&entry.data_type);
+
+    writer
+        .create_element("UAVariable")
+        .with_attributes([
+            ("NodeId", node_id),
+            ("BrowseName", browse_name.as_str()),
+            ("ParentNodeId", FOLDER_NODE_ID),
+            ("DataType", data_type(address)),
+            ("AccessLevel", access_level),
+            ("UserAccessLevel", access_level),
+        ])
+        .write_inner_content(|writer| {
+            text_element(writer, "DisplayName", display_name)?;
+            if !entry.comment.trim().is_empty() {
+                text_element(writer, "Description", entry.comment.trim())?;
+            }
+            writer.create_element("References").write_inner_content(|writer| {
+                reference(writer, "HasComponent", false, FOLDER_NODE_ID)?;
+                reference(writer, "HasTypeDefinition", true, "BaseDataVariableType")
+            })?;
+            Ok(())
+        })?;
+    Ok(())
+}
+
+fn text_element(writer: &mut Writer<Vec<u8>>, name: &str, text: &str) -> io::Result<()> {
+    writer.create_element(name).write_text_content(BytesText::new(text))?;
+    Ok(())
+}
+
+fn reference(writer: &mut Writer<Vec<u8>>, reference_type: &str, forward: bool, target: &str) -> io::Result<()> {
+    let element = writer.create_element("Reference").with_attribute(("ReferenceType", reference_type));
+    let element = if forward { element } else { element.with_attribute(("IsForward", "false")) };
+    element.write_text_content(BytesText::new(target))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quick_xml::events::BytesStart;
+    use quick_xml::Reader;
+    use std::collections::HashMap;
+
+    fn table() -> PlcTable {
+        let mut table = PlcTable::new("P-100 <Line 2>".to_string());
+        for (address, name, comment) in [
+            ("I0.0", "Start", "Green & white button"),
+            ("Q4.2", "Motor M1", ""),
+            ("IW64", "Pressure", ""),
+            ("Q4.2", "Motor M1 copy", ""),
+            ("MD20", "", ""),
+        ] {
+            let mut entry = PlcEntry::new(address.to_string(), name.to_string(), "1".to_string());
+            entry.comment = comment.to_string();
+            table.add_entry(entry);
+        }
+        table
+    }
+
+    fn attributes(start: &BytesStart) -> HashMap<String, String> {
+        start
+            .attributes()
+            .map(|attribute| {
+                let attribute = attribute.unwrap();
+                (
+                    String::from_utf8(attribute.key.as_ref().to_vec()).unwrap(),
+                    attribute.unescape_value().unwrap().to_string(),
+                )
+            })
+            .collect()
+    }
+
+    /// Structural checks of the output, not a validation against
+    /// UANodeSet.xsd: well-formed XML in the NodeSet2 namespace, header
+    /// elements in schema order before the nodes, the required attributes on
+    /// every node, known aliases and unique node ids
+    #[test]
+    fn test_nodeset_structure() {
+        let xml = String::from_utf8(to_nodeset(&table()).unwrap()).unwrap();
+        let mut reader = Reader::from_str(&xml);
+        reader.config_mut().trim_text(true);
+
+        let mut depth = 0;
+        let mut top_level = Vec::new();
+        let mut node_ids = HashSet::new();
+        let mut aliases = HashSet::new();
+        let mut variables = Vec::new();
+        let mut path: Vec<String> = Vec::new();
+        let mut texts: Vec<(String, String)> = Vec::new();
+
+        loop {
+            let event = reader.read_event().expect("well-formed XML");
+            match &event {
+                Event::Start(start) | Event::Empty(start) => {
+                    let name = String::from_utf8(start.name().as_ref().to_vec()).unwrap();
+                    let attributes = attributes(start);
+
+                    if depth == 0 {
+                        assert_eq!(name, "UANodeSet");
+                        assert_eq!(attributes["xmlns"], NODESET_XMLNS);
+                    } else if depth == 1 {
+                        top_level.push(name.clone());
+                        if name.starts_with("UA") {
+                            assert!(node_ids.insert(attributes["NodeId"].clone()), "duplicate {}", attributes["NodeId"]);
+                            assert!(attributes.contains_key("BrowseName"));
+                        }
+                        if name == "UAVariable" {
+                            assert!(aliases.contains(&attributes["DataType"]), "unknown alias {}", attributes["DataType"]);
+                            variables.push(attributes.clone());
+                        }
+                    }
+                    if name == "Alias" {
+                        aliases.insert(attributes["Alias"].clone());
+                    }
+
+                    if matches!(event, Event::Start(_)) {
+                        depth += 1;
+                        path.push(name);
+                    }
+                }
+                Event::Text(text) => {
+                    texts.push((path.join("/"), text.unescape().unwrap().to_string()));
+                }
+                Event::End(_) => {
+                    depth -= 1;
+                    path.pop();
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+        assert_eq!(depth, 0);
+
+        // Header first, in schema order, then the nodes
+        assert_eq!(&top_level[..4], ["NamespaceUris", "Models", "Aliases", "UAObject"]);
+        assert!(top_level[4..].iter().all(|name| name == "UAVariable"));
+        assert!(texts.contains(&("UANodeSet/NamespaceUris/Uri".to_string(), NAMESPACE_URI.to_string())));
+        assert!(texts.contains(&("UANodeSet/UAObject/DisplayName".to_string(), "P-100 <Line 2>".to_string())));
+
+        assert_eq!(variables.len(), 5);
+        assert_eq!(variables[0]["NodeId"], "ns=1;s=I0.0");
+        assert_eq!(variables[0]["BrowseName"], "1:I0.0");
+        assert_eq!(variables[0]["DataType"], "Boolean");
+        assert_eq!(variables[0]["AccessLevel"], "1");
+        assert_eq!(variables[1]["AccessLevel"], "3");
+        assert_eq!(variables[2]["DataType"], "UInt16");
+        assert_eq!(variables[3]["NodeId"], "ns=1;s=Q4.2_2");
+        assert_eq!(variables[4]["DataType"], "UInt32");
+
+        let display_names: Vec<&str> = texts
+            .iter()
+            .filter(|(path, _)| path == "UANodeSet/UAVariable/DisplayName")
+            .map(|(_, text)| text.as_str())
+            .collect();
+        assert_eq!(display_names, ["Start", "Motor M1", "Pressure", "Motor M1 copy", "MD20"]);
+        assert!(texts.contains(&("UANodeSet/UAVariable/Description".to_string(), "Green & white button".to_string())));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,317 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+use crate::models::{natural_sort, PlcEntry, PlcTable};
+use super::Exporter;
+
+/// Columns and layout of the Markdown and HTML reports
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReportOptions {
+    pub address: bool,
+    pub symbol_name: bool,
+    pub data_type: bool,
+    pub comment: bool,
+    pub page: bool,
+    pub split_by_page: bool, // One section per schematic page
+}
+
+impl Default for ReportOptions {
+    fn default() -> Self {
+        Self {
+            address: true,
+            symbol_name: true,
+            data_type: true,
+            comment: true,
+            page: true,
+            split_by_page: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ReportColumn {
+    Address,
+    SymbolName,
+    Type,
+    Comment,
+    Page,
+}
+
+impl ReportColumn {
+    fn title(&self) -> &'static str {
+        match self {
+            Self::Address => "Address",
+            Self::SymbolName => "Symbol Name",
+            Self::Type => "Type",
+            Self::Comment => "Comment",
+            Self::Page => "Page",
+        }
+    }
+
+    fn value(&self, entry: &PlcEntry) -> String {
+        match self {
+            Self::Address => entry.address.clone(),
+            Self::SymbolName => entry.symbol_name.clone(),
+            Self::Type => entry.data_type.to_string(),
+            Self::Comment => entry.comment.clone(),
+            Self::Page => entry.page.clone(),
+        }
+    }
+}
+
+impl ReportOptions {
+    /// Selected columns, the address is kept if none are selected
+    fn columns(&self) -> Vec<ReportColumn> {
+        let columns: Vec<ReportColumn> = [
+            (self.address, ReportColumn::Address),
+            (self.symbol_name, ReportColumn::SymbolName),
+            (self.data_type, ReportColumn::Type),
+            (self.comment, ReportColumn::Comment),
+            (self.page, ReportColumn::Page),
+        ]
+        .into_iter()
+        .filter_map(|(selected, column)| selected.then_some(column))
+        .collect();
+
+        if columns.is_empty() {
+            vec![ReportColumn::Address]
+        } else {
+            columns
+        }
+    }
+
+    /// Entries in one section per page (natural page order), or a single untitled section
+    fn sections<'a>(&self, table: &'a PlcTable) -> Vec<(Option<String>, Vec<&'a PlcEntry>)> {
+        if !self.split_by_page {
+            return vec![(None, table.entries.iter().collect())];
+        }
+
+        let mut pages: Vec<&str> = Vec::new();
+        for entry in &table.entries {
+            if !pages.contains(&entry.page.trim()) {
+                pages.push(entry.page.trim());
+            }
+        }
+        pages.sort_by(|a, b| natural_sort(a, b));
+
+        pages
+            .into_iter()
+            .map(|page| {
+                let title = if page.is_empty() { "No page".to_string() } else { format!("Page {}", page) };
+                let entries = table.entries.iter().filter(|e| e.page.trim() == page).collect();
+                (Some(title), entries)
+            })
+            .collect()
+    }
+}
+
+/// "Extracted 2024-03-05 14:07 · 12 entries"
+fn subtitle(table: &PlcTable) -> String {
+    format!(
+        "Extracted {} · {} entries",
+        table.extraction_date.format("%Y-%m-%d %H:%M"),
+        table.entries.len()
+    )
+}
+
+/// I/O list as a Markdown document, for wiki pages
+pub struct MarkdownExporter {
+    options: ReportOptions,
+}
+
+impl MarkdownExporter {
+    pub fn new(options: ReportOptions) -> Self {
+        Self { options }
+    }
+}
+
+impl Exporter for MarkdownExporter {
+    fn export(&self, table: &PlcTable, path: &str) -> Result<()> {
+        std::fs::write(path, to_markdown(table, &self.options))?;
+        Ok(())
+    }
+}
+
+pub fn to_markdown(table: &PlcTable, options: &ReportOptions) -> String {
+    let mut text = String::new();
+
+    let title = if table.project_name.is_empty() { "PLC Table" } else { table.project_name.as_str() };
+    let _ = writeln!(text, "# {}\n", markdown_cell(title));
+    let _ = writeln!(text, "{}\n", subtitle(table));
+
+    for (title, entries) in options.sections(table) {
+        if let Some(title) = title {
+            let _ = writeln!(text, "## {}\n", title);
+        }
+        text.push_str(&markdown_table(entries, options));
+        text.push('\n');
+    }
+
+    text
+}
+
+/// Just the table rows
+fn markdown_table<'a>(entries: impl IntoIterator<Item = &'a PlcEntry>, options: &ReportOptions) -> String {
+    let columns = options.columns();
+    let mut text = String::new();
+
+    let titles: Vec<&str> = columns.iter().map(|c| c.title()).collect();
+    let _ = writeln!(text, "| {} |", titles.join(" | "));
+    let _ = writeln!(text, "|{}", "---|".repeat(columns.len()));
+    for entry in entries {
+        let cells: Vec<String> = columns.iter().map(|c| markdown_cell(&c.value(entry))).collect();
+        let _ = writeln!(text, "| {} |", cells.join(" | "));
+    }
+
+    text
+}
+
+/// Keep cell text on one line and its pipes out of the table structure
+pub(super) fn markdown_cell(text: &str) -> String {
+    text.trim().replace('|', "\\|").replace(['\r', '\n'], " ")
+}
+
+/// I/O list as a standalone HTML page with inline styles
+pub struct HtmlExporter {
+    options: ReportOptions,
+}
+
+impl HtmlExporter {
+    pub fn new(options: ReportOptions) -> Self {
+        Self { options }
+    }
+}
+
+impl Exporter for HtmlExporter {
+    fn export(&self, table: &PlcTable, path: &str) -> Result<()> {
+        std::fs::write(path, to_html(table, &self.options))?;
+        Ok(())
+    }
+}
+
+const HTML_STYLE: &str = "\
+body { font-family: \"Segoe UI\", Arial, sans-serif; margin: 24px; color: #202124; }
+table { border-collapse: collapse; margin-bottom: 24px; }
+th, td { border: 1px solid #dadce0; padding: 4px 8px; text-align: left; }
+th { background: #f1f3f4; }
+tr:nth-child(even) td { background: #fafafa; }
+td.type { font-weight: 600; }";
+
+pub fn to_html(table: &PlcTable, options: &ReportOptions) -> String {
+    let columns = options.columns();
+    let title = if table.project_name.is_empty() { "PLC Table" } else { table.project_name.as_str() };
+    let mut html = String::new();
+
+    let _ = writeln!(html, "<!DOCTYPE html>");
+    let _ = writeln!(html, "<html lang=\"en\">");
+    let _ = writeln!(html, "<head>");
+    let _ = writeln!(html, "<meta charset=\"utf-8\">");
+    let _ = writeln!(html, "<title>{}</title>", escape_html(title));
+    let _ = writeln!(html, "<style>\n{}\n</style>", HTML_STYLE);
+    let _ = writeln!(html, "</head>");
+    let _ = writeln!(html, "<body>");
+    let _ = writeln!(html, "<h1>{}</h1>", escape_html(title));
+    let _ = writeln!(html, "<p>{}</p>", escape_html(&subtitle(table)));
+
+    for (title, entries) in options.sections(table) {
+        if let Some(title) = title {
+            let _ = writeln!(html, "<h2>{}</h2>", escape_html(&title));
+        }
+
+        let _ = writeln!(html, "<table>");
+        let headers: String = columns.iter().map(|c| format!("<th>{}</th>", c.title())).collect();
+        let _ = writeln!(html, "<tr>{}</tr>", headers);
+
+        for entry in entries {
+            let cells: String = columns
+                .iter()
+                .map(|column| {
+                    let value = escape_html(&column.value(entry));
+                    if *column == ReportColumn::Type {
+                        let color = entry.data_type.standard_color();
+                        format!(
+                            "<td class=\"type\" style=\"color: #{:02x}{:02x}{:02x}\">{}</td>",
+                            color.r(),
+                            color.g(),
+                            color.b(),
+                            value
+                        )
+                    } else {
+                        format!("<td>{}</td>", value)
+                    }
+                })
+                .collect();
+            let _ = writeln!(html, "<tr>{}</tr>", cells);
+        }
+
+        let _ = writeln!(html, "</table>");
+    }
+
+    let _ = writeln!(html, "</body>");
+    let _ = writeln!(html, "</html>");
+    html
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn table() -> PlcTable {
+        let mut table = PlcTable::new("P-100".to_string());
+        table.extraction_date = chrono::Local.with_ymd_and_hms(2024, 3, 5, 14, 7, 0).unwrap();
+        for (address, name, comment, page) in [
+            ("I0.0", "Start", "Green | button", "10"),
+            ("Q0.0", "Motor <M1>", "", "2"),
+            ("I0.1", "Stop", "", "2"),
+        ] {
+            let mut entry = PlcEntry::new(address.to_string(), name.to_string(), page.to_string());
+            entry.comment = comment.to_string();
+            table.add_entry(entry);
+        }
+        table
+    }
+
+    #[test]
+    fn test_markdown_report() {
+        let options = ReportOptions { data_type: false, page: false, ..ReportOptions::default() };
+        let markdown = to_markdown(&table(), &options);
+
+        assert!(markdown.starts_with("# P-100\n\nExtracted 2024-03-05 14:07 · 3 entries\n\n"));
+        assert!(markdown.contains("| Address | Symbol Name | Comment |\n|---|---|---|\n"));
+        assert!(markdown.contains("| I0.0 | Start | Green \\| button |\n"));
+        assert!(!markdown.contains("## "));
+    }
+
+    #[test]
+    fn test_markdown_sections_per_page() {
+        let options = ReportOptions { split_by_page: true, ..ReportOptions::default() };
+        let markdown = to_markdown(&table(), &options);
+
+        // Natural page order, entries keep their table order
+        let page_2 = markdown.find("## Page 2").unwrap();
+        let page_10 = markdown.find("## Page 10").unwrap();
+        assert!(page_2 < page_10);
+        assert!(markdown.find("| Q0.0 |").unwrap() < markdown.find("| I0.1 |").unwrap());
+        assert!(markdown.find("| I0.1 |").unwrap() < page_10);
+    }
+
+    #[test]
+    fn test_html_report() {
+        let html = to_html(&table(), &ReportOptions::default());
+
+        assert!(html.contains("<h1>P-100</h1>"));
+        assert!(html.contains("<td>Motor &lt;M1&gt;</td>"));
+        assert!(html.contains("<td class=\"type\" style=\"color: #2e7d32\">Input</td>"));
+        assert!(html.contains("<td class=\"type\" style=\"color: #2196f3\">Output</td>"));
+        assert_eq!(html.matches("<table>").count(), 1);
+    }
+}
\ No newline at end of file
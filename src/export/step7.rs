@@ -0,0 +1,207 @@
+use anyhow::Result;
+use encoding_rs::WINDOWS_1252;
+use serde::{Deserialize, Serialize};
+use crate::models::{AddressWidth, PlcEntry, PlcTable};
+use super::Exporter;
+
+/// The classic symbol editor limits symbol names to 24 and comments to 80 characters
+const MAX_SYMBOL_LENGTH: usize = 24;
+const MAX_COMMENT_LENGTH: usize = 80;
+
+/// File layouts the STEP 7 classic symbol editor imports
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum Step7SymbolFormat {
+    #[default]
+    Asc, // Fixed-width columns
+    Sdf, // Quoted, semicolon-separated fields
+}
+
+impl Step7SymbolFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Asc => "asc",
+            Self::Sdf => "sdf",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Asc => "Fixed width (.asc)",
+            Self::Sdf => "Semicolon separated (.sdf)",
+        }
+    }
+}
+
+/// Symbol table for STEP 7 classic (S7-300/400) with the columns Symbol,
+/// Address, Data type and Comment. The file is encoded as Windows-1252,
+/// characters outside of it become `?`.
+pub struct Step7SymbolExporter {
+    format: Step7SymbolFormat,
+}
+
+impl Step7SymbolExporter {
+    pub fn new(format: Step7SymbolFormat) -> Self {
+        Self { format }
+    }
+
+    fn line(&self, entry: &PlcEntry) -> String {
+        let symbol = truncate(entry.symbol_name.trim(), MAX_SYMBOL_LENGTH);
+        let comment = truncate(entry.comment.trim(), MAX_COMMENT_LENGTH);
+        let data_type = data_type(&entry.address);
+        let (operand, number) = split_address(&entry.address);
+
+        match self.format {
+            Step7SymbolFormat::Asc => format!(
+                "126,{:<24}{:<4}{:>7} {:<10}{:<80}",
+                symbol, operand, number, data_type, comment
+            ),
+            Step7SymbolFormat::Sdf => format!(
+                "\"{}\";\"{} {}\";\"{}\";\"{}\"",
+                symbol.replace('"', "'"),
+                operand,
+                number,
+                data_type,
+                comment.replace('"', "'")
+            ),
+        }
+    }
+
+    fn contents(&self, table: &PlcTable) -> Vec<u8> {
+        let mut text = String::new();
+        for entry in &table.entries {
+            text.push_str(&self.line(entry));
+            text.push_str("\r\n");
+        }
+        to_windows_1252(&text)
+    }
+}
+
+impl Exporter for Step7SymbolExporter {
+    fn export(&self, table: &PlcTable, path: &str) -> Result<()> {
+        std::fs::write(path, self.contents(table))?;
+        Ok(())
+    }
+
+    fn warnings(&self, table: &PlcTable) -> Vec<String> {
+        let mut warnings = Vec::new();
+
+        for entry in &table.entries {
+            let symbol = entry.symbol_name.trim();
+            if symbol.chars().count() > MAX_SYMBOL_LENGTH {
+                warnings.push(format!(
+                    "{}: symbol \"{}\" shortened to \"{}\"",
+                    entry.address,
+                    symbol,
+                    truncate(symbol, MAX_SYMBOL_LENGTH)
+                ));
+            }
+            if !is_windows_1252(symbol) || !is_windows_1252(&entry.comment) {
+                warnings.push(format!(
+                    "{}: characters outside Windows-1252 replaced with '?'",
+                    entry.address
+                ));
+            }
+        }
+
+        warnings
+    }
+}
+
+/// "I10.3" → ("I", "10.3"), "IW64" → ("IW", "64")
+fn split_address(address: &str) -> (String, String) {
+    let address = address.trim();
+    let split = address.find(|c: char| c.is_ascii_digit()).unwrap_or(address.len());
+    (address[..split].to_uppercase(), address[split..].to_string())
+}
+
+fn data_type(address: &str) -> &'static str {
+    match AddressWidth::from_address(address) {
+        AddressWidth::Bit => "BOOL",
+        AddressWidth::Byte => "BYTE",
+        AddressWidth::Word => "WORD",
+        AddressWidth::DWord => "DWORD",
+    }
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    text.chars().take(max_chars).collect()
+}
+
+fn is_windows_1252(text: &str) -> bool {
+    !WINDOWS_1252.encode(text).2
+}
+
+/// Encode `text` as Windows-1252, unmappable characters become '?'
+fn to_windows_1252(text: &str) -> Vec<u8> {
+    let (bytes, _, had_errors) = WINDOWS_1252.encode(text);
+    if !had_errors {
+        return bytes.into_owned();
+    }
+
+    // The encoder writes numeric character references, encode char by char instead
+    let mut buffer = [0; 4];
+    text.chars()
+        .flat_map(|c| {
+            let (bytes, _, had_errors) = WINDOWS_1252.encode(c.encode_utf8(&mut buffer));
+            if had_errors { vec![b'?'] } else { bytes.into_owned() }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table() -> PlcTable {
+        let mut table = PlcTable::new("S7".to_string());
+        let mut entry = PlcEntry::new("I10.3".to_string(), "Tür offen".to_string(), "1".to_string());
+        entry.comment = "Schutztür Station 2 → geöffnet".to_string();
+        table.add_entry(entry);
+        table.add_entry(PlcEntry::new(
+            "IW64".to_string(),
+            "Drucksensor_Hauptleitung_Pumpe_1".to_string(),
+            "2".to_string(),
+        ));
+        table
+    }
+
+    #[test]
+    fn test_asc_layout_and_encoding() {
+        let bytes = Step7SymbolExporter::new(Step7SymbolFormat::Asc).contents(&table());
+        let lines: Vec<&[u8]> = bytes.split(|&b| b == b'\n').collect();
+
+        // "ü" is a single byte in Windows-1252, the arrow has no mapping
+        assert_eq!(&lines[0][..50], b"126,T\xfcr offen               I      10.3 BOOL      ".as_slice());
+        assert!(lines[0][50..].starts_with(b"Schutzt\xfcr Station 2 ? ge\xf6ffnet"));
+        assert_eq!(lines[0].len(), 4 + 24 + 12 + 10 + 80 + 1); // Ends with \r
+
+        let second = String::from_utf8(lines[1].to_vec()).unwrap();
+        assert!(second.starts_with("126,Drucksensor_Hauptleitu"));
+        assert_eq!(&second[28..50], "IW       64 WORD      ");
+    }
+
+    #[test]
+    fn test_sdf_fields_and_truncation_warnings() {
+        let table = table();
+        let exporter = Step7SymbolExporter::new(Step7SymbolFormat::Sdf);
+
+        let bytes = exporter.contents(&table);
+        let text: String = bytes.iter().map(|&b| b as char).collect(); // Latin-1 view for the test
+        assert!(text.starts_with("\"T\u{fc}r offen\";\"I 10.3\";\"BOOL\";"));
+        assert!(text.contains("\"Drucksensor_Hauptleitung\";\"IW 64\";\"WORD\";\"\"\r\n"));
+
+        let warnings = exporter.warnings(&table);
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].contains("Windows-1252"));
+        assert_eq!(
+            warnings[1],
+            "IW64: symbol \"Drucksensor_Hauptleitung_Pumpe_1\" shortened to \"Drucksensor_Hauptleitung\""
+        );
+    }
+
+    #[test]
+    fn test_split_address() {
+        assert_eq!(split_address("I10.3"), ("I".to_string(), "10.3".to_string()));
+        assert_eq!(split_address(" qw2 "), ("QW".to_string(), "2".to_string()));
+    }
+}
\ No newline at end of file
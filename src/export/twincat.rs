@@ -0,0 +1,212 @@
+use anyhow::Result;
+use std::collections::HashSet;
+use std::fmt::Write;
+use crate::models::{AddressWidth, PlcDataType, PlcEntry, PlcTable};
+use super::Exporter;
+
+/// Writes a TwinCAT 3 module class (.tmc) with one data area per address
+/// area. Symbols are linked with `AT %I*`/`%Q*`/`%M*` in TwinCAT, so the
+/// EPLAN address is kept as the bit offset and as a property for reference.
+/// Entries whose area is unknown are left out.
+pub struct TwinCatExporter;
+
+/// A data area of the module, in the order they are written
+struct Area {
+    data_type: PlcDataType,
+    name: &'static str,
+    area_type: &'static str,
+    location: &'static str,
+}
+
+const AREAS: [Area; 3] = [
+    Area { data_type: PlcDataType::Input, name: "Inputs", area_type: "InputDst", location: "%I*" },
+    Area { data_type: PlcDataType::Output, name: "Outputs", area_type: "OutputSrc", location: "%Q*" },
+    Area { data_type: PlcDataType::Memory, name: "Memory", area_type: "Internal", location: "%M*" },
+];
+
+impl Exporter for TwinCatExporter {
+    fn export(&self, table: &PlcTable, path: &str) -> Result<()> {
+        std::fs::write(path, to_tmc(table))?;
+        Ok(())
+    }
+}
+
+fn to_tmc(table: &PlcTable) -> String {
+    let mut xml = String::new();
+    let module = match identifier(&table.project_name) {
+        name if name.is_empty() => "EplanIO".to_string(),
+        name => name,
+    };
+
+    let _ = writeln!(xml, r#"<?xml version="1.0" encoding="utf-8"?>"#);
+    let _ = writeln!(
+        xml,
+        r#"<TcModuleClass xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:noNamespaceSchemaLocation="http://www.beckhoff.com/schemas/2009/05/TcModuleClass">"#
+    );
+    let _ = writeln!(xml, "  <Modules>");
+    let _ = writeln!(xml, "    <Module>");
+    let _ = writeln!(xml, "      <Name>{}</Name>", escape(&module));
+    let _ = writeln!(xml, "      <DataAreas>");
+
+    let areas = AREAS.iter().filter(|area| table.entries.iter().any(|e| e.data_type == area.data_type));
+    for (area_no, area) in areas.enumerate() {
+        let _ = writeln!(xml, "        <DataArea>");
+        let _ = writeln!(xml, r#"          <AreaNo AreaType="{}" CreateSymbols="true">{}</AreaNo>"#, area.area_type, area_no);
+        let _ = writeln!(xml, "          <Name>{}</Name>", area.name);
+
+        // Symbol names must be unique within a data area
+        let mut names = HashSet::new();
+        for entry in table.entries.iter().filter(|e| e.data_type == area.data_type) {
+            write_symbol(&mut xml, entry, area, &mut names);
+        }
+
+        let _ = writeln!(xml, "        </DataArea>");
+    }
+
+    let _ = writeln!(xml, "      </DataAreas>");
+    let _ = writeln!(xml, "    </Module>");
+    let _ = writeln!(xml, "  </Modules>");
+    let _ = writeln!(xml, "</TcModuleClass>");
+    xml
+}
+
+fn write_symbol(xml: &mut String, entry: &PlcEntry, area: &Area, names: &mut HashSet<String>) {
+    let (base_type, bit_size) = twincat_type(&entry.address);
+
+    let base = match identifier(&entry.symbol_name) {
+        name if name.is_empty() => identifier(&entry.address),
+        name => name,
+    };
+    let mut name = base.clone();
+    let mut suffix = 2;
+    while !names.insert(name.to_lowercase()) {
+        name = format!("{}_{}", base, suffix);
+        suffix += 1;
+    }
+
+    let _ = writeln!(xml, "          <Symbol>");
+    let _ = writeln!(xml, "            <Name>{}</Name>", escape(&name));
+    if !entry.comment.trim().is_empty() {
+        let _ = writeln!(xml, "            <Comment>{}</Comment>", escape(entry.comment.trim()));
+    }
+    let _ = writeln!(xml, "            <BitSize>{}</BitSize>", bit_size);
+    let _ = writeln!(xml, "            <BaseType>{}</BaseType>", base_type);
+    if let Some(offset) = bit_offset(&entry.address) {
+        let _ = writeln!(xml, "            <BitOffs>{}</BitOffs>", offset);
+    }
+    let _ = writeln!(xml, "            <Properties>");
+    write_property(xml, "AT", area.location);
+    write_property(xml, "EplanAddress", entry.address.trim());
+    if !entry.page.trim().is_empty() {
+        write_property(xml, "EplanPage", entry.page.trim());
+    }
+    let _ = writeln!(xml, "            </Properties>");
+    let _ = writeln!(xml, "          </Symbol>");
+}
+
+fn write_property(xml: &mut String, name: &str, value: &str) {
+    let _ = writeln!(
+        xml,
+        "              <Property><Name>{}</Name><Value>{}</Value></Property>",
+        name,
+        escape(value)
+    );
+}
+
+/// IEC 61131-3 type and size in bits for an address
+fn twincat_type(address: &str) -> (&'static str, u32) {
+    match AddressWidth::from_address(address) {
+        AddressWidth::Bit => ("BOOL", 1),
+        AddressWidth::Byte => ("BYTE", 8),
+        AddressWidth::Word => ("WORD", 16),
+        AddressWidth::DWord => ("DWORD", 32),
+    }
+}
+
+/// Position of an address in its area in bits, "I2.3" is 19 and "IW64" is 512
+fn bit_offset(address: &str) -> Option<u32> {
+    let address = address.trim();
+    let rest = address.get(1..)?.trim_start_matches(|c: char| matches!(c.to_ascii_uppercase(), 'B' | 'W' | 'D' | 'X'));
+
+    let (byte, bit) = match rest.split_once('.') {
+        Some((byte, bit)) => (byte, bit.parse::<u32>().ok().filter(|bit| *bit < 8)?),
+        None => (rest, 0),
+    };
+    Some(byte.parse::<u32>().ok()? * 8 + bit)
+}
+
+/// TwinCAT identifier for a symbol name: letters, digits and single
+/// underscores, not starting with a digit
+fn identifier(name: &str) -> String {
+    let mut identifier = String::new();
+    for c in name.trim().chars() {
+        let c = if c.is_ascii_alphanumeric() { c } else { '_' };
+        if !(c == '_' && identifier.ends_with('_')) {
+            identifier.push(c);
+        }
+    }
+
+    let identifier = identifier.trim_end_matches('_');
+    if identifier.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{}", identifier)
+    } else {
+        identifier.to_string()
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export_matches_golden_file() {
+        let mut table = PlcTable::new("P-100 Line 2".to_string());
+        for (address, name, comment, page) in [
+            ("I0.0", "Start", "Green button", "3"),
+            ("I0.1", "Stop & reset", "", "3"),
+            ("IW64", "Pressure", "Tank <B1>", "7"),
+            ("Q4.2", "Motor M1", "", "12"),
+            ("Q4.3", "Motor M1", "Second contactor", "12"),
+            ("QB8", "8 lamps", "", ""),
+            ("MD20", "Counter", "", "4"),
+        ] {
+            let mut entry = PlcEntry::new(address.to_string(), name.to_string(), page.to_string());
+            entry.comment = comment.to_string();
+            table.add_entry(entry);
+        }
+
+        let path = std::env::temp_dir().join(format!("eview_twincat_{}.tmc", std::process::id()));
+        let path = path.to_string_lossy().to_string();
+        TwinCatExporter.export(&table, &path).unwrap();
+        let written = std::fs::read_to_string(&path);
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(written.unwrap(), include_str!("testdata/twincat.tmc"));
+    }
+
+    #[test]
+    fn test_bit_offset() {
+        assert_eq!(bit_offset("I0.0"), Some(0));
+        assert_eq!(bit_offset("I2.3"), Some(19));
+        assert_eq!(bit_offset("IW64"), Some(512));
+        assert_eq!(bit_offset("QB8"), Some(64));
+        assert_eq!(bit_offset("I0.8"), None);
+        assert_eq!(bit_offset("I"), None);
+    }
+
+    #[test]
+    fn test_identifier() {
+        assert_eq!(identifier("Motor M1"), "Motor_M1");
+        assert_eq!(identifier("Tür  offen!"), "T_r_offen");
+        assert_eq!(identifier("8 lamps"), "_8_lamps");
+        assert_eq!(identifier("  "), "");
+    }
+}
\ No newline at end of file
@@ -0,0 +1,111 @@
+use anyhow::Result;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::{OnceLock, RwLock};
+use crate::config::Language;
+
+/// Translation tables are embedded so the executable stays self-contained
+const GERMAN_TOML: &str = include_str!("../locales/de.toml");
+
+/// UI strings for one language. Keys are the English source strings,
+/// so English needs no table and missing keys fall back to English.
+#[derive(Debug, Clone, Default)]
+pub struct Translations {
+    strings: HashMap<String, String>,
+}
+
+impl Translations {
+    pub fn from_toml(content: &str) -> Result<Self> {
+        let strings: HashMap<String, String> = toml::from_str(content)?;
+        Ok(Self { strings })
+    }
+
+    pub fn for_language(language: &Language) -> Self {
+        match language {
+            Language::English => Self::default(),
+            Language::German => Self::from_toml(GERMAN_TOML).unwrap_or_else(|e| {
+                eprintln!("Warning: Failed to parse German translations: {}", e);
+                Self::default()
+            }),
+        }
+    }
+
+    pub fn get<'a>(&'a self, key: &'a str) -> &'a str {
+        self.strings.get(key).map(String::as_str).unwrap_or(key)
+    }
+}
+
+fn current() -> &'static RwLock<Translations> {
+    static CURRENT: OnceLock<RwLock<Translations>> = OnceLock::new();
+    CURRENT.get_or_init(|| RwLock::new(Translations::default()))
+}
+
+/// Switch the language used by `t!`
+pub fn set_language(language: &Language) {
+    if let Ok(mut translations) = current().write() {
+        *translations = Translations::for_language(language);
+    }
+}
+
+pub fn translate(key: &str) -> String {
+    match current().read() {
+        Ok(translations) => translations.get(key).to_string(),
+        Err(_) => key.to_string(),
+    }
+}
+
+/// Translate `key` and fill its `{}` placeholders in order
+pub fn translate_args(key: &str, args: &[&dyn Display]) -> String {
+    let template = translate(key);
+    let mut result = String::with_capacity(template.len());
+    let mut args = args.iter();
+    let mut rest = template.as_str();
+
+    while let Some(pos) = rest.find("{}") {
+        result.push_str(&rest[..pos]);
+        match args.next() {
+            Some(arg) => result.push_str(&arg.to_string()),
+            None => result.push_str("{}"),
+        }
+        rest = &rest[pos + 2..];
+    }
+    result.push_str(rest);
+
+    result
+}
+
+/// Look up a UI string in the current language, e.g. `t!("Export Options:")`
+/// or `t!("Showing {} of {} entries", shown, total)`
+#[macro_export]
+macro_rules! t {
+    ($key:expr) => {
+        $crate::i18n::translate($key)
+    };
+    ($key:expr, $($arg:expr),+ $(,)?) => {
+        $crate::i18n::translate_args($key, &[$(&$arg as &dyn std::fmt::Display),+])
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_key_falls_back_to_key() {
+        let translations = Translations::from_toml("\"Ready\" = \"Bereit\"").unwrap();
+        assert_eq!(translations.get("Ready"), "Bereit");
+        assert_eq!(translations.get("Unknown text"), "Unknown text");
+    }
+
+    #[test]
+    fn test_german_table_parses() {
+        let translations = Translations::for_language(&Language::German);
+        assert_ne!(translations.get("Ready"), "Ready");
+    }
+
+    #[test]
+    fn test_translate_args_fills_placeholders() {
+        assert_eq!(translate_args("{} of {} entries", &[&3, &10]), "3 of 10 entries");
+        assert_eq!(translate_args("{} and {}", &[&1]), "1 and {}");
+    }
+}
@@ -0,0 +1,105 @@
+use anyhow::{Context, Result};
+use directories::ProjectDirs;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::Level;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::filter::{LevelFilter, Targets};
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::time::FormatTime;
+use tracing_subscriber::prelude::*;
+
+/// Log files are named `eview.YYYY-MM-DD.log`
+const FILE_PREFIX: &str = "eview";
+const FILE_SUFFIX: &str = "log";
+
+/// Timestamps in local time, the file is read next to the in-app log
+struct LocalTime;
+
+impl FormatTime for LocalTime {
+    fn format_time(&self, w: &mut Writer<'_>) -> std::fmt::Result {
+        write!(w, "{}", chrono::Local::now().format("%Y-%m-%d %H:%M:%S%.3f"))
+    }
+}
+
+/// The `logs` folder of the config dir
+pub fn log_dir() -> Result<PathBuf> {
+    let proj_dirs = ProjectDirs::from("com", "eplan", "eview-scraper")
+        .ok_or_else(|| anyhow::anyhow!("Could not determine config directory"))?;
+
+    Ok(proj_dirs.config_dir().join("logs"))
+}
+
+/// Send tracing events to stdout and to a log file per day in `log_dir`,
+/// keeping the newest `keep_files`. Stdout alone is used if the folder can't be written.
+pub fn init(keep_files: usize) {
+    let stdout = tracing_subscriber::fmt::layer().with_filter(LevelFilter::INFO);
+
+    let file = match file_appender(keep_files) {
+        Ok(appender) => Some(
+            tracing_subscriber::fmt::layer()
+                .with_writer(appender)
+                .with_ansi(false)
+                .with_target(false)
+                .with_timer(LocalTime)
+                // Everything the app logs, only problems of the libraries
+                .with_filter(Targets::new().with_target("eview_scraper", Level::DEBUG).with_default(Level::WARN)),
+        ),
+        Err(e) => {
+            eprintln!("Warning: Failed to open the log file: {:#}", e);
+            None
+        }
+    };
+
+    tracing_subscriber::registry().with(stdout).with(file).init();
+}
+
+fn file_appender(keep_files: usize) -> Result<RollingFileAppender> {
+    let dir = log_dir()?;
+    fs::create_dir_all(&dir).with_context(|| format!("Could not create {}", dir.display()))?;
+
+    RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix(FILE_PREFIX)
+        .filename_suffix(FILE_SUFFIX)
+        .max_log_files(keep_files.max(1))
+        .build(&dir)
+        .context("Could not open the log file")
+}
+
+/// The file being written to, the newest one in `dir`
+pub fn current_file(dir: &Path) -> Option<PathBuf> {
+    let prefix = format!("{}.", FILE_PREFIX);
+    let suffix = format!(".{}", FILE_SUFFIX);
+
+    fs::read_dir(dir)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            let name = path.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+            name.starts_with(&prefix) && name.ends_with(&suffix)
+        })
+        .max() // The date in the name sorts as text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_current_file_is_newest_log() {
+        let dir = std::env::temp_dir().join(format!("eview_logging_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        assert_eq!(current_file(&dir), None);
+
+        for name in ["eview.2024-03-05.log", "eview.2024-03-07.log", "eview.2024-03-06.log", "notes.2024-03-08.log", "eview.2024-03-09.txt"] {
+            fs::write(dir.join(name), "").unwrap();
+        }
+        let current = current_file(&dir);
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(current, Some(dir.join("eview.2024-03-07.log")));
+    }
+}
@@ -3,7 +3,6 @@
 
 use anyhow::Result;
 use eframe::egui;
-use tracing_subscriber;
 
 mod ui;
 mod scraper;
@@ -12,22 +11,47 @@ mod export;
 mod config;
 mod chromedriver_manager;
 mod crypto;
+mod notifications;
+mod i18n;
+mod logging;
 
+use config::ConfigOverrides;
 use ui::EviewApp;
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    // Initialize logging
-    tracing_subscriber::fmt::init();
+    // Log to stdout and to the daily files in the logs folder
+    let saved_config = config::AppConfig::load().unwrap_or_default();
+    logging::init(saved_config.log_files_kept);
 
-    // Setup native options
+    // Command line flags and EVIEW_* variables override the config file
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        println!("{}", config::USAGE);
+        return Ok(());
+    }
+    let overrides = match ConfigOverrides::from_args_and_env(args) {
+        Ok(overrides) => overrides,
+        Err(e) => {
+            eprintln!("Error: {}\n\n{}", e, config::USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    // Setup native options, with the window geometry of the last session
+    let ui_state = saved_config.ui_state.sanitized();
+    let mut viewport = egui::ViewportBuilder::default()
+        .with_title("EPLAN eVIEW SPS Table Extractor")
+        .with_inner_size(ui_state.window_size.unwrap_or([1200.0, 800.0]))
+        .with_min_inner_size(config::MIN_WINDOW_SIZE)
+        .with_maximized(ui_state.maximized)
+        .with_icon(load_icon());
+    if let Some(position) = ui_state.window_pos {
+        viewport = viewport.with_position(position);
+    }
     let options = eframe::NativeOptions {
-        viewport: egui::ViewportBuilder::default()
-            .with_title("EPLAN eVIEW SPS Table Extractor")
-            .with_inner_size([1200.0, 800.0])
-            .with_min_inner_size([900.0, 600.0])
-            .with_icon(load_icon()),
-        centered: true,
+        viewport,
+        centered: ui_state.window_pos.is_none(),
         ..Default::default()
     };
 
@@ -35,11 +59,7 @@ async fn main() -> Result<()> {
     eframe::run_native(
         "EPLAN eVIEW Scraper",
         options,
-        Box::new(|cc| {
-            // Configure fonts and style
-            configure_fonts(&cc.egui_ctx);
-            Ok(Box::new(EviewApp::new(cc)))
-        }),
+        Box::new(|cc| Ok(Box::new(EviewApp::new(cc, overrides)))),
     ).map_err(|e| anyhow::anyhow!("Failed to run application: {}", e))
 }
 
@@ -64,23 +84,4 @@ fn load_icon() -> egui::IconData {
             egui::IconData::default()
         }
     }
-}
-
-fn configure_fonts(ctx: &egui::Context) {
-    // Use default fonts for now
-    // Later we can add custom fonts if needed
-    let mut style = (*ctx.style()).clone();
-    style.text_styles.insert(
-        egui::TextStyle::Body,
-        egui::FontId::proportional(14.0),
-    );
-    style.text_styles.insert(
-        egui::TextStyle::Button,
-        egui::FontId::proportional(14.0),
-    );
-    style.text_styles.insert(
-        egui::TextStyle::Heading,
-        egui::FontId::proportional(18.0),
-    );
-    ctx.set_style(style);
 }
\ No newline at end of file
@@ -0,0 +1,313 @@
+use anyhow::Result;
+use regex::Regex;
+use std::fmt;
+use super::{PlcEntry, PlcTable, TableFilter};
+
+/// An edit applied to every selected or filtered entry at once
+#[derive(Debug, Clone, PartialEq)]
+pub enum BulkOperation {
+    SetComment(String),
+    AppendComment(String),
+    AddCommentPrefix(String),
+    ReplaceInComments {
+        find: String,
+        replace: String,
+        regex: bool,
+    },
+    AddPrefix(String),
+    AddSuffix(String),
+    ReplaceInNames {
+        find: String,
+        replace: String,
+        regex: bool,
+    },
+    SetPage(String),
+}
+
+impl BulkOperation {
+    /// Compiled pattern for regex find-and-replace, fails on an invalid pattern
+    fn pattern(&self) -> Result<Option<Regex>> {
+        match self {
+            Self::ReplaceInNames { find, regex: true, .. }
+            | Self::ReplaceInComments { find, regex: true, .. } => Ok(Some(Regex::new(find)?)),
+            _ => Ok(None),
+        }
+    }
+
+    fn edits_comment(&self) -> bool {
+        matches!(
+            self,
+            Self::SetComment(_) | Self::AppendComment(_) | Self::AddCommentPrefix(_) | Self::ReplaceInComments { .. }
+        )
+    }
+
+    fn edits_name(&self) -> bool {
+        matches!(self, Self::AddPrefix(_) | Self::AddSuffix(_) | Self::ReplaceInNames { .. })
+    }
+
+    /// Apply to a single entry, returns whether anything changed
+    fn apply(&self, entry: &mut PlcEntry, pattern: Option<&Regex>) -> bool {
+        let changed = self.apply_text(entry, pattern);
+        if changed && self.edits_comment() {
+            entry.comment_edited = true;
+        }
+        // A name set by hand is no longer the parser's guess
+        if changed && self.edits_name() {
+            entry.confidence = 1.0;
+        }
+        changed
+    }
+
+    fn apply_text(&self, entry: &mut PlcEntry, pattern: Option<&Regex>) -> bool {
+        match self {
+            Self::SetComment(comment) => replace_if_changed(&mut entry.comment, comment.clone()),
+            Self::AppendComment(text) => {
+                if text.is_empty() {
+                    return false;
+                }
+                let comment = if entry.comment.trim().is_empty() {
+                    text.clone()
+                } else {
+                    format!("{}; {}", entry.comment, text)
+                };
+                replace_if_changed(&mut entry.comment, comment)
+            }
+            Self::AddCommentPrefix(prefix) => {
+                // Applying the same prefix twice doesn't stack it
+                if entry.comment.starts_with(prefix.as_str()) {
+                    return false;
+                }
+                entry.comment.insert_str(0, prefix);
+                true
+            }
+            Self::ReplaceInComments { find, .. } if find.is_empty() => false,
+            Self::ReplaceInComments { find, replace, .. } => {
+                let comment = replace_text(&entry.comment, find, replace, pattern);
+                replace_if_changed(&mut entry.comment, comment)
+            }
+            Self::AddPrefix(prefix) => {
+                entry.symbol_name.insert_str(0, prefix);
+                !prefix.is_empty()
+            }
+            Self::AddSuffix(suffix) => {
+                entry.symbol_name.push_str(suffix);
+                !suffix.is_empty()
+            }
+            Self::ReplaceInNames { find, .. } if find.is_empty() => false,
+            Self::ReplaceInNames { find, replace, .. } => {
+                let name = replace_text(&entry.symbol_name, find, replace, pattern);
+                replace_if_changed(&mut entry.symbol_name, name)
+            }
+            Self::SetPage(page) => replace_if_changed(&mut entry.page, page.clone()),
+        }
+    }
+}
+
+impl fmt::Display for BulkOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SetComment(comment) => write!(f, "set comment to \"{}\"", comment),
+            Self::AppendComment(text) => write!(f, "append \"{}\" to comment", text),
+            Self::AddCommentPrefix(prefix) => write!(f, "add prefix \"{}\" to comments", prefix),
+            Self::ReplaceInComments { find, replace, regex } => write!(
+                f,
+                "replace {}\"{}\" with \"{}\" in comments",
+                if *regex { "pattern " } else { "" },
+                find,
+                replace
+            ),
+            Self::AddPrefix(prefix) => write!(f, "add prefix \"{}\" to names", prefix),
+            Self::AddSuffix(suffix) => write!(f, "add suffix \"{}\" to names", suffix),
+            Self::ReplaceInNames { find, replace, regex } => write!(
+                f,
+                "replace {}\"{}\" with \"{}\" in names",
+                if *regex { "pattern " } else { "" },
+                find,
+                replace
+            ),
+            Self::SetPage(page) => write!(f, "set page to \"{}\"", page),
+        }
+    }
+}
+
+/// Which entries a bulk edit touches
+#[derive(Debug, Clone, Copy)]
+pub enum BulkTarget<'a> {
+    Selected,
+    Filtered(&'a TableFilter), // The rows the table currently shows
+}
+
+impl BulkTarget<'_> {
+    pub fn includes(&self, entry: &PlcEntry) -> bool {
+        match self {
+            Self::Selected => entry.selected,
+            Self::Filtered(filter) => filter.matches(entry),
+        }
+    }
+}
+
+fn replace_text(text: &str, find: &str, replace: &str, pattern: Option<&Regex>) -> String {
+    match pattern {
+        Some(pattern) => pattern.replace_all(text, replace).into_owned(),
+        None => text.replace(find, replace),
+    }
+}
+
+fn replace_if_changed(field: &mut String, value: String) -> bool {
+    if *field == value {
+        false
+    } else {
+        *field = value;
+        true
+    }
+}
+
+impl PlcTable {
+    /// Number of target entries `operation` would change, without modifying the table
+    pub fn preview_bulk_edit(&self, operation: &BulkOperation, target: BulkTarget) -> Result<usize> {
+        let pattern = operation.pattern()?;
+
+        Ok(self.entries
+            .iter()
+            .filter(|entry| target.includes(entry))
+            .filter(|entry| operation.apply(&mut (*entry).clone(), pattern.as_ref()))
+            .count())
+    }
+
+    /// Apply `operation` to all target entries, returns the number of entries that changed
+    pub fn apply_bulk_edit(&mut self, operation: &BulkOperation, target: BulkTarget) -> Result<usize> {
+        let pattern = operation.pattern()?;
+        let mut changed = 0;
+
+        for entry in self.entries.iter_mut().filter(|entry| target.includes(entry)) {
+            if operation.apply(entry, pattern.as_ref()) {
+                changed += 1;
+            }
+        }
+
+        if changed > 0 {
+            self.mark_changed();
+        }
+
+        Ok(changed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table() -> PlcTable {
+        let mut table = PlcTable::new("Test".to_string());
+        for (address, name) in [("I0.0", "Start_1"), ("I0.1", "Stop_2"), ("Q0.0", "Motor_3")] {
+            table.add_entry(PlcEntry::new(address.to_string(), name.to_string(), "1".to_string()));
+        }
+        table.entries[0].selected = true;
+        table.entries[1].selected = true;
+        table
+    }
+
+    #[test]
+    fn test_bulk_edit_only_touches_selected_entries() {
+        let mut table = table();
+        let operation = BulkOperation::AddPrefix("ST1_".to_string());
+
+        assert_eq!(table.preview_bulk_edit(&operation, BulkTarget::Selected).unwrap(), 2);
+        assert!(!table.dirty);
+
+        assert_eq!(table.apply_bulk_edit(&operation, BulkTarget::Selected).unwrap(), 2);
+        assert_eq!(table.entries[0].symbol_name, "ST1_Start_1");
+        assert_eq!(table.entries[2].symbol_name, "Motor_3");
+        assert!(table.dirty);
+    }
+
+    #[test]
+    fn test_bulk_name_edit_resets_the_confidence() {
+        let mut table = table();
+        for entry in &mut table.entries {
+            entry.confidence = 0.3;
+        }
+
+        table.apply_bulk_edit(&BulkOperation::AddSuffix("_A".to_string()), BulkTarget::Selected).unwrap();
+        assert_eq!(table.entries[0].confidence, 1.0);
+        assert_eq!(table.entries[2].confidence, 0.3);
+
+        // Other columns leave the guess as it was
+        table.entries[0].confidence = 0.3;
+        table.apply_bulk_edit(&BulkOperation::SetPage("9".to_string()), BulkTarget::Selected).unwrap();
+        assert_eq!(table.entries[0].confidence, 0.3);
+    }
+
+    #[test]
+    fn test_bulk_append_comment() {
+        let mut table = table();
+        table.entries[0].comment = "Pushbutton".to_string();
+
+        table.apply_bulk_edit(&BulkOperation::AppendComment("checked".to_string()), BulkTarget::Selected).unwrap();
+        assert_eq!(table.entries[0].comment, "Pushbutton; checked");
+        assert_eq!(table.entries[1].comment, "checked");
+    }
+
+    #[test]
+    fn test_bulk_replace_plain_and_regex() {
+        let mut table = table();
+        let plain = BulkOperation::ReplaceInNames {
+            find: "Start".to_string(),
+            replace: "Begin".to_string(),
+            regex: false,
+        };
+        assert_eq!(table.apply_bulk_edit(&plain, BulkTarget::Selected).unwrap(), 1);
+        assert_eq!(table.entries[0].symbol_name, "Begin_1");
+
+        let regex = BulkOperation::ReplaceInNames {
+            find: r"_(\d+)$".to_string(),
+            replace: "-$1".to_string(),
+            regex: true,
+        };
+        assert_eq!(table.apply_bulk_edit(&regex, BulkTarget::Selected).unwrap(), 2);
+        assert_eq!(table.entries[1].symbol_name, "Stop-2");
+
+        let invalid = BulkOperation::ReplaceInNames {
+            find: "(".to_string(),
+            replace: String::new(),
+            regex: true,
+        };
+        assert!(table.preview_bulk_edit(&invalid, BulkTarget::Selected).is_err());
+    }
+
+    #[test]
+    fn test_bulk_comment_edits_on_filtered_entries() {
+        let mut table = table();
+        table.entries[0].comment = "Pump 1 start".to_string();
+        table.entries[1].comment = "Pump 1 stop".to_string();
+        table.entries[2].comment = "Pump 1 motor".to_string();
+
+        // Only the outputs match, the selection doesn't matter
+        let mut filter = TableFilter::default();
+        filter.address = "Q".to_string();
+        let target = BulkTarget::Filtered(&filter);
+
+        let replace = BulkOperation::ReplaceInComments {
+            find: "Pump 1".to_string(),
+            replace: "Pump 2".to_string(),
+            regex: false,
+        };
+        assert_eq!(table.preview_bulk_edit(&replace, target).unwrap(), 1);
+        assert_eq!(table.apply_bulk_edit(&replace, target).unwrap(), 1);
+        assert_eq!(table.entries[2].comment, "Pump 2 motor");
+        assert_eq!(table.entries[0].comment, "Pump 1 start");
+
+        let regex = BulkOperation::ReplaceInComments {
+            find: r"^Pump (\d)".to_string(),
+            replace: "P$1".to_string(),
+            regex: true,
+        };
+        assert_eq!(table.apply_bulk_edit(&regex, BulkTarget::Selected).unwrap(), 2);
+        assert_eq!(table.entries[1].comment, "P1 stop");
+
+        let prefix = BulkOperation::AddCommentPrefix("+A1 ".to_string());
+        assert_eq!(table.apply_bulk_edit(&prefix, target).unwrap(), 1);
+        assert_eq!(table.entries[2].comment, "+A1 Pump 2 motor");
+        assert_eq!(table.apply_bulk_edit(&prefix, target).unwrap(), 0);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,253 @@
+use serde::{Deserialize, Serialize};
+use super::PlcEntry;
+
+/// Data column of the table, the clipboard and the CSV and Excel exports.
+/// The names match the former table columns so saved widths still apply.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ColumnId {
+    Address,
+    Name,
+    Type,
+    Comment,
+    Page,
+    Tags,
+    Module,       // With the slot, split into Module and Slot in CSV and Excel
+    DeviceTag,    // EPLAN device tag at the start of the symbol name, e.g. "-K1"
+    FunctionText, // Symbol name without the device tag
+    Confidence,   // `PlcEntry::confidence` with two decimals
+}
+
+impl ColumnId {
+    pub const ALL: [Self; 10] = [
+        Self::Address,
+        Self::Name,
+        Self::Type,
+        Self::Comment,
+        Self::Page,
+        Self::Tags,
+        Self::Module,
+        Self::DeviceTag,
+        Self::FunctionText,
+        Self::Confidence,
+    ];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Self::Address => "Address",
+            Self::Name => "Symbol Name",
+            Self::Type => "Type",
+            Self::Comment => "Comment",
+            Self::Page => "Page",
+            Self::Tags => "Tags",
+            Self::Module => "Module",
+            Self::DeviceTag => "Device Tag",
+            Self::FunctionText => "Function Text",
+            Self::Confidence => "Confidence",
+        }
+    }
+
+    /// Key in JSON objects, the field names of the JSON export
+    pub fn key(&self) -> &'static str {
+        match self {
+            Self::Address => "address",
+            Self::Name => "symbol_name",
+            Self::Type => "data_type",
+            Self::Comment => "comment",
+            Self::Page => "page",
+            Self::Tags => "tags",
+            Self::Module => "module",
+            Self::DeviceTag => "device_tag",
+            Self::FunctionText => "function_text",
+            Self::Confidence => "confidence",
+        }
+    }
+
+    /// Cell text of `entry`, tags comma-separated and the module with its slot
+    pub fn value(&self, entry: &PlcEntry) -> String {
+        match self {
+            Self::Address => entry.address.clone(),
+            Self::Name => entry.symbol_name.clone(),
+            Self::Type => entry.data_type.to_string(),
+            Self::Comment => entry.comment.clone(),
+            Self::Page => entry.page.clone(),
+            Self::Tags => entry.tags_text(),
+            Self::Module => entry.module_text(),
+            Self::DeviceTag => entry.device_tag().unwrap_or_default().to_string(),
+            Self::FunctionText => entry.function_text().to_string(),
+            Self::Confidence => format!("{:.2}", entry.confidence),
+        }
+    }
+
+    /// Shown before any columns were configured: the columns the CSV and Excel
+    /// exports always had, `export::FILE_COLUMNS`, so exports still import back
+    fn shown_by_default(&self) -> bool {
+        matches!(self, Self::Address | Self::Name | Self::Type | Self::Comment | Self::Page | Self::Tags | Self::Module)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ColumnSetting {
+    pub column: ColumnId,
+    pub visible: bool,
+}
+
+/// Order and visibility of every column, shared by the table, the clipboard
+/// and the CSV and Excel exports so what is shown is what gets exported.
+/// Always holds each column once, and the address is always visible.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(from = "Vec<ColumnSetting>", into = "Vec<ColumnSetting>")]
+pub struct ColumnSet {
+    columns: [ColumnSetting; ColumnId::ALL.len()],
+}
+
+impl Default for ColumnSet {
+    fn default() -> Self {
+        Self::from_settings(&[])
+    }
+}
+
+impl From<Vec<ColumnSetting>> for ColumnSet {
+    fn from(settings: Vec<ColumnSetting>) -> Self {
+        Self::from_settings(&settings)
+    }
+}
+
+impl From<ColumnSet> for Vec<ColumnSetting> {
+    fn from(set: ColumnSet) -> Self {
+        set.columns.to_vec()
+    }
+}
+
+impl ColumnSet {
+    /// `settings` with duplicates dropped and missing columns appended with
+    /// their default visibility, so a hand-edited config can't lose a column
+    pub fn from_settings(settings: &[ColumnSetting]) -> Self {
+        let defaults = ColumnId::ALL.map(|column| ColumnSetting { column, visible: column.shown_by_default() });
+        let mut columns = defaults;
+        let mut count = 0;
+
+        for setting in settings.iter().chain(defaults.iter()) {
+            if !columns[..count].iter().any(|taken| taken.column == setting.column) {
+                columns[count] = *setting;
+                count += 1;
+            }
+        }
+
+        let mut set = Self { columns };
+        set.set_visible(ColumnId::Address, true);
+        set
+    }
+
+    /// The listed columns shown in this order, all others hidden behind them
+    pub fn from_visible(visible: &[ColumnId]) -> Self {
+        let mut settings: Vec<ColumnSetting> = visible
+            .iter()
+            .map(|&column| ColumnSetting { column, visible: true })
+            .collect();
+        settings.extend(ColumnId::ALL.map(|column| ColumnSetting { column, visible: false }));
+        Self::from_settings(&settings)
+    }
+
+    /// All columns in order, hidden ones included
+    pub fn settings(&self) -> &[ColumnSetting] {
+        &self.columns
+    }
+
+    /// Shown columns in order
+    pub fn visible(&self) -> Vec<ColumnId> {
+        self.columns
+            .iter()
+            .filter(|setting| setting.visible)
+            .map(|setting| setting.column)
+            .collect()
+    }
+
+    pub fn is_visible(&self, column: ColumnId) -> bool {
+        self.columns.iter().any(|setting| setting.column == column && setting.visible)
+    }
+
+    /// Show or hide `column`, the address can't be hidden
+    pub fn set_visible(&mut self, column: ColumnId, visible: bool) {
+        if let Some(setting) = self.columns.iter_mut().find(|setting| setting.column == column) {
+            setting.visible = visible || column == ColumnId::Address;
+        }
+    }
+
+    /// Move the column at `position` one place up (towards the start) or down
+    pub fn move_column(&mut self, position: usize, up: bool) {
+        let target = if up { position.wrapping_sub(1) } else { position + 1 };
+        if position < self.columns.len() && target < self.columns.len() {
+            self.columns.swap(position, target);
+        }
+    }
+
+    /// Swap two columns, e.g. neighbours in the table while hidden ones sit between them
+    pub fn swap(&mut self, a: ColumnId, b: ColumnId) {
+        let position = |column| self.columns.iter().position(|setting| setting.column == column);
+        if let (Some(a), Some(b)) = (position(a), position(b)) {
+            self.columns.swap(a, b);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_matches_former_layout() {
+        let set = ColumnSet::default();
+        assert_eq!(set.visible(), crate::export::FILE_COLUMNS);
+        assert_eq!(set.settings().len(), ColumnId::ALL.len());
+    }
+
+    #[test]
+    fn test_normalize_settings() {
+        use ColumnId::*;
+        let set = ColumnSet::from_settings(&[
+            ColumnSetting { column: DeviceTag, visible: true },
+            ColumnSetting { column: Address, visible: false },
+            ColumnSetting { column: DeviceTag, visible: false },
+            ColumnSetting { column: Comment, visible: false },
+        ]);
+        assert_eq!(set.visible(), vec![DeviceTag, Address, Name, Type, Page, Tags, Module]);
+        assert_eq!(set.settings().len(), ColumnId::ALL.len());
+
+        let set = ColumnSet::from_visible(&[Page, Address, Tags]);
+        assert_eq!(set.visible(), vec![Page, Address, Tags]);
+
+        // Saved as a list and read back, a shortened list gets the missing columns
+        let json = serde_json::to_string(&set).unwrap();
+        assert_eq!(serde_json::from_str::<ColumnSet>(&json).unwrap(), set);
+        let short: ColumnSet = serde_json::from_str(r#"[{"column": "FunctionText", "visible": true}]"#).unwrap();
+        assert_eq!(short.visible(), vec![FunctionText, Address, Name, Type, Comment, Page, Tags, Module]);
+    }
+
+    #[test]
+    fn test_move_and_hide() {
+        use ColumnId::*;
+        let mut set = ColumnSet::default();
+        set.set_visible(Comment, false);
+        set.set_visible(Address, false);
+        set.set_visible(Name, false);
+        set.set_visible(DeviceTag, true);
+        set.move_column(0, true);
+        set.move_column(0, false);
+        set.swap(DeviceTag, Name);
+
+        assert_eq!(set.visible(), vec![DeviceTag, Address, Type, Page, Tags, Module]);
+        assert!(set.is_visible(Address));
+        assert!(!set.is_visible(Name));
+    }
+
+    #[test]
+    fn test_device_tag_and_function_text() {
+        let entry = PlcEntry::new("I0.0".to_string(), "-S1  Start button".to_string(), "3".to_string());
+        assert_eq!(ColumnId::DeviceTag.value(&entry), "-S1");
+        assert_eq!(ColumnId::FunctionText.value(&entry), "Start button");
+
+        let entry = PlcEntry::new("I0.1".to_string(), "Pump 1.2".to_string(), "3".to_string());
+        assert_eq!(ColumnId::DeviceTag.value(&entry), "");
+        assert_eq!(ColumnId::FunctionText.value(&entry), "Pump 1.2");
+    }
+}
@@ -0,0 +1,178 @@
+use super::{next_revision, PlcDataType, PlcEntry, PlcTable, SearchQuery};
+
+/// Combined table filter. The search text matches any column (or the columns its
+/// advanced terms name), the column filters match their own column only, and
+/// all parts must match (AND).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TableFilter {
+    text: String,
+    query: SearchQuery, // Parsed from `text` once per change
+    query_error: Option<String>,
+    pub address: String,
+    pub symbol_name: String,
+    pub comment: String,
+    pub page: String,
+    pub data_types: Vec<PlcDataType>, // Empty means all types
+    pub changed_only: bool,           // Only entries with a status from `PlcTable::mark_status`
+    pub below_confidence: Option<f32>, // Only entries with a lower `PlcEntry::confidence`
+}
+
+impl TableFilter {
+    /// Update the search text, an invalid pattern matches everything and is reported by `query_error`
+    pub fn set_text(&mut self, text: &str) {
+        if self.text == text {
+            return;
+        }
+
+        self.text = text.to_string();
+        match SearchQuery::parse(text) {
+            Ok(query) => {
+                self.query = query;
+                self.query_error = None;
+            }
+            Err(e) => {
+                self.query = SearchQuery::default();
+                self.query_error = Some(e.to_string());
+            }
+        }
+    }
+
+    pub fn query_error(&self) -> Option<&str> {
+        self.query_error.as_deref()
+    }
+
+    /// Whether any column filter or type chip is set
+    pub fn has_column_filters(&self) -> bool {
+        !self.address.is_empty()
+            || !self.symbol_name.is_empty()
+            || !self.comment.is_empty()
+            || !self.page.is_empty()
+            || !self.data_types.is_empty()
+            || self.changed_only
+            || self.below_confidence.is_some()
+    }
+
+    pub fn is_active(&self) -> bool {
+        !self.text.is_empty() || self.has_column_filters()
+    }
+
+    pub fn matches(&self, entry: &PlcEntry) -> bool {
+        self.query.matches(entry)
+            && contains_ignore_case(&entry.address, &self.address)
+            && contains_ignore_case(&entry.symbol_name, &self.symbol_name)
+            && contains_ignore_case(&entry.comment, &self.comment)
+            && contains_ignore_case(&entry.page, &self.page)
+            && (self.data_types.is_empty() || self.data_types.contains(&entry.data_type))
+            && (!self.changed_only || entry.entry_status.is_changed())
+            && self.below_confidence.is_none_or(|threshold| entry.confidence < threshold)
+    }
+
+    /// Turn a data type chip on or off
+    pub fn toggle_data_type(&mut self, data_type: PlcDataType) {
+        if let Some(pos) = self.data_types.iter().position(|t| *t == data_type) {
+            self.data_types.remove(pos);
+        } else {
+            self.data_types.push(data_type);
+        }
+    }
+
+    /// Clear the column filters and type chips, keeping the free text
+    pub fn clear_columns(&mut self) {
+        *self = Self {
+            text: std::mem::take(&mut self.text),
+            query: std::mem::take(&mut self.query),
+            query_error: self.query_error.take(),
+            ..Self::default()
+        };
+    }
+
+    /// Copy of `table` containing only the matching entries
+    pub fn apply(&self, table: &PlcTable) -> PlcTable {
+        PlcTable {
+            entries: table.entries.iter().filter(|e| self.matches(e)).cloned().collect(),
+            project_name: table.project_name.clone(),
+            extraction_date: table.extraction_date,
+            dirty: false,
+            revision: next_revision(),
+            deleted: Vec::new(),
+        }
+    }
+}
+
+fn contains_ignore_case(value: &str, filter: &str) -> bool {
+    filter.is_empty() || value.to_lowercase().contains(&filter.to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(address: &str, name: &str) -> PlcEntry {
+        PlcEntry::new(address.to_string(), name.to_string(), "1".to_string())
+    }
+
+    #[test]
+    fn test_column_filters_use_and_semantics() {
+        let filter = TableFilter {
+            address: "i".to_string(),
+            symbol_name: "start".to_string(),
+            ..Default::default()
+        };
+
+        assert!(filter.matches(&entry("I0.0", "Start Pump")));
+        assert!(!filter.matches(&entry("Q0.0", "Start Pump")));   // Address doesn't match
+        assert!(!filter.matches(&entry("I0.1", "Input Stop")));   // "I" in the name alone is not enough
+    }
+
+    #[test]
+    fn test_data_type_chips() {
+        let mut filter = TableFilter::default();
+        assert!(filter.matches(&entry("M0.0", "Flag")));
+
+        filter.toggle_data_type(PlcDataType::Input);
+        filter.toggle_data_type(PlcDataType::Output);
+        assert!(filter.matches(&entry("I0.0", "Start")));
+        assert!(filter.matches(&entry("Q0.0", "Motor")));
+        assert!(!filter.matches(&entry("M0.0", "Flag")));
+
+        filter.toggle_data_type(PlcDataType::Input);
+        assert!(!filter.matches(&entry("I0.0", "Start")));
+    }
+
+    #[test]
+    fn test_changed_only() {
+        let filter = TableFilter { changed_only: true, ..Default::default() };
+        let mut new = entry("I0.0", "Start");
+        assert!(!filter.matches(&new));
+
+        new.entry_status = crate::models::EntryStatus::New;
+        assert!(filter.matches(&new));
+        assert!(filter.has_column_filters());
+    }
+
+    #[test]
+    fn test_below_confidence() {
+        let filter = TableFilter { below_confidence: Some(0.5), ..Default::default() };
+        let mut guessed = entry("I0.0", "Start");
+        assert!(!filter.matches(&guessed));
+
+        guessed.confidence = 0.4;
+        assert!(filter.matches(&guessed));
+        assert!(!TableFilter { below_confidence: Some(0.3), ..Default::default() }.matches(&guessed));
+        assert!(filter.has_column_filters());
+    }
+
+    #[test]
+    fn test_apply_returns_matching_entries() {
+        let mut table = PlcTable::new("Test".to_string());
+        table.add_entry(entry("I0.0", "Start"));
+        table.add_entry(entry("Q0.0", "Motor"));
+
+        let mut filter = TableFilter::default();
+        filter.set_text("motor");
+        let filtered = filter.apply(&table);
+
+        assert_eq!(filtered.entries.len(), 1);
+        assert_eq!(filtered.entries[0].address, "Q0.0");
+    }
+}
\ No newline at end of file
@@ -0,0 +1,296 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use super::{next_revision, PlcTable};
+
+/// Row counts reported after `PlcTable::merge`
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MergeSummary {
+    pub added: usize,
+    pub updated: usize, // Symbol name or page changed
+    pub removed: usize,
+}
+
+/// Comment counts reported after `PlcTable::carry_comments`
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CommentCarry {
+    pub preserved: usize,
+    pub dropped: usize, // Address and symbol name no longer extracted
+}
+
+impl PlcTable {
+    /// Take the comments the user edited in an earlier extraction of the same
+    /// project. Entries are matched by address and symbol name, in order when
+    /// both are used several times. Comments that were only extracted are not
+    /// carried, the new extraction has eVIEW's current text.
+    pub fn carry_comments(&mut self, old: &PlcTable) -> CommentCarry {
+        let mut old_comments: HashMap<(&str, &str), VecDeque<&str>> = HashMap::new();
+        for entry in &old.entries {
+            if !entry.comment_edited {
+                continue;
+            }
+            old_comments
+                .entry((entry.address.as_str(), entry.symbol_name.as_str()))
+                .or_default()
+                .push_back(entry.comment.as_str());
+        }
+
+        let mut carry = CommentCarry::default();
+        for entry in &mut self.entries {
+            let comment = old_comments
+                .get_mut(&(entry.address.as_str(), entry.symbol_name.as_str()))
+                .and_then(|comments| comments.pop_front());
+            if let Some(comment) = comment {
+                entry.comment_edited = true;
+                if entry.comment != comment {
+                    entry.comment = comment.to_string();
+                    carry.preserved += 1;
+                }
+            }
+        }
+        carry.dropped = old_comments.values().map(VecDeque::len).sum();
+        carry
+    }
+
+    /// Apply a newer extraction to this (edited) table. Entries are matched by
+    /// address, an address used several times is matched in order. Matched
+    /// entries take the symbol name and page of the new extraction but keep
+    /// their comment, unless it is empty, their tags and their selection. Old entries
+    /// without a match are appended with `removed` set when `keep_removed` is on.
+    pub fn merge(&self, new: PlcTable, keep_removed: bool) -> (PlcTable, MergeSummary) {
+        let mut old_by_address: HashMap<&str, VecDeque<usize>> = HashMap::new();
+        for (index, entry) in self.entries.iter().enumerate() {
+            old_by_address.entry(entry.address.as_str()).or_default().push_back(index);
+        }
+
+        let mut summary = MergeSummary::default();
+        let mut matched = vec![false; self.entries.len()];
+        let mut merged = new;
+
+        for mut entry in std::mem::take(&mut merged.entries) {
+            let old_index = old_by_address
+                .get_mut(entry.address.as_str())
+                .and_then(|indices| indices.pop_front());
+
+            match old_index {
+                Some(old_index) => {
+                    let old = &self.entries[old_index];
+                    matched[old_index] = true;
+
+                    if old.symbol_name != entry.symbol_name || old.page != entry.page {
+                        summary.updated += 1;
+                    }
+                    if !old.comment.is_empty() {
+                        entry.comment = old.comment.clone();
+                        entry.comment_edited = old.comment_edited;
+                    }
+                    entry.selected = old.selected;
+                    entry.tags = old.tags.clone();
+                }
+                None => summary.added += 1,
+            }
+
+            merged.entries.push(entry);
+        }
+
+        for (entry, _) in self.entries.iter().zip(&matched).filter(|(_, &matched)| !matched) {
+            // Entries flagged by an earlier merge count only once
+            if !entry.removed {
+                summary.removed += 1;
+            }
+            if keep_removed {
+                let mut entry = entry.clone();
+                entry.removed = true;
+                merged.entries.push(entry);
+            }
+        }
+
+        merged.dirty = true; // Holds edits that were never exported
+        (merged, summary)
+    }
+
+    /// Combine the extraction of another eView instance (e.g. another building)
+    /// with this one. The entries of `other` are appended, an address this
+    /// table already has is handled by `on_duplicate`.
+    ///
+    /// Named `combine` because `merge` applies a newer extraction of the same project.
+    pub fn combine(&self, other: PlcTable, on_duplicate: DuplicatePolicy) -> PlcTable {
+        let mut combined = self.clone();
+        let mut existing: HashMap<String, usize> = HashMap::new();
+        for (index, entry) in self.entries.iter().enumerate() {
+            existing.entry(entry.address.trim().to_string()).or_insert(index);
+        }
+
+        for mut entry in other.entries {
+            match (existing.get(entry.address.trim()), on_duplicate) {
+                (None, _) => combined.entries.push(entry),
+                (Some(_), DuplicatePolicy::KeepFirst) => {}
+                (Some(&index), DuplicatePolicy::KeepLast) => combined.entries[index] = entry,
+                (Some(_), DuplicatePolicy::KeepBoth) => {
+                    entry.symbol_name = format!("{} (2)", entry.symbol_name);
+                    combined.entries.push(entry);
+                }
+            }
+        }
+
+        combined.project_name = format!("{} + {}", self.project_name, other.project_name);
+        combined.extraction_date = self.extraction_date.max(other.extraction_date);
+        combined.dirty = true;
+        combined.revision = next_revision();
+        combined.deleted.clear(); // Compared with a baseline of this table alone
+        combined
+    }
+
+    /// Addresses of `other` this table already has, what `combine` treats as duplicates
+    pub fn shared_addresses(&self, other: &PlcTable) -> usize {
+        let existing: HashSet<&str> = self.entries.iter().map(|entry| entry.address.trim()).collect();
+        other.entries.iter().filter(|entry| existing.contains(entry.address.trim())).count()
+    }
+}
+
+/// What `PlcTable::combine` does with an address both tables have
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[allow(clippy::enum_variant_names)] // The names read as the choice, "keep first"
+pub enum DuplicatePolicy {
+    KeepFirst, // The entry of the current table
+    KeepLast,  // The entry of the added table, at the place of the current one
+    #[default]
+    KeepBoth,  // Both, the added one with "(2)" after its symbol name
+}
+
+impl DuplicatePolicy {
+    pub const ALL: [Self; 3] = [Self::KeepFirst, Self::KeepLast, Self::KeepBoth];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::KeepFirst => "Keep the current entry",
+            Self::KeepLast => "Keep the added entry",
+            Self::KeepBoth => "Keep both, the added one marked (2)",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::PlcEntry;
+
+    fn table(entries: &[(&str, &str, &str)]) -> PlcTable {
+        let mut table = PlcTable::new("Test".to_string());
+        for (address, name, page) in entries {
+            table.add_entry(PlcEntry::new(address.to_string(), name.to_string(), page.to_string()));
+        }
+        table
+    }
+
+    #[test]
+    fn test_merge_keeps_comments_and_selection() {
+        let mut old = table(&[("I0.0", "Start", "1"), ("I0.1", "Stop", "1"), ("Q0.0", "Motor", "2")]);
+        old.entries[0].comment = "Checked on site".to_string();
+        old.entries[0].selected = true;
+        old.entries[2].comment = "Spare".to_string();
+
+        let mut new = table(&[("I0.0", "Start button", "3"), ("Q0.0", "Motor", "2"), ("Q0.1", "Lamp", "2")]);
+        new.entries[1].comment = "Main drive".to_string();
+
+        let (merged, summary) = old.merge(new.clone(), false);
+        assert_eq!(summary, MergeSummary { added: 1, updated: 1, removed: 1 });
+        assert_eq!(merged.entries.len(), 3);
+        assert_eq!(merged.entries[0].symbol_name, "Start button");
+        assert_eq!(merged.entries[0].page, "3");
+        assert_eq!(merged.entries[0].comment, "Checked on site");
+        assert!(merged.entries[0].selected);
+        assert_eq!(merged.entries[1].comment, "Spare");
+        assert!(merged.dirty);
+
+        // Without a manual comment the extracted one is used
+        old.entries[2].comment.clear();
+        let (merged, _) = old.merge(new, false);
+        assert_eq!(merged.entries[1].comment, "Main drive");
+    }
+
+    #[test]
+    fn test_carry_comments_by_address_and_name() {
+        let mut old = table(&[("I0.0", "Start", "1"), ("I0.1", "Stop", "1"), ("Q0.0", "Motor", "2"), ("Q0.0", "Motor", "2")]);
+        for (entry, comment) in old.entries.iter_mut().zip(["Checked on site", "Wired to X2", "Main drive", "Spare drive"]) {
+            entry.comment = comment.to_string();
+            entry.comment_edited = true;
+        }
+
+        let mut new = table(&[("I0.0", "Start", "1"), ("I0.1", "Stop button", "1"), ("Q0.0", "Motor", "2"), ("Q0.0", "Motor", "3")]);
+        new.entries[2].comment = "Main drive".to_string(); // Extracted, nothing to carry
+
+        let carry = new.carry_comments(&old);
+        assert_eq!(carry, CommentCarry { preserved: 2, dropped: 1 });
+        assert_eq!(new.entries[0].comment, "Checked on site");
+        assert_eq!(new.entries[1].comment, ""); // Symbol name changed
+        assert!(!new.entries[1].comment_edited);
+        assert_eq!(new.entries[2].comment, "Main drive");
+        assert_eq!(new.entries[3].comment, "Spare drive");
+        assert!(new.entries[3].comment_edited); // Carried again by the next extraction
+    }
+
+    #[test]
+    fn test_carry_comments_takes_changed_eview_comments() {
+        let mut old = table(&[("I0.0", "Start", "1"), ("I0.1", "Stop", "1")]);
+        old.entries[0].comment = "Green button".to_string(); // Extracted
+        old.entries[1].comment = "Checked on site".to_string();
+        old.entries[1].comment_edited = true;
+
+        // The designer changed both comments in eVIEW
+        let mut new = table(&[("I0.0", "Start", "1"), ("I0.1", "Stop", "1")]);
+        new.entries[0].comment = "Green button, lit".to_string();
+        new.entries[1].comment = "Red button".to_string();
+        new.mark_status(&old);
+
+        let carry = new.carry_comments(&old);
+        assert_eq!(carry, CommentCarry { preserved: 1, dropped: 0 });
+        assert_eq!(new.entries[0].comment, "Green button, lit");
+        assert_eq!(new.entries[0].entry_status, crate::models::EntryStatus::Modified("Start".to_string()));
+        assert_eq!(new.entries[1].comment, "Checked on site");
+    }
+
+    #[test]
+    fn test_merge_flags_removed_entries() {
+        let old = table(&[("I0.0", "Start", "1"), ("I0.1", "Stop", "1")]);
+        let new = table(&[("I0.0", "Start", "1")]);
+
+        let (merged, summary) = old.merge(new.clone(), true);
+        assert_eq!(summary.removed, 1);
+        assert_eq!(merged.entries.len(), 2);
+        assert_eq!(merged.entries[1].address, "I0.1");
+        assert!(merged.entries[1].removed);
+        assert!(!merged.entries[0].removed);
+
+        // Merging again keeps the flagged entry without counting it twice
+        let (merged, summary) = merged.merge(new, true);
+        assert_eq!(summary, MergeSummary::default());
+        assert_eq!(merged.entries.len(), 2);
+    }
+
+    #[test]
+    fn test_combine_duplicate_policies() {
+        use chrono::TimeZone;
+
+        let mut first = table(&[("I0.0", "Start", "1"), ("Q0.0", "Motor", "1")]);
+        first.project_name = "Building A".to_string();
+        first.extraction_date = chrono::Local.with_ymd_and_hms(2024, 3, 5, 8, 0, 0).unwrap();
+        let mut second = table(&[("Q0.0", "Fan", "7"), ("I1.0", "Door", "7")]);
+        second.project_name = "Building B".to_string();
+        second.extraction_date = chrono::Local.with_ymd_and_hms(2024, 3, 6, 8, 0, 0).unwrap();
+        assert_eq!(first.shared_addresses(&second), 1);
+
+        let names = |table: &PlcTable| table.entries.iter().map(|e| e.symbol_name.clone()).collect::<Vec<_>>();
+
+        let combined = first.combine(second.clone(), DuplicatePolicy::KeepFirst);
+        assert_eq!(names(&combined), ["Start", "Motor", "Door"]);
+        assert_eq!(combined.project_name, "Building A + Building B");
+        assert_eq!(combined.extraction_date, second.extraction_date);
+        assert!(combined.dirty);
+
+        let combined = first.combine(second.clone(), DuplicatePolicy::KeepLast);
+        assert_eq!(names(&combined), ["Start", "Fan", "Door"]);
+
+        let combined = first.combine(second, DuplicatePolicy::KeepBoth);
+        assert_eq!(names(&combined), ["Start", "Motor", "Fan (2)", "Door"]);
+    }
+}
\ No newline at end of file
@@ -1,3 +1,17 @@
 pub mod plc_data;
+pub mod validation;
+pub mod bulk_edit;
+pub mod filter;
+pub mod query;
+pub mod statistics;
+pub mod merge;
+pub mod columns;
 
-pub use plc_data::{PlcEntry, PlcDataType, PlcTable};
\ No newline at end of file
+pub use plc_data::{natural_sort, next_revision, AddressWidth, EntryStatus, PlcEntry, LOW_CONFIDENCE, PlcDataType, PlcTable, NameNormalization, TableDiff};
+pub use validation::{is_plc_address, IssueSeverity, ValidationReport, ValidationRules};
+pub use bulk_edit::{BulkOperation, BulkTarget};
+pub use filter::TableFilter;
+pub use query::SearchQuery;
+pub use statistics::TableStatistics;
+pub use merge::DuplicatePolicy;
+pub use columns::{ColumnId, ColumnSet};
\ No newline at end of file
@@ -1,6 +1,11 @@
+use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::fmt;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
 use eframe::egui;
+use super::SearchQuery;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum PlcDataType {
@@ -23,7 +28,9 @@ impl PlcDataType {
         }
     }
 
-    pub fn color(&self) -> egui::Color32 {
+    /// Regular palette, independent of the theme (used in exported reports),
+    /// see `themes::data_type_color` for the UI
+    pub fn standard_color(&self) -> egui::Color32 {
         match self {
             Self::Input => egui::Color32::from_rgb(46, 125, 50),   // Green
             Self::Output => egui::Color32::from_rgb(33, 150, 243), // Blue
@@ -44,14 +51,63 @@ impl fmt::Display for PlcDataType {
     }
 }
 
+/// Access width of an address: `I0.0` is a bit, `IB1` or plain `I1` a byte,
+/// `IW64` a word and `MD20` a double word
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AddressWidth {
+    Bit,
+    Byte,
+    Word,
+    DWord,
+}
+
+impl AddressWidth {
+    pub fn from_address(address: &str) -> Self {
+        let address = address.trim();
+        if address.contains('.') {
+            return Self::Bit;
+        }
+
+        // The letter after the area names the width, e.g. the W in "IW64"
+        match address.chars().nth(1).map(|c| c.to_ascii_uppercase()) {
+            Some('W') => Self::Word,
+            Some('D') => Self::DWord,
+            Some('X') => Self::Bit,
+            _ => Self::Byte,
+        }
+    }
+}
+
+/// `PlcEntry::confidence` below which an entry is shown dimmed, and the default
+/// threshold of the "Low confidence" filter and the validation warning
+pub const LOW_CONFIDENCE: f32 = 0.5;
+
+fn default_confidence() -> f32 {
+    1.0
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PlcEntry {
     pub address: String,
     pub symbol_name: String,
     pub data_type: PlcDataType,
     pub comment: String,
+    #[serde(default)]
+    pub comment_edited: bool, // Typed by the user, `carry_comments` keeps it in the next extraction
     pub page: String,
     pub selected: bool,
+    #[serde(default)]
+    pub removed: bool, // Kept by a merge although the newer extraction no longer has the address
+    #[serde(default)]
+    pub tags: Vec<String>, // User labels like "Critical", colors are kept in `AppConfig::tag_colors`
+    #[serde(default)]
+    pub entry_status: EntryStatus, // Set by `PlcTable::mark_status`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub module: Option<String>, // Hardware module near the address, e.g. "ET 200SP IM155-6 PN"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slot: Option<u32>,
+    #[serde(default = "default_confidence")]
+    pub confidence: f32, // How sure the parser was of the symbol name, 0.0 to 1.0, 1.0 for imported and typed entries
 }
 
 impl PlcEntry {
@@ -62,29 +118,245 @@ impl PlcEntry {
             symbol_name,
             data_type,
             comment: String::new(),
+            comment_edited: false,
             page,
             selected: false,
+            removed: false,
+            tags: Vec::new(),
+            entry_status: EntryStatus::Unchanged,
+            module: None,
+            slot: None,
+            confidence: default_confidence(),
+        }
+    }
+
+    /// Add a tag unless the entry already has it, returns whether it was added
+    pub fn add_tag(&mut self, tag: &str) -> bool {
+        let tag = tag.trim();
+        if tag.is_empty() || self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+            return false;
+        }
+        self.tags.push(tag.to_string());
+        true
+    }
+
+    pub fn remove_tag(&mut self, tag: &str) {
+        self.tags.retain(|t| t != tag);
+    }
+
+    /// Tags as one comma-separated cell, for exports
+    pub fn tags_text(&self) -> String {
+        self.tags.join(", ")
+    }
+
+    /// Module and slot for the Module column, e.g. "ET 200SP IM155-6 PN, slot 3"
+    pub fn module_text(&self) -> String {
+        match (&self.module, self.slot) {
+            (Some(module), Some(slot)) => format!("{}, slot {}", module, slot),
+            (Some(module), None) => module.clone(),
+            (None, Some(slot)) => format!("Slot {}", slot),
+            (None, None) => String::new(),
+        }
+    }
+
+    /// EPLAN device tag at the start of the symbol name, e.g. "-K1" in
+    /// "-K1 Motor contactor" or "=A1+B2-K1". `None` if the name has none.
+    pub fn device_tag(&self) -> Option<&str> {
+        let word = self.symbol_name.split_whitespace().next()?;
+        (word.len() > 1 && word.starts_with(['=', '+', '-'])).then_some(word)
+    }
+
+    /// Symbol name without the device tag, e.g. "Motor contactor"
+    pub fn function_text(&self) -> &str {
+        let name = self.symbol_name.trim();
+        match self.device_tag() {
+            Some(tag) => name[tag.len()..].trim_start(),
+            None => name,
         }
     }
 
+    /// Change the address and keep the data type in sync with it
+    pub fn set_address(&mut self, address: String) {
+        self.data_type = PlcDataType::from_address(&address);
+        self.address = address;
+    }
+
+    /// Match search box text, see `SearchQuery` for the advanced syntax
     pub fn matches_filter(&self, filter: &str) -> bool {
-        if filter.is_empty() {
+        match SearchQuery::parse(filter) {
+            Ok(query) => query.matches(self),
+            Err(_) => true, // Invalid regex, the search box shows the error
+        }
+    }
+
+    /// Case-insensitive substring match on the text columns
+    pub fn contains_text(&self, text: &str) -> bool {
+        if text.is_empty() {
             return true;
         }
 
-        let filter = filter.to_lowercase();
+        let filter = text.to_lowercase();
         self.address.to_lowercase().contains(&filter)
             || self.symbol_name.to_lowercase().contains(&filter)
             || self.comment.to_lowercase().contains(&filter)
             || self.page.to_lowercase().contains(&filter)
+            || self.tags.iter().any(|tag| tag.to_lowercase().contains(&filter))
+            || self.module.as_ref().is_some_and(|module| module.to_lowercase().contains(&filter))
+    }
+
+    /// Equal symbol name and comment, what a diff counts as unchanged.
+    /// Page, selection and flags don't matter.
+    pub fn same_content(&self, other: &PlcEntry) -> bool {
+        self.symbol_name == other.symbol_name && self.comment == other.comment
+    }
+}
+
+/// Differences between two extractions, entries are matched by address
+#[derive(Debug, Clone, Default)]
+pub struct TableDiff {
+    pub added: Vec<PlcEntry>,
+    pub removed: Vec<PlcEntry>,
+    pub modified: Vec<(PlcEntry, PlcEntry)>, // (old, new)
+}
+
+impl TableDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+}
+
+/// Change of an entry since a baseline table, see `PlcTable::mark_status`
+#[non_exhaustive]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub enum EntryStatus {
+    #[default]
+    Unchanged,
+    New,
+    Modified(String), // Symbol name in the baseline
+    Deleted,          // Only in the baseline, the rows of `PlcTable::deleted`
+}
+
+impl EntryStatus {
+    pub fn is_changed(&self) -> bool {
+        *self != Self::Unchanged
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Unchanged => "Unchanged",
+            Self::New => "New",
+            Self::Modified(_) => "Modified",
+            Self::Deleted => "Deleted",
+        }
+    }
+
+    /// Indicator color in the table, none for unchanged entries
+    pub fn color(&self) -> Option<egui::Color32> {
+        match self {
+            Self::Unchanged => None,
+            Self::New => Some(egui::Color32::from_rgb(76, 175, 80)),       // Green
+            Self::Modified(_) => Some(egui::Color32::from_rgb(255, 152, 0)), // Orange
+            Self::Deleted => Some(egui::Color32::from_rgb(158, 158, 158)),   // Gray
+        }
+    }
+}
+
+/// Options for the symbol name cleanup pass
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NameNormalization {
+    pub collapse_repeated_tokens: bool, // "Ventil Ventil 3.1 3.1" -> "Ventil 3.1"
+    pub transliterate_umlauts: bool,    // ä -> ae, ß -> ss
+    pub max_length: Option<usize>,      // Longer names are cut with "..."
+}
+
+impl Default for NameNormalization {
+    fn default() -> Self {
+        Self {
+            collapse_repeated_tokens: true,
+            transliterate_umlauts: false,
+            max_length: None,
+        }
+    }
+}
+
+impl NameNormalization {
+    pub fn normalize(&self, name: &str) -> String {
+        let mut tokens: Vec<&str> = name.split_whitespace().collect();
+
+        if self.collapse_repeated_tokens {
+            collapse_repeated_tokens(&mut tokens);
+        }
+
+        let mut normalized = tokens.join(" ");
+
+        if self.transliterate_umlauts {
+            normalized = transliterate_umlauts(&normalized);
+        }
+
+        if let Some(max_length) = self.max_length {
+            if normalized.chars().count() > max_length {
+                let keep = max_length.saturating_sub(3);
+                normalized = normalized.chars().take(keep).collect::<String>().trim_end().to_string();
+                normalized.push_str("...");
+            }
+        }
+
+        normalized
+    }
+}
+
+/// Remove immediately repeated token sequences, longest repetition first
+fn collapse_repeated_tokens(tokens: &mut Vec<&str>) {
+    let mut i = 0;
+    while i < tokens.len() {
+        let mut collapsed = false;
+        for n in (1..=(tokens.len() - i) / 2).rev() {
+            if tokens[i..i + n] == tokens[i + n..i + 2 * n] {
+                tokens.drain(i + n..i + 2 * n);
+                collapsed = true;
+                break;
+            }
+        }
+        if !collapsed {
+            i += 1;
+        }
     }
 }
 
+fn transliterate_umlauts(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            'ä' => result.push_str("ae"),
+            'ö' => result.push_str("oe"),
+            'ü' => result.push_str("ue"),
+            'Ä' => result.push_str("Ae"),
+            'Ö' => result.push_str("Oe"),
+            'Ü' => result.push_str("Ue"),
+            'ß' => result.push_str("ss"),
+            _ => result.push(ch),
+        }
+    }
+    result
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PlcTable {
     pub entries: Vec<PlcEntry>,
     pub project_name: String,
     pub extraction_date: chrono::DateTime<chrono::Local>,
+    #[serde(skip)]
+    pub dirty: bool, // Edited by hand since the last export or session save
+    #[serde(skip, default = "next_revision")]
+    pub revision: u64, // Changes when rows are added, removed or replaced, see `mark_changed`
+    #[serde(skip)]
+    pub deleted: Vec<PlcEntry>, // Baseline rows missing from `entries`, only shown, see `mark_status`
+}
+
+/// Unique revision for a new or changed table, so views can cache per revision
+pub fn next_revision() -> u64 {
+    static REVISION: AtomicU64 = AtomicU64::new(1);
+    REVISION.fetch_add(1, Ordering::Relaxed)
 }
 
 impl PlcTable {
@@ -93,9 +365,23 @@ impl PlcTable {
             entries: Vec::new(),
             project_name,
             extraction_date: chrono::Local::now(),
+            dirty: false,
+            revision: next_revision(),
+            deleted: Vec::new(),
         }
     }
 
+    /// Read a table written by `JsonExporter`, in any of its layouts
+    pub fn from_json_file(path: &Path) -> Result<PlcTable> {
+        crate::export::json::read_table(path)
+    }
+
+    /// Flag an edit that changed the rows, so cached filter and sort results are rebuilt
+    pub fn mark_changed(&mut self) {
+        self.dirty = true;
+        self.revision = next_revision();
+    }
+
     pub fn add_entry(&mut self, entry: PlcEntry) {
         self.entries.push(entry);
     }
@@ -120,6 +406,132 @@ impl PlcTable {
         }
     }
 
+    /// Insert an entry at `index`, appending if the index is past the end
+    pub fn insert_entry(&mut self, index: usize, entry: PlcEntry) {
+        let index = index.min(self.entries.len());
+        self.entries.insert(index, entry);
+        self.mark_changed();
+    }
+
+    pub fn remove_entry(&mut self, index: usize) -> Option<PlcEntry> {
+        if index >= self.entries.len() {
+            return None;
+        }
+
+        self.mark_changed();
+        Some(self.entries.remove(index))
+    }
+
+    /// Insert a copy of the entry at `index` directly below it
+    pub fn duplicate_entry(&mut self, index: usize) {
+        if let Some(entry) = self.entries.get(index) {
+            let mut copy = entry.clone();
+            copy.selected = false;
+            self.insert_entry(index + 1, copy);
+        }
+    }
+
+    /// Compare this (older) table against `other`. An entry counts as modified
+    /// when its symbol name or comment changed. Only the first entry per address is compared.
+    pub fn diff(&self, other: &PlcTable) -> TableDiff {
+        let mut old_by_address: HashMap<&str, &PlcEntry> = HashMap::new();
+        for entry in &self.entries {
+            old_by_address.entry(entry.address.as_str()).or_insert(entry);
+        }
+
+        let mut new_by_address: HashMap<&str, &PlcEntry> = HashMap::new();
+        for entry in &other.entries {
+            new_by_address.entry(entry.address.as_str()).or_insert(entry);
+        }
+
+        let mut diff = TableDiff::default();
+
+        for entry in &other.entries {
+            // Skip duplicate addresses, only the first one is matched
+            if !std::ptr::eq(new_by_address[entry.address.as_str()], entry) {
+                continue;
+            }
+
+            match old_by_address.get(entry.address.as_str()) {
+                Some(old) => {
+                    if !old.same_content(entry) {
+                        diff.modified.push(((*old).clone(), entry.clone()));
+                    }
+                }
+                None => diff.added.push(entry.clone()),
+            }
+        }
+
+        for entry in &self.entries {
+            if std::ptr::eq(old_by_address[entry.address.as_str()], entry)
+                && !new_by_address.contains_key(entry.address.as_str())
+            {
+                diff.removed.push(entry.clone());
+            }
+        }
+
+        diff
+    }
+
+    /// Set the status of every entry relative to `baseline`, e.g. the previous
+    /// extraction. Entries are matched by address (the first baseline entry per
+    /// address) and count as modified like in `diff`. Baseline addresses missing
+    /// here replace `deleted`, they are shown but not part of the table.
+    pub fn mark_status(&mut self, baseline: &PlcTable) {
+        let mut old_by_address: HashMap<&str, &PlcEntry> = HashMap::new();
+        for entry in &baseline.entries {
+            old_by_address.entry(entry.address.as_str()).or_insert(entry);
+        }
+
+        for entry in &mut self.entries {
+            entry.entry_status = match old_by_address.get(entry.address.as_str()) {
+                Some(old) if old.same_content(entry) => EntryStatus::Unchanged,
+                Some(old) => EntryStatus::Modified(old.symbol_name.clone()),
+                None => EntryStatus::New,
+            };
+        }
+
+        let mut seen: HashSet<&str> = self.entries.iter().map(|entry| entry.address.as_str()).collect();
+        let mut deleted = Vec::new();
+        for old in &baseline.entries {
+            if seen.insert(old.address.as_str()) {
+                deleted.push(PlcEntry {
+                    selected: false,
+                    entry_status: EntryStatus::Deleted,
+                    ..old.clone()
+                });
+            }
+        }
+        self.deleted = deleted;
+        self.revision = next_revision();
+    }
+
+    /// Clean up all symbol names, returns the number of entries that changed.
+    /// Names cut to the maximum length keep their original text in the comment.
+    pub fn normalize_names(&mut self, options: &NameNormalization) -> usize {
+        let mut changed = 0;
+
+        for entry in &mut self.entries {
+            let normalized = options.normalize(&entry.symbol_name);
+            if normalized == entry.symbol_name {
+                continue;
+            }
+
+            if normalized.ends_with("...") && !entry.comment.contains(&entry.symbol_name) {
+                entry.comment = if entry.comment.is_empty() {
+                    entry.symbol_name.clone()
+                } else {
+                    format!("{}; {}", entry.comment, entry.symbol_name)
+                };
+            }
+
+            entry.symbol_name = normalized;
+            changed += 1;
+        }
+
+        changed
+    }
+
     pub fn sort_by_address(&mut self) {
         self.entries.sort_by(|a, b| {
             natural_sort(&a.address, &b.address)
@@ -139,7 +551,7 @@ impl PlcTable {
     }
 }
 
-fn natural_sort(a: &str, b: &str) -> std::cmp::Ordering {
+pub fn natural_sort(a: &str, b: &str) -> std::cmp::Ordering {
     // Extract numbers from addresses for natural sorting
     let extract_nums = |s: &str| -> (String, Vec<u32>) {
         let mut prefix = String::new();
@@ -178,4 +590,226 @@ fn natural_sort(a: &str, b: &str) -> std::cmp::Ordering {
         std::cmp::Ordering::Equal => nums_a.cmp(&nums_b),
         other => other,
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table_with(name: &str) -> PlcTable {
+        let mut table = PlcTable::new("Test".to_string());
+        table.add_entry(PlcEntry::new("I0.0".to_string(), name.to_string(), "1".to_string()));
+        table
+    }
+
+    #[test]
+    fn test_row_editing_marks_table_dirty() {
+        let mut table = table_with("Start");
+        assert!(!table.dirty);
+
+        table.duplicate_entry(0);
+        assert_eq!(table.entries.len(), 2);
+        assert_eq!(table.entries[1].symbol_name, "Start");
+        assert!(table.dirty);
+
+        table.insert_entry(0, PlcEntry::new("Q0.0".to_string(), "Motor".to_string(), "2".to_string()));
+        assert_eq!(table.entries[0].address, "Q0.0");
+
+        let removed = table.remove_entry(2).unwrap();
+        assert_eq!(removed.address, "I0.0");
+        assert!(table.remove_entry(5).is_none());
+        assert_eq!(table.entries.len(), 2);
+    }
+
+    #[test]
+    fn test_device_tag() {
+        let entry = |name: &str| PlcEntry::new("I0.0".to_string(), name.to_string(), String::new());
+
+        assert_eq!(entry("-K1 Motor contactor").device_tag(), Some("-K1"));
+        assert_eq!(entry("-K1 Motor contactor").function_text(), "Motor contactor");
+        assert_eq!(entry("=A1+B2-K1").device_tag(), Some("=A1+B2-K1"));
+        assert_eq!(entry("=A1+B2-K1").function_text(), "");
+        assert_eq!(entry("Motor -K1").device_tag(), None);
+        assert_eq!(entry("- spare").device_tag(), None);
+        assert_eq!(entry("- spare").function_text(), "- spare");
+    }
+
+    #[test]
+    fn test_address_width() {
+        assert_eq!(AddressWidth::from_address("I0.0"), AddressWidth::Bit);
+        assert_eq!(AddressWidth::from_address("I0"), AddressWidth::Byte);
+        assert_eq!(AddressWidth::from_address("QB2"), AddressWidth::Byte);
+        assert_eq!(AddressWidth::from_address("IW64"), AddressWidth::Word);
+        assert_eq!(AddressWidth::from_address("MD20"), AddressWidth::DWord);
+    }
+
+    #[test]
+    fn test_add_tag_once() {
+        let mut entry = PlcEntry::new("Q0.0".to_string(), "Motor".to_string(), "1".to_string());
+        assert!(entry.add_tag(" Critical "));
+        assert!(!entry.add_tag("critical"));
+        assert!(!entry.add_tag(""));
+        entry.add_tag("Maintenance");
+        assert_eq!(entry.tags_text(), "Critical, Maintenance");
+
+        entry.remove_tag("Critical");
+        assert_eq!(entry.tags, vec!["Maintenance".to_string()]);
+    }
+
+    #[test]
+    fn test_set_address_updates_data_type() {
+        let mut entry = PlcEntry::new("I0.0".to_string(), "Start".to_string(), "1".to_string());
+        entry.set_address("Q4.1".to_string());
+        assert_eq!(entry.data_type, PlcDataType::Output);
+    }
+
+    #[test]
+    fn test_diff_matches_by_address() {
+        let mut old = PlcTable::new("Old".to_string());
+        old.add_entry(PlcEntry::new("I0.0".to_string(), "Start".to_string(), "1".to_string()));
+        old.add_entry(PlcEntry::new("I0.1".to_string(), "Stop".to_string(), "1".to_string()));
+        old.add_entry(PlcEntry::new("Q0.0".to_string(), "Motor".to_string(), "2".to_string()));
+
+        let mut new = PlcTable::new("New".to_string());
+        new.add_entry(PlcEntry::new("I0.0".to_string(), "Start".to_string(), "3".to_string()));
+        new.add_entry(PlcEntry::new("Q0.0".to_string(), "Motor Ein".to_string(), "2".to_string()));
+        new.add_entry(PlcEntry::new("Q0.1".to_string(), "Lampe".to_string(), "2".to_string()));
+
+        let diff = old.diff(&new);
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].address, "Q0.1");
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.removed[0].address, "I0.1");
+        assert_eq!(diff.modified.len(), 1);
+        assert_eq!(diff.modified[0].0.symbol_name, "Motor");
+        assert_eq!(diff.modified[0].1.symbol_name, "Motor Ein");
+
+        assert!(old.diff(&old).is_empty());
+
+        // A changed comment counts as modified, the page an entry moved to does not
+        new.entries[0].comment = "Green button".to_string();
+        let diff = old.diff(&new);
+        assert_eq!(diff.modified.len(), 2);
+        assert_eq!(diff.modified[0].0.address, "I0.0");
+        assert!(!old.entries[0].same_content(&new.entries[0]));
+        new.entries[0].comment.clear();
+        assert!(old.entries[0].same_content(&new.entries[0]));
+    }
+
+    #[test]
+    fn test_mark_status_against_baseline() {
+        let mut old = PlcTable::new("Old".to_string());
+        old.add_entry(PlcEntry::new("I0.0".to_string(), "Start".to_string(), "1".to_string()));
+        old.add_entry(PlcEntry::new("I0.1".to_string(), "Stop".to_string(), "1".to_string()));
+        old.add_entry(PlcEntry::new("Q0.0".to_string(), "Motor".to_string(), "2".to_string()));
+
+        let mut new = PlcTable::new("New".to_string());
+        new.add_entry(PlcEntry::new("I0.0".to_string(), "Start".to_string(), "3".to_string()));
+        new.add_entry(PlcEntry::new("Q0.0".to_string(), "Motor Ein".to_string(), "2".to_string()));
+        new.add_entry(PlcEntry::new("Q0.1".to_string(), "Lampe".to_string(), "2".to_string()));
+
+        new.mark_status(&old);
+        let statuses: Vec<(&str, &EntryStatus)> = new.entries.iter().map(|e| (e.address.as_str(), &e.entry_status)).collect();
+        assert_eq!(
+            statuses,
+            [
+                ("I0.0", &EntryStatus::Unchanged),
+                ("Q0.0", &EntryStatus::Modified("Motor".to_string())),
+                ("Q0.1", &EntryStatus::New),
+            ]
+        );
+        // The removed address is shown next to the table, not part of it
+        let deleted: Vec<(&str, &EntryStatus)> = new.deleted.iter().map(|e| (e.address.as_str(), &e.entry_status)).collect();
+        assert_eq!(deleted, [("I0.1", &EntryStatus::Deleted)]);
+        assert_eq!(new.entries.len(), 3);
+
+        // Marking again replaces the deleted rows instead of adding more
+        new.mark_status(&old);
+        assert_eq!(new.deleted.len(), 1);
+        new.mark_status(&new.clone());
+        assert!(new.entries.iter().all(|e| e.entry_status == EntryStatus::Unchanged));
+        assert!(new.deleted.is_empty());
+    }
+
+    #[test]
+    fn test_collapse_repeated_tokens() {
+        let options = NameNormalization::default();
+        assert_eq!(options.normalize("Ventil  Ventil 3.1  3.1 AUF"), "Ventil 3.1 AUF");
+        assert_eq!(options.normalize("Motor 1 Motor 1 Ein"), "Motor 1 Ein");
+        assert_eq!(options.normalize("Ventil 3.1 AUF"), "Ventil 3.1 AUF");
+    }
+
+    #[test]
+    fn test_whitespace_is_normalized() {
+        let options = NameNormalization {
+            collapse_repeated_tokens: false,
+            ..Default::default()
+        };
+        assert_eq!(options.normalize("  Pumpe \t 2   Ein "), "Pumpe 2 Ein");
+        assert_eq!(options.normalize("Aus Aus"), "Aus Aus");
+    }
+
+    #[test]
+    fn test_transliterate_umlauts() {
+        let options = NameNormalization {
+            transliterate_umlauts: true,
+            ..Default::default()
+        };
+        assert_eq!(options.normalize("Türschalter Öffner groß"), "Tuerschalter Oeffner gross");
+
+        let untouched = NameNormalization::default();
+        assert_eq!(untouched.normalize("Türschalter"), "Türschalter");
+    }
+
+    #[test]
+    fn test_max_length_keeps_original_in_comment() {
+        let options = NameNormalization {
+            max_length: Some(10),
+            ..Default::default()
+        };
+        let mut table = table_with("Druckschalter Kessel");
+
+        assert_eq!(table.normalize_names(&options), 1);
+        assert_eq!(table.entries[0].symbol_name, "Drucksc...");
+        assert_eq!(table.entries[0].comment, "Druckschalter Kessel");
+
+        // Running the pass again must not touch the entry
+        assert_eq!(table.normalize_names(&options), 0);
+    }
+
+    #[test]
+    fn test_max_length_appends_to_existing_comment() {
+        let options = NameNormalization {
+            max_length: Some(8),
+            ..Default::default()
+        };
+        let mut table = table_with("Endschalter oben");
+        table.entries[0].comment = "geprüft".to_string();
+
+        table.normalize_names(&options);
+        assert_eq!(table.entries[0].comment, "geprüft; Endschalter oben");
+    }
+
+    #[test]
+    fn test_from_json_file_reads_exported_table() {
+        use crate::export::{json::JsonExporter, Exporter};
+
+        let mut table = table_with("Start");
+        table.entries[0].comment = "Green button".to_string();
+        table.add_entry(PlcEntry::new("QW2".to_string(), "Valve".to_string(), "4".to_string()));
+
+        let path = std::env::temp_dir().join(format!("eview_json_import_{}.json", std::process::id()));
+        JsonExporter::new().export(&table, &path.to_string_lossy()).unwrap();
+        let loaded = PlcTable::from_json_file(&path);
+        let _ = std::fs::remove_file(&path);
+
+        let loaded = loaded.unwrap();
+        assert_eq!(loaded.project_name, "Test");
+        assert_eq!(loaded.entries.len(), 2);
+        assert_eq!(loaded.entries[0].comment, "Green button");
+        assert_eq!(loaded.entries[1].data_type, PlcDataType::Output);
+        assert!(!loaded.dirty);
+
+        assert!(PlcTable::from_json_file(Path::new("/nonexistent/table.json")).is_err());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,231 @@
+use anyhow::Result;
+use regex::{Regex, RegexBuilder};
+use super::PlcEntry;
+
+/// Parsed search box text.
+///
+/// Plain text matches any column as a case-insensitive substring. Two opt-in
+/// advanced forms are recognized:
+/// - `re:<pattern>` matches a case-insensitive regex against any column
+/// - column terms like `addr:I10 name:pump -name:reserve tag:critical`, where a leading `-`
+///   excludes matches and terms without a column match any column. All terms must hold.
+#[derive(Debug, Clone)]
+pub enum SearchQuery {
+    Plain(String),
+    Regex(Regex),
+    Terms(Vec<QueryTerm>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryTerm {
+    pub column: Option<QueryColumn>, // None matches any column
+    pub value: String,
+    pub negated: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QueryColumn {
+    Address,
+    Name,
+    Comment,
+    Page,
+    Type,
+    Tag,
+}
+
+impl QueryColumn {
+    fn from_prefix(prefix: &str) -> Option<Self> {
+        match prefix.to_lowercase().as_str() {
+            "addr" | "address" => Some(Self::Address),
+            "name" | "symbol" => Some(Self::Name),
+            "comment" => Some(Self::Comment),
+            "page" => Some(Self::Page),
+            "type" => Some(Self::Type),
+            "tag" | "tags" => Some(Self::Tag),
+            _ => None,
+        }
+    }
+
+    fn value(&self, entry: &PlcEntry) -> String {
+        match self {
+            Self::Address => entry.address.clone(),
+            Self::Name => entry.symbol_name.clone(),
+            Self::Comment => entry.comment.clone(),
+            Self::Page => entry.page.clone(),
+            Self::Type => entry.data_type.to_string(),
+            Self::Tag => entry.tags_text(),
+        }
+    }
+}
+
+impl Default for SearchQuery {
+    fn default() -> Self {
+        Self::Plain(String::new())
+    }
+}
+
+impl PartialEq for SearchQuery {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Plain(a), Self::Plain(b)) => a == b,
+            (Self::Regex(a), Self::Regex(b)) => a.as_str() == b.as_str(),
+            (Self::Terms(a), Self::Terms(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl SearchQuery {
+    /// Parse search box text, fails only on an invalid `re:` pattern
+    pub fn parse(text: &str) -> Result<Self> {
+        let trimmed = text.trim_start();
+
+        if let Some(pattern) = trimmed.strip_prefix("re:") {
+            let regex = RegexBuilder::new(pattern.trim())
+                .case_insensitive(true)
+                .build()?;
+            return Ok(Self::Regex(regex));
+        }
+
+        let terms: Vec<QueryTerm> = tokenize(text).iter().filter_map(|token| parse_term(token)).collect();
+        if terms.iter().any(|term| term.column.is_some()) {
+            Ok(Self::Terms(terms))
+        } else {
+            // No column terms: keep the plain substring search
+            Ok(Self::Plain(text.to_string()))
+        }
+    }
+
+    pub fn matches(&self, entry: &PlcEntry) -> bool {
+        match self {
+            Self::Plain(text) => entry.contains_text(text),
+            Self::Regex(regex) => {
+                regex.is_match(&entry.address)
+                    || regex.is_match(&entry.symbol_name)
+                    || regex.is_match(&entry.comment)
+                    || regex.is_match(&entry.page)
+                    || entry.tags.iter().any(|tag| regex.is_match(tag))
+            }
+            Self::Terms(terms) => terms.iter().all(|term| term.matches(entry)),
+        }
+    }
+}
+
+impl QueryTerm {
+    fn matches(&self, entry: &PlcEntry) -> bool {
+        let found = match self.column {
+            Some(column) => column.value(entry).to_lowercase().contains(&self.value.to_lowercase()),
+            None => entry.contains_text(&self.value),
+        };
+        found != self.negated
+    }
+}
+
+/// Split on whitespace, keeping double-quoted parts together (`name:"emergency stop"`)
+fn tokenize(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+
+    for c in text.chars() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            c if c.is_whitespace() && !in_quotes => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+
+    tokens
+}
+
+/// Parse one token, returns `None` for terms without a value (e.g. `addr:` while typing)
+fn parse_term(token: &str) -> Option<QueryTerm> {
+    let (negated, token) = match token.strip_prefix('-') {
+        Some(rest) if !rest.is_empty() => (true, rest),
+        _ => (false, token),
+    };
+
+    let (column, value) = match token.split_once(':') {
+        Some((prefix, value)) => match QueryColumn::from_prefix(prefix) {
+            Some(column) => (Some(column), value),
+            None => (None, token), // Not a column, e.g. "http://"
+        },
+        None => (None, token),
+    };
+
+    if value.is_empty() {
+        return None;
+    }
+
+    Some(QueryTerm {
+        column,
+        value: value.to_string(),
+        negated,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(address: &str, name: &str) -> PlcEntry {
+        PlcEntry::new(address.to_string(), name.to_string(), "1".to_string())
+    }
+
+    #[test]
+    fn test_parse_column_terms() {
+        let query = SearchQuery::parse("addr:I10 name:\"main pump\" -name:reserve motor").unwrap();
+        let SearchQuery::Terms(terms) = query else {
+            panic!("expected column terms");
+        };
+
+        assert_eq!(terms.len(), 4);
+        assert_eq!(terms[0], QueryTerm { column: Some(QueryColumn::Address), value: "I10".to_string(), negated: false });
+        assert_eq!(terms[1].value, "main pump");
+        assert_eq!(terms[2], QueryTerm { column: Some(QueryColumn::Name), value: "reserve".to_string(), negated: true });
+        assert_eq!(terms[3].column, None);
+    }
+
+    #[test]
+    fn test_plain_text_stays_plain() {
+        assert_eq!(SearchQuery::parse("Motor Pump").unwrap(), SearchQuery::Plain("Motor Pump".to_string()));
+        assert_eq!(SearchQuery::parse("-5").unwrap(), SearchQuery::Plain("-5".to_string()));
+        assert_eq!(SearchQuery::parse("http://x").unwrap(), SearchQuery::Plain("http://x".to_string()));
+    }
+
+    #[test]
+    fn test_column_terms_with_exclusion() {
+        let query = SearchQuery::parse("addr:I10. -addr:I10.7").unwrap();
+
+        assert!(query.matches(&entry("I10.0", "Start")));
+        assert!(!query.matches(&entry("I10.7", "Stop")));
+        assert!(!query.matches(&entry("I11.0", "Reset")));
+    }
+
+    #[test]
+    fn test_regex_mode() {
+        let query = SearchQuery::parse(r"re:^i10\.[0-6]$").unwrap();
+        assert!(query.matches(&entry("I10.3", "Start")));
+        assert!(!query.matches(&entry("I10.7", "Stop")));
+
+        assert!(SearchQuery::parse("re:(").is_err());
+    }
+
+    #[test]
+    fn test_tags_are_searched() {
+        let mut tagged = entry("Q4.0", "Motor");
+        tagged.add_tag("Critical");
+
+        assert!(tagged.matches_filter("critic"));
+        assert!(tagged.matches_filter("tag:critical"));
+        assert!(!entry("Q4.1", "Valve").matches_filter("tag:critical"));
+        assert!(tagged.matches_filter("re:^crit"));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,302 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+use super::{natural_sort, PlcDataType, PlcTable};
+
+/// Bit occupancy of the input or output area.
+///
+/// Only bit addresses like `I12.3` are counted, word and byte addresses
+/// (`IW64`, `QB2`) don't map to single bits and are left out. So are bytes
+/// above `MAX_COVERAGE_BYTE`, a mistyped address would allocate them all.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AreaCoverage {
+    pub prefix: char,        // 'I' or 'Q'
+    pub bytes: Vec<[u8; 8]>, // Distinct symbols per bit, indexed by byte number up to the highest used byte
+}
+
+/// Highest byte of the I/O area a CPU addresses (64 KB)
+const MAX_COVERAGE_BYTE: u32 = 65535;
+
+/// Run of unused bits, `start` and `end` are inclusive
+#[derive(Debug, Clone, PartialEq)]
+pub struct AddressGap {
+    pub start: String,
+    pub end: String,
+    pub bits: usize,
+}
+
+impl AddressGap {
+    /// "I0.3" for a single bit, "I0.3–I1.1" for a range
+    pub fn label(&self) -> String {
+        if self.bits == 1 {
+            self.start.clone()
+        } else {
+            format!("{}–{}", self.start, self.end)
+        }
+    }
+}
+
+/// Coverage and distribution figures of a table, see `PlcTable::statistics`
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableStatistics {
+    pub total: usize,
+    pub inputs: AreaCoverage,
+    pub outputs: AreaCoverage,
+    pub by_type: Vec<(PlcDataType, usize)>,
+    pub entries_per_page: Vec<(String, usize)>, // In natural page order
+    pub with_comment: usize,
+    pub duplicate_addresses: usize, // Addresses used by more than one entry
+}
+
+impl AreaCoverage {
+    fn new(prefix: char) -> Self {
+        Self { prefix, bytes: Vec::new() }
+    }
+
+    pub fn bit_name(&self, byte: usize, bit: usize) -> String {
+        format!("{}{}.{}", self.prefix, byte, bit)
+    }
+
+    pub fn used_bits(&self) -> usize {
+        self.bytes.iter().flatten().filter(|&&symbols| symbols > 0).count()
+    }
+
+    /// Runs of unused bits between the first and the last used bit
+    pub fn gaps(&self) -> Vec<AddressGap> {
+        let bits: Vec<u8> = self.bytes.iter().flatten().copied().collect();
+        let Some(first) = bits.iter().position(|&symbols| symbols > 0) else {
+            return Vec::new();
+        };
+
+        let mut gaps = Vec::new();
+        let mut gap_start = None;
+
+        for (index, &symbols) in bits.iter().enumerate().skip(first) {
+            match (symbols, gap_start) {
+                (0, None) => gap_start = Some(index),
+                (0, Some(_)) => {}
+                (_, Some(start)) => {
+                    gaps.push(AddressGap {
+                        start: self.bit_name(start / 8, start % 8),
+                        end: self.bit_name((index - 1) / 8, (index - 1) % 8),
+                        bits: index - start,
+                    });
+                    gap_start = None;
+                }
+                (_, None) => {}
+            }
+        }
+
+        // Unused bits after the last used one are not a gap
+        gaps
+    }
+
+    /// Bits that carry more than one symbol name
+    pub fn double_assigned(&self) -> Vec<String> {
+        let mut addresses = Vec::new();
+        for (byte, bits) in self.bytes.iter().enumerate() {
+            for (bit, &symbols) in bits.iter().enumerate() {
+                if symbols > 1 {
+                    addresses.push(self.bit_name(byte, bit));
+                }
+            }
+        }
+        addresses
+    }
+}
+
+impl PlcTable {
+    pub fn statistics(&self) -> TableStatistics {
+        // Symbol names per bit, the same symbol on several pages counts once
+        let mut symbols: HashMap<(char, u32, u8), HashSet<&str>> = HashMap::new();
+        let mut pages: HashMap<&str, usize> = HashMap::new();
+        let mut addresses: HashMap<&str, usize> = HashMap::new();
+
+        for entry in &self.entries {
+            *addresses.entry(entry.address.trim()).or_default() += 1;
+            if let Some(bit) = bit_address(&entry.address) {
+                symbols.entry(bit).or_default().insert(entry.symbol_name.trim());
+            }
+            *pages.entry(entry.page.trim()).or_default() += 1;
+        }
+
+        let mut inputs = AreaCoverage::new('I');
+        let mut outputs = AreaCoverage::new('Q');
+        for ((prefix, byte, bit), names) in symbols {
+            let area = if prefix == 'I' { &mut inputs } else { &mut outputs };
+            let byte = byte as usize;
+            if area.bytes.len() <= byte {
+                area.bytes.resize(byte + 1, [0; 8]);
+            }
+            area.bytes[byte][bit as usize] = names.len().min(u8::MAX as usize) as u8;
+        }
+
+        let by_type = [PlcDataType::Input, PlcDataType::Output, PlcDataType::Memory, PlcDataType::Unknown]
+            .into_iter()
+            .map(|data_type| {
+                let count = self.entries.iter().filter(|e| e.data_type == data_type).count();
+                (data_type, count)
+            })
+            .collect();
+
+        let mut entries_per_page: Vec<(String, usize)> = pages
+            .into_iter()
+            .map(|(page, count)| (page.to_string(), count))
+            .collect();
+        entries_per_page.sort_by(|a, b| natural_sort(&a.0, &b.0));
+
+        TableStatistics {
+            total: self.entries.len(),
+            inputs,
+            outputs,
+            by_type,
+            entries_per_page,
+            with_comment: self.entries.iter().filter(|e| !e.comment.trim().is_empty()).count(),
+            duplicate_addresses: addresses.values().filter(|&&count| count > 1).count(),
+        }
+    }
+}
+
+impl TableStatistics {
+    /// Plain text summary for status reports
+    pub fn summary(&self, project_name: &str) -> String {
+        let mut text = String::new();
+
+        let _ = writeln!(text, "Statistics for {} ({} entries)", project_name, self.total);
+
+        let types: Vec<String> = self.by_type
+            .iter()
+            .map(|(data_type, count)| format!("{} {}", data_type, count))
+            .collect();
+        let _ = writeln!(text, "Types: {}", types.join(", "));
+        let _ = writeln!(
+            text,
+            "Comments: {} with, {} without; {} duplicate addresses",
+            self.with_comment,
+            self.total - self.with_comment,
+            self.duplicate_addresses
+        );
+
+        for (name, area) in [("Inputs", &self.inputs), ("Outputs", &self.outputs)] {
+            let gaps = area.gaps();
+            let double_assigned = area.double_assigned();
+
+            if area.bytes.is_empty() {
+                let _ = writeln!(text, "{}: no bit addresses", name);
+                continue;
+            }
+
+            let _ = writeln!(
+                text,
+                "{}: {} of {} bits used in {}B0..{}B{}, {} gaps, {} double-assigned",
+                name,
+                area.used_bits(),
+                area.bytes.len() * 8,
+                area.prefix,
+                area.prefix,
+                area.bytes.len() - 1,
+                gaps.len(),
+                double_assigned.len()
+            );
+            if !gaps.is_empty() {
+                let labels: Vec<String> = gaps.iter().map(AddressGap::label).collect();
+                let _ = writeln!(text, "  Gaps: {}", labels.join(", "));
+            }
+            if !double_assigned.is_empty() {
+                let _ = writeln!(text, "  Double-assigned: {}", double_assigned.join(", "));
+            }
+        }
+
+        let _ = writeln!(text, "Entries per page:");
+        for (page, count) in &self.entries_per_page {
+            let page = if page.is_empty() { "(no page)" } else { page };
+            let _ = writeln!(text, "  {}: {}", page, count);
+        }
+
+        text
+    }
+}
+
+/// Area, byte and bit of an input or output bit address like "I12.3"
+fn bit_address(address: &str) -> Option<(char, u32, u8)> {
+    let address = address.trim();
+    let prefix = address.chars().next().filter(|c| matches!(c, 'I' | 'Q'))?;
+    let (byte, bit) = address[1..].split_once('.')?;
+    let byte = byte.parse().ok().filter(|&byte| byte <= MAX_COVERAGE_BYTE)?;
+    let bit: u8 = bit.parse().ok()?;
+
+    (bit < 8).then_some((prefix, byte, bit))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::PlcEntry;
+
+    fn table(entries: &[(&str, &str, &str)]) -> PlcTable {
+        let mut table = PlcTable::new("Test".to_string());
+        for (address, name, page) in entries {
+            table.add_entry(PlcEntry::new(address.to_string(), name.to_string(), page.to_string()));
+        }
+        table
+    }
+
+    #[test]
+    fn test_coverage_gaps_and_double_assigned_bits() {
+        let table = table(&[
+            ("I0.0", "Start", "1"),
+            ("I0.0", "Start", "2"), // Same symbol on another page
+            ("I0.1", "Stop", "1"),
+            ("I0.1", "Reset", "3"),
+            ("I1.2", "Door", "10"),
+            ("IW64", "Pressure", "2"),
+            ("Q0.0", "Motor", "2"),
+        ]);
+        let stats = table.statistics();
+
+        assert_eq!(stats.inputs.bytes.len(), 2);
+        assert_eq!(stats.inputs.used_bits(), 3);
+        assert_eq!(stats.inputs.double_assigned(), vec!["I0.1"]);
+        assert_eq!(
+            stats.inputs.gaps(),
+            vec![AddressGap { start: "I0.2".to_string(), end: "I1.1".to_string(), bits: 8 }]
+        );
+
+        assert_eq!(stats.outputs.used_bits(), 1);
+        assert!(stats.outputs.gaps().is_empty());
+
+        assert_eq!(stats.by_type[0], (PlcDataType::Input, 6));
+        let pages: Vec<&str> = stats.entries_per_page.iter().map(|(page, _)| page.as_str()).collect();
+        assert_eq!(pages, vec!["1", "2", "3", "10"]);
+        assert_eq!(stats.entries_per_page[1].1, 3);
+        assert_eq!(stats.duplicate_addresses, 2); // I0.0 and I0.1
+        assert_eq!(stats.with_comment, 0);
+    }
+
+    #[test]
+    fn test_summary_text() {
+        let table = table(&[("I0.0", "Start", "1"), ("I0.2", "Stop", "1"), ("M5.0", "Flag", "")]);
+        let summary = table.statistics().summary("P-100");
+
+        assert!(summary.starts_with("Statistics for P-100 (3 entries)"));
+        assert!(summary.contains("Types: Input 2, Output 0, Memory 1, Unknown 0"));
+        assert!(summary.contains("Comments: 0 with, 3 without; 0 duplicate addresses"));
+        assert!(summary.contains("Inputs: 2 of 8 bits used in IB0..IB0, 1 gaps, 0 double-assigned"));
+        assert!(summary.contains("  Gaps: I0.1\n"));
+        assert!(summary.contains("Outputs: no bit addresses"));
+        assert!(summary.contains("  (no page): 1"));
+    }
+
+    #[test]
+    fn test_bit_address() {
+        assert_eq!(bit_address("I12.3"), Some(('I', 12, 3)));
+        assert_eq!(bit_address(" Q0.7 "), Some(('Q', 0, 7)));
+        assert_eq!(bit_address("I0.8"), None);
+        assert_eq!(bit_address("IW64"), None);
+        assert_eq!(bit_address("M0.0"), None);
+        assert_eq!(bit_address("I65535.0"), Some(('I', 65535, 0)));
+        assert_eq!(bit_address("I4000000000.0"), None); // Not worth a 32 GB grid
+
+        let table = table(&[("I0.0", "Start", "1"), ("Q4000000000.1", "Typo", "1")]);
+        assert!(table.statistics().outputs.bytes.is_empty());
+    }
+}
\ No newline at end of file
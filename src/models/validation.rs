@@ -0,0 +1,383 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use super::{PlcDataType, PlcTable, LOW_CONFIDENCE};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum IssueSeverity {
+    Warning,
+    Error,
+}
+
+impl IssueSeverity {
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Self::Warning => "⚠️",
+            Self::Error => "❌",
+        }
+    }
+}
+
+impl fmt::Display for IssueSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Warning => write!(f, "Warning"),
+            Self::Error => write!(f, "Error"),
+        }
+    }
+}
+
+/// What a `ValidationIssue` is about, independent of its message text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IssueKind {
+    DuplicateAddress,
+    EmptyName,
+    NotAName, // A column header or other text without letters
+    ShortName,
+    NameTooLong,
+    InvalidCharacters,
+    NonAsciiName,
+    AddressFormat,
+    AddressRange,
+    Removed,
+    LowConfidence,
+}
+
+/// A suspicious entry found by `PlcTable::validate`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationIssue {
+    pub entry_index: usize,
+    pub severity: IssueSeverity,
+    pub kind: IssueKind,
+    pub address: String,
+    pub message: String,
+}
+
+/// All issues of a table, in entry order
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ValidationReport {
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    pub fn is_empty(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.issues.len()
+    }
+
+    pub fn errors(&self) -> usize {
+        self.issues.iter().filter(|i| i.severity == IssueSeverity::Error).count()
+    }
+
+    pub fn warnings(&self) -> usize {
+        self.len() - self.errors()
+    }
+}
+
+/// Longest symbol name STEP 7 accepts
+pub const MAX_SYMBOL_NAME_LENGTH: usize = 24;
+
+/// Column headers of symbol tables, lowercase, picked up as a name from a table on the page
+const HEADER_NAMES: [&str; 16] = [
+    "address", "adresse", "operand", "symbol", "symbol name", "symbolname", "symbolischer name", "name",
+    "comment", "kommentar", "function text", "funktionstext", "data type", "datentyp", "page", "seite",
+];
+
+/// User-configurable validation thresholds
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ValidationRules {
+    pub min_name_length: usize,
+    pub min_byte_address: u32,
+    pub max_byte_address: u32,
+    pub strict_names: bool, // Flag non-ASCII characters in symbol names
+    #[serde(default = "default_min_confidence")]
+    pub min_confidence: f32, // Flag entries the parser was less sure about, see `PlcEntry::confidence`
+}
+
+fn default_min_confidence() -> f32 {
+    LOW_CONFIDENCE
+}
+
+impl Default for ValidationRules {
+    fn default() -> Self {
+        Self {
+            min_name_length: 3,
+            min_byte_address: 0,
+            max_byte_address: 1023,
+            strict_names: false,
+            min_confidence: default_min_confidence(),
+        }
+    }
+}
+
+impl PlcTable {
+    pub fn validate(&self, rules: &ValidationRules) -> ValidationReport {
+        let mut issues = Vec::new();
+
+        // Collect all names per address to detect conflicting mappings
+        let mut names_by_address: HashMap<&str, Vec<&str>> = HashMap::new();
+        for entry in &self.entries {
+            names_by_address
+                .entry(entry.address.as_str())
+                .or_default()
+                .push(entry.symbol_name.trim());
+        }
+
+        for (index, entry) in self.entries.iter().enumerate() {
+            let name = entry.symbol_name.trim();
+            let mut push = |severity, kind, message: String| {
+                issues.push(ValidationIssue {
+                    entry_index: index,
+                    severity,
+                    kind,
+                    address: entry.address.clone(),
+                    message,
+                });
+            };
+
+            if let Some(names) = names_by_address.get(entry.address.as_str()) {
+                let distinct: HashSet<&str> = names.iter().copied().collect();
+                if distinct.len() > 1 {
+                    push(
+                        IssueSeverity::Error,
+                        IssueKind::DuplicateAddress,
+                        format!("Address {} is mapped to {} different symbols", entry.address, distinct.len()),
+                    );
+                }
+            }
+
+            if name.is_empty() {
+                push(IssueSeverity::Error, IssueKind::EmptyName, "Symbol name is empty".to_string());
+            } else if HEADER_NAMES.contains(&name.to_lowercase().as_str()) {
+                push(IssueSeverity::Warning, IssueKind::NotAName, format!("Symbol name '{}' is a column header", name));
+            } else if !name.chars().any(char::is_alphabetic) {
+                push(IssueSeverity::Warning, IssueKind::NotAName, format!("Symbol name '{}' has no letters", name));
+            } else if name.chars().count() < rules.min_name_length {
+                push(
+                    IssueSeverity::Warning,
+                    IssueKind::ShortName,
+                    format!("Symbol name '{}' is shorter than {} characters", name, rules.min_name_length),
+                );
+            } else if name.chars().count() > MAX_SYMBOL_NAME_LENGTH {
+                push(
+                    IssueSeverity::Warning,
+                    IssueKind::NameTooLong,
+                    format!("Symbol name '{}' is longer than {} characters (STEP 7 limit)", name, MAX_SYMBOL_NAME_LENGTH),
+                );
+            }
+
+            let mut invalid: Vec<char> = Vec::new();
+            for c in name.chars().filter(|c| !c.is_alphanumeric() && *c != '_') {
+                if !invalid.contains(&c) {
+                    invalid.push(c);
+                }
+            }
+            if !invalid.is_empty() {
+                let listed: Vec<String> = invalid.iter().map(|c| format!("'{}'", c)).collect();
+                push(
+                    IssueSeverity::Warning,
+                    IssueKind::InvalidCharacters,
+                    format!("Symbol name '{}' contains invalid characters: {}", name, listed.join(" ")),
+                );
+            }
+
+            if !matches_data_type(&entry.address, &entry.data_type) {
+                let message = match &entry.data_type {
+                    PlcDataType::Unknown => format!("Address '{}' is not an input, output or memory address", entry.address),
+                    data_type => format!("Address '{}' is not a valid {} address", entry.address, data_type),
+                };
+                push(IssueSeverity::Error, IssueKind::AddressFormat, message);
+            } else if let Some(byte) = byte_address(&entry.address) {
+                if byte < rules.min_byte_address || byte > rules.max_byte_address {
+                    push(
+                        IssueSeverity::Warning,
+                        IssueKind::AddressRange,
+                        format!(
+                            "Address {} is outside the range {}..{}",
+                            entry.address, rules.min_byte_address, rules.max_byte_address
+                        ),
+                    );
+                }
+            }
+
+            if entry.removed {
+                push(
+                    IssueSeverity::Warning,
+                    IssueKind::Removed,
+                    format!("Address {} is no longer in the latest extraction", entry.address),
+                );
+            }
+
+            if entry.confidence < rules.min_confidence {
+                push(
+                    IssueSeverity::Warning,
+                    IssueKind::LowConfidence,
+                    format!("Symbol name '{}' has a low extraction confidence ({:.2})", name, entry.confidence),
+                );
+            }
+
+            if rules.strict_names && !name.is_ascii() {
+                push(
+                    IssueSeverity::Warning,
+                    IssueKind::NonAsciiName,
+                    format!("Symbol name '{}' contains non-ASCII characters", name),
+                );
+            }
+        }
+
+        ValidationReport { issues }
+    }
+}
+
+/// Whether `address` is an input, output or memory address, see `matches_data_type`
+pub fn is_plc_address(address: &str) -> bool {
+    matches_data_type(address, &PlcDataType::from_address(address))
+}
+
+/// Whether `address` is a bit ("I12.3") or byte/word/double word ("IW64")
+/// address in the area of `data_type`
+fn matches_data_type(address: &str, data_type: &PlcDataType) -> bool {
+    let area = match data_type {
+        PlcDataType::Input => 'I',
+        PlcDataType::Output => 'Q',
+        PlcDataType::Memory => 'M',
+        PlcDataType::Unknown => return false,
+    };
+
+    let Some(rest) = address.strip_prefix(area) else {
+        return false;
+    };
+    let is_number = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+
+    if let Some(byte) = rest.strip_prefix(['B', 'W', 'D']) {
+        return is_number(byte);
+    }
+    match rest.split_once('.') {
+        Some((byte, bit)) => is_number(byte) && bit.len() == 1 && bit.parse::<u8>().is_ok_and(|bit| bit < 8),
+        None => false,
+    }
+}
+
+/// Byte part of an address, e.g. 12 for "I12.3" or 64 for "IW64"
+fn byte_address(address: &str) -> Option<u32> {
+    let digits: String = address
+        .trim_start_matches(|c: char| c.is_ascii_alphabetic())
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect();
+
+    digits.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::PlcEntry;
+
+    fn table(entries: &[(&str, &str)]) -> PlcTable {
+        let mut table = PlcTable::new("Test".to_string());
+        for (address, name) in entries {
+            table.add_entry(PlcEntry::new(address.to_string(), name.to_string(), "1".to_string()));
+        }
+        table
+    }
+
+    #[test]
+    fn test_conflicting_duplicate_addresses() {
+        let table = table(&[("I0.0", "Start"), ("I0.0", "Stop"), ("I0.1", "Reset"), ("I0.1", "Reset")]);
+        let report = table.validate(&ValidationRules::default());
+
+        let flagged: Vec<usize> = report.issues.iter().map(|i| i.entry_index).collect();
+        assert_eq!(flagged, vec![0, 1]);
+        assert!(report.issues.iter().all(|i| i.severity == IssueSeverity::Error && i.kind == IssueKind::DuplicateAddress));
+        assert_eq!(report.issues[1].address, "I0.0");
+    }
+
+    #[test]
+    fn test_conflicts_count_distinct_names() {
+        let table = table(&[("I0.0", "Start"), ("I0.0", "Start"), ("I0.0", "Stop")]);
+        let report = table.validate(&ValidationRules::default());
+
+        assert_eq!(report.len(), 3);
+        assert!(report.issues[0].message.ends_with("mapped to 2 different symbols"));
+    }
+
+    #[test]
+    fn test_header_and_non_data_names() {
+        let table = table(&[("I0.0", "Symbolname"), ("I0.1", "12"), ("I0.2", "Start_1")]);
+        let report = table.validate(&ValidationRules::default());
+
+        let kinds: Vec<(usize, IssueKind)> = report.issues.iter().map(|i| (i.entry_index, i.kind)).collect();
+        assert_eq!(kinds, vec![(0, IssueKind::NotAName), (1, IssueKind::NotAName)]);
+        assert!(report.issues[0].message.contains("column header"));
+    }
+
+    #[test]
+    fn test_empty_and_short_names() {
+        let table = table(&[("I0.0", ""), ("I0.1", "AB"), ("I0.2", "Valid")]);
+        let issues = table.validate(&ValidationRules::default()).issues;
+
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].severity, IssueSeverity::Error);
+        assert_eq!(issues[1].entry_index, 1);
+        assert_eq!(issues[1].severity, IssueSeverity::Warning);
+    }
+
+    #[test]
+    fn test_address_range_and_strict_names() {
+        let rules = ValidationRules {
+            max_byte_address: 10,
+            strict_names: true,
+            ..Default::default()
+        };
+        let table = table(&[("IW64", "Analog_Wert"), ("Q1.0", "Tür_zu")]);
+        let issues = table.validate(&rules).issues;
+
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].entry_index, 0);
+        assert!(issues[0].message.contains("outside"));
+        assert_eq!(issues[1].entry_index, 1);
+        assert!(issues[1].message.contains("non-ASCII"));
+    }
+
+    #[test]
+    fn test_low_confidence() {
+        let mut table = table(&[("I0.0", "Start"), ("I0.1", "Stop"), ("I0.2", "Reset")]);
+        table.entries[1].confidence = 0.4;
+        table.entries[2].confidence = 0.7;
+
+        let issues = table.validate(&ValidationRules::default()).issues;
+        assert_eq!(issues.len(), 1);
+        assert_eq!((issues[0].entry_index, issues[0].kind, issues[0].severity), (1, IssueKind::LowConfidence, IssueSeverity::Warning));
+
+        let rules = ValidationRules { min_confidence: 0.8, ..Default::default() };
+        assert_eq!(table.validate(&rules).issues.len(), 2);
+    }
+
+    #[test]
+    fn test_step7_name_rules() {
+        let table = table(&[("I0.0", "Conveyor_belt_2_running_fwd"), ("I0.1", "Start-Button 1"), ("I0.2", "Start_1")]);
+        let report = table.validate(&ValidationRules::default());
+
+        let kinds: Vec<(usize, IssueKind)> = report.issues.iter().map(|i| (i.entry_index, i.kind)).collect();
+        assert_eq!(kinds, vec![(0, IssueKind::NameTooLong), (1, IssueKind::InvalidCharacters)]);
+        assert!(report.issues[1].message.ends_with("'-' ' '"));
+        assert_eq!((report.errors(), report.warnings()), (0, 2));
+    }
+
+    #[test]
+    fn test_address_format() {
+        let mut table = table(&[("I0.8", "Start"), ("QW2", "Valve"), ("MD10", "Counter"), ("X1", "Other"), ("I1", "Stop")]);
+        table.entries[1].data_type = PlcDataType::Input;
+        let report = table.validate(&ValidationRules::default());
+
+        let flagged: Vec<usize> = report.issues.iter().map(|i| i.entry_index).collect();
+        assert_eq!(flagged, vec![0, 1, 3, 4]);
+        assert!(report.issues.iter().all(|i| i.kind == IssueKind::AddressFormat));
+        assert_eq!(report.errors(), 4);
+        assert!(report.issues[1].message.contains("valid Input address"));
+    }
+}
@@ -0,0 +1,71 @@
+use anyhow::{Context, Result};
+use crate::chromedriver_manager::proxied_client_builder;
+use serde::Serialize;
+use std::time::Duration;
+
+/// Kind of event reported to the webhook
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEventKind {
+    ExtractionComplete,
+    ExtractionError,
+}
+
+/// JSON payload POSTed to the configured webhook URL
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookEvent {
+    pub event: WebhookEventKind,
+    pub project: String,
+    pub entry_count: u32,
+    pub duration_secs: f64,
+    pub timestamp: String,
+    pub error: Option<String>,
+}
+
+impl WebhookEvent {
+    pub fn completed(project: &str, entry_count: usize, duration: Duration) -> Self {
+        Self {
+            event: WebhookEventKind::ExtractionComplete,
+            project: project.to_string(),
+            entry_count: entry_count as u32,
+            duration_secs: duration.as_secs_f64(),
+            timestamp: chrono::Local::now().to_rfc3339(),
+            error: None,
+        }
+    }
+
+    pub fn failed(project: &str, error: String, duration: Duration) -> Self {
+        Self {
+            event: WebhookEventKind::ExtractionError,
+            project: project.to_string(),
+            entry_count: 0,
+            duration_secs: duration.as_secs_f64(),
+            timestamp: chrono::Local::now().to_rfc3339(),
+            error: Some(error),
+        }
+    }
+
+    /// Sample payload used by the "Test" button in the settings
+    pub fn sample(project: &str) -> Self {
+        Self::completed(project, 42, Duration::from_secs(30))
+    }
+}
+
+/// POST the event as JSON to the given URL, through `proxy` like the
+/// ChromeDriver download
+pub async fn send_webhook(url: &str, proxy: Option<&str>, event: WebhookEvent) -> Result<()> {
+    let client = proxied_client_builder(proxy)?
+        .timeout(Duration::from_secs(10))
+        .build()?;
+
+    client
+        .post(url)
+        .json(&event)
+        .send()
+        .await
+        .with_context(|| format!("Failed to reach webhook {}", url))?
+        .error_for_status()
+        .context("Webhook returned an error status")?;
+
+    Ok(())
+}
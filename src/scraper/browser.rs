@@ -1,13 +1,53 @@
 use anyhow::{Result, Context};
+use serde::{Deserialize, Serialize};
+use thirtyfour::common::command::{Command, ExtensionCommand};
 use thirtyfour::prelude::*;
-use tokio::time::{sleep, Duration};
+use thirtyfour::RequestMethod;
+use tokio::time::Duration;
+use crate::chromedriver_manager::driver_ready;
+use super::{wait_until, ScraperConfig, WAIT_POLL_INTERVAL};
+
+/// Port of the ChromeDriver started by `ChromeDriverManager`
+pub const DRIVER_PORT: u16 = 9516;
 
 pub struct BrowserDriver {
     driver: WebDriver,
 }
 
+/// One message from the page's JavaScript console, as returned by ChromeDriver
+#[derive(Debug, Clone, Deserialize)]
+pub struct BrowserConsoleEntry {
+    pub level: String, // "SEVERE", "WARNING", "INFO", ...
+    pub message: String,
+    pub timestamp: u64, // Milliseconds since the epoch
+}
+
+/// A browser cookie as saved between runs, see `session_cookies`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Cookie(pub thirtyfour::Cookie);
+
+/// ChromeDriver's legacy log endpoint, which thirtyfour has no method for
+#[derive(Debug)]
+struct GetBrowserLog;
+
+impl ExtensionCommand for GetBrowserLog {
+    fn parameters_json(&self) -> Option<serde_json::Value> {
+        Some(serde_json::json!({ "type": "browser" }))
+    }
+
+    fn method(&self) -> RequestMethod {
+        RequestMethod::Post
+    }
+
+    fn endpoint(&self) -> String {
+        "/se/log".to_string()
+    }
+}
+
 impl BrowserDriver {
-    pub async fn new(headless: bool) -> Result<Self> {
+    pub async fn new(config: &ScraperConfig) -> Result<Self> {
+        let headless = config.headless;
         println!("DEBUG: BrowserDriver::new() - Starting with headless={}", headless);
 
         // Create Chrome capabilities with proper arguments
@@ -21,44 +61,55 @@ impl BrowserDriver {
             "--disable-web-security".to_string(),
             "--disable-features=VizDisplayCompositor".to_string(),
             "--remote-debugging-port=9222".to_string(),
-            "--window-size=1920,1080".to_string(),
+            format!("--window-size={},{}", config.window_width, config.window_height),
         ];
 
         if headless {
             chrome_args.push("--headless".to_string());
         }
 
+        if let Some(proxy) = config.proxy.as_deref().filter(|p| !p.trim().is_empty()) {
+            chrome_args.push(format!("--proxy-server={}", chrome_proxy_server(proxy)));
+        }
+
+        if let Some(user_agent) = config.user_agent.as_deref().map(str::trim).filter(|ua| !ua.is_empty()) {
+            chrome_args.push(format!("--user-agent={}", user_agent));
+        }
+
         // Add Chrome arguments to capabilities
         let args_count = chrome_args.len();
         for arg in chrome_args {
             caps.add_arg(&arg)?;
         }
 
+        // Let ChromeDriver collect the console output for get_console_logs()
+        caps.set_base_capability("goog:loggingPrefs", serde_json::json!({ "browser": "ALL" }))?;
+
         println!("DEBUG: BrowserDriver::new() - Chrome capabilities created with {} args", args_count);
 
-        // Connect to ChromeDriver with reduced retry logic
-        let mut last_error = None;
-        for attempt in 1..=3 {
-            println!("DEBUG: BrowserDriver::new() - Connection attempt {}/3", attempt);
-            match WebDriver::new("http://localhost:9516", caps.clone()).await {
-                Ok(driver) => {
-                    println!("DEBUG: BrowserDriver::new() - Successfully connected to ChromeDriver");
-                    return Ok(Self { driver });
-                }
-                Err(e) => {
-                    println!("DEBUG: BrowserDriver::new() - Attempt {} failed: {}", attempt, e);
-                    last_error = Some(e);
-                    if attempt < 3 {
-                        // Short delay between attempts
-                        let delay = Duration::from_millis(1000);
-                        tokio::time::sleep(delay).await;
-                    }
-                }
-            }
+        let local_url = format!("http://localhost:{}", DRIVER_PORT);
+        let driver_url = config.external_chromedriver_url.as_deref().unwrap_or(&local_url);
+        let hint = match &config.external_chromedriver_url {
+            Some(url) => format!("Is the external ChromeDriver running at {}?", url),
+            None => format!("ChromeDriver should have been started automatically on port {}", DRIVER_PORT),
+        };
+
+        // Only ask for a browser session once ChromeDriver answers
+        let timeout = Duration::from_secs(config.wait_timeouts.driver_secs);
+        if !wait_until(timeout, WAIT_POLL_INTERVAL, || driver_ready(driver_url)).await {
+            anyhow::bail!("ChromeDriver did not answer within {} seconds. {}", timeout.as_secs(), hint);
+        }
+
+        let driver = WebDriver::new(driver_url, caps).await
+            .context(format!("Failed to connect to ChromeDriver. {}", hint))?;
+        println!("DEBUG: BrowserDriver::new() - Successfully connected to ChromeDriver");
+
+        // --window-size is ignored by some Chrome builds, so also size the window explicitly
+        if let Err(e) = driver.set_window_rect(0, 0, config.window_width, config.window_height).await {
+            println!("DEBUG: BrowserDriver::new() - Could not resize window: {}", e);
         }
 
-        Err(last_error.unwrap())
-            .context("Failed to connect to ChromeDriver after 3 attempts. ChromeDriver should have been started automatically on port 9516")
+        Ok(Self { driver })
     }
 
     pub async fn navigate(&self, url: &str) -> Result<()> {
@@ -77,19 +128,13 @@ impl BrowserDriver {
 
     pub async fn wait_for_element(&self, selector: By, timeout_secs: u64) -> Result<WebElement> {
         let timeout = Duration::from_secs(timeout_secs);
-        let start = std::time::Instant::now();
-
-        loop {
-            if let Ok(element) = self.driver.find(selector.clone()).await {
-                return Ok(element);
-            }
-
-            if start.elapsed() > timeout {
-                return Err(anyhow::anyhow!("Timeout waiting for element"));
-            }
-
-            sleep(Duration::from_millis(500)).await;
+        let found = wait_until(timeout, WAIT_POLL_INTERVAL, || async {
+            self.driver.find(selector.clone()).await.is_ok()
+        }).await;
+        if !found {
+            return Err(anyhow::anyhow!("Timeout waiting for element"));
         }
+        self.find_element(selector).await
     }
 
     pub async fn click_element(&self, element: &WebElement) -> Result<()> {
@@ -111,26 +156,47 @@ impl BrowserDriver {
         Ok(self.driver.current_url().await?.to_string())
     }
 
-    pub async fn execute_script(&self, script: &str, args: Vec<WebElement>) -> Result<()> {
-        // Convert WebElement to serde_json::Value
-        let json_args: Vec<serde_json::Value> = args.into_iter()
-            .map(|el| serde_json::json!(el))
-            .collect();
+    /// Run a script with arbitrary JSON arguments and return its result.
+    /// Elements can be passed with `serde_json::json!(element)`.
+    pub async fn execute_script_with_args(&self, script: &str, args: Vec<serde_json::Value>) -> Result<serde_json::Value> {
+        match self.driver.execute(script, args).await {
+            Ok(value) => Ok(value.json().clone()),
+            Err(e) => Err(anyhow::anyhow!("Script execution failed: {}", e)),
+        }
+    }
 
-        self.driver.execute(script, json_args).await?;
+    pub async fn execute_script(&self, script: &str, args: Vec<WebElement>) -> Result<()> {
+        self.execute_script_with_args(script, element_args(args)).await?;
         Ok(())
     }
 
     pub async fn execute_script_and_get_value(&self, script: &str, args: Vec<WebElement>) -> Result<serde_json::Value> {
-        // Convert WebElements to JSON values for the script execution
-        let json_args: Vec<serde_json::Value> = args.into_iter()
-            .map(|el| serde_json::json!(el))
-            .collect();
+        self.execute_script_with_args(script, element_args(args)).await
+    }
 
-        match self.driver.execute(script, json_args).await {
-            Ok(value) => Ok(value.json().clone()),
-            Err(e) => Err(anyhow::anyhow!("Script execution failed: {}", e)),
+    /// Console messages logged since the previous call; ChromeDriver clears them once read
+    pub async fn get_console_logs(&self) -> Result<Vec<BrowserConsoleEntry>> {
+        let response = self.driver.cmd(Command::ExtensionCommand(Box::new(GetBrowserLog))).await?;
+        Ok(response.value()?)
+    }
+
+    /// All cookies of the current page's domain
+    pub async fn export_cookies(&self) -> Result<Vec<Cookie>> {
+        let cookies = self.driver.get_all_cookies().await?;
+        Ok(cookies.into_iter().map(Cookie).collect())
+    }
+
+    /// Add `cookies` to the browser. WebDriver only accepts cookies of the
+    /// current page's domain, the others are skipped. Returns how many were added.
+    pub async fn import_cookies(&self, cookies: &[Cookie]) -> Result<usize> {
+        let mut added = 0;
+        for cookie in cookies {
+            match self.driver.add_cookie(cookie.0.clone()).await {
+                Ok(()) => added += 1,
+                Err(e) => println!("DEBUG: Skipped cookie '{}': {}", cookie.0.name, e),
+            }
         }
+        Ok(added)
     }
 
     pub async fn quit(&self) -> Result<()> {
@@ -167,6 +233,9 @@ impl BrowserDriver {
             By::Css("input[type='email']"),
             By::Css("input[name='loginfmt']"),
             By::Css("input[id='i0116']"),
+            // eVIEW's own login form
+            By::Css("input[name='username']"),
+            By::Css("input[autocomplete='username']"),
         ];
 
         for selector in selectors {
@@ -216,4 +285,24 @@ impl BrowserDriver {
 
         Ok(None)
     }
+}
+
+/// Chrome's `--proxy-server` does not accept inline credentials, so strip any `user:pass@` part.
+/// Chrome will prompt for proxy authentication itself if the proxy requires it.
+pub fn chrome_proxy_server(proxy: &str) -> String {
+    let proxy = proxy.trim();
+    let (scheme, rest) = match proxy.split_once("://") {
+        Some((scheme, rest)) => (Some(scheme), rest),
+        None => (None, proxy),
+    };
+    let host = rest.rsplit_once('@').map(|(_, host)| host).unwrap_or(rest);
+
+    match scheme {
+        Some(scheme) => format!("{}://{}", scheme, host),
+        None => host.to_string(),
+    }
+}
+
+fn element_args(elements: Vec<WebElement>) -> Vec<serde_json::Value> {
+    elements.into_iter().map(|el| serde_json::json!(el)).collect()
 }
\ No newline at end of file
@@ -0,0 +1,54 @@
+use anyhow::Result;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Error message of an extraction stopped through `CancellationToken::cancel`
+pub const CANCELLED_MESSAGE: &str = "Extraction cancelled";
+
+/// Cooperative cancellation for a running extraction.
+///
+/// The scraper checks the token between steps and scroll iterations, so a
+/// stop request lets the run return normally and close the browser instead
+/// of dropping the task mid-await. Clones share the same state.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Fails with `CANCELLED_MESSAGE` once cancelled, for use with `?` between steps
+    pub fn check(&self) -> Result<()> {
+        if self.is_cancelled() {
+            return Err(anyhow::anyhow!(CANCELLED_MESSAGE));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clones_share_cancellation() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+        assert!(clone.check().is_ok());
+
+        token.cancel();
+        assert!(clone.is_cancelled());
+        assert_eq!(clone.check().unwrap_err().to_string(), CANCELLED_MESSAGE);
+    }
+}
\ No newline at end of file
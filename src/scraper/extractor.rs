@@ -1,8 +1,108 @@
 use regex::Regex;
+use unicode_normalization::UnicodeNormalization;
 use crate::models::{PlcEntry, PlcTable};
 
 pub struct PlcDataExtractor;
 
+/// `PlcEntry::confidence` of a symbol name that matches the function text
+/// pattern, that is other text before the address, or that was carried over
+/// from an earlier address because there was no text before this one
+pub const CONFIDENCE_FUNCTION_MATCH: f32 = 1.0;
+pub const CONFIDENCE_TEXT_BEFORE: f32 = 0.7;
+pub const CONFIDENCE_NO_CONTEXT: f32 = 0.4;
+
+/// Text of an SVG element as it should be shown: entities like `&auml;` or
+/// `&#228;` decoded, combining diacritics composed (NFC) so "ä" is one
+/// character, control characters and byte order marks removed and the ends trimmed
+pub fn sanitise_text(input: &str) -> String {
+    html_escape::decode_html_entities(input)
+        .nfc()
+        .filter(|c| (!c.is_control() || c.is_whitespace()) && *c != '\u{FEFF}')
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+/// Hardware module, rack and slot named in the diagram text. They apply to
+/// the addresses on the same line and the following ones, until the text
+/// names another module or rack.
+pub struct HardwareContext {
+    rack: Option<u32>,
+    module: Option<String>,
+    slot: Option<u32>,
+    module_pattern: Regex,
+    designation_pattern: Regex,
+    rack_pattern: Regex,
+    slot_pattern: Regex,
+}
+
+impl HardwareContext {
+    pub fn new() -> Self {
+        Self {
+            rack: None,
+            module: None,
+            slot: None,
+            // Siemens families: "ET 200SP", "S7-300", "IM155-6", "SM 321", "CPU 1516"
+            module_pattern: Regex::new(r"\b(?:ET ?200 ?(?:SP|MP|S|M|pro|eco)?|S7-\d{3,4}|IM ?\d{3}-\d+|SM ?\d{3}|CPU ?\d{3,4}[A-Z]?)\b").unwrap(),
+            // Further words of the module name like "IM155-6", "PN" or "DI 8x24VDC"
+            designation_pattern: Regex::new(r"^[A-Z0-9][A-Za-z0-9\-./]*$").unwrap(),
+            rack_pattern: Regex::new(r"(?i)\b(?:Rack|Baugruppenträger)\s*[:=]?\s*(\d+)").unwrap(),
+            slot_pattern: Regex::new(r"(?i)\b(?:Slot|Steckplatz)\s*[:=]?\s*(\d+)").unwrap(),
+        }
+    }
+
+    /// Pick up a module, rack or slot named on `line`. A new module or rack
+    /// starts without a slot.
+    pub fn update(&mut self, line: &str, address_pattern: &Regex) {
+        let rack = Self::number(&self.rack_pattern, line);
+        let module = self.module_name(line, address_pattern);
+
+        if rack.is_some() {
+            self.rack = rack;
+            self.module = module;
+            self.slot = None;
+        } else if module.is_some() {
+            self.module = module;
+            self.slot = None;
+        }
+        if let Some(slot) = Self::number(&self.slot_pattern, line) {
+            self.slot = Some(slot);
+        }
+    }
+
+    /// Set the module and slot of an entry found on the current line
+    pub fn apply(&self, entry: &mut PlcEntry) {
+        entry.module = match (self.rack, &self.module) {
+            (Some(rack), Some(module)) => Some(format!("Rack {}: {}", rack, module)),
+            (Some(rack), None) => Some(format!("Rack {}", rack)),
+            (None, module) => module.clone(),
+        };
+        entry.slot = self.slot;
+    }
+
+    /// The module family and the designation words following it, e.g.
+    /// "ET 200SP IM155-6 PN" in "=A1 ET 200SP IM155-6 PN Slot 3"
+    fn module_name(&self, line: &str, address_pattern: &Regex) -> Option<String> {
+        let start = self.module_pattern.find(line)?;
+        let mut name = start.as_str().to_string();
+
+        for word in line[start.end()..].split_whitespace() {
+            let is_keyword = self.slot_pattern.is_match(word) || self.rack_pattern.is_match(word)
+                || word.eq_ignore_ascii_case("slot") || word.eq_ignore_ascii_case("rack");
+            if is_keyword || address_pattern.is_match(word) || !self.designation_pattern.is_match(word) {
+                break;
+            }
+            name.push(' ');
+            name.push_str(word);
+        }
+        Some(name)
+    }
+
+    fn number(pattern: &Regex, line: &str) -> Option<u32> {
+        pattern.captures(line)?.get(1)?.as_str().parse().ok()
+    }
+}
+
 impl PlcDataExtractor {
     pub fn parse_plc_data(input: &str) -> Vec<PlcEntry> {
         let mut results = Vec::new();
@@ -16,6 +116,7 @@ impl PlcDataExtractor {
 
         let mut current_function = String::new();
         let mut current_page = String::new();
+        let mut hardware = HardwareContext::new();
 
         for line in lines {
             let line = line.trim();
@@ -24,6 +125,9 @@ impl PlcDataExtractor {
                 continue;
             }
 
+            // Before the header check, module lines like "ET 200SP" are skipped
+            hardware.update(line, &address_pattern);
+
             // Skip header lines
             if Self::is_header_line(line) {
                 continue;
@@ -42,9 +146,11 @@ impl PlcDataExtractor {
 
                 // Extract function name before address
                 let text_before = &line[..address_match.start()].trim();
+                let mut confidence = CONFIDENCE_NO_CONTEXT;
 
                 if let Some(func_match) = function_pattern.find(text_before) {
                     current_function = func_match.as_str().trim().to_string();
+                    confidence = CONFIDENCE_FUNCTION_MATCH;
                 } else if !text_before.is_empty() && !text_before.starts_with('=') {
                     // Use the text before address as function name
                     let parts: Vec<&str> = text_before.split_whitespace().collect();
@@ -55,15 +161,18 @@ impl PlcDataExtractor {
 
                     if !valid_parts.is_empty() {
                         current_function = valid_parts.join(" ");
+                        confidence = CONFIDENCE_TEXT_BEFORE;
                     }
                 }
 
                 if !current_function.is_empty() {
-                    let entry = PlcEntry::new(
+                    let mut entry = PlcEntry::new(
                         address,
                         current_function.clone(),
                         current_page.clone(),
                     );
+                    entry.confidence = confidence;
+                    hardware.apply(&mut entry);
                     results.push(entry);
                 }
             }
@@ -95,44 +204,58 @@ impl PlcDataExtractor {
         None
     }
 
+    /// Texts of the SVG's `<text>` elements in document order, without
+    /// duplicates. The `<tspan>` lines of one `<text>` element are joined when
+    /// one continues the other, see `continues_line`, so a symbol name split
+    /// over two lines stays one string.
     pub fn extract_from_svg(svg_content: &str) -> Vec<String> {
         let mut extracted = Vec::new();
 
-        // Pattern for text elements in SVG
-        let text_pattern = Regex::new(r"<text[^>]*>([^<]+)</text>").unwrap();
-        let tspan_pattern = Regex::new(r"<tspan[^>]*>([^<]+)</tspan>").unwrap();
+        let text_pattern = Regex::new(r"(?s)<text\b[^>]*>(.*?)</text>").unwrap();
+        let tspan_pattern = Regex::new(r"(?s)<tspan\b[^>]*>(.*?)</tspan>").unwrap();
+        let tag_pattern = Regex::new(r"<[^>]*>").unwrap();
 
-        // Extract from text elements
-        for cap in text_pattern.captures_iter(svg_content) {
-            if let Some(text_match) = cap.get(1) {
-                let text = text_match.as_str().trim();
-                if !text.is_empty() && text.len() > 2 {
-                    extracted.push(text.to_string());
-                }
+        for text_cap in text_pattern.captures_iter(svg_content) {
+            let inner = &text_cap[1];
+            let lines: Vec<String> = tspan_pattern
+                .captures_iter(inner)
+                .map(|tspan_cap| sanitise_text(&tag_pattern.replace_all(&tspan_cap[1], "")))
+                .filter(|line| !line.is_empty())
+                .collect();
+
+            if lines.is_empty() {
+                extracted.push(sanitise_text(&tag_pattern.replace_all(inner, "")));
+                continue;
             }
-        }
 
-        // Extract from tspan elements
-        for cap in tspan_pattern.captures_iter(svg_content) {
-            if let Some(text_match) = cap.get(1) {
-                let text = text_match.as_str().trim();
-                if !text.is_empty() && text.len() > 2 {
-                    extracted.push(text.to_string());
+            let mut lines = lines.into_iter();
+            let mut current = lines.next().unwrap_or_default();
+            for line in lines {
+                if Self::continues_line(&current, &line) {
+                    current.push(' ');
+                    current.push_str(&line);
+                } else {
+                    extracted.push(std::mem::replace(&mut current, line));
                 }
             }
+            extracted.push(current);
         }
 
-        // Remove duplicates while preserving order
+        // Remove short fragments and duplicates while preserving order
         let mut seen = std::collections::HashSet::new();
-        let mut unique = Vec::new();
-
-        for item in extracted {
-            if seen.insert(item.clone()) {
-                unique.push(item);
-            }
-        }
+        extracted
+            .into_iter()
+            .filter(|text| text.len() > 2)
+            .filter(|text| seen.insert(text.clone()))
+            .collect()
+    }
 
-        unique
+    /// A `<tspan>` line continues the one before when that one doesn't end
+    /// in punctuation and it starts with a lowercase letter or a digit
+    fn continues_line(previous: &str, next: &str) -> bool {
+        let open_end = previous.chars().last().is_some_and(|c| !c.is_ascii_punctuation());
+        let continuation = next.chars().next().is_some_and(|c| c.is_lowercase() || c.is_ascii_digit());
+        open_end && continuation
     }
 
     pub fn clean_and_format(entries: Vec<PlcEntry>) -> PlcTable {
@@ -147,4 +270,56 @@ impl PlcDataExtractor {
 
         table
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_svg_tspan_lines_of_one_text_are_joined() {
+        let svg = r#"<svg>
+            <text x="10" y="20"><tspan x="10" dy="0">Conveyor belt motor</tspan><tspan x="10" dy="12">left side</tspan></text>
+            <text x="10" y="60">
+                <tspan x="10">Pump station</tspan>
+                <tspan x="10">2 inlet valve</tspan>
+            </text>
+            <text x="90" y="20">I0.0</text>
+        </svg>"#;
+
+        assert_eq!(
+            PlcDataExtractor::extract_from_svg(svg),
+            vec!["Conveyor belt motor left side", "Pump station 2 inlet valve", "I0.0"]
+        );
+    }
+
+    #[test]
+    fn test_svg_separate_tspan_lines_stay_apart() {
+        let svg = r#"<svg>
+            <text><tspan>Emergency stop.</tspan><tspan>reset required</tspan></text>
+            <text><tspan>Motor</tspan><tspan>Q0.1</tspan></text>
+            <text><tspan>Fan 1</tspan></text><text><tspan>Motor</tspan></text>
+        </svg>"#;
+
+        assert_eq!(
+            PlcDataExtractor::extract_from_svg(svg),
+            vec!["Emergency stop.", "reset required", "Motor", "Q0.1", "Fan 1"]
+        );
+    }
+
+    #[test]
+    fn test_sanitise_text() {
+        assert_eq!(sanitise_text("Stra&szlig;e K&uuml;hlung &Ouml;l"), "Straße Kühlung Öl");
+        assert_eq!(sanitise_text("T&#228;nzer &#xFC;berlast &amp; St&ouml;rung"), "Tänzer überlast & Störung");
+        assert_eq!(sanitise_text("Pumpe Ru\u{308}cklauf"), "Pumpe Rücklauf");
+        assert_eq!(sanitise_text("Pumpe Ru\u{308}cklauf").chars().count(), "Pumpe Rücklauf".chars().count());
+        assert_eq!(sanitise_text("\u{FEFF} Ventil\u{0007} 3\u{0000} "), "Ventil 3");
+    }
+
+    #[test]
+    fn test_svg_texts_are_sanitised() {
+        let svg = "<svg><text><tspan>K&uuml;hlwasser</tspan><tspan>ru\u{308}cklauf</tspan></text><text>\u{FEFF}&#196;nderung</text></svg>";
+
+        assert_eq!(PlcDataExtractor::extract_from_svg(svg), vec!["Kühlwasser rücklauf", "Änderung"]);
+    }
+}
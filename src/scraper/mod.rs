@@ -1,9 +1,20 @@
 pub mod browser;
+pub mod cancel;
 pub mod extractor;
+pub mod selectors;
+pub mod session_cookies;
+pub mod version;
 
 use anyhow::Result;
+use serde::{Deserialize, Serialize};
 use crate::models::{PlcTable, PlcEntry};
 use crate::chromedriver_manager::ChromeDriverManager;
+pub use cancel::CancellationToken;
+pub use selectors::Selectors;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::future::Future;
+use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
@@ -13,19 +24,95 @@ pub struct ScraperEngine {
     logger: Arc<Mutex<Box<dyn Logger>>>,
     chromedriver_manager: Arc<ChromeDriverManager>,
     extracted_table: Option<PlcTable>,
+    cancel: CancellationToken,
+    phase: Option<LogPhase>, // Passed to the logger with each message, None before `run_extraction`
 }
 
 #[derive(Debug, Clone)]
 pub struct ScraperConfig {
     pub base_url: String,
+    pub organization: String, // Clicked in the multi-organization dialog, empty to skip it
     pub username: String,
     pub password: String,
     pub project_number: String,
     pub headless: bool,
+    pub proxy: Option<String>,
+    pub user_agent: Option<String>,
+    pub external_chromedriver_url: Option<String>, // Connect to this driver instead of starting one
+    pub window_width: u32,
+    pub window_height: u32,
+    pub login_attempts: u32, // Tries of each login step before giving up
+    pub attempt_interval_ms: u64, // Pause between two tries
+    pub page_category_keywords: Vec<String>, // Page list items containing any of these are extracted
+    pub selectors: Selectors,
+    pub dry_run: bool, // Stop after the list view and only count the pages
+    pub native_login: bool, // Fill in eVIEW's own login form instead of looking for the Microsoft button
+    pub session_cookies: Option<std::path::PathBuf>, // Reuse the login saved here, None to always log in
+    pub wait_timeouts: WaitTimeouts,
+}
+
+/// How long to wait for eVIEW before giving up on a step, in seconds
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WaitTimeouts {
+    pub login_secs: u64, // Back on eVIEW after the login form was submitted
+    pub page_load_secs: u64, // Project list, opened project and restored session
+    pub element_secs: u64, // Single elements, e.g. the scroll container or a page's drawing
+    pub scroll_secs: u64, // New page list items after a scroll step
+    pub driver_secs: u64, // ChromeDriver answering its status request
+}
+
+impl Default for WaitTimeouts {
+    fn default() -> Self {
+        Self {
+            login_secs: 30,
+            page_load_secs: 30,
+            element_secs: 10,
+            scroll_secs: 5,
+            driver_secs: 15,
+        }
+    }
 }
 
 pub trait Logger: Send + Sync {
     fn log(&self, message: String, level: LogLevel);
+
+    /// Overall extraction progress between 0.0 and 1.0
+    fn progress(&self, _fraction: f32) {}
+
+    /// Short status line, e.g. the ChromeDriver download progress
+    fn status(&self, _text: String) {}
+
+    /// Message from the page's JavaScript console, see `ScraperEngine::forward_browser_console`
+    fn browser_console(&self, message: String, level: LogLevel) {
+        self.log(message, level);
+    }
+
+    /// Message logged during a part of the extraction, for loggers that group by it
+    fn log_in_phase(&self, message: String, level: LogLevel, _phase: LogPhase) {
+        self.log(message, level);
+    }
+}
+
+/// Part of the extraction a log message belongs to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LogPhase {
+    Login, // Navigating to eVIEW and signing in
+    OpenProject, // Opening the project and switching to the list view
+    Extract, // Scrolling the page list
+    Page(usize), // Opening and reading the n-th PLC page
+}
+
+impl std::fmt::Display for LogPhase {
+    /// The tag of the exported log records, e.g. "open_project" or "page:3"
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LogPhase::Login => write!(f, "login"),
+            LogPhase::OpenProject => write!(f, "open_project"),
+            LogPhase::Extract => write!(f, "extract"),
+            LogPhase::Page(number) => write!(f, "page:{}", number),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -37,20 +124,273 @@ pub enum LogLevel {
     Debug,
 }
 
+/// Login form the eVIEW start page offers
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum LoginForm {
+    Microsoft, // Microsoft SSO, the button was clicked
+    Native,    // eVIEW's own username and password form
+}
+
+/// Returns description text and outerHTML for every rendered `pv-page-list-item`
+const VIEWPORT_ITEMS_SCRIPT: &str = r#"
+    return Array.from(document.querySelectorAll('pv-page-list-item')).map((item, index) => ({
+        index: index,
+        description: Array.from(item.querySelectorAll('.ev-description.ev-hi'))
+            .map(el => el.textContent || '')
+            .join('\n'),
+        text: item.textContent || '',
+        html: item.outerHTML,
+    }));
+"#;
+
+/// Estimates the number of page list items from the scroll height and the height of one item
+const ESTIMATE_ITEM_COUNT_SCRIPT: &str = r#"
+    const item = arguments[0].querySelector('pv-page-list-item');
+    if (!item || item.offsetHeight <= 0) {
+        return 0;
+    }
+    return Math.ceil(arguments[0].scrollHeight / item.offsetHeight);
+"#;
+
+/// Text of the first and last rendered page list item, changes once a scroll step has rendered new items
+const RENDERED_ITEMS_SCRIPT: &str = r#"
+    const items = arguments[0].querySelectorAll('pv-page-list-item');
+    if (items.length === 0) {
+        return '';
+    }
+    return items[0].textContent + '|' + items[items.length - 1].textContent;
+"#;
+
+/// Text of the drawings on the page, changes once a clicked PLC page has rendered
+const DRAWING_TEXT_SCRIPT: &str = r#"
+    return Array.from(document.querySelectorAll('svg')).map(svg => svg.textContent).join('|');
+"#;
+
+/// Progress range covered by the page list scroll loop
+const PAGE_PROGRESS_START: f32 = 0.4;
+const PAGE_PROGRESS_END: f32 = 0.95;
+
+/// Item count assumed when the page list can't be measured
+const DEFAULT_ESTIMATED_ITEMS: usize = 100;
+
+/// Attempts and first delay for clicks that can hit a re-rendered element
+const CLICK_ATTEMPTS: u32 = 4;
+const CLICK_BACKOFF: tokio::time::Duration = tokio::time::Duration::from_millis(250);
+pub(crate) const WAIT_POLL_INTERVAL: tokio::time::Duration = tokio::time::Duration::from_millis(500);
+
+/// WebDriver error texts worth another attempt: the element was re-rendered or
+/// briefly covered. Anything else, like a missing element, fails right away.
+const RETRYABLE_ERRORS: [&str; 4] = [
+    "stale element",
+    "element reference is stale",
+    "click intercepted",
+    "click was intercepted",
+];
+
+/// Tracks processed page list items against an estimate of the total
+struct PageProgress {
+    seen: HashSet<u64>,
+    processed: usize,
+    estimated_total: usize,
+    reported: f32,
+}
+
+impl PageProgress {
+    fn new(estimated_total: usize) -> Self {
+        Self {
+            seen: HashSet::new(),
+            processed: 0,
+            estimated_total: if estimated_total == 0 { DEFAULT_ESTIMATED_ITEMS } else { estimated_total },
+            reported: PAGE_PROGRESS_START,
+        }
+    }
+
+    /// Count an item identified by its outerHTML, items seen in an earlier scroll iteration are ignored
+    fn item_seen(&mut self, html: &str) {
+        let mut hasher = DefaultHasher::new();
+        html.hash(&mut hasher);
+        if self.seen.insert(hasher.finish()) {
+            self.processed += 1;
+        }
+    }
+
+    /// Count an item that can't be identified (fallback path)
+    fn item_processed(&mut self) {
+        self.processed += 1;
+    }
+
+    /// Progress scaled into the scroll loop's range. Never decreases, and the
+    /// estimate grows when more items turn up than expected.
+    fn fraction(&mut self) -> f32 {
+        self.estimated_total = self.estimated_total.max(self.processed + 1);
+        let done = self.processed as f32 / self.estimated_total as f32;
+        let fraction = PAGE_PROGRESS_START + done * (PAGE_PROGRESS_END - PAGE_PROGRESS_START);
+        self.reported = self.reported.max(fraction.min(PAGE_PROGRESS_END));
+        self.reported
+    }
+}
+
+fn contains_keyword(text: &str, keywords: &[String]) -> bool {
+    keywords.iter().any(|keyword| text.contains(keyword.as_str()))
+}
+
+/// XPath matching descendants whose text contains any of `keywords`
+fn keywords_xpath(keywords: &[String]) -> String {
+    let conditions: Vec<String> = keywords
+        .iter()
+        .map(|keyword| format!("contains(text(), {})", xpath_literal(keyword)))
+        .collect();
+    format!(".//*[{}]", conditions.join(" or "))
+}
+
+/// Quote a string for XPath 1.0, which has no escape sequences
+fn xpath_literal(value: &str) -> String {
+    if !value.contains('\'') {
+        format!("'{}'", value)
+    } else if !value.contains('"') {
+        format!("\"{}\"", value)
+    } else {
+        let parts: Vec<String> = value.split('\'').map(|part| format!("'{}'", part)).collect();
+        format!("concat({})", parts.join(", \"'\", "))
+    }
+}
+
+fn is_retryable(error: &anyhow::Error) -> bool {
+    let message = format!("{:#}", error).to_lowercase();
+    RETRYABLE_ERRORS.iter().any(|pattern| message.contains(pattern))
+}
+
+/// Run `op` up to `attempts` times while it fails with a retryable WebDriver
+/// error, sleeping `backoff` before the first retry and twice as long each time after
+pub async fn retry_async<T, F, Fut>(attempts: u32, backoff: tokio::time::Duration, mut op: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut delay = backoff;
+    let mut attempt = 1;
+
+    loop {
+        match op().await {
+            Err(e) if attempt < attempts && is_retryable(&e) => {
+                tokio::time::sleep(delay).await;
+                delay *= 2;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Check `condition` every `poll` until it holds or `timeout` has passed,
+/// returning whether it held
+pub async fn wait_until<F, Fut>(timeout: tokio::time::Duration, poll: tokio::time::Duration, mut condition: F) -> bool
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = bool>,
+{
+    let deadline = tokio::time::Instant::now() + timeout;
+
+    loop {
+        if condition().await {
+            return true;
+        }
+        if tokio::time::Instant::now() >= deadline {
+            return false;
+        }
+        tokio::time::sleep(poll).await;
+    }
+}
+
+/// An eVIEW page rather than one of the login pages
+fn looks_logged_in(current_url: &str, base_url: &str) -> bool {
+    let url = current_url.to_lowercase();
+    !url.contains("login") && (current_url.contains(base_url) || url.contains("eview"))
+}
+
+/// First element matching any of the XPath `selectors`
+async fn find_first_xpath(browser: &browser::BrowserDriver, selectors: &[String]) -> Result<thirtyfour::WebElement> {
+    for xpath in selectors {
+        if let Ok(element) = browser.find_element(thirtyfour::By::XPath(xpath)).await {
+            return Ok(element);
+        }
+    }
+    Err(anyhow::anyhow!("no such element: none of the selectors matched"))
+}
+
+/// The rendered page list item whose outerHTML is `html`, the identity the
+/// scroll loop uses. `index` is where `VIEWPORT_ITEMS_SCRIPT` saw it and is
+/// tried first. After a re-render another page can sit at that index, so an
+/// item is only returned when its HTML matches.
+async fn find_page_item(browser: &browser::BrowserDriver, index: usize, html: &str) -> Result<thirtyfour::WebElement> {
+    let items = browser.find_elements(thirtyfour::By::Tag("pv-page-list-item")).await?;
+    let positions = std::iter::once(index).chain((0..items.len()).filter(|&position| position != index));
+    for item in positions.filter_map(|position| items.get(position)) {
+        if item.outer_html().await? == html {
+            return Ok(item.clone());
+        }
+    }
+    Err(anyhow::anyhow!("no such element: page item {} is no longer rendered", index))
+}
+
+/// A page list item as read by `VIEWPORT_ITEMS_SCRIPT`
+#[derive(Debug, Deserialize)]
+struct ViewportItem {
+    index: usize,
+    description: String,
+    text: String,
+    html: String,
+}
+
+impl browser::BrowserConsoleEntry {
+    /// Chrome's "SEVERE" and "WARNING" map onto our levels, everything else is debug output
+    fn log_level(&self) -> LogLevel {
+        match self.level.as_str() {
+            "SEVERE" => LogLevel::Error,
+            "WARNING" => LogLevel::Warning,
+            _ => LogLevel::Debug,
+        }
+    }
+}
+
+impl ViewportItem {
+    fn is_plc_diagram(&self, keywords: &[String]) -> bool {
+        contains_keyword(&self.description, keywords) || contains_keyword(&self.text, keywords)
+    }
+}
+
 impl ScraperEngine {
     pub async fn new(config: ScraperConfig, logger: Arc<Mutex<Box<dyn Logger>>>, chromedriver_manager: Arc<ChromeDriverManager>) -> Result<Self> {
         println!("DEBUG: ScraperEngine::new() - Starting");
 
-        // Start ChromeDriver first
-        println!("DEBUG: ScraperEngine::new() - Starting ChromeDriver on port 9516");
-        chromedriver_manager.start_driver(9516).await
-            .map_err(|e| anyhow::anyhow!("Failed to start ChromeDriver: {}", e))?;
-
-        // Wait a bit for ChromeDriver to fully start
-        tokio::time::sleep(tokio::time::Duration::from_millis(2000)).await;
+        if let Some(url) = &config.external_chromedriver_url {
+            println!("DEBUG: ScraperEngine::new() - Using the external ChromeDriver at {}", url);
+        } else {
+            // Route the ChromeDriver download through the proxy as well
+            chromedriver_manager.set_proxy(config.proxy.clone()).await;
+
+            // Start ChromeDriver first
+            println!("DEBUG: ScraperEngine::new() - Starting ChromeDriver on port {}", browser::DRIVER_PORT);
+            // First run: show the download progress instead of a frozen status
+            let last_status = std::sync::Mutex::new(String::new());
+            let on_download = |downloaded: u64, total: Option<u64>| {
+                let status = crate::chromedriver_manager::download_status(downloaded, total);
+                let mut last = last_status.lock().unwrap_or_else(|e| e.into_inner());
+                if *last != status {
+                    if let Ok(logger) = logger.try_lock() {
+                        logger.status(status.clone());
+                    }
+                    *last = status;
+                }
+            };
+            // Returns once the driver answers its status request
+            let timeout = tokio::time::Duration::from_secs(config.wait_timeouts.driver_secs);
+            chromedriver_manager.start_driver(browser::DRIVER_PORT, timeout, &on_download).await
+                .map_err(|e| anyhow::anyhow!("Failed to start ChromeDriver: {}", e))?;
+        }
 
         println!("DEBUG: ScraperEngine::new() - About to create BrowserDriver");
-        let browser = browser::BrowserDriver::new(config.headless).await?;
+        let browser = browser::BrowserDriver::new(&config).await?;
 
         println!("DEBUG: ScraperEngine::new() - BrowserDriver created successfully");
 
@@ -60,13 +400,25 @@ impl ScraperEngine {
             logger,
             chromedriver_manager,
             extracted_table: None,
+            cancel: CancellationToken::new(),
+            phase: None,
         })
     }
 
+    /// Token checked between extraction steps, cancelling it makes `run_extraction` return early
+    pub fn set_cancellation_token(&mut self, cancel: CancellationToken) {
+        self.cancel = cancel;
+    }
+
     pub async fn run_extraction(&mut self) -> Result<PlcTable> {
         self.log("🚀 Starting eVIEW extraction process...".to_string(), LogLevel::Info).await;
+        if let Ok(user_agent) = self.browser.execute_script_and_get_value("return navigator.userAgent", Vec::new()).await {
+            self.log(format!("Browser user agent: {}", user_agent.as_str().unwrap_or_default()), LogLevel::Debug).await;
+        }
 
         // Step 1: Navigate to base URL
+        self.cancel.check()?;
+        self.phase = Some(LogPhase::Login);
         self.log("📍 Step 1/6: Navigating to eVIEW...".to_string(), LogLevel::Info).await;
         match self.browser.navigate(&self.config.base_url).await {
             Ok(_) => {
@@ -78,30 +430,57 @@ impl ScraperEngine {
             }
         }
 
+        self.log_eview_version().await;
+
         // Step 2: Handle Microsoft login
+        self.cancel.check()?;
         self.log("📍 Step 2/6: Handling Microsoft login...".to_string(), LogLevel::Info).await;
-        match self.click_microsoft_login().await {
-            Ok(_) => {
-                self.log("✅ Microsoft login button clicked successfully".to_string(), LogLevel::Success).await;
-            }
-            Err(e) => {
-                self.log(format!("❌ Failed to click Microsoft login: {}", e), LogLevel::Error).await;
-                return Err(anyhow::anyhow!("Microsoft login button click failed: {}", e));
-            }
-        }
+        if self.restore_session().await {
+            self.log("✅ Saved login session is still valid, skipping the Microsoft login".to_string(), LogLevel::Success).await;
+        } else {
+            let login_form = if self.config.native_login {
+                LoginForm::Native
+            } else {
+                match self.click_microsoft_login().await {
+                    Ok(LoginForm::Microsoft) => {
+                        self.log("✅ Microsoft login button clicked successfully".to_string(), LogLevel::Success).await;
+                        LoginForm::Microsoft
+                    }
+                    Ok(LoginForm::Native) => {
+                        self.log("ℹ️ No Microsoft login button, the page has its own login form".to_string(), LogLevel::Info).await;
+                        LoginForm::Native
+                    }
+                    Err(e) => {
+                        self.log(format!("❌ Failed to click Microsoft login: {}", e), LogLevel::Error).await;
+                        return Err(anyhow::anyhow!("Microsoft login button click failed: {}", e));
+                    }
+                }
+            };
 
-        self.log("🔐 Performing Microsoft SSO login...".to_string(), LogLevel::Info).await;
-        match self.perform_login().await {
-            Ok(_) => {
-                self.log("✅ Microsoft SSO login completed successfully".to_string(), LogLevel::Success).await;
-            }
-            Err(e) => {
-                self.log(format!("❌ Microsoft login process failed: {}", e), LogLevel::Error).await;
-                return Err(anyhow::anyhow!("Microsoft login failed: {}", e));
+            if login_form == LoginForm::Native {
+                self.log("🔐 Performing eVIEW login...".to_string(), LogLevel::Info).await;
+                if let Err(e) = self.perform_native_login().await {
+                    self.log(format!("❌ eVIEW login failed: {}", e), LogLevel::Error).await;
+                    return Err(anyhow::anyhow!("eVIEW login failed: {}", e));
+                }
+                self.log("✅ eVIEW login completed successfully".to_string(), LogLevel::Success).await;
+            } else {
+                self.log("🔐 Performing Microsoft SSO login...".to_string(), LogLevel::Info).await;
+                match self.perform_login().await {
+                    Ok(_) => {
+                        self.log("✅ Microsoft SSO login completed successfully".to_string(), LogLevel::Success).await;
+                    }
+                    Err(e) => {
+                        self.log(format!("❌ Microsoft login process failed: {}", e), LogLevel::Error).await;
+                        return Err(anyhow::anyhow!("Microsoft login failed: {}", e));
+                    }
+                }
             }
         }
 
         // Step 3: Open the specific project
+        self.cancel.check()?;
+        self.phase = Some(LogPhase::OpenProject);
         self.log("📍 Step 3/6: Opening project...".to_string(), LogLevel::Info).await;
         match self.open_project().await {
             Ok(_) => {
@@ -114,6 +493,7 @@ impl ScraperEngine {
         }
 
         // Step 4: Switch to list view
+        self.cancel.check()?;
         self.log("📍 Step 4/6: Switching to list view...".to_string(), LogLevel::Info).await;
         match self.switch_to_list_view().await {
             Ok(_) => {
@@ -125,7 +505,13 @@ impl ScraperEngine {
             }
         }
 
+        self.phase = Some(LogPhase::Extract);
+        if self.config.dry_run {
+            return self.count_pages().await;
+        }
+
         // Step 5: Extract the tables
+        self.cancel.check()?;
         self.log("📍 Step 5/6: Extracting SPS tables...".to_string(), LogLevel::Info).await;
         match self.extract_tables().await {
             Ok(success) => {
@@ -152,17 +538,189 @@ impl ScraperEngine {
         Ok(table)
     }
 
+    /// Log the eVIEW version of the start page, which tells which eVIEW a
+    /// selectors.json has to be adjusted for when a step stops finding its elements
+    async fn log_eview_version(&self) {
+        match version::EviewVersionDetector::detect(&self.browser).await {
+            Ok(version) => self.log(format!("🏷️ Detected eVIEW version {}", version), LogLevel::Info).await,
+            Err(e) => self.log(format!("⚠️ Could not detect the eVIEW version: {}", e), LogLevel::Warning).await,
+        }
+    }
+
+    /// Import the cookies of an earlier login and check whether eVIEW still
+    /// accepts them. False when there is no saved session or it expired.
+    async fn restore_session(&self) -> bool {
+        let Some(path) = &self.config.session_cookies else {
+            return false;
+        };
+        let cookies = match session_cookies::load(path) {
+            Ok(cookies) => cookies,
+            Err(e) => {
+                self.log(format!("No saved login session to reuse: {}", e), LogLevel::Debug).await;
+                return false;
+            }
+        };
+
+        // WebDriver only takes cookies for the open page's domain, so this runs after step 1
+        self.log(format!("🍪 Restoring the saved login session ({} cookies)...", cookies.len()), LogLevel::Info).await;
+        match self.browser.import_cookies(&cookies).await {
+            Ok(0) | Err(_) => {
+                self.log("⚠️ None of the saved cookies belong to this site, logging in again".to_string(), LogLevel::Warning).await;
+                return false;
+            }
+            Ok(added) => self.log(format!("Imported {} of {} cookies", added, cookies.len()), LogLevel::Debug).await,
+        }
+
+        // Load the start page again, now with the session cookies
+        if let Err(e) = self.browser.navigate(&self.config.base_url).await {
+            self.log(format!("⚠️ Could not reload eVIEW with the saved session: {}", e), LogLevel::Warning).await;
+            return false;
+        }
+        let timeout = tokio::time::Duration::from_secs(self.config.wait_timeouts.page_load_secs);
+        wait_until(timeout, WAIT_POLL_INTERVAL, || async {
+            // Either eVIEW or one of the login pages has loaded
+            let current_url = self.browser.get_current_url().await.unwrap_or_default();
+            current_url.to_lowercase().contains("login")
+                || find_first_xpath(&self.browser, &self.config.selectors.microsoft_login).await.is_ok()
+                || self.is_logged_in().await
+        }).await;
+
+        if self.is_logged_in().await {
+            true
+        } else {
+            self.log("⚠️ Saved login session expired, logging in again".to_string(), LogLevel::Warning).await;
+            session_cookies::remove(path);
+            false
+        }
+    }
+
+    /// On an eVIEW page and no Microsoft login button in sight
+    async fn is_logged_in(&self) -> bool {
+        let current_url = self.browser.get_current_url().await.unwrap_or_default();
+        if !looks_logged_in(&current_url, &self.config.base_url) {
+            return false;
+        }
+        match find_first_xpath(&self.browser, &self.config.selectors.microsoft_login).await {
+            Ok(button) => !button.is_displayed().await.unwrap_or(false),
+            Err(_) => true,
+        }
+    }
+
+    /// Keep the cookies of a successful login for the next run
+    async fn save_session(&self) {
+        let Some(path) = &self.config.session_cookies else {
+            return;
+        };
+        let saved = match self.browser.export_cookies().await {
+            Ok(cookies) => session_cookies::save(path, &cookies).map(|()| cookies.len()),
+            Err(e) => Err(e),
+        };
+        match saved {
+            Ok(count) => self.log(format!("🍪 Saved {} session cookies for the next run", count), LogLevel::Debug).await,
+            Err(e) => self.log(format!("⚠️ Could not save the login session: {}", e), LogLevel::Warning).await,
+        }
+    }
+
+    /// Dry run: log how many page list items and PLC pages the list view
+    /// holds, without opening any page. The list only renders the items in
+    /// view, so it's scrolled through like in `extract_tables` and each item
+    /// is counted once by its outerHTML.
+    async fn count_pages(&self) -> Result<PlcTable> {
+        self.cancel.check()?;
+        let scroll_container = self.browser
+            .wait_for_element(thirtyfour::By::Css(&self.config.selectors.scroll_container), self.config.wait_timeouts.element_secs)
+            .await
+            .map_err(|e| anyhow::anyhow!("Scroll container not found: {}", e))?;
+        let scroll_timeout = tokio::time::Duration::from_secs(self.config.wait_timeouts.scroll_secs);
+
+        if self.browser.execute_script("arguments[0].scrollTop = 0", vec![scroll_container.clone()]).await.is_ok() {
+            wait_until(scroll_timeout, WAIT_POLL_INTERVAL, || async {
+                !self.rendered_items_fingerprint(&scroll_container).await.is_empty()
+            }).await;
+        }
+
+        let mut seen = std::collections::HashSet::new();
+        let mut plc_pages = 0;
+        let mut last_position = -1i64;
+        loop {
+            self.cancel.check()?;
+            for item in self.read_viewport_items().await {
+                if seen.insert(item.html.clone()) && item.is_plc_diagram(&self.config.page_category_keywords) {
+                    plc_pages += 1;
+                }
+            }
+
+            let before_scroll = self.rendered_items_fingerprint(&scroll_container).await;
+            if self.browser.execute_script("arguments[0].scrollTop += 400", vec![scroll_container.clone()]).await.is_err() {
+                break;
+            }
+            let position = self.browser
+                .execute_script_and_get_value("return arguments[0].scrollTop", vec![scroll_container.clone()])
+                .await
+                .ok()
+                .and_then(|value| value.as_i64());
+            match position {
+                Some(position) if position != last_position => last_position = position,
+                _ => break, // Reached the bottom
+            }
+            wait_until(scroll_timeout, WAIT_POLL_INTERVAL, || async {
+                self.rendered_items_fingerprint(&scroll_container).await != before_scroll
+            }).await;
+        }
+
+        self.log(format!("🧪 Dry run: {} page list items, {} of them PLC pages", seen.len(), plc_pages), LogLevel::Info).await;
+        self.log(format!("Dry-run complete: {} PLC pages found", plc_pages), LogLevel::Success).await;
+        Ok(PlcTable::new(self.config.project_number.clone()))
+    }
+
     async fn log(&self, message: String, level: LogLevel) {
         let logger = self.logger.lock().await;
-        logger.log(message, level);
+        match self.phase {
+            Some(phase) => logger.log_in_phase(message, level, phase),
+            None => logger.log(message, level),
+        }
+    }
+
+    /// Pass the browser console messages collected since the last call on to the logger.
+    /// The console is a diagnostic aid only, so failing to read it is not an error.
+    async fn forward_browser_console(&self) {
+        let entries = match self.browser.get_console_logs().await {
+            Ok(entries) => entries,
+            Err(e) => {
+                println!("DEBUG: Could not read the browser console: {}", e);
+                return;
+            }
+        };
+
+        let logger = self.logger.lock().await;
+        for entry in entries {
+            // The console's own time, the line can reach us a few seconds later
+            let logged_at = chrono::DateTime::from_timestamp_millis(entry.timestamp as i64)
+                .map(|time| time.with_timezone(&chrono::Local).format("%H:%M:%S%.3f").to_string())
+                .unwrap_or_default();
+            logger.browser_console(format!("🌐 [{}] {}", logged_at, entry.message), entry.log_level());
+        }
+    }
+
+    async fn report_progress(&self, fraction: f32) {
+        let logger = self.logger.lock().await;
+        logger.progress(fraction);
+    }
+
+    /// Pause between two tries of a login step
+    fn login_retry_delay(&self) -> tokio::time::Duration {
+        tokio::time::Duration::from_millis(self.config.attempt_interval_ms)
     }
 
-    async fn click_microsoft_login(&mut self) -> Result<()> {
+    /// Click the Microsoft sign-in button. Returns `LoginForm::Native` instead
+    /// when the page shows a username and password form and no such button.
+    async fn click_microsoft_login(&mut self) -> Result<LoginForm> {
         self.log("Looking for Microsoft login button".to_string(), LogLevel::Info).await;
 
-        // Try multiple times to find the Microsoft login button (Python: 15 attempts)
-        for attempt in 1..=15 {
-            self.log(format!("Looking for Microsoft button... [{}/15]", attempt), LogLevel::Info).await;
+        // Try multiple times to find the Microsoft login button
+        let attempts = self.config.login_attempts;
+        for attempt in 1..=attempts {
+            self.log(format!("Looking for Microsoft button... [{}/{}]", attempt, attempts), LogLevel::Info).await;
 
             // Find all buttons first (debugging)
             if let Ok(all_buttons) = self.browser.find_elements(thirtyfour::By::Tag("button")).await {
@@ -182,24 +740,20 @@ impl ScraperEngine {
             }
 
             // Find all elements containing 'Microsoft' text
-            let microsoft_selectors = vec![
-                "//*[contains(text(), 'Microsoft') or contains(text(), 'microsoft') or contains(@title, 'Microsoft')]"
-            ];
-
-            for selector in microsoft_selectors {
+            for selector in &self.config.selectors.microsoft_login {
                 if let Ok(elements) = self.browser.find_elements(thirtyfour::By::XPath(selector)).await {
                     for elem in elements {
                         match (elem.is_displayed().await, elem.is_enabled().await) {
                             (Ok(true), Ok(true)) => {
                                 if let Ok(()) = elem.click().await {
-                                    tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
-
                                     // Check if we navigated to Microsoft login
-                                    if let Ok(url) = self.browser.get_current_url().await {
-                                        if url.contains("login.microsoft") {
-                                            self.log("Successfully clicked Microsoft login button".to_string(), LogLevel::Success).await;
-                                            return Ok(());
-                                        }
+                                    let timeout = tokio::time::Duration::from_secs(self.config.wait_timeouts.element_secs);
+                                    let on_microsoft = wait_until(timeout, WAIT_POLL_INTERVAL, || async {
+                                        self.browser.get_current_url().await.unwrap_or_default().contains("login.microsoft")
+                                    }).await;
+                                    if on_microsoft {
+                                        self.log("Successfully clicked Microsoft login button".to_string(), LogLevel::Success).await;
+                                        return Ok(LoginForm::Microsoft);
                                     }
                                 }
                             }
@@ -209,33 +763,69 @@ impl ScraperEngine {
                 }
             }
 
-            tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+            if self.has_native_login_form().await {
+                return Ok(LoginForm::Native);
+            }
+
+            tokio::time::sleep(self.login_retry_delay()).await;
+        }
+
+        Err(anyhow::anyhow!("Could not find Microsoft login button after {} attempts", attempts))
+    }
+
+    /// Email and password fields on the page itself, not on Microsoft's login pages
+    async fn has_native_login_form(&self) -> bool {
+        let on_microsoft = self.browser.get_current_url().await.unwrap_or_default().contains("login.microsoft");
+        !on_microsoft
+            && matches!(self.browser.find_email_field().await, Ok(Some(_)))
+            && matches!(self.browser.find_password_field().await, Ok(Some(_)))
+    }
+
+    /// Fill in eVIEW's own login form with the profile's email and password
+    async fn perform_native_login(&mut self) -> Result<()> {
+        let attempts = self.config.login_attempts;
+        let mut email_field = None;
+        for attempt in 1..=attempts {
+            self.log(format!("Waiting for the login form... [{}/{}]", attempt, attempts), LogLevel::Debug).await;
+            email_field = self.browser.find_email_field().await?;
+            if email_field.is_some() { break; }
+            tokio::time::sleep(self.login_retry_delay()).await;
+        }
+        let email_field = email_field.ok_or_else(|| anyhow::anyhow!("Email field not found"))?;
+
+        self.log("Type in email...".to_string(), LogLevel::Info).await;
+        self.browser.send_keys(&email_field, &self.config.username).await?;
+
+        let password_field = self.browser.find_password_field().await?
+            .ok_or_else(|| anyhow::anyhow!("Password field not found"))?;
+        self.log("Inserting password...".to_string(), LogLevel::Info).await;
+        self.browser.send_keys(&password_field, &self.config.password).await?;
+
+        match self.browser.find_submit_button().await? {
+            Some(button) => {
+                self.browser.click_element(&button).await?;
+                self.log("Login form submitted".to_string(), LogLevel::Debug).await;
+            }
+            None => {
+                password_field.send_keys(thirtyfour::Key::Return).await?;
+                self.log("Submit pressed instead of a login button click".to_string(), LogLevel::Debug).await;
+            }
         }
 
-        Err(anyhow::anyhow!("Could not find Microsoft login button after 15 attempts"))
+        self.finish_login().await
     }
 
     async fn perform_login(&mut self) -> Result<()> {
         self.log("Waiting for Microsoft email field...".to_string(), LogLevel::Info).await;
 
-        // Email field selectors from Python
-        let email_selectors = vec![
-            "input[type='email']",
-            "input[name='loginfmt']",
-            "input[id='i0116']",
-            "input[id='email']",
-            "input[placeholder*='Email']",
-            "input[placeholder*='E-Mail']",
-            "input[name='username']",
-        ];
-
         // Find email field with retry logic
+        let attempts = self.config.login_attempts;
         let mut email_field = None;
-        for attempt in 1..=15 {
-            self.log(format!("Waiting for email field... [{}/15]", attempt), LogLevel::Debug).await;
+        for attempt in 1..=attempts {
+            self.log(format!("Waiting for email field... [{}/{}]", attempt, attempts), LogLevel::Debug).await;
 
-            for selector in &email_selectors {
-                if let Ok(field) = self.browser.find_element(thirtyfour::By::Css(*selector)).await {
+            for selector in &self.config.selectors.email_field {
+                if let Ok(field) = self.browser.find_element(thirtyfour::By::Css(selector)).await {
                     if field.is_displayed().await.unwrap_or(false) {
                         self.log(format!("Email field found with selector: {}", selector), LogLevel::Debug).await;
                         email_field = Some(field);
@@ -244,7 +834,7 @@ impl ScraperEngine {
                 }
             }
             if email_field.is_some() { break; }
-            tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+            tokio::time::sleep(self.login_retry_delay()).await;
         }
 
         let email_field = email_field.ok_or_else(|| anyhow::anyhow!("Email field not found"))?;
@@ -256,18 +846,9 @@ impl ScraperEngine {
 
         // Click Next button
         self.log("Looking for 'Next' button...".to_string(), LogLevel::Info).await;
-        let next_button_selectors = vec![
-            "input[type='submit']",
-            "input[id='idSIButton9']",
-            "button[type='submit']",
-            "input[value='Next']",
-            "input[value='Weiter']",
-            "button[id='idSIButton9']",
-        ];
-
         let mut next_clicked = false;
-        for selector in &next_button_selectors {
-            if let Ok(next_button) = self.browser.find_element(thirtyfour::By::Css(*selector)).await {
+        for selector in &self.config.selectors.next_button {
+            if let Ok(next_button) = self.browser.find_element(thirtyfour::By::Css(selector)).await {
                 if next_button.is_displayed().await.unwrap_or(false) && next_button.is_enabled().await.unwrap_or(false) {
                     next_button.click().await?;
                     self.log(format!("'Next' button clicked with selector: {}", selector), LogLevel::Debug).await;
@@ -283,24 +864,26 @@ impl ScraperEngine {
             self.log("Submit-button pressed instead of Next-button".to_string(), LogLevel::Debug).await;
         }
 
-        // Wait for password page
-        tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
+        // Wait for password page, or eVIEW itself with Single Sign-On
+        let timeout = tokio::time::Duration::from_secs(self.config.wait_timeouts.login_secs);
+        wait_until(timeout, WAIT_POLL_INTERVAL, || async {
+            for selector in &self.config.selectors.password_field {
+                if let Ok(field) = self.browser.find_element(thirtyfour::By::Css(selector)).await {
+                    if field.is_displayed().await.unwrap_or(false) {
+                        return true;
+                    }
+                }
+            }
+            let current_url = self.browser.get_current_url().await.unwrap_or_default();
+            looks_logged_in(&current_url, &self.config.base_url)
+        }).await;
 
         // Password field logic
         self.log("Looking for password field...".to_string(), LogLevel::Info).await;
-        let password_selectors = vec![
-            "input[type='password']",
-            "input[name='passwd']",
-            "input[id='i0118']",
-            "input[id='passwordInput']",
-            "input[placeholder*='Password']",
-            "input[placeholder*='Passwort']",
-        ];
-
         let mut password_field = None;
-        for attempt in 1..=15 {
-            for selector in &password_selectors {
-                if let Ok(field) = self.browser.find_element(thirtyfour::By::Css(*selector)).await {
+        for attempt in 1..=attempts {
+            for selector in &self.config.selectors.password_field {
+                if let Ok(field) = self.browser.find_element(thirtyfour::By::Css(selector)).await {
                     if field.is_displayed().await.unwrap_or(false) {
                         self.log(format!("Password field found with selector: {}", selector), LogLevel::Debug).await;
                         password_field = Some(field);
@@ -309,8 +892,8 @@ impl ScraperEngine {
                 }
             }
             if password_field.is_some() { break; }
-            tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
-            self.log(format!("Waiting for password field... [{}/15]", attempt), LogLevel::Debug).await;
+            tokio::time::sleep(self.login_retry_delay()).await;
+            self.log(format!("Waiting for password field... [{}/{}]", attempt, attempts), LogLevel::Debug).await;
         }
 
         if let Some(password_field) = password_field {
@@ -320,18 +903,9 @@ impl ScraperEngine {
 
             // Click Sign-In button
             self.log("Looking for 'Sign-In' button".to_string(), LogLevel::Info).await;
-            let signin_button_selectors = vec![
-                "input[type='submit']",
-                "input[id='idSIButton9']",
-                "button[type='submit']",
-                "input[value='Sign in']",
-                "input[value='Anmelden']",
-                "button[id='idSIButton9']",
-            ];
-
             let mut signin_clicked = false;
-            for selector in &signin_button_selectors {
-                if let Ok(signin_button) = self.browser.find_element(thirtyfour::By::Css(*selector)).await {
+            for selector in &self.config.selectors.sign_in_button {
+                if let Ok(signin_button) = self.browser.find_element(thirtyfour::By::Css(selector)).await {
                     if signin_button.is_displayed().await.unwrap_or(false) && signin_button.is_enabled().await.unwrap_or(false) {
                         signin_button.click().await?;
                         self.log(format!("'Sign-In' button clicked with selector: {}", selector), LogLevel::Debug).await;
@@ -350,19 +924,12 @@ impl ScraperEngine {
         }
 
         // Handle "Stay signed in?" dialog
-        for attempt in 1..=15 {
-            self.log(format!("Trying to click on 'Yes' button... [{}/15]", attempt), LogLevel::Debug).await;
-
-            let stay_signed_selectors = vec![
-                "input[id='idSIButton9']",
-                "input[value='Yes']",
-                "input[value='Ja']",
-                "button[id='idSIButton9']",
-            ];
+        for attempt in 1..=attempts {
+            self.log(format!("Trying to click on 'Yes' button... [{}/{}]", attempt, attempts), LogLevel::Debug).await;
 
             let mut clicked = false;
-            for selector in &stay_signed_selectors {
-                if let Ok(button) = self.browser.find_element(thirtyfour::By::Css(*selector)).await {
+            for selector in &self.config.selectors.stay_signed_in_button {
+                if let Ok(button) = self.browser.find_element(thirtyfour::By::Css(selector)).await {
                     if button.is_displayed().await.unwrap_or(false) && button.is_enabled().await.unwrap_or(false) {
                         button.click().await?;
                         self.log("'Stay logged in' dialogue answered with 'Yes'".to_string(), LogLevel::Debug).await;
@@ -372,20 +939,29 @@ impl ScraperEngine {
                 }
             }
             if clicked { break; }
-            tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+            tokio::time::sleep(self.login_retry_delay()).await;
         }
 
+        self.finish_login().await
+    }
+
+    /// Organization dialog and check after the login form was submitted
+    async fn finish_login(&mut self) -> Result<()> {
         // Handle organization selection if multi-org dialog appears
         self.handle_organization_selection().await?;
 
         self.log("Waiting for return to EPLAN eVIEW...".to_string(), LogLevel::Info).await;
-        tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+        let timeout = tokio::time::Duration::from_secs(self.config.wait_timeouts.login_secs);
+        wait_until(timeout, WAIT_POLL_INTERVAL, || async {
+            let current_url = self.browser.get_current_url().await.unwrap_or_default();
+            looks_logged_in(&current_url, &self.config.base_url)
+        }).await;
 
         // Check if login was successful
         let current_url = self.browser.get_current_url().await?;
-        if !current_url.to_lowercase().contains("login") &&
-           (current_url.contains(&self.config.base_url) || current_url.to_lowercase().contains("eview")) {
-            self.log("Microsoft SSO login successful!".to_string(), LogLevel::Success).await;
+        if looks_logged_in(&current_url, &self.config.base_url) {
+            self.log("Login successful!".to_string(), LogLevel::Success).await;
+            self.save_session().await;
             Ok(())
         } else {
             self.log(format!("Login status unclear. Current URL: {}", current_url), LogLevel::Warning).await;
@@ -405,25 +981,26 @@ impl ScraperEngine {
 
         self.log("Organization selection dialog detected!".to_string(), LogLevel::Info).await;
 
-        // Try to find and click the 3CON organization
-        let organization_selectors = vec![
-            "//div[contains(text(), '3CON Anlagenbau')]",
-            "//div[contains(text(), '3con')]",
-            "//div[contains(text(), '3CON')]",
-            "//span[contains(text(), '3CON Anlagenbau')]",
-            "//span[contains(text(), '3con')]",
-            "//a[contains(text(), '3CON Anlagenbau')]",
-            "//button[contains(text(), '3CON Anlagenbau')]",
-            "//td[contains(text(), '3CON Anlagenbau')]",
-        ];
+        let organization = self.config.organization.clone();
+        if organization.is_empty() {
+            self.log("No organization set in the profile, proceeding anyway...".to_string(), LogLevel::Warning).await;
+            return Ok(());
+        }
+
+        // Try to find and click the organization of the profile
+        let literal = xpath_literal(&organization);
+        let organization_selectors: Vec<String> = ["div", "span", "a", "button", "td"]
+            .iter()
+            .map(|tag| format!("//{}[contains(text(), {})]", tag, literal))
+            .collect();
 
         let mut organization_selected = false;
         for selector in &organization_selectors {
             self.log(format!("Trying selector: {}", selector), LogLevel::Debug).await;
 
-            if let Ok(element) = self.browser.find_element(thirtyfour::By::XPath(*selector)).await {
+            if let Ok(element) = self.browser.find_element(thirtyfour::By::XPath(selector)).await {
                 if element.is_displayed().await.unwrap_or(false) {
-                    self.log("Found 3CON organization option, clicking...".to_string(), LogLevel::Info).await;
+                    self.log(format!("Found organization option '{}', clicking...", organization), LogLevel::Info).await;
                     element.click().await?;
                     organization_selected = true;
                     break;
@@ -432,10 +1009,14 @@ impl ScraperEngine {
         }
 
         if !organization_selected {
-            self.log("Could not find 3CON organization option, trying fallback detection...".to_string(), LogLevel::Warning).await;
-
-            // Fallback: look for any clickable element containing "3con" or "3CON"
-            if let Ok(elements) = self.browser.find_elements(thirtyfour::By::XPath("//*[contains(translate(text(), 'ABCDEFGHIJKLMNOPQRSTUVWXYZ', 'abcdefghijklmnopqrstuvwxyz'), '3con')]")).await {
+            self.log(format!("Could not find organization option '{}', trying fallback detection...", organization), LogLevel::Warning).await;
+
+            // Fallback: look for any clickable element containing the name in any case
+            let fallback = format!(
+                "//*[contains(translate(text(), 'ABCDEFGHIJKLMNOPQRSTUVWXYZ', 'abcdefghijklmnopqrstuvwxyz'), {})]",
+                xpath_literal(&organization.to_lowercase())
+            );
+            if let Ok(elements) = self.browser.find_elements(thirtyfour::By::XPath(&fallback)).await {
                 for element in elements {
                     if element.is_displayed().await.unwrap_or(false) && element.is_enabled().await.unwrap_or(false) {
                         let text = element.text().await.unwrap_or_default();
@@ -451,10 +1032,14 @@ impl ScraperEngine {
         if organization_selected {
             self.log("Organization selection completed successfully".to_string(), LogLevel::Success).await;
 
-            // Give it a moment to process
-            tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
+            // Until the dialog has been left
+            let timeout = tokio::time::Duration::from_secs(self.config.wait_timeouts.element_secs);
+            wait_until(timeout, WAIT_POLL_INTERVAL, || async {
+                let current_url = self.browser.get_current_url().await.unwrap_or_default().to_lowercase();
+                !current_url.contains("organization") && !current_url.contains("tenant")
+            }).await;
         } else {
-            self.log("No 3CON organization found, proceeding anyway...".to_string(), LogLevel::Warning).await;
+            self.log(format!("No organization '{}' found, proceeding anyway...", organization), LogLevel::Warning).await;
         }
 
         Ok(())
@@ -465,19 +1050,15 @@ impl ScraperEngine {
 
         // Wait for project overview
         self.log("Waiting for project overview...".to_string(), LogLevel::Info).await;
-        tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
 
-        self.log(format!("Looking for project '{}' in the list...", self.config.project_number), LogLevel::Info).await;
+        // Various ways the project could be displayed
+        let project_selectors = self.config.selectors.project_row_for(&self.config.project_number);
+        let timeout = tokio::time::Duration::from_secs(self.config.wait_timeouts.page_load_secs);
+        wait_until(timeout, WAIT_POLL_INTERVAL, || async {
+            find_first_xpath(&self.browser, &project_selectors).await.is_ok()
+        }).await;
 
-        // Various ways the project could be displayed (from Python)
-        let project_selectors = vec![
-            format!("//td[contains(text(), '{}')]", self.config.project_number),
-            format!("//span[contains(text(), '{}')]", self.config.project_number),
-            format!("//div[contains(text(), '{}')]", self.config.project_number),
-            format!("//a[contains(text(), '{}')]", self.config.project_number),
-            format!("//tr[contains(., '{}')]", self.config.project_number),
-            format!("//*[text()='{}']", self.config.project_number),
-        ];
+        self.log(format!("Looking for project '{}' in the list...", self.config.project_number), LogLevel::Info).await;
 
         let mut project_element = None;
 
@@ -527,8 +1108,22 @@ impl ScraperEngine {
             self.log("Couldn't scroll to element, continuing".to_string(), LogLevel::Debug).await;
         }
 
-        // Click on the project element
-        match project_element.click().await {
+        // Click on the project element, looking it up again if the list re-rendered
+        let browser = &self.browser;
+        let selectors = &project_selectors;
+        let mut first_attempt = true;
+        let clicked = retry_async(CLICK_ATTEMPTS, CLICK_BACKOFF, || {
+            let element = std::mem::take(&mut first_attempt).then(|| project_element.clone());
+            async move {
+                let element = match element {
+                    Some(element) => element,
+                    None => find_first_xpath(browser, selectors).await?,
+                };
+                Ok(element.click().await?)
+            }
+        }).await;
+
+        match clicked {
             Ok(_) => {
                 self.log("Project clicked".to_string(), LogLevel::Debug).await;
             }
@@ -574,13 +1169,8 @@ impl ScraperEngine {
             self.log("'Open' button clicked".to_string(), LogLevel::Success).await;
 
             self.log("Waiting for fully loading the project...".to_string(), LogLevel::Info).await;
-            tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
-
-            // Wait for sidebar using WebDriverWait equivalent
-            // For now, just check if sidebar exists
-            tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
-
-            if let Ok(_sidebar) = self.browser.find_element(thirtyfour::By::XPath("//div[contains(@class, 'tree') or contains(@class, 'sidebar')]")).await {
+            let sidebar = thirtyfour::By::XPath("//div[contains(@class, 'tree') or contains(@class, 'sidebar')]");
+            if self.browser.wait_for_element(sidebar, self.config.wait_timeouts.page_load_secs).await.is_ok() {
                 self.log("Project sidebar found".to_string(), LogLevel::Success).await;
             } else {
                 self.log("Project sidebar not found, still continuing".to_string(), LogLevel::Warning).await;
@@ -593,7 +1183,6 @@ impl ScraperEngine {
                current_url.to_lowercase().contains("viewer") ||
                current_url.to_lowercase().contains("view") {
                 self.log(format!("Project '{}' successfully opened!", self.config.project_number), LogLevel::Success).await;
-                tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
                 Ok(())
             } else if current_url != self.config.base_url {
                 self.log("Navigated to new page, project probably opened".to_string(), LogLevel::Success).await;
@@ -608,82 +1197,65 @@ impl ScraperEngine {
     }
 
     async fn switch_to_list_view(&mut self) -> Result<()> {
-        tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+        let three_dots = thirtyfour::By::Css(&self.config.selectors.three_dots_button);
+        if let Err(e) = self.browser.wait_for_element(three_dots, self.config.wait_timeouts.element_secs).await {
+            self.log(format!("Button with three dots did not show up: {}", e), LogLevel::Debug).await;
+        }
 
         // Click on button with three dots
-        self.log("Looking for buttons that are 'eplan-icon-button'".to_string(), LogLevel::Info).await;
+        let this = &*self;
+        retry_async(CLICK_ATTEMPTS, CLICK_BACKOFF, move || this.click_three_dots())
+            .await
+            .map_err(|e| anyhow::anyhow!("Can't click on button with three dots: {}", e))?;
 
-        let buttons = self.browser.find_elements(thirtyfour::By::Tag("eplan-icon-button")).await?;
-        self.log(format!("Found {} eplan-icon-button elements", buttons.len()), LogLevel::Info).await;
+        // Now find the list view button in the dropdown
+        retry_async(CLICK_ATTEMPTS, CLICK_BACKOFF, move || this.click_list_view_button()).await
+    }
+
+    /// Open the three-dots menu unless it is already open
+    async fn click_three_dots(&self) -> Result<()> {
+        let three_dots_selector = &self.config.selectors.three_dots_button;
+        self.log(format!("Looking for three dots button '{}'", three_dots_selector), LogLevel::Info).await;
+
+        let buttons = self.browser.find_elements(thirtyfour::By::Css(three_dots_selector)).await?;
+        self.log(format!("Found {} three dots buttons", buttons.len()), LogLevel::Info).await;
 
-        for (i, btn) in buttons.iter().enumerate() {
+        for btn in &buttons {
             if !btn.is_displayed().await.unwrap_or(false) {
                 continue;
             }
 
-            // Check for the specific data-t attribute
-            if let Ok(data_t) = btn.attr("data-t").await {
-                if let Some(data_t_value) = data_t {
-                    if !data_t_value.contains("ev-btn-page-more") {
-                        continue;
-                    }
-
-                    // Check if popup is already open
-                    if let Ok(class_attr) = btn.attr("class").await {
-                        if let Some(class_value) = class_attr {
-                            if class_value.contains("fl-pop-up-open") {
-                                self.log("Three dots pop-up is already open".to_string(), LogLevel::Info).await;
-                                break;
-                            }
-                        }
-                    }
-
-                    // Try to click the button
-                    match btn.click().await {
-                        Ok(_) => {
-                            self.log("Clicked button with three dots.".to_string(), LogLevel::Info).await;
-                            break;
-                        }
-                        Err(_) => {
-                            return Err(anyhow::anyhow!("Can't click on button with three dots"));
-                        }
-                    }
-                } else {
-                    self.log(format!("Can't find button with three dots, called at index {}", i), LogLevel::Error).await;
-                    continue;
+            // Check if popup is already open
+            if let Ok(Some(class_value)) = btn.attr("class").await {
+                if class_value.contains("fl-pop-up-open") {
+                    self.log("Three dots pop-up is already open".to_string(), LogLevel::Info).await;
+                    return Ok(());
                 }
-            } else {
-                self.log(format!("No data-t attribute found for button {}", i), LogLevel::Debug).await;
-                continue;
             }
+
+            btn.click().await?;
+            self.log("Clicked button with three dots.".to_string(), LogLevel::Info).await;
+            return Ok(());
         }
 
-        // Now find the list view button in the dropdown
-        let dropdown_buttons = self.browser.find_elements(thirtyfour::By::Tag("eplan-dropdown-item")).await?;
+        Ok(())
+    }
+
+    async fn click_list_view_button(&self) -> Result<()> {
+        let dropdown_buttons = self.browser.find_elements(thirtyfour::By::Css(&self.config.selectors.list_view_button)).await?;
 
         for btn in dropdown_buttons {
             if !btn.is_displayed().await.unwrap_or(false) {
                 continue;
             }
 
-            // Check for the specific data-name attribute
-            if let Ok(data_name) = btn.attr("data-name").await {
-                if let Some(data_name_value) = data_name {
-                    if !data_name_value.contains("ev-page-list-view-btn") {
-                        continue;
-                    }
-
-                    match btn.click().await {
-                        Ok(_) => {
-                            self.log("Clicked 'List' Button".to_string(), LogLevel::Info).await;
-                            return Ok(());
-                        }
-                        Err(_) => {
-                            return Err(anyhow::anyhow!("Can't click on 'List' button"));
-                        }
-                    }
+            return match btn.click().await {
+                Ok(_) => {
+                    self.log("Clicked 'List' Button".to_string(), LogLevel::Info).await;
+                    Ok(())
                 }
-            }
+                Err(e) => Err(anyhow::anyhow!("Can't click on 'List' button: {}", e)),
+            };
         }
 
         Err(anyhow::anyhow!("Failed to switch to list view"))
@@ -696,8 +1268,9 @@ impl ScraperEngine {
         let mut table = PlcTable::new(self.config.project_number.clone());
 
         // Find the scroll container
-        self.log("🔍 Looking for scroll container 'cdk-virtual-scroll-viewport'...".to_string(), LogLevel::Debug).await;
-        let scroll_container = match self.browser.find_element(thirtyfour::By::Css("cdk-virtual-scroll-viewport")).await {
+        let scroll_selector = &self.config.selectors.scroll_container;
+        self.log(format!("🔍 Looking for scroll container '{}'...", scroll_selector), LogLevel::Debug).await;
+        let scroll_container = match self.browser.wait_for_element(thirtyfour::By::Css(scroll_selector), self.config.wait_timeouts.element_secs).await {
             Ok(container) => {
                 self.log("✅ Found scroll container successfully".to_string(), LogLevel::Success).await;
                 container
@@ -713,13 +1286,29 @@ impl ScraperEngine {
         match self.browser.execute_script("arguments[0].scrollTop = 0", vec![scroll_container.clone()]).await {
             Ok(_) => {
                 self.log("✅ Successfully scrolled to top (scrollTop = 0)".to_string(), LogLevel::Success).await;
-                tokio::time::sleep(tokio::time::Duration::from_secs(1)).await; // Wait for content to load
+                // Wait for content to load
+                let timeout = tokio::time::Duration::from_secs(self.config.wait_timeouts.scroll_secs);
+                wait_until(timeout, WAIT_POLL_INTERVAL, || async {
+                    !self.rendered_items_fingerprint(&scroll_container).await.is_empty()
+                }).await;
             }
             Err(e) => {
                 self.log(format!("⚠️ Could not scroll to top: {}", e), LogLevel::Warning).await;
             }
         }
 
+        // Estimate the number of page items so progress can be reported while scrolling
+        let estimated_items = self.browser
+            .execute_script_and_get_value(ESTIMATE_ITEM_COUNT_SCRIPT, vec![scroll_container.clone()])
+            .await
+            .ok()
+            .and_then(|value| value.as_f64())
+            .unwrap_or(0.0) as usize;
+        self.log(format!("📏 Estimated {} page items in the list", estimated_items), LogLevel::Debug).await;
+
+        let mut progress = PageProgress::new(estimated_items);
+        self.report_progress(PAGE_PROGRESS_START).await;
+
         // STEP 2: Start systematic page-by-page processing
         self.log("📍 STEP 2: Starting systematic page-by-page processing...".to_string(), LogLevel::Info).await;
 
@@ -731,138 +1320,160 @@ impl ScraperEngine {
 
         // Main scrolling loop
         loop {
+            self.cancel.check()?;
             scroll_iteration += 1;
             self.log(format!("🔄 SCROLL ITERATION #{}: Scanning for page items...", scroll_iteration), LogLevel::Info).await;
 
-            // Find visible items
-            let visible_items = match self.browser.find_elements(thirtyfour::By::Tag("pv-page-list-item")).await {
-                Ok(items) => {
-                    self.log(format!("📋 Found {} visible page items in iteration #{}", items.len(), scroll_iteration), LogLevel::Debug).await;
-                    items
-                }
-                Err(e) => {
-                    self.log(format!("⚠️ Could not find page list items: {}", e), LogLevel::Warning).await;
-                    break;
-                }
-            };
+            // Fast path: read all rendered items in a single script call
+            let viewport_items = self.read_viewport_items().await;
 
-            // Process each visible item systematically
-            for i in 0..visible_items.len() {
-                total_pages_processed += 1;
+            if !viewport_items.is_empty() {
+                self.log(format!("📋 Read {} visible page items in one batch (iteration #{})", viewport_items.len(), scroll_iteration), LogLevel::Debug).await;
 
-                // Re-fetch element to avoid stale references
-                if let Ok(current_items) = self.browser.find_elements(thirtyfour::By::Tag("pv-page-list-item")).await {
-                    if i >= current_items.len() {
-                        self.log(format!("⚠️ Item index {} out of bounds ({}), skipping", i, current_items.len()), LogLevel::Warning).await;
+                for viewport_item in viewport_items {
+                    self.cancel.check()?;
+                    total_pages_processed += 1;
+                    progress.item_seen(&viewport_item.html);
+
+                    if !viewport_item.is_plc_diagram(&self.config.page_category_keywords) {
+                        self.log(format!("⚪ Page item #{} is not a PLC-Diagram (skipped)", total_pages_processed), LogLevel::Debug).await;
                         continue;
                     }
 
-                    let item = &current_items[i];
-                    self.log(format!("🔍 Processing page item #{} (iteration #{}, item #{})", total_pages_processed, scroll_iteration, i+1), LogLevel::Debug).await;
+                    let found_text = viewport_item.description.replace("\n", " ");
+                    if !plc_diagram_pages.insert(viewport_item.html.clone()) {
+                        self.log(format!("⚠️ PLC page already processed (duplicate): '{}'", found_text.trim()), LogLevel::Debug).await;
+                        continue;
+                    }
 
-                    // Check for PLC-Diagram using the correct selectors from screenshots
-                    let mut is_plc_diagram = false;
-                    let mut found_text = String::new();
+                    self.log(format!("🎯 CLICKING PLC-Diagram page #{} (found text: '{}')", plc_diagram_pages.len(), found_text.trim()), LogLevel::Info).await;
 
-                    // Method 1: Look for .ev-description.ev-hi elements (from screenshot analysis)
-                    if let Ok(description_elements) = item.find_all(thirtyfour::By::Css(".ev-description.ev-hi")).await {
-                        self.log(format!("🔍 Found {} .ev-description.ev-hi elements", description_elements.len()), LogLevel::Debug).await;
+                    // Only PLC-Diagram items need an element handle for the click
+                    let item = match find_page_item(&self.browser, viewport_item.index, &viewport_item.html).await {
+                        Ok(item) => item,
+                        Err(e) => {
+                            self.log(format!("⚠️ Page '{}' is no longer rendered, skipping: {}", found_text.trim(), e), LogLevel::Warning).await;
+                            continue;
+                        }
+                    };
 
-                        for desc_element in &description_elements {
-                            if let Ok(text) = desc_element.text().await {
-                                self.log(format!("📝 .ev-description.ev-hi text: '{}'", text), LogLevel::Debug).await;
-                                if text.contains("PLC-Diagram") {
-                                    is_plc_diagram = true;
-                                    found_text = text.clone();
-                                    self.log(format!("✅ FOUND PLC-Diagram in .ev-description.ev-hi: '{}'", text), LogLevel::Success).await;
-                                    break;
+                    if let Some(extracted_text) = self.open_and_extract_page(&item, viewport_item.index, &viewport_item.html, plc_diagram_pages.len()).await {
+                        extracted_page_texts.push(extracted_text);
+                    }
+                    self.report_progress(progress.fraction()).await;
+                }
+            } else {
+                // Fallback: find visible items and inspect them one by one
+                let visible_items = match self.browser.find_elements(thirtyfour::By::Tag("pv-page-list-item")).await {
+                    Ok(items) => {
+                        self.log(format!("📋 Found {} visible page items in iteration #{}", items.len(), scroll_iteration), LogLevel::Debug).await;
+                        items
+                    }
+                    Err(e) => {
+                        self.log(format!("⚠️ Could not find page list items: {}", e), LogLevel::Warning).await;
+                        break;
+                    }
+                };
+
+                // Process each visible item systematically
+                for i in 0..visible_items.len() {
+                    total_pages_processed += 1;
+                    progress.item_processed();
+
+                    // Re-fetch element to avoid stale references
+                    if let Ok(current_items) = self.browser.find_elements(thirtyfour::By::Tag("pv-page-list-item")).await {
+                        if i >= current_items.len() {
+                            self.log(format!("⚠️ Item index {} out of bounds ({}), skipping", i, current_items.len()), LogLevel::Warning).await;
+                            continue;
+                        }
+
+                        let item = &current_items[i];
+                        self.log(format!("🔍 Processing page item #{} (iteration #{}, item #{})", total_pages_processed, scroll_iteration, i+1), LogLevel::Debug).await;
+
+                        // Check for PLC-Diagram using the correct selectors from screenshots
+                        let mut is_plc_diagram = false;
+                        let mut found_text = String::new();
+
+                        // Method 1: Look for .ev-description.ev-hi elements (from screenshot analysis)
+                        if let Ok(description_elements) = item.find_all(thirtyfour::By::Css(".ev-description.ev-hi")).await {
+                            self.log(format!("🔍 Found {} .ev-description.ev-hi elements", description_elements.len()), LogLevel::Debug).await;
+
+                            for desc_element in &description_elements {
+                                if let Ok(text) = desc_element.text().await {
+                                    self.log(format!("📝 .ev-description.ev-hi text: '{}'", text), LogLevel::Debug).await;
+                                    if contains_keyword(&text, &self.config.page_category_keywords) {
+                                        is_plc_diagram = true;
+                                        found_text = text.clone();
+                                        self.log(format!("✅ FOUND PLC-Diagram in .ev-description.ev-hi: '{}'", text), LogLevel::Success).await;
+                                        break;
+                                    }
                                 }
                             }
                         }
-                    }
 
-                    // Method 2: Fallback - look in all nested elements
-                    if !is_plc_diagram {
-                        if let Ok(all_nested) = item.find_all(thirtyfour::By::XPath(".//*[contains(text(), 'PLC-Diagram')]")).await {
-                            if !all_nested.is_empty() {
-                                if let Ok(text) = all_nested[0].text().await {
-                                    is_plc_diagram = true;
-                                    found_text = text.clone();
-                                    self.log(format!("✅ FOUND PLC-Diagram via XPath fallback: '{}'", text), LogLevel::Success).await;
+                        // Method 2: Fallback - look in all nested elements
+                        if !is_plc_diagram {
+                            let xpath = keywords_xpath(&self.config.page_category_keywords);
+                            if let Ok(all_nested) = item.find_all(thirtyfour::By::XPath(&xpath)).await {
+                                if !all_nested.is_empty() {
+                                    if let Ok(text) = all_nested[0].text().await {
+                                        is_plc_diagram = true;
+                                        found_text = text.clone();
+                                        self.log(format!("✅ FOUND PLC-Diagram via XPath fallback: '{}'", text), LogLevel::Success).await;
+                                    }
                                 }
                             }
                         }
-                    }
 
-                    // Method 3: Ultimate fallback - check all text content
-                    if !is_plc_diagram {
-                        if let Ok(item_text) = item.text().await {
-                            self.log(format!("📝 Full item text: '{}'", item_text.replace("\n", " ").trim()), LogLevel::Debug).await;
-                            if item_text.contains("PLC-Diagram") {
-                                is_plc_diagram = true;
-                                found_text = item_text.clone();
-                                self.log(format!("✅ FOUND PLC-Diagram in full text: '{}'", item_text.replace("\n", " ").trim()), LogLevel::Success).await;
+                        // Method 3: Ultimate fallback - check all text content
+                        if !is_plc_diagram {
+                            if let Ok(item_text) = item.text().await {
+                                self.log(format!("📝 Full item text: '{}'", item_text.replace("\n", " ").trim()), LogLevel::Debug).await;
+                                if contains_keyword(&item_text, &self.config.page_category_keywords) {
+                                    is_plc_diagram = true;
+                                    found_text = item_text.clone();
+                                    self.log(format!("✅ FOUND PLC-Diagram in full text: '{}'", item_text.replace("\n", " ").trim()), LogLevel::Success).await;
+                                }
                             }
                         }
-                    }
 
-                    if is_plc_diagram {
-                        // Get unique identifier using outerHTML
-                        if let Ok(Some(outer_html)) = item.attr("outerHTML").await {
-                            if plc_diagram_pages.insert(outer_html) {
-                                self.log(format!("🎯 CLICKING PLC-Diagram page #{} (found text: '{}')", plc_diagram_pages.len(), found_text.replace("\n", " ").trim()), LogLevel::Info).await;
-
-                                // Small delay to stabilize
-                                tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
-
-                                // Click the item
-                                match item.click().await {
-                                    Ok(_) => {
-                                        self.log(format!("✅ Successfully clicked PLC page #{}", plc_diagram_pages.len()), LogLevel::Success).await;
-
-                                        // Wait for page to update
-                                        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
-
-                                        // Extract content from this page
-                                        self.log(format!("⚙️ Extracting content from PLC page #{}...", plc_diagram_pages.len()), LogLevel::Info).await;
-                                        match self.extract_current_plc_diagram_page().await {
-                                            Ok(extracted_text) => {
-                                                if !extracted_text.is_empty() {
-                                                    extracted_page_texts.push(extracted_text);
-                                                    self.log(format!("✅ Successfully extracted content from PLC page #{} (total: {})", plc_diagram_pages.len(), extracted_page_texts.len()), LogLevel::Success).await;
-                                                } else {
-                                                    self.log(format!("⚠️ No content extracted from PLC page #{}", plc_diagram_pages.len()), LogLevel::Warning).await;
-                                                }
-                                            }
-                                            Err(e) => {
-                                                self.log(format!("❌ Error extracting content from PLC page #{}: {}", plc_diagram_pages.len(), e), LogLevel::Error).await;
-                                            }
-                                        }
-                                    }
-                                    Err(e) => {
-                                        self.log(format!("❌ Failed to click PLC page #{}: {}", plc_diagram_pages.len(), e), LogLevel::Error).await;
+                        if is_plc_diagram {
+                            // Get unique identifier using outerHTML
+                            if let Ok(Some(outer_html)) = item.attr("outerHTML").await {
+                                if plc_diagram_pages.insert(outer_html.clone()) {
+                                    self.log(format!("🎯 CLICKING PLC-Diagram page #{} (found text: '{}')", plc_diagram_pages.len(), found_text.replace("\n", " ").trim()), LogLevel::Info).await;
+
+                                    if let Some(extracted_text) = self.open_and_extract_page(item, i, &outer_html, plc_diagram_pages.len()).await {
+                                        extracted_page_texts.push(extracted_text);
                                     }
+                                    self.report_progress(progress.fraction()).await;
+                                } else {
+                                    self.log(format!("⚠️ PLC page already processed (duplicate): '{}'", found_text.replace("\n", " ").trim()), LogLevel::Debug).await;
                                 }
-                            } else {
-                                self.log(format!("⚠️ PLC page already processed (duplicate): '{}'", found_text.replace("\n", " ").trim()), LogLevel::Debug).await;
                             }
+                        } else {
+                            self.log(format!("⚪ Page item #{} is not a PLC-Diagram (skipped)", total_pages_processed), LogLevel::Debug).await;
                         }
-                    } else {
-                        self.log(format!("⚪ Page item #{} is not a PLC-Diagram (skipped)", total_pages_processed), LogLevel::Debug).await;
                     }
-                }
 
-                // Small delay between items to avoid overwhelming the browser
-                tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+                    // Opening a page re-renders the list, wait until its items are back
+                    let timeout = tokio::time::Duration::from_secs(self.config.wait_timeouts.element_secs);
+                    wait_until(timeout, WAIT_POLL_INTERVAL, || async {
+                        self.browser.find_elements(thirtyfour::By::Tag("pv-page-list-item")).await
+                            .is_ok_and(|items| !items.is_empty())
+                    }).await;
+                }
             }
 
+            self.report_progress(progress.fraction()).await;
+
             // Scroll down for next batch of items
             self.log(format!("⬇️ Scrolling down for next batch (iteration #{})...", scroll_iteration), LogLevel::Debug).await;
+            let before_scroll = self.rendered_items_fingerprint(&scroll_container).await;
             if let Err(e) = self.browser.execute_script("arguments[0].scrollTop += 400", vec![scroll_container.clone()]).await {
                 self.log(format!("❌ Could not scroll down: {}", e), LogLevel::Warning).await;
                 break;
             }
-            tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
 
             // Check if reached bottom
             if let Ok(new_height) = self.browser.execute_script_and_get_value("return arguments[0].scrollTop", vec![scroll_container.clone()]).await {
@@ -874,6 +1485,12 @@ impl ScraperEngine {
                         break; // reached bottom
                     }
                     last_height = height_num;
+
+                    // Wait until the list rendered the items scrolled into view
+                    let timeout = tokio::time::Duration::from_secs(self.config.wait_timeouts.scroll_secs);
+                    wait_until(timeout, WAIT_POLL_INTERVAL, || async {
+                        self.rendered_items_fingerprint(&scroll_container).await != before_scroll
+                    }).await;
                 } else {
                     self.log("⚠️ Could not get scroll height, assuming bottom reached".to_string(), LogLevel::Warning).await;
                     break;
@@ -884,6 +1501,8 @@ impl ScraperEngine {
             }
         }
 
+        self.report_progress(PAGE_PROGRESS_END).await;
+
         // Final results summary
         self.log("📊 EXTRACTION SUMMARY:".to_string(), LogLevel::Info).await;
         self.log(format!("   📋 Total pages scanned: {}", total_pages_processed), LogLevel::Info).await;
@@ -916,64 +1535,136 @@ impl ScraperEngine {
         Ok(!plc_diagram_pages.is_empty())
     }
 
-    async fn wait_for_svg_content(&self) -> Result<()> {
-        // Try to wait for SVG content to load (similar to Python WebDriverWait)
-        for _ in 0..10 { // 5 second timeout
-            if let Ok(_) = self.browser.find_element(thirtyfour::By::Tag("svg")).await {
-                return Ok(());
+    /// Collect description text and outerHTML of every rendered page list item in one round-trip.
+    /// Returns an empty list if the script fails, so callers can fall back to per-item queries.
+    async fn read_viewport_items(&self) -> Vec<ViewportItem> {
+        let value = match self.browser.execute_script_with_args(VIEWPORT_ITEMS_SCRIPT, Vec::new()).await {
+            Ok(value) => value,
+            Err(e) => {
+                self.log(format!("⚠️ Batch read of page items failed, using per-item fallback: {}", e), LogLevel::Debug).await;
+                return Vec::new();
+            }
+        };
+
+        match serde_json::from_value(value) {
+            Ok(items) => items,
+            Err(e) => {
+                self.log(format!("⚠️ Unexpected batch read result, using per-item fallback: {}", e), LogLevel::Debug).await;
+                Vec::new()
             }
-            tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
         }
-        Err(anyhow::anyhow!("SVG content not found"))
+    }
+
+    /// See `RENDERED_ITEMS_SCRIPT`, empty while no item is rendered
+    async fn rendered_items_fingerprint(&self, scroll_container: &thirtyfour::WebElement) -> String {
+        self.browser
+            .execute_script_and_get_value(RENDERED_ITEMS_SCRIPT, vec![scroll_container.clone()])
+            .await
+            .ok()
+            .and_then(|value| value.as_str().map(str::to_string))
+            .unwrap_or_default()
+    }
+
+    /// See `DRAWING_TEXT_SCRIPT`, empty while no drawing is shown
+    async fn drawing_text(&self) -> String {
+        self.browser
+            .execute_script_and_get_value(DRAWING_TEXT_SCRIPT, Vec::new())
+            .await
+            .ok()
+            .and_then(|value| value.as_str().map(str::to_string))
+            .unwrap_or_default()
+    }
+
+    /// `click_and_extract_page` with its messages logged in the page's phase
+    async fn open_and_extract_page(&mut self, item: &thirtyfour::WebElement, item_index: usize, item_html: &str, page_number: usize) -> Option<String> {
+        self.phase = Some(LogPhase::Page(page_number));
+        let extracted_text = self.click_and_extract_page(item, item_index, item_html, page_number).await;
+        self.phase = Some(LogPhase::Extract);
+        extracted_text
+    }
+
+    /// Click a PLC-Diagram page list item and extract its content. `item_index`
+    /// and `item_html` are the item's position among the rendered
+    /// `pv-page-list-item`s and its outerHTML, used by `find_page_item` to find
+    /// the same page again when the list re-rendered. Gives up if it is gone.
+    async fn click_and_extract_page(&self, item: &thirtyfour::WebElement, item_index: usize, item_html: &str, page_number: usize) -> Option<String> {
+        // Drawing of the page shown before, a re-rendered item is retried below
+        let previous_drawing = self.drawing_text().await;
+
+        let browser = &self.browser;
+        let mut first_attempt = true;
+        let clicked = retry_async(CLICK_ATTEMPTS, CLICK_BACKOFF, || {
+            let element = std::mem::take(&mut first_attempt).then(|| item.clone());
+            async move {
+                let element = match element {
+                    Some(element) => element,
+                    None => find_page_item(browser, item_index, item_html).await?,
+                };
+                Ok(element.click().await?)
+            }
+        }).await;
+
+        if let Err(e) = clicked {
+            self.log(format!("❌ Failed to click PLC page #{}: {}", page_number, e), LogLevel::Error).await;
+            return None;
+        }
+        self.log(format!("✅ Successfully clicked PLC page #{}", page_number), LogLevel::Success).await;
+
+        // Wait for page to update
+        let timeout = tokio::time::Duration::from_secs(self.config.wait_timeouts.element_secs);
+        let rendered = wait_until(timeout, WAIT_POLL_INTERVAL, || async {
+            let drawing = self.drawing_text().await;
+            !drawing.is_empty() && drawing != previous_drawing
+        }).await;
+        if !rendered {
+            self.log(format!("⚠️ Drawing of PLC page #{} did not change, extracting anyway", page_number), LogLevel::Debug).await;
+        }
+        self.forward_browser_console().await;
+
+        // Extract content from this page
+        self.log(format!("⚙️ Extracting content from PLC page #{}...", page_number), LogLevel::Info).await;
+        match self.extract_current_plc_diagram_page().await {
+            Ok(extracted_text) if !extracted_text.is_empty() => {
+                self.log(format!("✅ Successfully extracted content from PLC page #{}", page_number), LogLevel::Success).await;
+                Some(extracted_text)
+            }
+            Ok(_) => {
+                self.log(format!("⚠️ No content extracted from PLC page #{}", page_number), LogLevel::Warning).await;
+                None
+            }
+            Err(e) => {
+                self.log(format!("❌ Error extracting content from PLC page #{}: {}", page_number, e), LogLevel::Error).await;
+                None
+            }
+        }
+    }
+
+    async fn wait_for_svg_content(&self) -> Result<()> {
+        self.browser
+            .wait_for_element(thirtyfour::By::Tag("svg"), self.config.wait_timeouts.element_secs)
+            .await
+            .map(|_| ())
+            .map_err(|_| anyhow::anyhow!("SVG content not found"))
     }
 
     async fn extract_current_plc_diagram_page(&self) -> Result<String> {
         // This method should match Python extract_current_plc_diagram_page_advanced()
-        let mut extracted_content = Vec::new();
-
         // Try to extract content (Python line 1032-1056)
-        match self.browser.get_page_source().await {
+        let mut extracted_content = match self.browser.get_page_source().await {
             Ok(page_source) => {
-                // Use regex patterns exactly like Python (line 1038-1042)
-                let text_pattern = regex::Regex::new(r"<text[^>]*>([^<]+)</text>").unwrap();
-                let tspan_pattern = regex::Regex::new(r"<tspan[^>]*>([^<]+)</tspan>").unwrap();
-
-                // Find text matches (Python line 1039)
-                for capture in text_pattern.captures_iter(&page_source) {
-                    if let Some(text_match) = capture.get(1) {
-                        extracted_content.push(text_match.as_str().to_string());
-                    }
-                }
-
-                // Extend with tspan matches (Python line 1041-1042)
-                for capture in tspan_pattern.captures_iter(&page_source) {
-                    if let Some(text_match) = capture.get(1) {
-                        extracted_content.push(text_match.as_str().to_string());
-                    }
-                }
-
-                if !extracted_content.is_empty() {
-                    self.log(format!("Regex found {} text matches", extracted_content.len()), LogLevel::Debug).await;
-
-                    // Filter content (Python line 1047-1053)
-                    let mut filtered_content = Vec::new();
-                    for text in extracted_content {
-                        let trimmed = text.trim();
-                        if !trimmed.is_empty() && trimmed.len() > 2 {
-                            // Filter out unwanted elements (Python line 1050-1052)
-                            if !["Date", "Datum", "ET 200SP"].iter().any(|skip| trimmed.contains(skip)) {
-                                filtered_content.push(trimmed.to_string());
-                            }
-                        }
-                    }
-                    extracted_content = filtered_content;
-                }
+                // Texts of the drawing, a symbol name split over two lines joined again
+                let texts = extractor::PlcDataExtractor::extract_from_svg(&page_source);
+                self.log(format!("Found {} text elements", texts.len()), LogLevel::Debug).await;
+                texts
             }
             Err(e) => {
                 self.log(format!("Page source extraction failed: {}", e), LogLevel::Error).await;
                 return Ok(String::new());
             }
-        }
+        };
+
+        // Filter out unwanted elements (Python line 1050-1052)
+        extracted_content.retain(|text| !["Date", "Datum", "ET 200SP"].iter().any(|skip| text.contains(skip)));
 
         if !extracted_content.is_empty() {
             // Remove duplicates while preserving order (Python line 1058-1064)
@@ -992,13 +1683,13 @@ impl ScraperEngine {
 
             // Parse the data (Python line 1071-1073)
             self.log("TRYING TO CALL PARSE".to_string(), LogLevel::Debug).await;
-            let parsed_data = self.parse_plc_data(&result);
+            let parsed_data = Self::parse_plc_data(&result);
 
-            // Format result like Python (line 1073: "; ".join(" ".join(d.values()) for d in parsed_data))
+            // One entry per line as "name address comment", which parse_plc_data reads back unchanged
             let result_string = parsed_data.into_iter()
-                .map(|entry| format!("{} {}", entry.address, entry.symbol_name))
+                .map(|entry| format!("{} {} {}", entry.symbol_name, entry.address, entry.comment).trim_end().to_string())
                 .collect::<Vec<_>>()
-                .join("; ");
+                .join("\n");
 
             Ok(result_string)
         } else {
@@ -1023,14 +1714,18 @@ impl ScraperEngine {
     }
 
     async fn parse_and_add_to_table(&self, page_text: &str, table: &mut PlcTable) {
-        let entries = self.parse_plc_data(page_text);
+        let entries = Self::parse_plc_data(page_text);
         for entry in entries {
             table.entries.push(entry);
         }
     }
 
-    fn parse_plc_data(&self, input_string: &str) -> Vec<PlcEntry> {
-        let mut results = Vec::new();
+    /// Parse extracted diagram text. The function text before an address becomes
+    /// the symbol name and the descriptive text after it the comment. Between two
+    /// addresses on one line, a trailing function text like "Pump 1.2" belongs to
+    /// the next address and the words before it are the previous comment.
+    fn parse_plc_data(input_string: &str) -> Vec<PlcEntry> {
+        let mut results: Vec<PlcEntry> = Vec::new();
 
         // Split string into lines
         let normalized = input_string.replace("\r\n", "\n").replace('\r', "\n");
@@ -1039,8 +1734,10 @@ impl ScraperEngine {
         // Regex patterns from Python
         let address_pattern = regex::Regex::new(r"\b([IQ]W?\d+\.\d+|[IQ]W\d+)\b").unwrap();
         let function_pattern = regex::Regex::new(r"([A-Za-z][A-Za-z\s]+(?:\d+\.)+\d+(?:\s+[A-Z]+)?)").unwrap();
+        let whole_function_pattern = regex::Regex::new(r"^[A-Za-z][A-Za-z\s]+(?:\d+\.)+\d+(?:\s+[A-Z]+)?$").unwrap();
 
         let mut current_function = String::new();
+        let mut hardware = extractor::HardwareContext::new();
 
         for line in lines {
             let line = line.trim();
@@ -1049,33 +1746,58 @@ impl ScraperEngine {
                 continue;
             }
 
-            if let Some(address_match) = address_pattern.find(line) {
+            hardware.update(line, &address_pattern);
+            let mut segment_start = 0;
+            let mut previous_entry: Option<usize> = None;
+
+            for address_match in address_pattern.find_iter(line) {
                 let address = address_match.as_str().to_string();
-                let text_before_address = &line[..address_match.start()].trim();
-
-                if let Some(function_match) = function_pattern.find(text_before_address) {
-                    current_function = function_match.as_str().trim().to_string();
-                } else if !text_before_address.is_empty() && !text_before_address.starts_with('=') {
-                    let parts: Vec<&str> = text_before_address.split_whitespace().collect();
-                    let valid_parts: Vec<&str> = parts.into_iter()
-                        .filter(|p| !p.starts_with('=') && !p.starts_with(':'))
-                        .collect();
-                    if !valid_parts.is_empty() {
-                        current_function = valid_parts.join(" ");
+                let segment = line[segment_start..address_match.start()].trim();
+                segment_start = address_match.end();
+
+                let split = previous_entry.and_then(|_| split_trailing_function(segment, &whole_function_pattern));
+                let confidence = match (previous_entry, split) {
+                    (Some(index), Some((comment, function))) => {
+                        results[index].comment = clean_comment(&comment);
+                        current_function = function;
+                        extractor::CONFIDENCE_FUNCTION_MATCH
                     }
-                }
+                    _ => match function_text(segment, &function_pattern) {
+                        Some((function, confidence)) => {
+                            current_function = function;
+                            confidence
+                        }
+                        None => extractor::CONFIDENCE_NO_CONTEXT, // Carried over from an earlier address
+                    },
+                };
 
+                previous_entry = None;
                 if !current_function.is_empty() {
-                    results.push(PlcEntry {
+                    let mut entry = PlcEntry {
                         address: address.clone(),
                         symbol_name: current_function.clone(),
                         data_type: crate::models::PlcDataType::from_address(&address),
                         page: "".to_string(), // Will be set elsewhere if needed
                         selected: false,
                         comment: String::new(),
-                    });
+                        comment_edited: false,
+                        removed: false,
+                        tags: Vec::new(),
+                        entry_status: crate::models::EntryStatus::Unchanged,
+                        module: None,
+                        slot: None,
+                        confidence,
+                    };
+                    hardware.apply(&mut entry);
+                    results.push(entry);
+                    previous_entry = Some(results.len() - 1);
                 }
             }
+
+            // Text after the last address describes it
+            if let Some(index) = previous_entry {
+                results[index].comment = clean_comment(&line[segment_start..]);
+            }
         }
 
         results
@@ -1085,9 +1807,414 @@ impl ScraperEngine {
         // Close browser first
         self.browser.quit().await?;
 
-        // Then stop ChromeDriver
-        self.chromedriver_manager.stop_driver().await?;
+        // Then stop ChromeDriver, unless it was started by hand
+        if self.config.external_chromedriver_url.is_none() {
+            self.chromedriver_manager.stop_driver().await?;
+        }
 
         Ok(())
     }
+
+    /// Quit only this run's browser after a cancel. ChromeDriver is left running,
+    /// a run started right after the cancel may already be using it.
+    pub async fn close_cancelled(&self) {
+        if let Err(e) = self.browser.quit().await {
+            self.log(format!("⚠️ Browser cleanup after cancel failed: {}", e), LogLevel::Warning).await;
+        }
+    }
+}
+
+/// Function text in front of an address: the first function-like match, or
+/// the words of `text` without `=`/`:` prefixed tags, with the confidence of each
+fn function_text(text: &str, function_pattern: &regex::Regex) -> Option<(String, f32)> {
+    if let Some(function_match) = function_pattern.find(text) {
+        return Some((function_match.as_str().trim().to_string(), extractor::CONFIDENCE_FUNCTION_MATCH));
+    }
+
+    if text.is_empty() || text.starts_with('=') {
+        return None;
+    }
+
+    let valid_parts: Vec<&str> = text
+        .split_whitespace()
+        .filter(|p| !p.starts_with('=') && !p.starts_with(':'))
+        .collect();
+    if valid_parts.is_empty() {
+        None
+    } else {
+        Some((valid_parts.join(" "), extractor::CONFIDENCE_TEXT_BEFORE))
+    }
+}
+
+/// Split the text between two addresses into the previous comment and the
+/// shortest trailing run of words that forms a function text
+fn split_trailing_function(segment: &str, whole_function_pattern: &regex::Regex) -> Option<(String, String)> {
+    let words: Vec<&str> = segment.split_whitespace().collect();
+
+    (1..=words.len()).find_map(|count| {
+        let function = words[words.len() - count..].join(" ");
+        whole_function_pattern
+            .is_match(&function)
+            .then(|| (words[..words.len() - count].join(" "), function))
+    })
+}
+
+fn clean_comment(text: &str) -> String {
+    text.trim().trim_matches(';').trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_browser_console_levels() {
+        let entries: Vec<browser::BrowserConsoleEntry> = serde_json::from_value(serde_json::json!([
+            { "level": "SEVERE", "message": "Uncaught TypeError", "source": "javascript", "timestamp": 1700000000000u64 },
+            { "level": "WARNING", "message": "Deprecated API", "source": "console-api", "timestamp": 1700000000001u64 },
+            { "level": "INFO", "message": "Loaded", "source": "console-api", "timestamp": 1700000000002u64 },
+        ])).unwrap();
+
+        assert_eq!(entries[0].timestamp, 1700000000000);
+        assert!(matches!(entries[0].log_level(), LogLevel::Error));
+        assert!(matches!(entries[1].log_level(), LogLevel::Warning));
+        assert!(matches!(entries[2].log_level(), LogLevel::Debug));
+    }
+
+    #[test]
+    fn test_looks_logged_in() {
+        let base = "https://eview.eplan.com/";
+        assert!(looks_logged_in("https://eview.eplan.com/projects", base));
+        assert!(!looks_logged_in("https://login.microsoftonline.com/common/oauth2", base));
+        assert!(!looks_logged_in("https://eview.eplan.com/Login?returnUrl=%2F", base));
+        assert!(!looks_logged_in("", base));
+    }
+
+    #[test]
+    fn test_retryable_errors() {
+        assert!(is_retryable(&anyhow::anyhow!("stale element reference: stale element not found")));
+        assert!(is_retryable(&anyhow::anyhow!("element click intercepted: Element <div> is not clickable").context("click")));
+        assert!(!is_retryable(&anyhow::anyhow!("no such element: Unable to locate element")));
+    }
+
+    #[tokio::test]
+    async fn test_retry_async_stops_on_fatal_errors() {
+        let backoff = tokio::time::Duration::from_millis(1);
+
+        let mut calls = 0;
+        let result: Result<u32> = retry_async(4, backoff, || {
+            calls += 1;
+            let call = calls;
+            async move {
+                if call < 3 {
+                    Err(anyhow::anyhow!("stale element reference"))
+                } else {
+                    Ok(call)
+                }
+            }
+        }).await;
+        assert_eq!(result.unwrap(), 3);
+
+        // A missing element is not retried
+        calls = 0;
+        let result: Result<()> = retry_async(4, backoff, || {
+            calls += 1;
+            async { Err(anyhow::anyhow!("no such element")) }
+        }).await;
+        assert!(result.is_err());
+        assert_eq!(calls, 1);
+
+        // Retryable errors give up after the last attempt
+        calls = 0;
+        let result: Result<()> = retry_async(3, backoff, || {
+            calls += 1;
+            async { Err(anyhow::anyhow!("element click intercepted")) }
+        }).await;
+        assert!(result.is_err());
+        assert_eq!(calls, 3);
+    }
+
+    #[tokio::test]
+    async fn test_wait_until_polls_until_timeout() {
+        let timeout = tokio::time::Duration::from_millis(50);
+        let poll = tokio::time::Duration::from_millis(1);
+
+        let mut calls = 0;
+        assert!(wait_until(timeout, poll, || {
+            calls += 1;
+            let call = calls;
+            async move { call == 3 }
+        }).await);
+        assert_eq!(calls, 3);
+
+        // A condition that never holds is given up on
+        assert!(!wait_until(timeout, poll, || async { false }).await);
+    }
+
+    /// WebDriver server that starts one session, answers every other command
+    /// with the value `respond` gives for its request line and records the request lines
+    async fn fake_webdriver(
+        respond: impl Fn(&str) -> serde_json::Value + Send + Sync + 'static,
+    ) -> (String, Arc<std::sync::Mutex<Vec<String>>>) {
+        use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let seen = requests.clone();
+        let respond = Arc::new(respond);
+
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let seen = seen.clone();
+                let respond = respond.clone();
+                tokio::spawn(async move {
+                    let mut stream = BufReader::new(stream);
+                    loop {
+                        let mut request_line = String::new();
+                        if stream.read_line(&mut request_line).await.unwrap_or(0) == 0 {
+                            return;
+                        }
+                        let mut content_length = 0;
+                        loop {
+                            let mut header = String::new();
+                            stream.read_line(&mut header).await.unwrap();
+                            if header.trim().is_empty() {
+                                break;
+                            }
+                            if let Some((name, value)) = header.split_once(':') {
+                                if name.eq_ignore_ascii_case("content-length") {
+                                    content_length = value.trim().parse().unwrap();
+                                }
+                            }
+                        }
+                        let mut body = vec![0; content_length];
+                        stream.read_exact(&mut body).await.unwrap();
+
+                        let request_line = request_line.trim().to_string();
+                        let value = if request_line.starts_with("POST /session ") {
+                            serde_json::json!({ "sessionId": "fake", "capabilities": {} })
+                        } else {
+                            respond(&request_line)
+                        };
+                        seen.lock().unwrap().push(request_line);
+
+                        let body = serde_json::json!({ "value": value }).to_string();
+                        let response = format!(
+                            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                            body.len(),
+                            body
+                        );
+                        stream.get_mut().write_all(response.as_bytes()).await.unwrap();
+                    }
+                });
+            }
+        });
+
+        (url, requests)
+    }
+
+    struct NoLogger;
+
+    impl Logger for NoLogger {
+        fn log(&self, _message: String, _level: LogLevel) {}
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_run_keeps_the_shared_driver() {
+        let (url, requests) = fake_webdriver(|_| serde_json::Value::Null).await;
+        let browser = browser::BrowserDriver::new(&test_config(&url)).await.unwrap();
+
+        // A run on the ChromeDriver started by the app, cancelled before its first step
+        let chromedriver_manager = Arc::new(ChromeDriverManager::with_stand_in().await);
+        let cancel = CancellationToken::new();
+        let mut scraper = ScraperEngine {
+            browser,
+            config: test_config(""),
+            logger: Arc::new(Mutex::new(Box::new(NoLogger) as Box<dyn Logger>)),
+            chromedriver_manager: chromedriver_manager.clone(),
+            extracted_table: None,
+            cancel: CancellationToken::new(),
+            phase: None,
+        };
+        scraper.set_cancellation_token(cancel.clone());
+        cancel.cancel();
+        assert!(scraper.run_extraction().await.is_err());
+
+        // The browser session is gone, the driver a new run may use is not
+        scraper.close_cancelled().await;
+        assert!(requests.lock().unwrap().iter().any(|request| request.starts_with("DELETE /session/fake ")));
+        assert!(chromedriver_manager.is_running().await);
+
+        chromedriver_manager.stop_driver().await.unwrap();
+    }
+
+    /// Config for a browser on the fake WebDriver at `driver_url`, empty for the app's ChromeDriver
+    fn test_config(driver_url: &str) -> ScraperConfig {
+        ScraperConfig {
+            base_url: "https://eview.eplan.com/".to_string(),
+            organization: String::new(),
+            username: String::new(),
+            password: String::new(),
+            project_number: "P1".to_string(),
+            headless: true,
+            proxy: None,
+            user_agent: None,
+            external_chromedriver_url: (!driver_url.is_empty()).then(|| driver_url.to_string()),
+            window_width: 800,
+            window_height: 600,
+            login_attempts: 1,
+            attempt_interval_ms: 0,
+            page_category_keywords: Vec::new(),
+            selectors: Selectors::default(),
+            dry_run: false,
+            native_login: false,
+            session_cookies: None,
+            wait_timeouts: WaitTimeouts::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_find_page_item_checks_the_html() {
+        // The list re-rendered: the item read at index 0 is now at index 1
+        let (url, _) = fake_webdriver(|request| {
+            let element = |id: &str| serde_json::json!({ "element-6066-11e4-a52e-4f735466cecf": id });
+            if request.starts_with("POST /session/fake/elements ") {
+                serde_json::json!([element("e0"), element("e1")])
+            } else if request.starts_with("GET /session/fake/element/e0/property/outerHTML ") {
+                serde_json::json!("<pv-page-list-item>=A1/3 PLC diagram</pv-page-list-item>")
+            } else if request.starts_with("GET /session/fake/element/e1/property/outerHTML ") {
+                serde_json::json!("<pv-page-list-item>=A1/2 PLC diagram</pv-page-list-item>")
+            } else {
+                serde_json::Value::Null
+            }
+        }).await;
+        let browser = browser::BrowserDriver::new(&test_config(&url)).await.unwrap();
+
+        let item = find_page_item(&browser, 0, "<pv-page-list-item>=A1/2 PLC diagram</pv-page-list-item>").await.unwrap();
+        assert_eq!(item.element_id().to_string(), "e1");
+
+        // A page no longer rendered is not replaced by the one at its index
+        let missing = find_page_item(&browser, 0, "<pv-page-list-item>=A1/1 PLC diagram</pv-page-list-item>").await;
+        assert!(!is_retryable(&missing.unwrap_err()));
+    }
+
+    #[test]
+    fn test_parse_comments_after_addresses() {
+        let text = "=+A1 Motor 1.1 I0.0 Motor running Pump 1.2 ON Q0.1 Start pump Valve 3.1 I0.2 Valve open";
+        let entries = ScraperEngine::parse_plc_data(text);
+
+        let parsed: Vec<(&str, &str, &str)> = entries
+            .iter()
+            .map(|e| (e.address.as_str(), e.symbol_name.as_str(), e.comment.as_str()))
+            .collect();
+        assert_eq!(parsed, vec![
+            ("I0.0", "Motor 1.1", "Motor running"),
+            ("Q0.1", "Pump 1.2 ON", "Start pump"),
+            ("I0.2", "Valve 3.1", "Valve open"),
+        ]);
+    }
+
+    #[test]
+    fn test_parse_hardware_modules() {
+        let text = "Motor 1.1 I0.0 No module yet\n\
+                    =A1 ET 200SP IM155-6 PN Slot 3\n\
+                    Valve 2.1 Q0.1 Open valve\n\
+                    Rack 2\n\
+                    SM 321 DI 16x24VDC Slot 4 Pump 3.1 I4.0 Pump on\n\
+                    Steckplatz 5\n\
+                    Pump 3.2 I5.0";
+        let entries = ScraperEngine::parse_plc_data(text);
+
+        let hardware: Vec<(&str, Option<&str>, Option<u32>)> = entries
+            .iter()
+            .map(|e| (e.address.as_str(), e.module.as_deref(), e.slot))
+            .collect();
+        assert_eq!(hardware, vec![
+            ("I0.0", None, None),
+            ("Q0.1", Some("ET 200SP IM155-6 PN"), Some(3)),
+            ("I4.0", Some("Rack 2: SM 321 DI 16x24VDC"), Some(4)),
+            ("I5.0", Some("Rack 2: SM 321 DI 16x24VDC"), Some(5)),
+        ]);
+        assert_eq!(entries[1].module_text(), "ET 200SP IM155-6 PN, slot 3");
+    }
+
+    #[test]
+    fn test_parse_one_entry_per_line() {
+        // Format written by extract_current_plc_diagram_page and read back for the table
+        let text = "Motor 1.1 I0.0 Motor running\nValve 2.1 Q0.1 Open valve; \n-K1 Start I0.2";
+        let entries = ScraperEngine::parse_plc_data(text);
+
+        assert_eq!(entries.len(), 3);
+        assert_eq!((entries[0].symbol_name.as_str(), entries[0].comment.as_str()), ("Motor 1.1", "Motor running"));
+        assert_eq!((entries[1].symbol_name.as_str(), entries[1].comment.as_str()), ("Valve 2.1", "Open valve"));
+        assert_eq!((entries[2].symbol_name.as_str(), entries[2].comment.as_str()), ("-K1 Start", ""));
+    }
+
+    #[test]
+    fn test_parse_confidence() {
+        let text = "Motor 1.1 I0.0 Motor running\n-K1 Start I0.1\nI0.2";
+        let entries = ScraperEngine::parse_plc_data(text);
+
+        let parsed: Vec<(&str, &str, f32)> = entries
+            .iter()
+            .map(|e| (e.address.as_str(), e.symbol_name.as_str(), e.confidence))
+            .collect();
+        assert_eq!(parsed, vec![
+            ("I0.0", "Motor 1.1", extractor::CONFIDENCE_FUNCTION_MATCH),
+            ("I0.1", "-K1 Start", extractor::CONFIDENCE_TEXT_BEFORE),
+            ("I0.2", "-K1 Start", extractor::CONFIDENCE_NO_CONTEXT), // Carried over
+        ]);
+    }
+
+    #[test]
+    fn test_page_progress_stays_in_range_and_ignores_duplicates() {
+        let mut progress = PageProgress::new(4);
+        assert_eq!(progress.fraction(), PAGE_PROGRESS_START);
+
+        progress.item_seen("<a>");
+        progress.item_seen("<a>");
+        progress.item_seen("<b>");
+        let halfway = progress.fraction();
+        assert!((halfway - (PAGE_PROGRESS_START + PAGE_PROGRESS_END) / 2.0).abs() < 1e-6);
+
+        // More items than estimated: the estimate grows instead of overshooting
+        for i in 0..10 {
+            progress.item_seen(&format!("<item {}>", i));
+        }
+        let late = progress.fraction();
+        assert!(late > halfway && late < PAGE_PROGRESS_END);
+    }
+
+    #[test]
+    fn test_page_keywords() {
+        let keywords = vec!["PLC-Diagram".to_string(), "SPS-Diagramm".to_string()];
+        let item = ViewportItem {
+            index: 0,
+            description: "=A1+S1 SPS-Diagramm".to_string(),
+            text: String::new(),
+            html: String::new(),
+        };
+
+        assert!(item.is_plc_diagram(&keywords));
+        assert!(!item.is_plc_diagram(&keywords[..1]));
+        assert_eq!(
+            keywords_xpath(&keywords),
+            ".//*[contains(text(), 'PLC-Diagram') or contains(text(), 'SPS-Diagramm')]"
+        );
+    }
+
+    #[test]
+    fn test_xpath_literal_quotes() {
+        assert_eq!(xpath_literal("PLC"), "'PLC'");
+        assert_eq!(xpath_literal("Operator's"), "\"Operator's\"");
+        assert_eq!(xpath_literal("a'b\"c"), "concat('a', \"'\", 'b\"c')");
+    }
+
+    #[test]
+    fn test_page_progress_without_estimate() {
+        let mut progress = PageProgress::new(0);
+        progress.item_processed();
+        assert!(progress.fraction() < PAGE_PROGRESS_START + 0.01);
+    }
 }
\ No newline at end of file
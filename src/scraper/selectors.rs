@@ -0,0 +1,182 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use crate::config::Profiles;
+use super::xpath_literal;
+
+/// Placeholder in `project_row` selectors that is replaced by the project number
+pub const PROJECT_PLACEHOLDER: &str = "{project}";
+
+/// CSS and XPath selectors for the eVIEW and Microsoft login pages.
+///
+/// Loaded from an optional `config/<profile>.selectors.json`, or the shared
+/// `selectors.json` in the config dir, so the selectors can be patched after
+/// an eVIEW UI update without a rebuild.
+/// Keys missing from the file keep their built-in defaults. Lists are tried
+/// in order until one selector matches.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct Selectors {
+    /// XPath: Microsoft sign-in button on the eVIEW start page
+    pub microsoft_login: Vec<String>,
+    /// CSS: email input on the Microsoft login page
+    pub email_field: Vec<String>,
+    /// CSS: "Next" button after the email
+    pub next_button: Vec<String>,
+    /// CSS: password input
+    pub password_field: Vec<String>,
+    /// CSS: "Sign in" button after the password
+    pub sign_in_button: Vec<String>,
+    /// CSS: "Yes" button of the "Stay signed in?" dialog
+    pub stay_signed_in_button: Vec<String>,
+    /// XPath: project entry in the project list, `{project}` is replaced by the project number
+    pub project_row: Vec<String>,
+    /// CSS: three-dots button that opens the page view menu
+    pub three_dots_button: String,
+    /// CSS: "List" entry in the three-dots menu
+    pub list_view_button: String,
+    /// CSS: scrollable container of the page list
+    pub scroll_container: String,
+}
+
+impl Default for Selectors {
+    fn default() -> Self {
+        Self {
+            microsoft_login: strings(&[
+                "//*[contains(text(), 'Microsoft') or contains(text(), 'microsoft') or contains(@title, 'Microsoft')]",
+            ]),
+            email_field: strings(&[
+                "input[type='email']",
+                "input[name='loginfmt']",
+                "input[id='i0116']",
+                "input[id='email']",
+                "input[placeholder*='Email']",
+                "input[placeholder*='E-Mail']",
+                "input[name='username']",
+            ]),
+            next_button: strings(&[
+                "input[type='submit']",
+                "input[id='idSIButton9']",
+                "button[type='submit']",
+                "input[value='Next']",
+                "input[value='Weiter']",
+                "button[id='idSIButton9']",
+            ]),
+            password_field: strings(&[
+                "input[type='password']",
+                "input[name='passwd']",
+                "input[id='i0118']",
+                "input[id='passwordInput']",
+                "input[placeholder*='Password']",
+                "input[placeholder*='Passwort']",
+            ]),
+            sign_in_button: strings(&[
+                "input[type='submit']",
+                "input[id='idSIButton9']",
+                "button[type='submit']",
+                "input[value='Sign in']",
+                "input[value='Anmelden']",
+                "button[id='idSIButton9']",
+            ]),
+            stay_signed_in_button: strings(&[
+                "input[id='idSIButton9']",
+                "input[value='Yes']",
+                "input[value='Ja']",
+                "button[id='idSIButton9']",
+            ]),
+            project_row: strings(&[
+                "//td[contains(text(), '{project}')]",
+                "//span[contains(text(), '{project}')]",
+                "//div[contains(text(), '{project}')]",
+                "//a[contains(text(), '{project}')]",
+                "//tr[contains(., '{project}')]",
+                "//*[text()='{project}']",
+            ]),
+            three_dots_button: "eplan-icon-button[data-t*='ev-btn-page-more']".to_string(),
+            list_view_button: "eplan-dropdown-item[data-name*='ev-page-list-view-btn']".to_string(),
+            scroll_container: "cdk-virtual-scroll-viewport".to_string(),
+        }
+    }
+}
+
+impl Selectors {
+    /// Load the selectors of `profile`, or the shared `selectors.json` if it
+    /// has none. None if neither file exists.
+    pub fn load(profile: &str) -> Result<Option<Self>> {
+        let profiles = Profiles::new()?;
+        let path = Some(profiles.selectors_path(profile))
+            .filter(|path| path.exists())
+            .unwrap_or_else(|| profiles.shared_selectors_path());
+
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(&path)?;
+        Ok(Some(serde_json::from_str(&content)?))
+    }
+
+    /// Write the built-in defaults to the selectors file of `profile` as a
+    /// starting point for edits
+    pub fn write_defaults(profile: &str) -> Result<PathBuf> {
+        let path = Self::path(profile)?;
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(&path, serde_json::to_string_pretty(&Self::default())?)?;
+        Ok(path)
+    }
+
+    pub fn path(profile: &str) -> Result<PathBuf> {
+        Ok(Profiles::new()?.selectors_path(profile))
+    }
+
+    /// Project row selectors with the project number filled in as an XPath
+    /// string. Quotes around the placeholder, as in the defaults, are replaced too.
+    pub fn project_row_for(&self, project_number: &str) -> Vec<String> {
+        let literal = xpath_literal(project_number);
+        self.project_row
+            .iter()
+            .map(|selector| {
+                selector
+                    .replace(&format!("'{}'", PROJECT_PLACEHOLDER), &literal)
+                    .replace(&format!("\"{}\"", PROJECT_PLACEHOLDER), &literal)
+                    .replace(PROJECT_PLACEHOLDER, &literal)
+            })
+            .collect()
+    }
+}
+
+fn strings(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partial_file_keeps_defaults() {
+        let selectors: Selectors = serde_json::from_str(r#"{ "scroll_container": "div.page-list" }"#).unwrap();
+
+        assert_eq!(selectors.scroll_container, "div.page-list");
+        assert_eq!(selectors.email_field, Selectors::default().email_field);
+    }
+
+    #[test]
+    fn test_project_row_placeholder() {
+        let selectors = Selectors::default();
+        let rows = selectors.project_row_for("P-1234");
+
+        assert_eq!(rows[0], "//td[contains(text(), 'P-1234')]");
+        assert!(rows.iter().all(|row| !row.contains(PROJECT_PLACEHOLDER)));
+
+        // A quote in the number can't end the XPath string
+        assert_eq!(selectors.project_row_for("P-12'34")[0], "//td[contains(text(), \"P-12'34\")]");
+        let unquoted = Selectors { project_row: vec!["//*[text()={project}]".to_string()], ..Selectors::default() };
+        assert_eq!(unquoted.project_row_for("P-1234"), ["//*[text()='P-1234']"]);
+    }
+}
\ No newline at end of file
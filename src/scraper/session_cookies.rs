@@ -0,0 +1,55 @@
+use anyhow::{Context, Result};
+use std::path::Path;
+use super::browser::Cookie;
+use crate::crypto::{EncryptedPassword, PasswordCrypto};
+
+/// Save the cookies of a successful login. They grant access to the eVIEW
+/// account like the password does, so they are encrypted with the same machine key.
+pub fn save(path: &Path, cookies: &[Cookie]) -> Result<()> {
+    let json = serde_json::to_string(cookies)?;
+    let encrypted = PasswordCrypto::encrypt_password(&json)?;
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(&encrypted)?)
+        .with_context(|| format!("Could not write {}", path.display()))
+}
+
+/// Cookies written by `save`, an error if there are none or they can't be decrypted
+pub fn load(path: &Path) -> Result<Vec<Cookie>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Could not read {}", path.display()))?;
+    let encrypted: EncryptedPassword = serde_json::from_str(&content)?;
+    let json = PasswordCrypto::decrypt_password(&encrypted)?;
+    Ok(serde_json::from_str(&json)?)
+}
+
+/// Forget the saved session, e.g. once it expired
+pub fn remove(path: &Path) {
+    let _ = std::fs::remove_file(path);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cookies_round_trip_encrypted() {
+        let path = std::env::temp_dir().join(format!("eview_session_cookies_{}.json", std::process::id()));
+
+        let mut cookie = thirtyfour::Cookie::new("ASP.NET_SessionId", "secret-session");
+        cookie.set_domain("eview.eplan.com");
+        cookie.set_expiry(1_900_000_000);
+        save(&path, &[Cookie(cookie)]).unwrap();
+
+        assert!(!std::fs::read_to_string(&path).unwrap().contains("secret-session"));
+        let loaded = load(&path).unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].0.value, "secret-session");
+        assert_eq!(loaded[0].0.expiry, Some(1_900_000_000));
+
+        remove(&path);
+        assert!(load(&path).is_err());
+    }
+}
@@ -0,0 +1,69 @@
+use anyhow::Result;
+use std::fmt;
+use super::browser::BrowserDriver;
+
+/// Reads the version eVIEW reports, from `<meta name="version">` or a
+/// `data-version` attribute on the start page
+const VERSION_SCRIPT: &str = r#"
+    const meta = document.querySelector('meta[name="version"], meta[name="application-version"]');
+    if (meta) return meta.getAttribute('content');
+    const tagged = document.querySelector('[data-version], [data-app-version]');
+    return tagged ? (tagged.getAttribute('data-version') || tagged.getAttribute('data-app-version')) : null;
+"#;
+
+/// Major and minor version of the eVIEW web app, e.g. 4.0
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EviewVersion {
+    pub major: u32,
+    pub minor: u32,
+}
+
+impl EviewVersion {
+    /// First two numbers of a version string like "4.0.1234" or "v3.5"
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut numbers = text
+            .trim()
+            .trim_start_matches(['v', 'V'])
+            .split(['.', '_'])
+            .map(|part| part.trim().parse::<u32>());
+
+        let major = numbers.next()?.ok()?;
+        let minor = numbers.next().and_then(|minor| minor.ok()).unwrap_or(0);
+        Some(Self { major, minor })
+    }
+}
+
+impl fmt::Display for EviewVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+pub struct EviewVersionDetector;
+
+impl EviewVersionDetector {
+    /// Version of the eVIEW page currently open in `browser`
+    pub async fn detect(browser: &BrowserDriver) -> Result<EviewVersion> {
+        let value = browser.execute_script_with_args(VERSION_SCRIPT, Vec::new()).await?;
+        let text = value
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("the page has no version meta tag"))?;
+
+        EviewVersion::parse(text).ok_or_else(|| anyhow::anyhow!("unrecognised version '{}'", text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(EviewVersion::parse("4.0.1234.5"), Some(EviewVersion { major: 4, minor: 0 }));
+        assert_eq!(EviewVersion::parse(" v3.5 "), Some(EviewVersion { major: 3, minor: 5 }));
+        assert_eq!(EviewVersion::parse("v4_0"), Some(EviewVersion { major: 4, minor: 0 }));
+        assert_eq!(EviewVersion::parse("5"), Some(EviewVersion { major: 5, minor: 0 }));
+        assert_eq!(EviewVersion::parse("latest"), None);
+        assert!(EviewVersion { major: 3, minor: 5 } < EviewVersion { major: 4, minor: 0 });
+    }
+}
@@ -1,10 +1,31 @@
-use crate::config::AppConfig;
-use crate::models::PlcTable;
-use crate::scraper::{ScraperEngine, ScraperConfig};
-use crate::ui::table_view::TableView;
-use crate::ui::themes;
-use crate::chromedriver_manager::ChromeDriverManager;
+use crate::config::{AppConfig, ConfigOverrides, Language, Profiles, Theme};
+use crate::export::{self, ExistingFilePolicy, ExportFilter, ExportFormat, ExportScope};
+use crate::export::clipboard::ClipboardFormat;
+use crate::export::csv::CsvDelimiter;
+use crate::export::json::JsonLayout;
+use crate::export::step7::Step7SymbolFormat;
+use crate::models::{DuplicatePolicy, IssueSeverity, PlcEntry, PlcTable, TableDiff, TableStatistics, ValidationReport, ValidationRules};
+use crate::scraper::{CancellationToken, ScraperEngine, ScraperConfig, Selectors};
+use crate::ui::table_view::{GroupBy, TableView};
+use crate::ui::session::Session;
+use crate::ui::bulk_edit::BulkEditDialog;
+use crate::ui::export_preview::ExportPreviewDialog;
+use crate::ui::diff_view;
+use crate::ui::fonts;
+use crate::ui::history::{self, ExtractionHistoryEntry};
+use crate::ui::statistics_view;
+use crate::ui::toast::{Toast, ToastQueue};
+use crate::ui::tray::{self, Tray, TrayCommand};
+use crate::ui::themes::{self, ThemePalette};
+use crate::chromedriver_manager::{self, ChromeDriverManager};
+use crate::notifications::{self, WebhookEvent};
+use crate::i18n;
+use crate::logging;
+use crate::t;
 use eframe::egui;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::{Mutex, mpsc};
 use chrono;
@@ -13,13 +34,23 @@ pub struct EviewApp {
     config: AppConfig,
     plc_table: PlcTable,
     table_view: TableView,
+    diff_table: Option<TableDiff>,
+    diff_source: Option<String>, // File the comparison was loaded from
     scraper: Arc<Mutex<Option<ScraperEngine>>>,
     is_extracting: bool,
 
     // Enhanced logging system
     log_messages: Vec<LogEntry>,
-    log_text_buffer: String, // For the text editor
+    next_log_id: u64,
+    log_selection: LogSelection, // Lines clicked in the log panel, copied with Ctrl+C
     log_filter_level: LogLevel,
+    log_browser_console_only: bool, // "Browser Console" filter: only lines from the page's console
+    log_search: String, // Case-insensitive text searched for in the log lines
+    log_search_regex: bool, // `log_search` is a regular expression
+    log_search_matcher: Result<Option<LogMatcher>, regex::Error>, // Compiled `log_search`, see `update_log_matcher`
+    log_search_compiled: (String, bool), // `log_search` and `log_search_regex` the matcher was compiled from
+    log_search_filter: bool, // Hide the lines without a match instead of only highlighting them
+    log_search_current: Option<u64>, // Match line F3 last jumped to
     log_auto_scroll: bool,
     log_panel_height: f32,
     show_timestamps: bool,
@@ -27,40 +58,292 @@ pub struct EviewApp {
     // UI state
     current_tab: AppTab,
     filter_text: String,
+    validation_severity_filter: Option<IssueSeverity>,
+    validation_search: String,
+    validation_cache: Option<(u64, ValidationRules, ValidationReport)>, // For the table revision and rules, dropped on a cell edit
+    statistics_cache: Option<(u64, TableStatistics)>, // For the table revision, dropped on a cell edit
     status_message: String,
+    toast_queue: ToastQueue, // Brief acknowledgements in the bottom-right corner
     progress: f32,
     app_status: AppStatus,
     password_buffer: String, // Temporary buffer for password input
+    user_agent_buffer: String, // User agent as typed, spaces included, see `config.user_agent`
+    filename_template_buffer: String, // Template as typed, copied into the config while it is valid
+    profile_names: Vec<String>, // Saved profiles, read again after creating one
+    new_profile_name: String,
+    confirm_profile_delete: bool, // "Delete" was clicked, waiting for Yes/No
+    session_banner: Option<String>, // Shown after restoring the last session
+    pending_merge: Option<PlcTable>, // Finished extraction waiting for the merge prompt, the results were edited
+    pending_json_import: Option<(PlcTable, PathBuf)>, // Waiting for confirmation, it belongs to another project
+    pending_combine: Option<(PlcTable, PathBuf, usize)>, // "Merge with JSON…" table, file and shared addresses
+    running_export: Option<RunningExport>,
+    show_exit_dialog: bool, // Asks before closing the window with an unexported extraction
+    show_close_extraction_dialog: bool, // Asks whether closing the window cancels the running extraction
+    extraction_exported: bool, // The latest extraction was exported at least once
+    exit_confirmed: bool,
+    bulk_edit: BulkEditDialog,
+    export_preview: ExportPreviewDialog,
+    bulk_undo: Vec<BulkUndo>,
+    applied_font_size: Option<f32>, // Font size currently set on the egui style
+    applied_theme: Theme,           // Theme currently set on the egui style
+    palette: ThemePalette,          // Colors of `applied_theme`
+    applied_ui_scale: Option<f32>,  // Zoom factor currently set on the context
+    export_scope: ExportScope,
+    extraction_history: Vec<ExtractionHistoryEntry>, // Oldest first, mirrored to the config
+    tags_color: HashMap<String, egui::Color32>, // Badge colors, mirrored to `config.tag_colors`
 
     // Communication channels
     progress_rx: Option<mpsc::UnboundedReceiver<ProgressUpdate>>,
     extraction_handle: Option<tokio::task::JoinHandle<()>>,
+    extraction_cancel: Option<CancellationToken>, // Stops the running extraction so it can close the browser
+    stopping_extraction: Option<tokio::task::JoinHandle<()>>, // Cancelled task still quitting its browser
+    close_deadline: Option<std::time::Instant>, // Closing waits for `stopping_extraction` until then
+    // Long-lived channel for background tasks outside of an extraction
+    background_tx: mpsc::UnboundedSender<ProgressUpdate>,
+    background_rx: mpsc::UnboundedReceiver<ProgressUpdate>,
 
     // ChromeDriver management
     chromedriver_manager: Arc<ChromeDriverManager>,
+
+    // System tray
+    tray: Option<Tray>,
+    hidden_to_tray: bool, // The window was minimised to the tray and is hidden
+    window_pos_checked: bool, // The saved position was checked against the monitor, see `keep_window_on_screen`
 }
 
-#[derive(Debug, Clone)]
+/// Table before a bulk edit or a JSON import, restored by the Undo button
+struct BulkUndo {
+    description: String,
+    table: PlcTable,
+}
+
+const MAX_BULK_UNDO: usize = 20;
+
+/// Closing waits this long for a cancelled extraction to quit its browser
+const CLOSE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+const MAX_LOG_ENTRIES: usize = 1000;
+const MAX_BROWSER_CONSOLE_ENTRIES: usize = 500; // Of MAX_LOG_ENTRIES, so a chatty page can't push out our own lines
+
+/// Export writing its file in the background, see `export::async_export`
+struct RunningExport {
+    handle: tokio::task::JoinHandle<()>,
+    format: ExportFormat,
+    entries: usize,
+    exit_when_done: bool, // Started by "Export and Exit"
+    complete: bool,       // Writes every row, so the results count as exported
+    revision: u64,        // Table revision the export was started from
+    edited: bool,         // A cell was edited meanwhile, which keeps the revision
+}
+
+/// One log line, saved as `{"ts", "level", "message", "phase"}` by "Save as JSON Lines"
+#[derive(Debug, Clone, Serialize)]
 pub struct LogEntry {
+    #[serde(rename = "ts")]
     pub timestamp: chrono::DateTime<chrono::Local>,
     pub message: String,
     pub level: LogLevel,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phase: Option<String>, // Extraction phase of a scraper message, e.g. "login" or "page:3"
+    #[serde(skip)]
+    pub browser_console: bool, // Forwarded from the page's JavaScript console
+    #[serde(skip)]
+    pub id: u64, // Increasing, identifies the line in the log panel selection
 }
 
-#[derive(Debug, Clone, PartialEq)]
+impl LogEntry {
+    /// Plain text line for copying and saving, e.g. "[14:07:09] ✅ Done"
+    pub fn line(&self, show_timestamp: bool) -> String {
+        if show_timestamp {
+            format!("[{}] {} {}", self.timestamp.format("%H:%M:%S"), self.level.icon(), self.message)
+        } else {
+            format!("{} {}", self.level.icon(), self.message)
+        }
+    }
+}
+
+/// Lines selected in the log panel by their `LogEntry::id`
+#[derive(Debug, Default)]
+struct LogSelection {
+    ids: BTreeSet<u64>,
+    anchor: Option<u64>, // Last plain or Ctrl click, where a Shift click range starts
+}
+
+impl LogSelection {
+    /// Click on line `id`: select only it, toggle it with Ctrl, or select the
+    /// range from the anchor with Shift. `visible` are the shown line ids in order.
+    fn click(&mut self, id: u64, visible: &[u64], ctrl: bool, shift: bool) {
+        let anchor_range = self.anchor.filter(|_| shift).and_then(|anchor| {
+            let from = visible.iter().position(|&line| line == anchor)?;
+            let to = visible.iter().position(|&line| line == id)?;
+            Some(from.min(to)..=from.max(to))
+        });
+
+        if let Some(range) = anchor_range {
+            if !ctrl {
+                self.ids.clear();
+            }
+            self.ids.extend(&visible[range]);
+            return;
+        }
+
+        if ctrl {
+            if !self.ids.remove(&id) {
+                self.ids.insert(id);
+            }
+        } else {
+            self.ids.clear();
+            self.ids.insert(id);
+        }
+        self.anchor = Some(id);
+    }
+
+    fn contains(&self, id: u64) -> bool {
+        self.ids.contains(&id)
+    }
+
+    fn clear(&mut self) {
+        self.ids.clear();
+        self.anchor = None;
+    }
+}
+
+/// Append `entry`, dropping the oldest line once the log is full and the
+/// oldest browser console line once there are too many of those
+fn push_capped(messages: &mut Vec<LogEntry>, entry: LogEntry) {
+    let browser_console = entry.browser_console;
+    messages.push(entry);
+
+    if browser_console && messages.iter().filter(|e| e.browser_console).count() > MAX_BROWSER_CONSOLE_ENTRIES {
+        if let Some(oldest) = messages.iter().position(|e| e.browser_console) {
+            messages.remove(oldest);
+        }
+    }
+    if messages.len() > MAX_LOG_ENTRIES {
+        messages.remove(0);
+    }
+}
+
+/// "Save as JSON Lines": a header record with the app version and the
+/// settings without credentials, then one record per entry
+fn log_json_lines(entries: &[LogEntry], config: &AppConfig) -> serde_json::Result<String> {
+    let header = serde_json::json!({
+        "type": "header",
+        "app_version": env!("CARGO_PKG_VERSION"),
+        "exported_at": chrono::Local::now(),
+        "config": config.log_summary(),
+    });
+
+    let mut lines = vec![serde_json::to_string(&header)?];
+    for entry in entries {
+        lines.push(serde_json::to_string(entry)?);
+    }
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Compiled log search box text
+#[derive(Debug)]
+enum LogMatcher {
+    Plain(String),
+    Regex(regex::Regex),
+}
+
+impl LogMatcher {
+    /// None for an empty search, an error for an invalid regular expression
+    fn new(search: &str, regex: bool) -> Result<Option<Self>, regex::Error> {
+        let search = search.trim();
+        if search.is_empty() {
+            return Ok(None);
+        }
+        if !regex {
+            return Ok(Some(Self::Plain(search.to_string())));
+        }
+        let regex = regex::RegexBuilder::new(search).case_insensitive(true).build()?;
+        Ok(Some(Self::Regex(regex)))
+    }
+
+    /// Byte ranges of the matches in `text`, empty matches left out
+    fn find(&self, text: &str) -> Vec<std::ops::Range<usize>> {
+        match self {
+            Self::Plain(search) => find_matches(text, search),
+            Self::Regex(regex) => regex.find_iter(text).map(|m| m.range()).filter(|range| !range.is_empty()).collect(),
+        }
+    }
+
+    fn is_match(&self, text: &str) -> bool {
+        match self {
+            Self::Plain(search) => text.to_lowercase().contains(&search.to_lowercase()),
+            Self::Regex(regex) => regex.is_match(text),
+        }
+    }
+}
+
+/// Match line after (or before, going back) `current` in `matches`, wrapping
+/// around at the ends. Starts at the first (or last) match without a current one.
+fn step_log_match(matches: &[u64], current: Option<u64>, forward: bool) -> Option<u64> {
+    let next = match (current, forward) {
+        (Some(current), true) => matches.iter().find(|&&id| id > current).or(matches.first()),
+        (Some(current), false) => matches.iter().rev().find(|&&id| id < current).or(matches.last()),
+        (None, true) => matches.first(),
+        (None, false) => matches.last(),
+    };
+    next.copied()
+}
+
+/// Byte ranges of the case-insensitive occurrences of `search` in `text`
+fn find_matches(text: &str, search: &str) -> Vec<std::ops::Range<usize>> {
+    let search = search.to_lowercase();
+    if search.is_empty() {
+        return Vec::new();
+    }
+
+    let mut matches = Vec::new();
+    let mut start = 0;
+    while start < text.len() {
+        // Lowercase char by char, so the ranges stay valid for `text` itself
+        let mut lowered = String::new();
+        let mut end = start;
+        for c in text[start..].chars() {
+            if lowered.len() >= search.len() {
+                break;
+            }
+            lowered.extend(c.to_lowercase());
+            end += c.len_utf8();
+        }
+
+        if lowered == search {
+            matches.push(start..end);
+            start = end;
+        } else {
+            start += text[start..].chars().next().map_or(1, char::len_utf8);
+        }
+    }
+    matches
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum AppTab {
     Main,
     Logs,
     Results,
+    Diff,
+    Statistics,
     Settings,
 }
 
 #[derive(Debug, Clone)]
 pub enum ProgressUpdate {
     Log(String, LogLevel),
+    PhaseLog(String, LogLevel, String), // Scraper message with its `scraper::LogPhase`
+    BrowserConsole(String, LogLevel),
     Progress(f32),
+    History(ExtractionHistoryEntry),
     Status(String),
     Complete(PlcTable),
+    DryRunComplete, // Login and navigation worked, nothing was extracted
+    AutoExportComplete(Vec<PathBuf>), // Files written after Complete
+    AutoExportNeedsFolder(PlcTable), // No output folder configured, the UI asks for one
+    ExportFinished { path: PathBuf, ok: bool },
     Error(String),
     StatusChange(AppStatus),
 }
@@ -75,7 +358,7 @@ pub enum AppStatus {
     Error(String),
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum LogLevel {
     Info,
     Warning,
@@ -86,6 +369,17 @@ pub enum LogLevel {
 
 impl LogLevel {
     pub fn color(&self) -> egui::Color32 {
+        if themes::is_high_contrast() {
+            // At least 4.5:1 against the black high-contrast background (WCAG AA)
+            return match self {
+                LogLevel::Info => egui::Color32::WHITE,
+                LogLevel::Warning => egui::Color32::from_rgb(255, 255, 0),
+                LogLevel::Error => egui::Color32::from_rgb(255, 102, 102),
+                LogLevel::Success => egui::Color32::from_rgb(0, 255, 0),
+                LogLevel::Debug => egui::Color32::from_rgb(0, 255, 255),
+            };
+        }
+
         match self {
             LogLevel::Info => egui::Color32::from_rgb(200, 200, 200),
             LogLevel::Warning => egui::Color32::from_rgb(255, 193, 7),
@@ -105,6 +399,18 @@ impl LogLevel {
         }
     }
 
+    /// Whether a message at `level` passes this level as the log panel filter.
+    /// Debug shows everything, Info everything but debug output.
+    pub fn shows(&self, level: &LogLevel) -> bool {
+        match self {
+            LogLevel::Debug => true,
+            LogLevel::Info => !matches!(level, LogLevel::Debug),
+            LogLevel::Warning => matches!(level, LogLevel::Warning | LogLevel::Error),
+            LogLevel::Error => matches!(level, LogLevel::Error),
+            LogLevel::Success => matches!(level, LogLevel::Success | LogLevel::Error | LogLevel::Warning),
+        }
+    }
+
     pub fn name(&self) -> &'static str {
         match self {
             LogLevel::Info => "Info",
@@ -116,91 +422,234 @@ impl LogLevel {
     }
 }
 
+/// Scraper messages keep their level, so debug output stays behind the Debug filter
+impl From<crate::scraper::LogLevel> for LogLevel {
+    fn from(level: crate::scraper::LogLevel) -> Self {
+        match level {
+            crate::scraper::LogLevel::Info => LogLevel::Info,
+            crate::scraper::LogLevel::Warning => LogLevel::Warning,
+            crate::scraper::LogLevel::Error => LogLevel::Error,
+            crate::scraper::LogLevel::Success => LogLevel::Success,
+            crate::scraper::LogLevel::Debug => LogLevel::Debug,
+        }
+    }
+}
+
 impl EviewApp {
-    pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
-        // Load config
-        let config = AppConfig::load().unwrap_or_default();
+    pub fn new(cc: &eframe::CreationContext<'_>, overrides: ConfigOverrides) -> Self {
+        // Load config, command line and environment values win for this run
+        let mut config = AppConfig::load().unwrap_or_default();
+        config.apply_overrides(&overrides);
 
         // Apply theme
-        themes::apply_theme(&cc.egui_ctx, &config.theme);
+        let palette = themes::apply_theme(&cc.egui_ctx, &config.theme);
+        let applied_theme = config.theme.clone();
+        i18n::set_language(&config.language);
 
         let password_buffer = config.password().to_string();
+        let user_agent_buffer = config.user_agent.clone().unwrap_or_default();
+        let filename_template_buffer = config.filename_template.clone();
+        let profile_names = Profiles::new().map(|profiles| profiles.list()).unwrap_or_default();
+        let extraction_history = config.extraction_history.clone();
+        let tags_color = config
+            .tag_colors
+            .iter()
+            .map(|(tag, [r, g, b])| (tag.clone(), egui::Color32::from_rgb(*r, *g, *b)))
+            .collect();
+        let ui_state = config.ui_state.sanitized();
+        let mut table_view = TableView::new(&config.columns, &config.table_column_widths);
+        table_view.set_sort(ui_state.sort_column, ui_state.sort_ascending);
+        let (background_tx, background_rx) = mpsc::unbounded_channel();
+
+        let tray = if config.show_tray_icon { create_tray(&cc.egui_ctx) } else { None };
+
+        // Restore the last extraction if one was saved
+        let mut session_banner = None;
+        let plc_table = match Session::load() {
+            Ok(Some(session)) => {
+                session_banner = Some(session.summary());
+                session.table
+            }
+            Ok(None) => PlcTable::new("".to_string()),
+            Err(e) => {
+                eprintln!("Warning: Failed to load last session: {}", e);
+                PlcTable::new("".to_string())
+            }
+        };
 
-        Self {
+        let mut app = Self {
             config,
-            plc_table: PlcTable::new("".to_string()),
-            table_view: TableView::new(),
+            plc_table,
+            table_view,
+            diff_table: None,
+            diff_source: None,
             scraper: Arc::new(Mutex::new(None)),
             is_extracting: false,
 
             // Enhanced logging system
             log_messages: Vec::new(),
-            log_text_buffer: String::new(),
-            log_filter_level: LogLevel::Info,
+            next_log_id: 0,
+            log_selection: LogSelection::default(),
+            log_filter_level: ui_state.log_filter_level.clone(),
+            log_browser_console_only: false,
+            log_search: String::new(),
+            log_search_regex: false,
+            log_search_matcher: Ok(None),
+            log_search_compiled: (String::new(), false),
+            log_search_filter: true,
+            log_search_current: None,
             log_auto_scroll: true,
-            log_panel_height: 200.0,
-            show_timestamps: true,
+            log_panel_height: ui_state.log_panel_height,
+            show_timestamps: ui_state.show_timestamps,
 
             // UI state
-            current_tab: AppTab::Main,
+            current_tab: ui_state.tab.clone(),
             filter_text: String::new(),
-            status_message: "Ready".to_string(),
+            validation_severity_filter: None,
+            validation_search: String::new(),
+            validation_cache: None,
+            statistics_cache: None,
+            status_message: t!("Ready"),
+            toast_queue: ToastQueue::default(),
             progress: 0.0,
             app_status: AppStatus::Ready,
             password_buffer,
+            user_agent_buffer,
+            filename_template_buffer,
+            profile_names,
+            new_profile_name: String::new(),
+            confirm_profile_delete: false,
+            session_banner,
+            pending_merge: None,
+            pending_json_import: None,
+            pending_combine: None,
+            running_export: None,
+            show_exit_dialog: false,
+            show_close_extraction_dialog: false,
+            extraction_exported: true, // Nothing extracted yet, a restored session is already on disk
+            exit_confirmed: false,
+            bulk_edit: BulkEditDialog::new(),
+            export_preview: ExportPreviewDialog::new(),
+            bulk_undo: Vec::new(),
+            applied_font_size: None,
+            applied_theme,
+            palette,
+            applied_ui_scale: None,
+            export_scope: ExportScope::All,
+            extraction_history,
+            tags_color,
 
             progress_rx: None,
             extraction_handle: None,
+            extraction_cancel: None,
+            stopping_extraction: None,
+            close_deadline: None,
+            background_tx,
+            background_rx,
             chromedriver_manager: Arc::new(ChromeDriverManager::new()),
+
+            tray,
+            hidden_to_tray: false,
+            window_pos_checked: false,
+        };
+
+        let overridden: Vec<&str> = [
+            ("email", overrides.email.is_some()),
+            ("password", overrides.password.is_some()),
+            ("project", overrides.project.is_some()),
+            ("headless", overrides.headless.is_some()),
+        ]
+        .into_iter()
+        .filter_map(|(name, set)| set.then_some(name))
+        .collect();
+        if !overridden.is_empty() {
+            app.log(
+                format!("Settings from the command line or environment (not saved): {}", overridden.join(", ")),
+                LogLevel::Info,
+            );
         }
+
+        app
     }
 
     fn log(&mut self, message: String, level: LogLevel) {
+        self.push_log(message, level, None, false);
+    }
+
+    fn push_log(&mut self, message: String, level: LogLevel, phase: Option<String>, browser_console: bool) {
         let log_entry = LogEntry {
             timestamp: chrono::Local::now(),
             message,
             level,
+            phase,
+            browser_console,
+            id: self.next_log_id,
         };
+        self.next_log_id += 1;
+
+        // The log file keeps what the capped list drops
+        match log_entry.level {
+            LogLevel::Debug => tracing::debug!("{}", log_entry.message),
+            LogLevel::Info | LogLevel::Success => tracing::info!("{}", log_entry.message),
+            LogLevel::Warning => tracing::warn!("{}", log_entry.message),
+            LogLevel::Error => tracing::error!("{}", log_entry.message),
+        }
+        push_capped(&mut self.log_messages, log_entry);
+    }
 
-        self.log_messages.push(log_entry);
-        self.update_log_buffer();
-
-        // Keep only last 1000 messages
-        if self.log_messages.len() > 1000 {
-            self.log_messages.remove(0);
-            self.update_log_buffer();
+    /// Compile the log search again, only once its text or the regex toggle changed
+    fn update_log_matcher(&mut self) {
+        if self.log_search_compiled.0 != self.log_search || self.log_search_compiled.1 != self.log_search_regex {
+            self.log_search_matcher = LogMatcher::new(&self.log_search, self.log_search_regex);
+            self.log_search_compiled = (self.log_search.clone(), self.log_search_regex);
         }
     }
 
-    fn update_log_buffer(&mut self) {
-        let filtered_messages: Vec<_> = self.log_messages
-            .iter()
-            .filter(|entry| self.should_show_log_level(&entry.level))
-            .collect();
+    /// The log search, None while it is empty or not a valid regular expression
+    fn log_matcher(&self) -> Option<&LogMatcher> {
+        self.log_search_matcher.as_ref().ok().and_then(Option::as_ref)
+    }
 
-        self.log_text_buffer = filtered_messages
-            .iter()
-            .map(|entry| {
-                let timestamp = if self.show_timestamps {
-                    format!("[{}] ", entry.timestamp.format("%H:%M:%S"))
-                } else {
-                    String::new()
-                };
-                let icon = entry.level.icon();
-                format!("{}{} {}", timestamp, icon, entry.message)
-            })
+    /// Log entries passing the level filter, and the search text unless it only highlights
+    fn visible_log_entries(&self) -> impl Iterator<Item = &LogEntry> {
+        let matcher = self.log_matcher().filter(|_| self.log_search_filter);
+        self.log_messages.iter().filter(move |entry| {
+            self.should_show_log_level(&entry.level)
+                && (!self.log_browser_console_only || entry.browser_console)
+                && matcher.is_none_or(|matcher| matcher.is_match(&entry.message))
+        })
+    }
+
+    /// Visible log as text, as shown in the panel
+    fn log_text(&self) -> String {
+        self.visible_log_entries()
+            .map(|entry| entry.line(self.show_timestamps))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// The selected lines that are shown, in order
+    fn selected_log_text(&self) -> String {
+        self.visible_log_entries()
+            .filter(|entry| self.log_selection.contains(entry.id))
+            .map(|entry| entry.line(self.show_timestamps))
             .collect::<Vec<_>>()
-            .join("\n");
+            .join("\n")
+    }
+
+    /// Ids of the shown lines with a search match, in order
+    fn log_match_ids(&self) -> Vec<u64> {
+        let Some(matcher) = self.log_matcher() else {
+            return Vec::new();
+        };
+        self.visible_log_entries()
+            .filter(|entry| matcher.is_match(&entry.message))
+            .map(|entry| entry.id)
+            .collect()
     }
 
     fn should_show_log_level(&self, level: &LogLevel) -> bool {
-        match self.log_filter_level {
-            LogLevel::Debug => true, // Show all
-            LogLevel::Info => !matches!(level, LogLevel::Debug),
-            LogLevel::Warning => matches!(level, LogLevel::Warning | LogLevel::Error),
-            LogLevel::Error => matches!(level, LogLevel::Error),
-            LogLevel::Success => matches!(level, LogLevel::Success | LogLevel::Error | LogLevel::Warning),
-        }
+        self.log_filter_level.shows(level)
     }
 
     fn render_toolbar(&mut self, ui: &mut egui::Ui) {
@@ -211,13 +660,13 @@ impl EviewApp {
             // Status badge
             let (badge_icon, badge_color, badge_text) = self.get_status_badge_info();
             ui.colored_label(badge_color, badge_icon);
-            ui.label(badge_text);
+            ui.label(t!(badge_text));
             ui.add_space(8.0);
 
             // Extract button
             let extract_btn = ui.add_enabled(
                 !self.is_extracting,
-                egui::Button::new("🔄 Extract (Ctrl+E)")
+                egui::Button::new(t!("🔄 Extract (Ctrl+E)"))
                     .min_size(egui::vec2(120.0, 30.0))
             );
 
@@ -227,7 +676,7 @@ impl EviewApp {
 
             // Stop button
             if self.is_extracting {
-                if ui.button("⏹ Stop").clicked() {
+                if ui.button(t!("⏹ Stop")).clicked() {
                     self.stop_extraction();
                 }
             }
@@ -235,34 +684,32 @@ impl EviewApp {
             ui.separator();
 
             // Export buttons
-            ui.add_enabled(
-                !self.plc_table.entries.is_empty(),
-                egui::Button::new("📊 Export Excel")
-            ).on_hover_text("Export to Excel format");
+            if ui.add_enabled(
+                self.can_export(),
+                egui::Button::new(t!("📊 Export Excel"))
+            ).on_hover_text(t!("Export to Excel format")).clicked() {
+                self.preview_export(ExportFormat::Excel(self.config.excel, self.config.columns));
+            }
 
-            ui.add_enabled(
-                !self.plc_table.entries.is_empty(),
-                egui::Button::new("📄 Export CSV")
-            ).on_hover_text("Export to CSV format");
+            if ui.add_enabled(
+                self.can_export(),
+                egui::Button::new(t!("📄 Export CSV"))
+            ).on_hover_text(t!("Export to CSV format")).clicked() {
+                self.preview_export(ExportFormat::Csv(self.config.columns, self.config.csv_delimiter));
+            }
 
-            ui.add_enabled(
-                !self.plc_table.entries.is_empty(),
-                egui::Button::new("📋 Copy Selected")
-            ).on_hover_text("Copy selected entries to clipboard");
+            if ui.add_enabled(
+                self.plc_table.entries.iter().any(|e| e.selected),
+                egui::Button::new(t!("📋 Copy Selected"))
+            ).on_hover_text(t!("Copy selected entries to clipboard")).clicked() {
+                self.copy_rows(ui.ctx(), self.config.clipboard_format);
+            }
 
             ui.separator();
 
             // Search field
             ui.label("🔍");
-            let search = ui.add(
-                egui::TextEdit::singleline(&mut self.filter_text)
-                    .desired_width(200.0)
-                    .hint_text("Filter...")
-            );
-
-            if search.changed() {
-                // Filter will be applied in table view
-            }
+            self.render_search_field(ui, 200.0, t!("Filter..."));
 
             // Clear filter
             if !self.filter_text.is_empty() {
@@ -276,21 +723,21 @@ impl EviewApp {
     }
 
     fn render_sidebar(&mut self, ui: &mut egui::Ui) {
-        ui.heading("Configuration");
+        ui.heading(t!("Configuration"));
         ui.separator();
 
         // Login credentials
         ui.group(|ui| {
-            ui.label("Microsoft Credentials");
+            ui.label(t!("Microsoft Credentials"));
             ui.spacing();
 
             ui.horizontal(|ui| {
-                ui.label("Email:");
+                ui.label(t!("Email:"));
                 ui.text_edit_singleline(&mut self.config.email);
             });
 
             ui.horizontal(|ui| {
-                ui.label("Password:");
+                ui.label(t!("Password:"));
                 let password_response = ui.add(egui::TextEdit::singleline(&mut self.password_buffer)
                     .password(true));
                 if password_response.changed() {
@@ -303,12 +750,13 @@ impl EviewApp {
 
         // Project settings
         ui.group(|ui| {
-            ui.label("Project Settings");
+            ui.label(t!("Project Settings"));
             ui.spacing();
 
             ui.horizontal(|ui| {
-                ui.label("Project Number:");
+                ui.label(t!("Project Number:"));
                 ui.text_edit_singleline(&mut self.config.project_number);
+                self.render_recent_projects(ui);
             });
         });
 
@@ -316,118 +764,58 @@ impl EviewApp {
 
         // Options
         ui.group(|ui| {
-            ui.label("Options");
-            ui.checkbox(&mut self.config.headless_mode, "Headless Mode");
-            ui.checkbox(&mut self.config.export_excel, "Auto-Export Excel");
-            ui.checkbox(&mut self.config.export_csv, "Auto-Export CSV");
+            ui.label(t!("Options"));
+            ui.checkbox(&mut self.config.headless_mode, t!("Headless Mode"));
+            ui.checkbox(&mut self.config.export_excel, t!("Auto-Export Excel"));
+            ui.checkbox(&mut self.config.export_csv, t!("Auto-Export CSV"));
         });
 
         ui.add_space(10.0);
 
         // Save config button
-        if ui.button("💾 Save Config").clicked() {
+        if ui.button(t!("💾 Save Config")).clicked() {
             match self.config.save() {
-                Ok(_) => self.log("Configuration saved".to_string(), LogLevel::Success),
+                Ok(_) => self.toast_queue.push(Toast::success(t!("Configuration saved"))),
                 Err(e) => self.log(format!("Failed to save config: {}", e), LogLevel::Error),
             }
         }
 
-        // Statistics
+        // Statistics, only counted while open
         ui.add_space(20.0);
         ui.separator();
-        ui.label("Statistics");
-        ui.label(format!("Total Entries: {}", self.plc_table.entries.len()));
-
-        let inputs = self.plc_table.entries.iter()
-            .filter(|e| matches!(e.data_type, crate::models::PlcDataType::Input))
-            .count();
-        let outputs = self.plc_table.entries.iter()
-            .filter(|e| matches!(e.data_type, crate::models::PlcDataType::Output))
-            .count();
-
-        ui.label(format!("Inputs: {}", inputs));
-        ui.label(format!("Outputs: {}", outputs));
-    }
-
-    fn apply_professional_theme(&self, ctx: &egui::Context) {
-        let visuals = match self.config.theme {
-            crate::config::Theme::Dark => {
-                let mut v = egui::Visuals::dark();
-
-                // Professional dark color scheme
-                v.widgets.inactive.bg_fill = egui::Color32::from_rgb(48, 49, 52);
-                v.widgets.hovered.bg_fill = egui::Color32::from_rgb(64, 65, 68);
-                v.widgets.active.bg_fill = egui::Color32::from_rgb(26, 115, 232);
-                v.widgets.inactive.fg_stroke = egui::Stroke::new(1.0, egui::Color32::from_rgb(200, 200, 200));
-                v.widgets.hovered.fg_stroke = egui::Stroke::new(1.0, egui::Color32::WHITE);
-
-                // Dark panel colors
-                v.panel_fill = egui::Color32::from_rgb(24, 25, 26);
-                v.window_fill = egui::Color32::from_rgb(32, 33, 36);
-                v.extreme_bg_color = egui::Color32::from_rgb(16, 17, 18);
-
-                // Dark selection colors
-                v.selection.bg_fill = egui::Color32::from_rgba_unmultiplied(26, 115, 232, 80);
-                v.selection.stroke = egui::Stroke::new(1.0, egui::Color32::from_rgb(26, 115, 232));
-
-                v
-            },
-            crate::config::Theme::Light => {
-                let mut v = egui::Visuals::light();
-
-                // Professional light color scheme
-                v.widgets.inactive.bg_fill = egui::Color32::from_rgb(248, 249, 250);
-                v.widgets.hovered.bg_fill = egui::Color32::from_rgb(241, 243, 244);
-                v.widgets.active.bg_fill = egui::Color32::from_rgb(26, 115, 232);
-                v.widgets.inactive.fg_stroke = egui::Stroke::new(1.0, egui::Color32::from_rgb(60, 64, 67));
-                v.widgets.hovered.fg_stroke = egui::Stroke::new(1.0, egui::Color32::from_rgb(32, 33, 36));
-
-                // Light panel colors
-                v.panel_fill = egui::Color32::WHITE;
-                v.window_fill = egui::Color32::from_rgb(255, 255, 255);
-                v.extreme_bg_color = egui::Color32::from_rgb(248, 249, 250);
-
-                // Light selection colors
-                v.selection.bg_fill = egui::Color32::from_rgba_unmultiplied(26, 115, 232, 40);
-                v.selection.stroke = egui::Stroke::new(1.0, egui::Color32::from_rgb(26, 115, 232));
+        egui::CollapsingHeader::new(t!("Statistics"))
+            .id_salt("sidebar_statistics")
+            .default_open(true)
+            .show(ui, |ui| {
+                let stats = cached_statistics(&mut self.statistics_cache, &self.plc_table);
+                ui.label(t!("Total Entries: {}", stats.total));
+
+                for (data_type, count) in &stats.by_type {
+                    ui.colored_label(themes::data_type_color(data_type), format!("{}: {}", data_type, count));
+                }
 
-                v
-            }
-        };
+                ui.label(t!("Pages: {}", stats.entries_per_page.len()));
+                ui.label(t!("With comment: {}", stats.with_comment));
+                ui.label(t!("Without comment: {}", stats.total - stats.with_comment));
 
-        ctx.set_visuals(visuals);
+                let duplicates = t!("Duplicate addresses: {}", stats.duplicate_addresses);
+                if stats.duplicate_addresses > 0 {
+                    ui.colored_label(themes::severity_color(IssueSeverity::Warning), duplicates);
+                } else {
+                    ui.label(duplicates);
+                }
+            });
     }
 
     fn get_status_badge_info(&self) -> (&'static str, egui::Color32, &'static str) {
+        let status = &self.palette.status;
         match &self.app_status {
-            AppStatus::Ready => ("●", egui::Color32::from_rgb(76, 175, 80), "Ready"),
-            AppStatus::Connecting => ("●", egui::Color32::from_rgb(255, 193, 7), "Connecting"),
-            AppStatus::Extracting => ("●", egui::Color32::from_rgb(33, 150, 243), "Extracting"),
-            AppStatus::Processing => ("●", egui::Color32::from_rgb(156, 39, 176), "Processing"),
-            AppStatus::Completed => ("●", egui::Color32::from_rgb(76, 175, 80), "Completed"),
-            AppStatus::Error(_) => ("●", egui::Color32::from_rgb(244, 67, 54), "Error"),
-        }
-    }
-
-    fn get_panel_colors(&self) -> (egui::Color32, egui::Color32, egui::Color32) {
-        match self.config.theme {
-            crate::config::Theme::Dark => (
-                egui::Color32::from_rgb(32, 33, 36),  // toolbar/status background
-                egui::Color32::from_rgb(40, 41, 44),  // tab bar background
-                egui::Color32::from_rgb(24, 25, 26),  // main content background
-            ),
-            crate::config::Theme::Light => (
-                egui::Color32::from_rgb(248, 249, 250), // toolbar/status background
-                egui::Color32::from_rgb(241, 243, 244), // tab bar background
-                egui::Color32::WHITE,                    // main content background
-            )
-        }
-    }
-
-    fn get_border_color(&self) -> egui::Color32 {
-        match self.config.theme {
-            crate::config::Theme::Dark => egui::Color32::from_rgb(60, 61, 64),
-            crate::config::Theme::Light => egui::Color32::from_rgb(218, 220, 224),
+            AppStatus::Ready => ("●", status.ready, "Ready"),
+            AppStatus::Connecting => ("●", status.connecting, "Connecting"),
+            AppStatus::Extracting => ("●", status.extracting, "Extracting"),
+            AppStatus::Processing => ("●", status.processing, "Processing"),
+            AppStatus::Completed => ("●", status.ready, "Completed"),
+            AppStatus::Error(_) => ("●", status.error, "Error"),
         }
     }
 
@@ -438,38 +826,30 @@ impl EviewApp {
             ui.spacing_mut().item_spacing.x = 2.0;
 
             let tabs = [
-                (AppTab::Main, "🏠 Main", "Main dashboard with extraction controls (Esc)"),
-                (AppTab::Logs, "📝 Logs (Ctrl+L)", "View detailed extraction logs"),
-                (AppTab::Results, "📊 Results (Ctrl+R)", "View and export extracted data"),
-                (AppTab::Settings, "🛠️ Settings (Ctrl+,)", "Login credentials and application preferences"),
+                (AppTab::Main, t!("🏠 Main"), t!("Main dashboard with extraction controls (Esc)")),
+                (AppTab::Logs, t!("📝 Logs (Ctrl+L)"), t!("View detailed extraction logs")),
+                (AppTab::Results, t!("📊 Results (Ctrl+R)"), t!("View and export extracted data")),
+                (AppTab::Diff, t!("🔀 Diff"), t!("Compare the current table with a previous extraction")),
+                (AppTab::Statistics, t!("📈 Statistics"), t!("Address coverage of the current table and past extraction runs")),
+                (AppTab::Settings, t!("🛠️ Settings (Ctrl+,)"), t!("Login credentials and application preferences")),
             ];
 
             for (tab, label, tooltip) in tabs {
                 let is_active = self.current_tab == tab;
 
-                // Theme-based colors for tabs
-                let (active_bg, inactive_bg, active_border, inactive_border) = match self.config.theme {
-                    crate::config::Theme::Dark => (
-                        egui::Color32::from_rgb(26, 115, 232),     // Active: Blue
-                        egui::Color32::from_rgb(48, 49, 52),       // Inactive: Dark gray
-                        egui::Color32::from_rgb(66, 135, 252),     // Active border: Light blue
-                        egui::Color32::from_rgb(60, 61, 64),       // Inactive border: Gray
-                    ),
-                    crate::config::Theme::Light => (
-                        egui::Color32::from_rgb(26, 115, 232),     // Active: Blue (same)
-                        egui::Color32::WHITE,                       // Inactive: White
-                        egui::Color32::from_rgb(66, 135, 252),     // Active border: Light blue
-                        egui::Color32::from_rgb(218, 220, 224),    // Inactive border: Light gray
-                    ),
-                };
+                let high_contrast = self.applied_theme == Theme::HighContrast;
 
-                let button_color = if is_active { active_bg } else { inactive_bg };
-                let border_color = if is_active { active_border } else { inactive_border };
+                let button_color = if is_active { self.palette.accent } else { self.palette.widget };
+                let border_color = if is_active { self.palette.accent_border } else { self.palette.border };
 
                 let button = egui::Button::new(label)
                     .fill(button_color)
                     .stroke(egui::Stroke::new(
-                        if is_active { 2.0 } else { 1.0 },
+                        match (high_contrast, is_active) {
+                            (true, true) => 3.0,
+                            (true, false) | (false, true) => 2.0,
+                            (false, false) => 1.0,
+                        },
                         border_color
                     ))
                     .min_size(egui::Vec2::new(120.0, 32.0));
@@ -487,16 +867,13 @@ impl EviewApp {
     }
 
     fn render_main_tab(&mut self, ctx: &egui::Context) {
-        let (toolbar_bg, _tab_bg, content_bg) = self.get_panel_colors();
-        let border_color = self.get_border_color();
-
         // Sidebar for main tab
-        egui::SidePanel::left("main_sidebar")
-            .default_width(320.0)
+        let sidebar = egui::SidePanel::left("main_sidebar")
+            .default_width(self.config.ui_state.sidebar_width)
             .resizable(true)
             .frame(egui::Frame {
-                fill: toolbar_bg,
-                stroke: egui::Stroke::new(1.0, border_color),
+                fill: self.palette.panel,
+                stroke: egui::Stroke::new(1.0, self.palette.border),
                 inner_margin: egui::Margin::same(12.0),
                 ..Default::default()
             })
@@ -505,31 +882,30 @@ impl EviewApp {
                     self.render_extraction_controls(ui);
                 });
             });
+        self.config.ui_state.sidebar_width = sidebar.response.rect.width();
 
         // Main content - Table view
         egui::CentralPanel::default()
             .frame(egui::Frame {
-                fill: content_bg,
+                fill: self.palette.content,
                 inner_margin: egui::Margin::same(8.0),
                 ..Default::default()
             })
             .show(ctx, |ui| {
-                self.table_view.render(ui, &mut self.plc_table, &self.filter_text);
+                self.render_table_view(ui);
             });
     }
 
 
     fn render_logs_tab(&mut self, ctx: &egui::Context) {
-        let (_toolbar_bg, _tab_bg, content_bg) = self.get_panel_colors();
-
         egui::CentralPanel::default()
             .frame(egui::Frame {
-                fill: content_bg,
+                fill: self.palette.content,
                 inner_margin: egui::Margin::same(8.0),
                 ..Default::default()
             })
             .show(ctx, |ui| {
-                ui.heading("📝 Extraction Logs");
+                ui.heading(t!("📝 Extraction Logs"));
                 ui.separator();
                 ui.add_space(8.0);
                 self.render_log_panel(ui);
@@ -537,501 +913,2825 @@ impl EviewApp {
     }
 
     fn render_results_tab(&mut self, ctx: &egui::Context) {
-        let (_toolbar_bg, _tab_bg, content_bg) = self.get_panel_colors();
-
         egui::CentralPanel::default()
             .frame(egui::Frame {
-                fill: content_bg,
+                fill: self.palette.content,
                 inner_margin: egui::Margin::same(8.0),
                 ..Default::default()
             })
             .show(ctx, |ui| {
-                ui.heading("📊 Extraction Results");
+                ui.heading(t!("📊 Extraction Results"));
                 ui.separator();
                 ui.add_space(8.0);
 
                 // Export options bar
                 ui.horizontal(|ui| {
-                    ui.label("Export Options:");
+                    ui.label(t!("Export Options:"));
 
-                    ui.add_enabled(
-                        !self.plc_table.entries.is_empty(),
-                        egui::Button::new("📊 Excel")
+                    egui::ComboBox::from_id_salt("export_scope")
+                        .selected_text(t!(self.export_scope.name()))
+                        .show_ui(ui, |ui| {
+                            for scope in [ExportScope::All, ExportScope::Filtered] {
+                                ui.selectable_value(&mut self.export_scope, scope, t!(scope.name()));
+                            }
+                        });
+                    // Same switch as "Export selected only" in the preview
+                    ui.checkbox(&mut self.export_preview.selected_only, t!("Selected only"))
+                        .on_hover_text(t!("Export only the checked rows, e.g. the outputs of one panel"));
+
+                    if ui.add_enabled(
+                        self.can_export(),
+                        egui::Button::new(t!("📊 Excel"))
                             .fill(egui::Color32::from_rgb(16, 124, 16))
-                    ).on_hover_text("Export to Excel format");
+                    ).on_hover_text(t!("Export to Excel format")).clicked() {
+                        self.preview_export(ExportFormat::Excel(self.config.excel, self.config.columns));
+                    }
 
-                    ui.add_enabled(
-                        !self.plc_table.entries.is_empty(),
-                        egui::Button::new("📄 CSV")
+                    if ui.add_enabled(
+                        self.can_export(),
+                        egui::Button::new(t!("📄 CSV"))
+                            .fill(egui::Color32::from_rgb(16, 124, 16))
+                    ).on_hover_text(t!("Export to CSV format")).clicked() {
+                        self.preview_export(ExportFormat::Csv(self.config.columns, self.config.csv_delimiter));
+                    }
+
+                    if ui.add_enabled(
+                        self.can_export(),
+                        egui::Button::new(t!("🧾 JSON"))
+                            .fill(egui::Color32::from_rgb(16, 124, 16))
+                    ).on_hover_text(t!("Export to JSON format")).clicked() {
+                        self.preview_export(ExportFormat::Json(self.config.json_layout));
+                    }
+
+                    if ui.add_enabled(
+                        self.can_export(),
+                        egui::Button::new(t!("🏭 TwinCAT"))
+                            .fill(egui::Color32::from_rgb(16, 124, 16))
+                    ).on_hover_text(t!("Export a TwinCAT 3 variable list (.tmc)")).clicked() {
+                        self.preview_export(ExportFormat::TwinCat);
+                    }
+
+                    if ui.add_enabled(
+                        self.can_export(),
+                        egui::Button::new(t!("🗂 STEP 7"))
+                            .fill(egui::Color32::from_rgb(16, 124, 16))
+                    ).on_hover_text(t!("Export a STEP 7 classic symbol table, the file type is set in Settings")).clicked() {
+                        self.preview_export(ExportFormat::Step7(self.config.step7_symbol_format));
+                    }
+
+                    if ui.add_enabled(
+                        self.can_export(),
+                        egui::Button::new(t!("📝 Markdown"))
+                            .fill(egui::Color32::from_rgb(16, 124, 16))
+                    ).on_hover_text(t!("Export a Markdown report, the columns are set in Settings")).clicked() {
+                        self.preview_export(ExportFormat::Markdown(self.config.report));
+                    }
+
+                    if ui.add_enabled(
+                        self.can_export(),
+                        egui::Button::new(t!("🌐 HTML"))
+                            .fill(egui::Color32::from_rgb(16, 124, 16))
+                    ).on_hover_text(t!("Export an HTML report, the columns are set in Settings")).clicked() {
+                        self.preview_export(ExportFormat::Html(self.config.report));
+                    }
+
+                    if ui.add_enabled(
+                        self.can_export(),
+                        egui::Button::new(t!("🔌 OPC UA"))
                             .fill(egui::Color32::from_rgb(16, 124, 16))
-                    ).on_hover_text("Export to CSV format");
+                    ).on_hover_text(t!("Export OPC UA NodeSet (NodeSet2 XML) for SCADA/MES integration")).clicked() {
+                        self.preview_export(ExportFormat::OpcUa);
+                    }
 
-                    ui.add_enabled(
+                    if ui.add_enabled(
                         !self.plc_table.entries.is_empty(),
-                        egui::Button::new("📋 Copy")
+                        egui::Button::new(t!("📋 Copy as {}", self.config.clipboard_format.name()))
                             .fill(egui::Color32::from_rgb(26, 115, 232))
-                    ).on_hover_text("Copy selected to clipboard");
+                    ).on_hover_text(t!("Copy the selected rows, or all shown rows when none are selected, with the shown columns")).clicked() {
+                        self.copy_rows(ui.ctx(), self.config.clipboard_format);
+                    }
+
+                    ui.add_enabled_ui(!self.plc_table.entries.is_empty(), |ui| {
+                        ui.menu_button("⏷", |ui| {
+                            for format in ClipboardFormat::ALL {
+                                if ui.button(t!("Copy as {}", format.name())).clicked() {
+                                    self.config.clipboard_format = format;
+                                    let _ = self.config.save();
+                                    self.copy_rows(ui.ctx(), format);
+                                    ui.close_menu();
+                                }
+                            }
+                        })
+                        .response
+                        .on_hover_text(t!("Format of copied rows: TSV pastes into Excel cells, CSV uses the delimiter of the CSV export"));
+                    });
+
+                    ui.separator();
+
+                    if ui.button(t!("📥 Import…"))
+                        .on_hover_text(t!("Load a table from a CSV or Excel file"))
+                        .clicked()
+                    {
+                        self.import_table();
+                    }
+
+                    if ui.button(t!("📂 Import JSON"))
+                        .on_hover_text(t!("Load a table exported as JSON, Undo brings back the current one"))
+                        .clicked()
+                    {
+                        self.import_json();
+                    }
+
+                    if ui.add_enabled(
+                        !self.plc_table.entries.is_empty(),
+                        egui::Button::new(t!("🔀 Compare with JSON…"))
+                    ).on_hover_text(t!("Show which addresses were added, removed or changed since an exported JSON table")).clicked() {
+                        self.compare_with_json();
+                    }
+
+                    if ui.add_enabled(
+                        !self.plc_table.entries.is_empty(),
+                        egui::Button::new(t!("🧩 Merge with JSON…"))
+                    ).on_hover_text(t!("Add the entries of another extraction, e.g. of another building")).clicked() {
+                        self.combine_with_json();
+                    }
+
+                    if ui.add_enabled(
+                        !self.plc_table.entries.is_empty(),
+                        egui::Button::new(t!("💾 Save Session"))
+                    ).on_hover_text(t!("Keep these results for the next launch")).clicked() {
+                        self.save_session();
+                    }
+
+                    if ui.button(t!("➕ Add entry"))
+                        .on_hover_text(t!("Append a blank entry to the table"))
+                        .clicked()
+                    {
+                        let end = self.plc_table.entries.len();
+                        self.plc_table.insert_entry(end, PlcEntry::new(String::new(), String::new(), String::new()));
+                    }
+
+                    ui.separator();
+
+                    if ui.add_enabled(
+                        !self.plc_table.entries.is_empty(),
+                        egui::Button::new(t!("✏️ Bulk edit"))
+                    ).on_hover_text(t!("Edit all selected or filtered entries at once, e.g. find and replace in comments")).clicked() {
+                        self.bulk_edit.open_for(self.plc_table.entries.iter().any(|e| e.selected));
+                    }
+
+                    let undo = ui.add_enabled(
+                        !self.bulk_undo.is_empty(),
+                        egui::Button::new(t!("↶ Undo"))
+                    );
+                    let undo = match self.bulk_undo.last() {
+                        Some(last) => undo.on_hover_text(t!("Undo: {}", last.description)),
+                        None => undo,
+                    };
+                    if undo.clicked() {
+                        self.undo_bulk_edit();
+                    }
                 });
 
+                ui.add_space(8.0);
+                self.render_validation_panel(ui);
                 ui.add_space(8.0);
 
                 // Search field
                 ui.horizontal(|ui| {
-                    ui.label("🔍 Filter:");
-                    ui.add(
-                        egui::TextEdit::singleline(&mut self.filter_text)
-                            .desired_width(300.0)
-                            .hint_text("Search entries...")
-                    );
+                    ui.label(t!("🔍 Filter:"));
+                    self.render_search_field(ui, 300.0, t!("Search entries..."));
                     if !self.filter_text.is_empty() {
                         if ui.button("✕").clicked() {
                             self.filter_text.clear();
                         }
                     }
+
+                    ui.separator();
+                    ui.label(t!("Group by:"));
+                    let mut group_by = self.table_view.group_by();
+                    egui::ComboBox::from_id_salt("table_group_by")
+                        .selected_text(t!(group_by.label()))
+                        .show_ui(ui, |ui| {
+                            for option in GroupBy::ALL {
+                                ui.selectable_value(&mut group_by, option, t!(option.label()));
+                            }
+                        });
+                    self.table_view.set_group_by(group_by);
                 });
 
                 ui.add_space(8.0);
-                self.table_view.render(ui, &mut self.plc_table, &self.filter_text);
+                self.render_table_view(ui);
             });
     }
 
-    fn render_settings_tab(&mut self, ctx: &egui::Context) {
-        let (_toolbar_bg, _tab_bg, content_bg) = self.get_panel_colors();
+    fn render_table_view(&mut self, ui: &mut egui::Ui) {
+        let cell_edited = self.table_view.render(ui, &mut self.plc_table, &self.filter_text, &mut self.tags_color);
+        if cell_edited {
+            self.validation_cache = None;
+            self.statistics_cache = None;
+            if let Some(export) = &mut self.running_export {
+                export.edited = true;
+            }
+        }
 
-        egui::CentralPanel::default()
-            .frame(egui::Frame {
-                fill: content_bg,
-                inner_margin: egui::Margin::same(16.0),
-                ..Default::default()
-            })
-            .show(ctx, |ui| {
-                egui::ScrollArea::vertical().show(ui, |ui| {
-                    ui.heading("🛠️ Configuration & Settings");
-                    ui.separator();
-                    ui.add_space(16.0);
+        // Colors of newly added tags
+        let tag_colors: HashMap<String, [u8; 3]> = self
+            .tags_color
+            .iter()
+            .map(|(tag, color)| (tag.clone(), [color.r(), color.g(), color.b()]))
+            .collect();
+        if tag_colors != self.config.tag_colors {
+            self.config.tag_colors = tag_colors;
+            let _ = self.config.save();
+        }
 
-                    // Microsoft Credentials
-                    ui.group(|ui| {
-                        ui.label("🔐 Microsoft Credentials");
-                        ui.separator();
+        // Remember the column order and visibility for the next launch
+        if *self.table_view.columns() != self.config.columns {
+            self.config.columns = *self.table_view.columns();
+            let _ = self.config.save();
+        }
 
-                        ui.horizontal(|ui| {
-                            ui.label("Email:");
-                            let email_response = ui.add(
-                                egui::TextEdit::singleline(&mut self.config.email)
-                                    .desired_width(250.0)
-                                    .hint_text("your.email@company.com")
-                            );
-                            if email_response.changed() {
-                                let _ = self.config.save();
-                            }
-                        });
+        // Column widths are saved once a resize drag is released
+        if *self.table_view.column_widths() != self.config.table_column_widths
+            && !ui.input(|i| i.pointer.any_down())
+        {
+            self.config.table_column_widths = self.table_view.column_widths().clone();
+            let _ = self.config.save();
+        }
+    }
 
-                        ui.horizontal(|ui| {
-                            ui.label("Password:");
-                            let password_response = ui.add(
-                                egui::TextEdit::singleline(&mut self.password_buffer)
-                                    .desired_width(250.0)
-                                    .password(true)
-                                    .hint_text("Enter password")
-                            );
-                            if password_response.changed() {
-                                self.config.set_password(self.password_buffer.clone());
-                                let _ = self.config.save();
-                            }
-                        });
-                    });
+    /// Search box shared by the toolbar and the Results tab, outlined in red
+    /// while a `re:` pattern doesn't compile
+    fn render_search_field(&mut self, ui: &mut egui::Ui, width: f32, hint: String) {
+        let response = ui.add(
+            egui::TextEdit::singleline(&mut self.filter_text)
+                .desired_width(width)
+                .hint_text(hint)
+        );
+        self.table_view.set_filter_text(&self.filter_text);
+
+        if let Some(error) = self.table_view.filter().query_error() {
+            ui.painter().rect_stroke(
+                response.rect,
+                ui.visuals().widgets.inactive.rounding,
+                egui::Stroke::new(1.5, ui.visuals().error_fg_color),
+            );
+            response.on_hover_text(t!("Invalid regular expression: {}", error));
+        } else {
+            response.on_hover_text(t!("Advanced search: re:<regex>, or column terms like addr:I10 name:pump -name:reserve (columns: addr, name, comment, page, type)"));
+        }
+    }
 
-                    ui.add_space(12.0);
+    fn render_validation_panel(&mut self, ui: &mut egui::Ui) {
+        let revision = self.plc_table.revision;
+        let rules = &self.config.validation;
+        if !matches!(&self.validation_cache, Some((cached, cached_rules, _)) if *cached == revision && cached_rules == rules) {
+            self.validation_cache = Some((revision, rules.clone(), self.plc_table.validate(rules)));
+        }
+        let Some((_, _, report)) = &self.validation_cache else {
+            return;
+        };
+        if report.is_empty() {
+            return;
+        }
+        let badge_severity = if report.errors() > 0 { IssueSeverity::Error } else { IssueSeverity::Warning };
 
-                    // Project Settings
-                    ui.group(|ui| {
-                        ui.label("📋 Project Settings");
-                        ui.separator();
+        egui::CollapsingHeader::new(
+            egui::RichText::new(t!("⚠️ Validation Issues ({})", report.len())).color(themes::severity_color(badge_severity)),
+        )
+        .id_salt("validation_issues")
+        .show(ui, |ui| {
+            ui.horizontal(|ui| {
+                ui.colored_label(themes::severity_color(IssueSeverity::Error), format!("{} {}", IssueSeverity::Error.icon(), t!("{} errors", report.errors())));
+                ui.colored_label(themes::severity_color(IssueSeverity::Warning), format!("{} {}", IssueSeverity::Warning.icon(), t!("{} warnings", report.warnings())));
+            });
 
-                        ui.horizontal(|ui| {
-                            ui.label("Project Number:");
-                            let project_response = ui.add(
-                                egui::TextEdit::singleline(&mut self.config.project_number)
-                                    .desired_width(150.0)
-                                    .hint_text("e.g., P12345")
-                            );
-                            if project_response.changed() {
-                                let _ = self.config.save();
-                            }
-                        });
-                    });
+            let search = self.validation_search.to_lowercase();
+            let visible: Vec<_> = report
+                .issues
+                .iter()
+                .filter(|issue| self.validation_severity_filter.is_none_or(|s| issue.severity == s))
+                .filter(|issue| {
+                    search.is_empty()
+                        || issue.message.to_lowercase().contains(&search)
+                        || issue.address.to_lowercase().contains(&search)
+                })
+                .collect();
 
-                    ui.add_space(16.0);
+            ui.horizontal(|ui| {
+                ui.label(t!("Show:"));
+                ui.selectable_value(&mut self.validation_severity_filter, None, t!("All"));
+                ui.selectable_value(&mut self.validation_severity_filter, Some(IssueSeverity::Error), t!("Errors"));
+                ui.selectable_value(&mut self.validation_severity_filter, Some(IssueSeverity::Warning), t!("Warnings"));
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.validation_search)
+                        .desired_width(200.0)
+                        .hint_text(t!("Search issues..."))
+                );
 
-                    // Theme settings
-                    ui.group(|ui| {
-                        ui.label("🎨 Theme Settings");
-                        ui.separator();
+                if ui.button(t!("☑ Select all flagged rows"))
+                    .on_hover_text(t!("Select the rows listed below and deselect all others"))
+                    .clicked()
+                {
+                    let flagged: std::collections::HashSet<usize> =
+                        visible.iter().map(|issue| issue.entry_index).collect();
+                    for (index, entry) in self.plc_table.entries.iter_mut().enumerate() {
+                        entry.selected = flagged.contains(&index);
+                    }
+                    self.toast_queue.push(Toast::info(t!("Selected {} flagged rows", flagged.len())));
+                }
+            });
 
+            egui::ScrollArea::vertical()
+                .id_salt("validation_list")
+                .max_height(150.0)
+                .show(ui, |ui| {
+                    for issue in &visible {
                         ui.horizontal(|ui| {
-                            ui.label("Theme:");
-                            egui::ComboBox::from_id_salt("theme_selector")
-                                .selected_text(match self.config.theme {
-                                    crate::config::Theme::Light => "Light",
-                                    crate::config::Theme::Dark => "Dark",
-                                })
-                                .show_ui(ui, |ui| {
-                                    if ui.selectable_value(&mut self.config.theme, crate::config::Theme::Light, "Light").clicked() {
-                                        let _ = self.config.save();
-                                    }
-                                    if ui.selectable_value(&mut self.config.theme, crate::config::Theme::Dark, "Dark").clicked() {
-                                        let _ = self.config.save();
-                                    }
-                                });
+                            ui.colored_label(themes::severity_color(issue.severity), issue.severity.icon())
+                                .on_hover_text(issue.severity.to_string());
+                            ui.strong(t!("Row {} ({})", issue.entry_index + 1, issue.address));
+                            ui.label(&issue.message);
                         });
-                    });
+                    }
+                });
+        });
+    }
 
-                    ui.add_space(12.0);
+    fn render_diff_tab(&mut self, ctx: &egui::Context) {
+        egui::CentralPanel::default()
+            .frame(egui::Frame {
+                fill: self.palette.content,
+                inner_margin: egui::Margin::same(8.0),
+                ..Default::default()
+            })
+            .show(ctx, |ui| {
+                ui.heading(t!("🔀 Compare Extractions"));
+                ui.separator();
+                ui.add_space(8.0);
 
-                    // Browser settings
-                    ui.group(|ui| {
-                        ui.label("🌐 Browser Settings");
-                        ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button(t!("📂 Load comparison session"))
+                        .on_hover_text(t!("Pick a previously exported JSON, CSV or Excel table to compare against the current one"))
+                        .clicked()
+                    {
+                        self.load_comparison_session();
+                    }
 
-                        if ui.checkbox(&mut self.config.headless_mode, "Headless mode (browser runs in background)").changed() {
-                            let _ = self.config.save();
-                        }
-                        if ui.checkbox(&mut self.config.debug_mode, "Debug mode (keep browser open on errors)").changed() {
-                            let _ = self.config.save();
-                        }
-                    });
+                    if let Some(source) = &self.diff_source {
+                        ui.label(t!("Comparing {} → current table", source));
+                    }
+                });
 
-                    ui.add_space(12.0);
+                ui.add_space(8.0);
 
-                    // Export settings
-                    ui.group(|ui| {
-                        ui.label("📤 Export Settings");
-                        ui.separator();
+                match &self.diff_table {
+                    Some(diff) => diff_view::render(ui, diff),
+                    None => {
+                        ui.label(t!("Load a previous extraction to see which entries were added, removed, or modified."));
+                    }
+                }
+            });
+    }
 
-                        if ui.checkbox(&mut self.config.export_excel, "Enable Excel export").changed() {
-                            let _ = self.config.save();
-                        }
-                        if ui.checkbox(&mut self.config.export_csv, "Enable CSV export").changed() {
-                            let _ = self.config.save();
-                        }
-                        if ui.checkbox(&mut self.config.export_json, "Enable JSON export").changed() {
-                            let _ = self.config.save();
+    fn render_statistics_tab(&mut self, ctx: &egui::Context) {
+        let mut load_index = None;
+        let mut copy_summary = None;
+
+        egui::CentralPanel::default()
+            .frame(egui::Frame {
+                fill: self.palette.content,
+                inner_margin: egui::Margin::same(8.0),
+                ..Default::default()
+            })
+            .show(ctx, |ui| {
+                ui.heading(t!("📈 Statistics"));
+                ui.separator();
+                ui.add_space(8.0);
+
+                egui::ScrollArea::vertical()
+                    .auto_shrink([false, false])
+                    .show(ui, |ui| {
+                        if !self.plc_table.entries.is_empty() {
+                            let stats = cached_statistics(&mut self.statistics_cache, &self.plc_table);
+
+                            ui.horizontal(|ui| {
+                                ui.strong(t!("Current Table"));
+                                ui.label(t!("{} entries", stats.total));
+                                if ui.button(t!("📋 Copy statistics"))
+                                    .on_hover_text(t!("Copy a text summary for status reports"))
+                                    .clicked()
+                                {
+                                    copy_summary = Some(stats.summary(&self.plc_table.project_name));
+                                }
+                            });
+                            ui.add_space(4.0);
+
+                            statistics_view::render(ui, stats);
+
+                            ui.add_space(8.0);
+                            ui.separator();
                         }
 
-                        ui.horizontal(|ui| {
-                            ui.label("Last export path:");
-                            if let Some(path) = &self.config.last_export_path {
-                                ui.label(path);
-                            } else {
-                                ui.label("(not set)");
-                            }
-                        });
-                    });
+                        let successful = self.extraction_history.iter().filter(|e| e.success).count();
+                        ui.label(t!(
+                            "{} runs — {} successful, {} failed",
+                            self.extraction_history.len(),
+                            successful,
+                            self.extraction_history.len() - successful
+                        ));
 
-                    ui.add_space(20.0);
+                        ui.add_space(8.0);
+                        ui.strong(t!("Extraction History"));
+                        ui.add_space(4.0);
 
-                    // Save button
-                    if ui.button("💾 Save Settings").clicked() {
-                        if let Err(_e) = self.config.save() {
-                            // Add error to log
-                        } else {
-                            // Add success to log
+                        if self.extraction_history.is_empty() {
+                            ui.label(t!("No extractions have been run yet."));
+                            return;
                         }
-                    }
-                });
+
+                        // Newest run first
+                        for (index, entry) in self.extraction_history.iter().enumerate().rev() {
+                            ui.horizontal(|ui| {
+                                let color = if entry.success { LogLevel::Success.color() } else { LogLevel::Error.color() };
+                                let (response, painter) = ui.allocate_painter(egui::vec2(10.0, 10.0), egui::Sense::hover());
+                                painter.circle_filled(response.rect.center(), 5.0, color);
+
+                                ui.label(entry.started_at.format("%Y-%m-%d %H:%M").to_string());
+
+                                let project = if entry.project.is_empty() { t!("(no project)") } else { entry.project.clone() };
+                                let mut response = ui.add_enabled(
+                                    entry.session_path.is_some(),
+                                    egui::Button::new(project).frame(false),
+                                );
+                                response = match &entry.error {
+                                    Some(error) => response.on_hover_text(error),
+                                    None => response.on_hover_text(t!("Click to load this table")),
+                                };
+                                if response.clicked() {
+                                    load_index = Some(index);
+                                }
+
+                                ui.label(t!("{} entries", entry.entry_count));
+                                ui.label(entry.duration_text());
+                            });
+                        }
+                    });
             });
+
+        if let Some(summary) = copy_summary {
+            ctx.output_mut(|o| o.copied_text = summary);
+            self.toast_queue.push(Toast::info(t!("Statistics copied to clipboard")));
+        }
+
+        if let Some(index) = load_index {
+            self.load_history_session(index);
+        }
     }
 
-    fn render_extraction_controls(&mut self, ui: &mut egui::Ui) {
-        ui.heading("🔧 Extraction Controls");
-        ui.separator();
-        ui.add_space(8.0);
+    /// Add a finished run to the history and persist it
+    fn record_history(&mut self, entry: ExtractionHistoryEntry) {
+        if entry.success {
+            history::push_recent_project(&mut self.config.recent_projects, &entry.project);
+        }
 
-        // Login credentials section
-        ui.group(|ui| {
-            ui.label("🔐 Microsoft Credentials");
-            ui.separator();
+        for dropped in history::push_history(&mut self.extraction_history, entry) {
+            if let Some(path) = dropped.session_path {
+                let _ = std::fs::remove_file(path);
+            }
+        }
 
-            ui.horizontal(|ui| {
-                ui.label("Email:");
-                let email_response = ui.add(
-                    egui::TextEdit::singleline(&mut self.config.email)
-                        .desired_width(200.0)
-                        .hint_text("your.email@company.com")
+        self.config.extraction_history = self.extraction_history.clone();
+        let _ = self.config.save();
+    }
+
+    fn load_history_session(&mut self, index: usize) {
+        let Some(path) = self.extraction_history.get(index).and_then(|e| e.session_path.clone()) else {
+            return;
+        };
+
+        if self.plc_table.dirty {
+            self.log(
+                "The results have unsaved edits - export or save them before loading another run".to_string(),
+                LogLevel::Warning,
+            );
+            return;
+        }
+
+        match Session::load_from(&path) {
+            Ok(session) => {
+                self.plc_table = session.table;
+                self.bulk_undo.clear();
+                self.session_banner = None;
+                self.current_tab = AppTab::Results;
+                self.log(
+                    format!("📂 Loaded {} ({} entries) from the extraction history", session.project_name, self.plc_table.entries.len()),
+                    LogLevel::Success,
                 );
-                if email_response.changed() {
-                    let _ = self.config.save();
+            }
+            Err(e) => self.log(format!("Failed to load {}: {}", path.display(), e), LogLevel::Error),
+        }
+    }
+
+    /// Replace the results with a table read from a CSV or Excel file
+    fn import_table(&mut self) {
+        if self.plc_table.dirty {
+            self.log(
+                "The results have unsaved edits - export or save them before importing a table".to_string(),
+                LogLevel::Warning,
+            );
+            return;
+        }
+
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter(t!("Tables"), &["csv", "xlsx", "xls", "ods"])
+            .pick_file()
+        else {
+            return;
+        };
+        let path_text = path.to_string_lossy().to_string();
+
+        match export::importer_for(&path_text).and_then(|importer| importer.import(&path_text)) {
+            Ok(result) => {
+                for row in &result.skipped {
+                    self.log(format!("Import: row {} skipped - {}", row.row, row.reason), LogLevel::Warning);
                 }
-            });
 
-            ui.horizontal(|ui| {
-                ui.label("Password:");
-                let password_response = ui.add(
-                    egui::TextEdit::singleline(&mut self.password_buffer)
-                        .desired_width(200.0)
-                        .password(true)
-                        .hint_text("Enter password")
+                let file_name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                let previous = std::mem::replace(&mut self.plc_table, result.table);
+                self.push_undo(format!("import of {}", file_name), previous);
+                self.session_banner = None;
+                self.log(
+                    format!(
+                        "📥 Imported {} entries from {} ({} rows skipped)",
+                        self.plc_table.entries.len(),
+                        path.display(),
+                        result.skipped.len()
+                    ),
+                    LogLevel::Success,
                 );
-                if password_response.changed() {
-                    self.config.set_password(self.password_buffer.clone());
-                    let _ = self.config.save();
-                }
-            });
-        });
+                self.toast_queue.push(Toast::success(t!("Imported {} entries", self.plc_table.entries.len())));
+            }
+            Err(e) => self.log(format!("Import of {} failed: {}", path.display(), e), LogLevel::Error),
+        }
+    }
 
-        ui.add_space(12.0);
+    fn load_comparison_session(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("JSON", &["json", "ndjson"])
+            .add_filter(t!("Tables"), &["csv", "xlsx", "xls", "ods"])
+            .pick_file()
+        else {
+            return;
+        };
 
-        // Project settings section
-        ui.group(|ui| {
-            ui.label("📋 Project Settings");
-            ui.separator();
+        let is_json = path
+            .extension()
+            .is_some_and(|e| e.eq_ignore_ascii_case("json") || e.eq_ignore_ascii_case("ndjson"));
+        let loaded = if is_json {
+            std::fs::read_to_string(&path)
+                .map_err(anyhow::Error::from)
+                .and_then(|content| {
+                    // Accept both exported tables and saved sessions
+                    serde_json::from_str::<Session>(&content)
+                        .map(|s| s.table)
+                        .or_else(|_| PlcTable::from_json_file(&path))
+                })
+        } else {
+            let path_text = path.to_string_lossy().to_string();
+            export::importer_for(&path_text)
+                .and_then(|importer| importer.import(&path_text))
+                .map(|result| result.table)
+        };
 
-            ui.horizontal(|ui| {
-                ui.label("Project Number:");
-                let project_response = ui.add(
-                    egui::TextEdit::singleline(&mut self.config.project_number)
-                        .desired_width(150.0)
-                        .hint_text("e.g., P12345")
-                );
-                if project_response.changed() {
-                    let _ = self.config.save();
-                }
-            });
-        });
+        match loaded {
+            Ok(previous) => self.show_comparison(&previous, &path),
+            Err(e) => {
+                self.log(format!("Failed to load comparison session: {}", e), LogLevel::Error);
+            }
+        }
+    }
 
-        ui.add_space(16.0);
+    /// Diff an exported JSON table against the current one and open the Diff tab
+    fn compare_with_json(&mut self) {
+        let Some(path) = rfd::FileDialog::new().add_filter("JSON", &["json", "ndjson"]).pick_file() else {
+            return;
+        };
 
-        // Status and progress
-        if self.is_extracting {
-            ui.group(|ui| {
-                ui.label("🚀 Extraction in Progress");
-                ui.separator();
+        match PlcTable::from_json_file(&path) {
+            Ok(previous) => {
+                self.show_comparison(&previous, &path);
+                self.current_tab = AppTab::Diff;
+            }
+            Err(e) => self.log(format!("Failed to load {} for comparison: {}", path.display(), e), LogLevel::Error),
+        }
+    }
 
-                let progress_bar = egui::ProgressBar::new(self.progress)
-                    .desired_width(280.0)
-                    .text(format!("{:.0}%", self.progress * 100.0));
-                ui.add(progress_bar);
+    /// Pick a JSON table to combine with the results, the dialog asks how to
+    /// handle addresses both have
+    fn combine_with_json(&mut self) {
+        let Some(path) = rfd::FileDialog::new().add_filter("JSON", &["json", "ndjson"]).pick_file() else {
+            return;
+        };
 
-                ui.label(&self.status_message);
+        match PlcTable::from_json_file(&path) {
+            Ok(table) => {
+                let shared = self.plc_table.shared_addresses(&table);
+                self.pending_combine = Some((table, path, shared));
+            }
+            Err(e) => self.log(format!("Failed to load {} for merging: {}", path.display(), e), LogLevel::Error),
+        }
+    }
 
-                if ui.button("⏹ Stop Extraction").clicked() {
-                    self.stop_extraction();
-                }
-            });
-        } else {
-            // Validation and extract button
-            let validation_errors = self.config.validate();
-            let can_extract = validation_errors.is_empty();
+    fn render_combine_dialog(&mut self, ctx: &egui::Context) {
+        let Some((table, path, shared)) = &self.pending_combine else {
+            return;
+        };
+        let file_name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        let (entries, shared) = (table.entries.len(), *shared);
 
-            if !validation_errors.is_empty() {
-                ui.group(|ui| {
-                    ui.label("⚠️ Configuration Issues");
-                    ui.separator();
-                    for error in &validation_errors {
-                        ui.colored_label(egui::Color32::from_rgb(244, 67, 54), format!("• {}", error));
+        let mut choice = None;
+
+        egui::Window::new(t!("Merge with JSON"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(t!("{} has {} entries, {} of their addresses are already in the table.", file_name, entries, shared));
+                ui.add_space(4.0);
+
+                ui.add_enabled_ui(shared > 0, |ui| {
+                    ui.label(t!("For addresses in both:"));
+                    for policy in DuplicatePolicy::ALL {
+                        if ui.radio_value(&mut self.config.combine_duplicates, policy, t!(policy.label())).changed() {
+                            let _ = self.config.save();
+                        }
                     }
                 });
                 ui.add_space(8.0);
-            }
-
-            // Keyboard shortcuts section
-            ui.group(|ui| {
-                ui.label("⌨️ Keyboard Shortcuts");
-                ui.separator();
 
                 ui.horizontal(|ui| {
-                    ui.label("Ctrl+E:");
-                    ui.weak("Start Extraction");
-                });
-                ui.horizontal(|ui| {
-                    ui.label("Ctrl+S:");
-                    ui.weak("Save Settings");
-                });
-                ui.horizontal(|ui| {
-                    ui.label("F5:");
-                    ui.weak("Restart Extraction");
-                });
-                ui.horizontal(|ui| {
-                    ui.label("Esc:");
-                    ui.weak("Cancel/Main Tab");
+                    if ui.button(t!("Merge")).clicked() {
+                        choice = Some(true);
+                    }
+                    if ui.button(t!("Cancel")).clicked() {
+                        choice = Some(false);
+                    }
                 });
             });
 
-            ui.add_space(12.0);
+        match choice {
+            Some(true) => {
+                if let Some((table, path, _)) = self.pending_combine.take() {
+                    let combined = self.plc_table.combine(table, self.config.combine_duplicates);
+                    let added = combined.entries.len() - self.plc_table.entries.len();
+                    let previous = std::mem::replace(&mut self.plc_table, combined);
+                    self.push_undo(format!("merge with {}", file_name), previous);
 
-            let extract_btn = ui.add_sized(
-                egui::Vec2::new(280.0, 40.0),
-                egui::Button::new("🚀 Start Extraction")
-                    .fill(if can_extract {
-                        egui::Color32::from_rgb(16, 124, 16)
-                    } else {
-                        egui::Color32::from_rgb(100, 100, 100)
-                    })
-            )
-            .on_hover_text(
-                if can_extract {
-                    "Start extracting PLC tables from eView"
+                    self.log(
+                        format!("🧩 Merged {} ({} entries added) into {}", path.display(), added, self.plc_table.project_name),
+                        LogLevel::Success,
+                    );
+                    self.toast_queue.push(Toast::success(t!("Merged, {} entries added", added)));
+                }
+            }
+            Some(false) => self.pending_combine = None,
+            None => {}
+        }
+    }
+
+    fn show_comparison(&mut self, previous: &PlcTable, path: &std::path::Path) {
+        let diff = previous.diff(&self.plc_table);
+        self.log(
+            format!(
+                "Compared with {}: {} added, {} removed, {} modified",
+                path.display(),
+                diff.added.len(),
+                diff.removed.len(),
+                diff.modified.len()
+            ),
+            LogLevel::Info,
+        );
+        self.diff_source = path.file_name().map(|n| n.to_string_lossy().to_string());
+        self.diff_table = Some(diff);
+
+        // The statuses are part of the table, undo brings back the earlier ones
+        let before = self.plc_table.clone();
+        self.plc_table.mark_status(previous);
+        self.plc_table.mark_changed();
+        let file_name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        self.push_undo(format!("comparison with {}", file_name), before);
+    }
+
+    fn render_settings_tab(&mut self, ctx: &egui::Context) {
+        egui::CentralPanel::default()
+            .frame(egui::Frame {
+                fill: self.palette.content,
+                inner_margin: egui::Margin::same(16.0),
+                ..Default::default()
+            })
+            .show(ctx, |ui| {
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    ui.heading(t!("🛠️ Configuration & Settings"));
+                    ui.separator();
+                    ui.add_space(16.0);
+
+                    // Profiles
+                    ui.group(|ui| {
+                        ui.label(t!("👤 Profile"));
+                        ui.separator();
+
+                        ui.add_enabled_ui(!self.is_extracting, |ui| {
+                            self.render_profile_selector(ctx, ui);
+
+                            ui.horizontal(|ui| {
+                                ui.add(
+                                    egui::TextEdit::singleline(&mut self.new_profile_name)
+                                        .desired_width(180.0)
+                                        .hint_text(t!("New profile name"))
+                                );
+                                let has_name = !self.new_profile_name.trim().is_empty();
+                                if ui.add_enabled(has_name, egui::Button::new(t!("➕ New")))
+                                    .on_hover_text(t!("Create a profile with the default settings"))
+                                    .clicked()
+                                {
+                                    self.create_profile(ctx, false);
+                                }
+                                if ui.add_enabled(has_name, egui::Button::new(t!("📄 Duplicate")))
+                                    .on_hover_text(t!("Create a profile starting from the current settings"))
+                                    .clicked()
+                                {
+                                    self.create_profile(ctx, true);
+                                }
+                                if ui.add_enabled(has_name, egui::Button::new(t!("✏ Rename")))
+                                    .on_hover_text(t!("Give the current profile the name entered here"))
+                                    .clicked()
+                                {
+                                    self.rename_profile();
+                                }
+                            });
+
+                            ui.horizontal(|ui| {
+                                if ui.button(t!("📤 Export…"))
+                                    .on_hover_text(t!("Save the current profile as JSON to share it, without your email, password, proxy, webhook and folders"))
+                                    .clicked()
+                                {
+                                    self.export_profile();
+                                }
+                                if ui.button(t!("📥 Import…"))
+                                    .on_hover_text(t!("Add a profile from a shared JSON file, named after the file"))
+                                    .clicked()
+                                {
+                                    self.import_profile(ctx);
+                                }
+
+                                if self.confirm_profile_delete {
+                                    ui.label(t!("Delete '{}'?", self.config.profile));
+                                    if ui.button(t!("Yes")).clicked() {
+                                        self.confirm_profile_delete = false;
+                                        self.delete_profile(ctx);
+                                    }
+                                    if ui.button(t!("Cancel")).clicked() {
+                                        self.confirm_profile_delete = false;
+                                    }
+                                } else if ui.add_enabled(self.profile_names.len() > 1, egui::Button::new(t!("🗑 Delete")))
+                                    .on_hover_text(t!("Remove the current profile and switch to another one"))
+                                    .clicked()
+                                {
+                                    self.confirm_profile_delete = true;
+                                }
+                            });
+                        });
+                        ui.weak(t!("Each profile keeps its own credentials, eVIEW URL, organization and selectors."));
+                    });
+
+                    ui.add_space(12.0);
+
+                    // Microsoft Credentials
+                    ui.group(|ui| {
+                        ui.label(t!("🔐 Microsoft Credentials"));
+                        ui.separator();
+
+                        ui.horizontal(|ui| {
+                            ui.label(t!("Email:"));
+                            let email_response = ui.add(
+                                egui::TextEdit::singleline(&mut self.config.email)
+                                    .desired_width(250.0)
+                                    .hint_text("your.email@company.com")
+                            );
+                            if email_response.changed() {
+                                let _ = self.config.save();
+                            }
+                        });
+
+                        ui.horizontal(|ui| {
+                            ui.label(t!("Password:"));
+                            let password_response = ui.add(
+                                egui::TextEdit::singleline(&mut self.password_buffer)
+                                    .desired_width(250.0)
+                                    .password(true)
+                                    .hint_text(t!("Enter password"))
+                            );
+                            if password_response.changed() {
+                                self.config.set_password(self.password_buffer.clone());
+                                let _ = self.config.save();
+                            }
+                        });
+
+                        ui.horizontal(|ui| {
+                            ui.label(t!("eVIEW URL:"));
+                            let url_response = ui.add(
+                                egui::TextEdit::singleline(&mut self.config.base_url)
+                                    .desired_width(250.0)
+                                    .hint_text("https://eview.eplan.com/")
+                            );
+                            if url_response.changed() {
+                                let _ = self.config.save();
+                            }
+                            if ui.button(t!("Test connection"))
+                                .on_hover_text(t!("Send a HEAD request to the eVIEW URL and log the HTTP status"))
+                                .clicked()
+                            {
+                                self.test_connection();
+                            }
+                        });
+
+                        ui.horizontal(|ui| {
+                            ui.label(t!("Organization:"));
+                            let organization_response = ui.add(
+                                egui::TextEdit::singleline(&mut self.config.organization)
+                                    .desired_width(250.0)
+                                    .hint_text(t!("Name shown in the organization dialog"))
+                            );
+                            if organization_response.changed() {
+                                let _ = self.config.save();
+                            }
+                        });
+                    });
+
+                    ui.add_space(12.0);
+
+                    // Project Settings
+                    ui.group(|ui| {
+                        ui.label(t!("📋 Project Settings"));
+                        ui.separator();
+
+                        ui.horizontal(|ui| {
+                            ui.label(t!("Project Number:"));
+                            let project_response = ui.add(
+                                egui::TextEdit::singleline(&mut self.config.project_number)
+                                    .desired_width(150.0)
+                                    .hint_text(t!("e.g., P12345"))
+                            );
+                            if project_response.changed() {
+                                let _ = self.config.save();
+                            }
+                            self.render_recent_projects(ui);
+                        });
+                    });
+
+                    ui.add_space(16.0);
+
+                    // Theme settings
+                    ui.group(|ui| {
+                        ui.label(t!("🎨 Theme Settings"));
+                        ui.separator();
+
+                        ui.horizontal(|ui| {
+                            ui.label(t!("Theme:"));
+                            egui::ComboBox::from_id_salt("theme_selector")
+                                .selected_text(match self.config.theme {
+                                    Theme::Light => t!("Light"),
+                                    Theme::Dark => t!("Dark"),
+                                    Theme::HighContrast => t!("High contrast"),
+                                })
+                                .show_ui(ui, |ui| {
+                                    if ui.selectable_value(&mut self.config.theme, Theme::Light, t!("Light")).clicked() {
+                                        let _ = self.config.save();
+                                    }
+                                    if ui.selectable_value(&mut self.config.theme, Theme::Dark, t!("Dark")).clicked() {
+                                        let _ = self.config.save();
+                                    }
+                                    if ui.selectable_value(&mut self.config.theme, Theme::HighContrast, t!("High contrast")).clicked() {
+                                        let _ = self.config.save();
+                                    }
+                                });
+                        });
+
+                        ui.horizontal(|ui| {
+                            ui.label(t!("Language:"));
+                            egui::ComboBox::from_id_salt("language_selector")
+                                .selected_text(self.config.language.name())
+                                .show_ui(ui, |ui| {
+                                    for language in [Language::English, Language::German] {
+                                        let name = language.name();
+                                        if ui.selectable_value(&mut self.config.language, language, name).clicked() {
+                                            i18n::set_language(&self.config.language);
+                                            let _ = self.config.save();
+                                            ui.ctx().request_repaint();
+                                        }
+                                    }
+                                });
+                        });
+
+                        ui.horizontal(|ui| {
+                            ui.label(t!("UI scale:"));
+                            let slider = ui.add(
+                                egui::Slider::new(&mut self.config.ui_scale, fonts::MIN_UI_SCALE..=fonts::MAX_UI_SCALE)
+                                    .step_by(0.05)
+                                    .custom_formatter(|value, _| format!("{:.0} %", value * 100.0))
+                            )
+                            .on_hover_text(t!("Zoom the whole window, on top of the Windows display scaling"));
+                            if slider.changed() {
+                                let _ = self.config.save();
+                            }
+                        });
+
+                        ui.horizontal(|ui| {
+                            ui.label(t!("Font size:"));
+                            let slider = ui.add(
+                                egui::Slider::new(&mut self.config.ui_font_size, fonts::MIN_FONT_SIZE..=fonts::MAX_FONT_SIZE)
+                                    .step_by(1.0)
+                                    .suffix(" pt")
+                            );
+                            if slider.changed() {
+                                let _ = self.config.save();
+                            }
+                        });
+
+                        if ui.add_enabled(tray::SUPPORTED, egui::Checkbox::new(&mut self.config.show_tray_icon, t!("Show tray icon")))
+                            .on_hover_text(t!("Show the extraction status and progress in the system tray"))
+                            .on_disabled_hover_text(t!("The system tray is only available on Windows and macOS"))
+                            .changed()
+                        {
+                            self.tray = if self.config.show_tray_icon { create_tray(ctx) } else { None };
+                            let _ = self.config.save();
+                        }
+                        ui.indent("minimize_to_tray", |ui| {
+                            ui.add_enabled_ui(tray::SUPPORTED && self.config.show_tray_icon, |ui| {
+                                if ui.checkbox(&mut self.config.minimize_to_tray, t!("Minimize to tray"))
+                                    .on_hover_text(t!("Minimising hides the taskbar entry, the tray icon shows the window again"))
+                                    .changed()
+                                {
+                                    let _ = self.config.save();
+                                }
+                            });
+                        });
+                    });
+
+                    ui.add_space(12.0);
+
+                    // Browser settings
+                    ui.group(|ui| {
+                        ui.label(t!("🌐 Browser Settings"));
+                        ui.separator();
+
+                        if ui.checkbox(&mut self.config.headless_mode, t!("Headless mode (browser runs in background)")).changed() {
+                            let _ = self.config.save();
+                        }
+                        if ui.checkbox(&mut self.config.debug_mode, t!("Debug mode (keep browser open on errors)")).changed() {
+                            let _ = self.config.save();
+                        }
+                        let log_folder = logging::log_dir()
+                            .map(|dir| dir.display().to_string())
+                            .unwrap_or_default();
+                        ui.horizontal(|ui| {
+                            ui.label(t!("Keep log files:"));
+                            if ui.add(egui::DragValue::new(&mut self.config.log_files_kept).range(1..=365).suffix(t!(" days")))
+                                .on_hover_text(t!("One file per day in {}, kept when the app crashes. Applies at the next start.", log_folder))
+                                .changed()
+                            {
+                                let _ = self.config.save();
+                            }
+                        });
+                        if ui.checkbox(&mut self.config.reuse_session, t!("Reuse login session"))
+                            .on_hover_text(t!("Keep the session cookies after a successful login, encrypted, and skip the Microsoft login while they are valid"))
+                            .changed()
+                        {
+                            if !self.config.reuse_session {
+                                // Nothing should keep granting access once the option is off
+                                if let Ok(dir) = Session::cookies_dir() {
+                                    let _ = std::fs::remove_dir_all(dir);
+                                }
+                            }
+                            let _ = self.config.save();
+                        }
+
+                        ui.horizontal(|ui| {
+                            ui.label(t!("Proxy:"));
+                            let mut proxy = self.config.proxy.clone().unwrap_or_default();
+                            let proxy_response = ui.add(
+                                egui::TextEdit::singleline(&mut proxy)
+                                    .desired_width(250.0)
+                                    .hint_text(t!("host:port or user:pass@host:port"))
+                            );
+                            if proxy_response.changed() {
+                                let trimmed = proxy.trim();
+                                self.config.proxy = if trimmed.is_empty() {
+                                    None
+                                } else {
+                                    Some(trimmed.to_string())
+                                };
+                                let _ = self.config.save();
+                            }
+                        });
+                        ui.weak(t!("Used by Chrome and for the ChromeDriver download. Leave empty for a direct connection."));
+
+                        ui.collapsing(t!("Advanced"), |ui| {
+                            ui.horizontal(|ui| {
+                                ui.label(t!("User agent:"));
+                                let user_agent_response = ui.add(
+                                    egui::TextEdit::singleline(&mut self.user_agent_buffer)
+                                        .desired_width(350.0)
+                                        .hint_text(t!("Chrome default"))
+                                );
+                                if user_agent_response.changed() {
+                                    // Trimmed for the run, see `run_extraction_async`
+                                    self.config.user_agent = Some(self.user_agent_buffer.clone()).filter(|ua| !ua.trim().is_empty());
+                                    let _ = self.config.save();
+                                }
+                                if ui.small_button(t!("Installed Chrome"))
+                                    .on_hover_text(t!("Fill in the user agent of the installed Chrome version without \"Headless\""))
+                                    .clicked()
+                                {
+                                    match ChromeDriverManager::detect_chrome_version() {
+                                        Some(version) => {
+                                            self.user_agent_buffer = chromedriver_manager::default_user_agent(&version);
+                                            self.config.user_agent = Some(self.user_agent_buffer.clone());
+                                            let _ = self.config.save();
+                                        }
+                                        None => self.toast_queue.push(Toast::new(t!("Chrome was not found, enter the user agent by hand"), LogLevel::Warning)),
+                                    }
+                                }
+                            });
+                            ui.weak(t!("Optional. Set this if a proxy or firewall blocks the headless Chrome user agent."));
+
+                            ui.add_space(4.0);
+                            ui.horizontal(|ui| {
+                                ui.label(t!("Window size:"));
+                                let width = ui.add(egui::DragValue::new(&mut self.config.browser_width).range(800..=7680).suffix(" px"));
+                                ui.label("×");
+                                let height = ui.add(egui::DragValue::new(&mut self.config.browser_height).range(600..=4320).suffix(" px"));
+                                if width.changed() || height.changed() {
+                                    let _ = self.config.save();
+                                }
+                            });
+                            ui.weak(t!("Larger windows render more pages per scroll step and speed up extraction."));
+
+                            ui.add_space(4.0);
+                            ui.horizontal(|ui| {
+                                ui.label(t!("Login attempts:"));
+                                let attempts = ui.add(egui::DragValue::new(&mut self.config.login_attempts).range(1..=120));
+                                ui.label(t!("every"));
+                                let interval = ui.add(
+                                    egui::DragValue::new(&mut self.config.login_attempt_interval_ms)
+                                        .range(100..=10_000)
+                                        .speed(50)
+                                        .suffix(" ms")
+                                );
+                                if attempts.changed() || interval.changed() {
+                                    let _ = self.config.save();
+                                }
+                            });
+                            ui.weak(t!("How often each login step is tried before giving up. Raise it on slow VPN connections."));
+
+                            ui.add_space(4.0);
+                            if ui.checkbox(&mut self.config.native_login, t!("Always use the eVIEW login form"))
+                                .on_hover_text(t!("For eVIEW instances without Microsoft sign-in. Without this, the form is only used when no Microsoft button shows up."))
+                                .changed()
+                            {
+                                let _ = self.config.save();
+                            }
+
+                            ui.add_space(4.0);
+                            ui.horizontal(|ui| {
+                                ui.label(t!("External ChromeDriver:"));
+                                let mut driver_url = self.config.external_chromedriver_url.clone().unwrap_or_default();
+                                let driver_response = ui.add(
+                                    egui::TextEdit::singleline(&mut driver_url)
+                                        .desired_width(250.0)
+                                        .hint_text("http://localhost:9515")
+                                );
+                                if driver_response.changed() {
+                                    let trimmed = driver_url.trim();
+                                    self.config.external_chromedriver_url = if trimmed.is_empty() {
+                                        None
+                                    } else {
+                                        Some(trimmed.to_string())
+                                    };
+                                    let _ = self.config.save();
+                                }
+                            });
+                            ui.weak(t!("For debugging selectors: connect to a ChromeDriver you started yourself. It is neither started nor stopped by the app. Leave empty to use the built-in one."));
+
+                            ui.add_space(4.0);
+                            if ui.button(t!("📝 Create selectors.json"))
+                                .on_hover_text(t!("Write the built-in page selectors to a file you can edit after an eVIEW update"))
+                                .clicked()
+                            {
+                                self.create_selectors_file();
+                            }
+                        });
+
+                        ui.collapsing(t!("Advanced Timeouts"), |ui| {
+                            let timeouts = &mut self.config.wait_timeouts;
+                            let mut changed = false;
+                            egui::Grid::new("wait_timeouts_grid").num_columns(2).show(ui, |ui| {
+                                for (label, secs, max) in [
+                                    (t!("Login:"), &mut timeouts.login_secs, 300),
+                                    (t!("Page load:"), &mut timeouts.page_load_secs, 300),
+                                    (t!("Element:"), &mut timeouts.element_secs, 120),
+                                    (t!("Scroll step:"), &mut timeouts.scroll_secs, 60),
+                                    (t!("ChromeDriver start:"), &mut timeouts.driver_secs, 120),
+                                ] {
+                                    ui.label(label);
+                                    changed |= ui.add(egui::DragValue::new(secs).range(1..=max).suffix(" s")).changed();
+                                    ui.end_row();
+                                }
+                            });
+                            if changed {
+                                let _ = self.config.save();
+                            }
+                            ui.weak(t!("Longest waits for eVIEW before a step gives up. Each step continues as soon as eVIEW is ready."));
+                        });
+                    });
+
+                    ui.add_space(12.0);
+
+                    // Page detection settings
+                    ui.group(|ui| {
+                        ui.label(t!("🔎 Page Detection"));
+                        ui.separator();
+
+                        ui.label(t!("Extract pages whose list entry contains any of these keywords:"));
+
+                        let mut changed = false;
+                        let mut remove = None;
+                        for (index, keyword) in self.config.page_category_keywords.iter_mut().enumerate() {
+                            ui.horizontal(|ui| {
+                                changed |= ui.add(egui::TextEdit::singleline(keyword).desired_width(250.0)).changed();
+                                if ui.small_button("🗑").on_hover_text(t!("Remove keyword")).clicked() {
+                                    remove = Some(index);
+                                }
+                            });
+                        }
+                        if let Some(index) = remove {
+                            self.config.page_category_keywords.remove(index);
+                            changed = true;
+                        }
+
+                        ui.horizontal(|ui| {
+                            if ui.button(t!("➕ Add keyword")).clicked() {
+                                self.config.page_category_keywords.push(String::new());
+                                changed = true;
+                            }
+                            if ui.button(t!("Reset to default")).clicked() {
+                                self.config.page_category_keywords = crate::config::default_page_category_keywords();
+                                changed = true;
+                            }
+                        });
+                        ui.weak(t!("Case-sensitive. Add the label your project uses, e.g. \"SPS-Diagramm\" or \"PLC Schematic\"."));
+
+                        if changed {
+                            let _ = self.config.save();
+                        }
+                    });
+
+                    ui.add_space(12.0);
+
+                    // Export settings
+                    ui.group(|ui| {
+                        ui.label(t!("📤 Export Settings"));
+                        ui.separator();
+
+                        if ui.checkbox(&mut self.config.export_excel, t!("Enable Excel export")).changed() {
+                            let _ = self.config.save();
+                        }
+                        ui.indent("excel_options", |ui| {
+                            let excel = &mut self.config.excel;
+                            let mut changed = false;
+
+                            ui.horizontal(|ui| {
+                                ui.label(t!("Extra sheets:"));
+                                changed |= ui.checkbox(&mut excel.sheet_per_page, t!("One per page")).changed();
+                                changed |= ui.checkbox(&mut excel.sheet_per_device, t!("One per device tag"))
+                                    .on_hover_text(t!("Device tag at the start of the symbol name, e.g. -K1 or =A1+B2-K1"))
+                                    .changed();
+                            });
+
+                            if changed {
+                                let _ = self.config.save();
+                            }
+                        });
+                        ui.horizontal(|ui| {
+                            if ui.checkbox(&mut self.config.export_csv, t!("Enable CSV export")).changed() {
+                                let _ = self.config.save();
+                            }
+                            egui::ComboBox::from_id_salt("csv_delimiter")
+                                .selected_text(t!(self.config.csv_delimiter.name()))
+                                .show_ui(ui, |ui| {
+                                    for delimiter in CsvDelimiter::ALL {
+                                        if ui.selectable_value(&mut self.config.csv_delimiter, delimiter, t!(delimiter.name())).changed() {
+                                            let _ = self.config.save();
+                                        }
+                                    }
+                                })
+                                .response
+                                .on_hover_text(t!("Also used for rows copied as CSV"));
+                        });
+                        ui.horizontal(|ui| {
+                            if ui.checkbox(&mut self.config.export_json, t!("Enable JSON export")).changed() {
+                                let _ = self.config.save();
+                            }
+                            egui::ComboBox::from_id_salt("json_layout")
+                                .selected_text(t!(self.config.json_layout.name()))
+                                .show_ui(ui, |ui| {
+                                    for layout in JsonLayout::ALL {
+                                        if ui.selectable_value(&mut self.config.json_layout, layout, t!(layout.name())).changed() {
+                                            let _ = self.config.save();
+                                        }
+                                    }
+                                });
+                        });
+                        if ui.checkbox(&mut self.config.export_twincat, t!("Enable TwinCAT export")).changed() {
+                            let _ = self.config.save();
+                        }
+                        ui.horizontal(|ui| {
+                            if ui.checkbox(&mut self.config.export_step7, t!("Enable STEP 7 symbol table export")).changed() {
+                                let _ = self.config.save();
+                            }
+                            ui.add_enabled_ui(self.config.export_step7, |ui| {
+                                egui::ComboBox::from_id_salt("step7_symbol_format")
+                                    .selected_text(t!(self.config.step7_symbol_format.name()))
+                                    .show_ui(ui, |ui| {
+                                        for format in [Step7SymbolFormat::Asc, Step7SymbolFormat::Sdf] {
+                                            if ui.selectable_value(&mut self.config.step7_symbol_format, format, t!(format.name())).changed() {
+                                                let _ = self.config.save();
+                                            }
+                                        }
+                                    });
+                            });
+                        });
+                        if ui.checkbox(&mut self.config.export_opcua, t!("Enable OPC UA NodeSet export")).changed() {
+                            let _ = self.config.save();
+                        }
+                        if ui.checkbox(&mut self.config.export_markdown, t!("Enable Markdown report export")).changed() {
+                            let _ = self.config.save();
+                        }
+                        if ui.checkbox(&mut self.config.export_html, t!("Enable HTML report export")).changed() {
+                            let _ = self.config.save();
+                        }
+                        ui.indent("report_options", |ui| {
+                            let report = &mut self.config.report;
+                            let mut changed = false;
+
+                            ui.horizontal(|ui| {
+                                ui.label(t!("Report columns:"));
+                                changed |= ui.checkbox(&mut report.address, t!("Address")).changed();
+                                changed |= ui.checkbox(&mut report.symbol_name, t!("Symbol Name")).changed();
+                                changed |= ui.checkbox(&mut report.data_type, t!("Type")).changed();
+                                changed |= ui.checkbox(&mut report.comment, t!("Comment")).changed();
+                                changed |= ui.checkbox(&mut report.page, t!("Page")).changed();
+                            });
+                            changed |= ui.checkbox(&mut report.split_by_page, t!("Split into sections per page")).changed();
+
+                            if changed {
+                                let _ = self.config.save();
+                            }
+                        });
+                        if ui.checkbox(&mut self.config.auto_save_session, t!("Save session automatically on exit")).changed() {
+                            let _ = self.config.save();
+                        }
+
+                        ui.add_space(4.0);
+                        if ui.checkbox(&mut self.config.normalize_names_on_export, t!("Clean up symbol names before export")).changed() {
+                            let _ = self.config.save();
+                        }
+                        ui.add_enabled_ui(self.config.normalize_names_on_export, |ui| {
+                            ui.indent("name_normalization", |ui| {
+                                let options = &mut self.config.name_normalization;
+                                let mut changed = false;
+
+                                changed |= ui.checkbox(&mut options.collapse_repeated_tokens, t!("Remove repeated words (\"Ventil Ventil\" → \"Ventil\")")).changed();
+                                changed |= ui.checkbox(&mut options.transliterate_umlauts, t!("Replace umlauts (ä → ae, ß → ss)")).changed();
+
+                                ui.horizontal(|ui| {
+                                    let mut limit_length = options.max_length.is_some();
+                                    if ui.checkbox(&mut limit_length, t!("Limit name length to")).changed() {
+                                        options.max_length = if limit_length { Some(24) } else { None };
+                                        changed = true;
+                                    }
+                                    if let Some(max_length) = &mut options.max_length {
+                                        changed |= ui.add(egui::DragValue::new(max_length).range(8..=128)).changed();
+                                    }
+                                });
+                                ui.weak(t!("Shortened names keep the full text in the comment"));
+
+                                if changed {
+                                    let _ = self.config.save();
+                                }
+                            });
+                        });
+
+                        ui.add_space(4.0);
+                        if ui.checkbox(&mut self.config.auto_export, t!("Auto-export after extraction"))
+                            .on_hover_text(t!("Write the enabled formats to the output folder after each successful extraction"))
+                            .changed()
+                        {
+                            let _ = self.config.save();
+                        }
+                        ui.add_enabled_ui(self.config.auto_export, |ui| {
+                            ui.horizontal(|ui| {
+                                ui.label(t!("Output folder:"));
+                                match &self.config.auto_export_dir {
+                                    Some(dir) => ui.label(dir.display().to_string()),
+                                    None => ui.label(t!("(not set)")),
+                                };
+                                if ui.button(t!("📁 Browse…")).clicked() {
+                                    if let Some(dir) = rfd::FileDialog::new().pick_folder() {
+                                        self.config.auto_export_dir = Some(dir);
+                                        let _ = self.config.save();
+                                    }
+                                }
+                            });
+                            if self.config.auto_export_dir.is_none() {
+                                ui.colored_label(
+                                    LogLevel::Warning.color(),
+                                    t!("Without a folder the last export folder is used, or you are asked once"),
+                                );
+                            }
+                            ui.horizontal(|ui| {
+                                ui.label(t!("If the file exists:"));
+                                egui::ComboBox::from_id_salt("auto_export_existing")
+                                    .selected_text(t!(self.config.auto_export_existing.name()))
+                                    .show_ui(ui, |ui| {
+                                        for policy in [ExistingFilePolicy::UniqueSuffix, ExistingFilePolicy::Overwrite] {
+                                            if ui.selectable_value(&mut self.config.auto_export_existing, policy, t!(policy.name())).changed() {
+                                                let _ = self.config.save();
+                                            }
+                                        }
+                                    });
+                            });
+                        });
+
+                        ui.add_space(4.0);
+                        ui.horizontal(|ui| {
+                            ui.label(t!("File name template:"));
+                            let response = ui.add(
+                                egui::TextEdit::singleline(&mut self.filename_template_buffer)
+                                    .desired_width(260.0)
+                                    .hint_text(export::DEFAULT_FILENAME_TEMPLATE),
+                            );
+                            // Only a valid template is used and saved, the error stays visible until it's fixed
+                            if response.changed() && export::validate_filename_template(&self.filename_template_buffer).is_ok() {
+                                self.config.filename_template = self.filename_template_buffer.clone();
+                                let _ = self.config.save();
+                            }
+                        });
+                        ui.weak(t!("Placeholders: {project}, {date}, {time}, {format}, {entries}. The extension is added."));
+                        if let Err(e) = export::validate_filename_template(&self.filename_template_buffer) {
+                            ui.colored_label(LogLevel::Error.color(), e.to_string());
+                        } else {
+                            let example = export::export_file_name(
+                                &self.config.filename_template,
+                                &self.config.project_number,
+                                ExportFormat::Excel(self.config.excel, self.config.columns),
+                                self.plc_table.entries.len(),
+                                chrono::Local::now(),
+                            );
+                            ui.weak(t!("Example: {}", example));
+                        }
+
+                        ui.horizontal(|ui| {
+                            ui.label(t!("Last export path:"));
+                            if let Some(path) = &self.config.last_export_path {
+                                ui.label(path);
+                            } else {
+                                ui.label(t!("(not set)"));
+                            }
+                        });
+                    });
+
+                    ui.add_space(12.0);
+
+                    // Notification settings
+                    ui.group(|ui| {
+                        ui.label(t!("🔔 Notifications"));
+                        ui.separator();
+
+                        ui.horizontal(|ui| {
+                            ui.label(t!("Webhook URL:"));
+                            let mut webhook_url = self.config.webhook_url.clone().unwrap_or_default();
+                            let webhook_response = ui.add(
+                                egui::TextEdit::singleline(&mut webhook_url)
+                                    .desired_width(300.0)
+                                    .hint_text("https://example.com/hooks/eview")
+                            );
+                            if webhook_response.changed() {
+                                let trimmed = webhook_url.trim();
+                                self.config.webhook_url = if trimmed.is_empty() {
+                                    None
+                                } else {
+                                    Some(trimmed.to_string())
+                                };
+                                let _ = self.config.save();
+                            }
+
+                            if ui.add_enabled(
+                                self.config.webhook_url.is_some(),
+                                egui::Button::new(t!("Test"))
+                            ).on_hover_text(t!("Send a sample payload to the webhook")).clicked() {
+                                self.test_webhook();
+                            }
+                        });
+                        ui.weak(t!("Receives a JSON POST when an extraction completes or fails"));
+                    });
+
+                    ui.add_space(12.0);
+
+                    // Validation settings
+                    ui.group(|ui| {
+                        ui.label(t!("✅ Validation Rules"));
+                        ui.separator();
+
+                        let rules = &mut self.config.validation;
+                        let mut changed = false;
+
+                        ui.horizontal(|ui| {
+                            ui.label(t!("Minimum symbol name length:"));
+                            changed |= ui.add(egui::DragValue::new(&mut rules.min_name_length).range(1..=32)).changed();
+                        });
+                        ui.horizontal(|ui| {
+                            ui.label(t!("Allowed byte addresses:"));
+                            changed |= ui.add(egui::DragValue::new(&mut rules.min_byte_address).range(0..=rules.max_byte_address)).changed();
+                            ui.label(t!("to"));
+                            changed |= ui.add(egui::DragValue::new(&mut rules.max_byte_address).range(rules.min_byte_address..=65535)).changed();
+                        });
+                        changed |= ui.checkbox(&mut rules.strict_names, t!("Strict names (flag non-ASCII characters)")).changed();
+                        ui.horizontal(|ui| {
+                            ui.label(t!("Flag entries with a confidence below:"));
+                            changed |= ui.add(egui::DragValue::new(&mut rules.min_confidence).range(0.0..=1.0).speed(0.01).fixed_decimals(2)).changed();
+                        });
+
+                        if changed {
+                            let _ = self.config.save();
+                        }
+                    });
+
+                    ui.add_space(20.0);
+
+                    // Save button
+                    if ui.button(t!("💾 Save Settings")).clicked() {
+                        match self.config.save() {
+                            Ok(_) => self.toast_queue.push(Toast::success(t!("Settings saved"))),
+                            Err(e) => self.log(format!("Failed to save settings: {}", e), LogLevel::Error),
+                        }
+                    }
+                });
+            });
+    }
+
+    /// Dropdown of the recently extracted projects, filling in the project
+    /// number on click
+    fn render_recent_projects(&mut self, ui: &mut egui::Ui) {
+        if self.config.recent_projects.is_empty() {
+            return;
+        }
+
+        let mut selected = None;
+        let mut clear = false;
+        egui::ComboBox::from_id_salt("recent_projects")
+            .selected_text(t!("Recent Projects"))
+            .show_ui(ui, |ui| {
+                for project in &self.config.recent_projects {
+                    let current = *project == self.config.project_number;
+                    if ui.selectable_label(current, project).clicked() {
+                        selected = Some(project.clone());
+                    }
+                }
+                ui.separator();
+                clear = ui.button(t!("✕ Clear history"))
+                    .on_hover_text(t!("Forget the recently extracted projects"))
+                    .clicked();
+            });
+
+        if let Some(project) = selected {
+            self.config.project_number = project;
+            let _ = self.config.save();
+        }
+        if clear {
+            self.config.recent_projects.clear();
+            let _ = self.config.save();
+        }
+    }
+
+    fn render_extraction_controls(&mut self, ui: &mut egui::Ui) {
+        ui.heading(t!("🔧 Extraction Controls"));
+        ui.separator();
+        ui.add_space(8.0);
+
+        if self.profile_names.len() > 1 {
+            let ctx = ui.ctx().clone();
+            ui.add_enabled_ui(!self.is_extracting, |ui| {
+                self.render_profile_selector(&ctx, ui);
+            });
+            ui.add_space(8.0);
+        }
+
+        // Login credentials section
+        ui.group(|ui| {
+            ui.label(t!("🔐 Microsoft Credentials"));
+            ui.separator();
+
+            ui.horizontal(|ui| {
+                ui.label(t!("Email:"));
+                let email_response = ui.add(
+                    egui::TextEdit::singleline(&mut self.config.email)
+                        .desired_width(fonts::scaled(ui, 200.0))
+                        .hint_text("your.email@company.com")
+                );
+                if email_response.changed() {
+                    let _ = self.config.save();
+                }
+            });
+
+            ui.horizontal(|ui| {
+                ui.label(t!("Password:"));
+                let password_response = ui.add(
+                    egui::TextEdit::singleline(&mut self.password_buffer)
+                        .desired_width(fonts::scaled(ui, 200.0))
+                        .password(true)
+                        .hint_text(t!("Enter password"))
+                );
+                if password_response.changed() {
+                    self.config.set_password(self.password_buffer.clone());
+                    let _ = self.config.save();
+                }
+            });
+        });
+
+        ui.add_space(12.0);
+
+        // Project settings section
+        ui.group(|ui| {
+            ui.label(t!("📋 Project Settings"));
+            ui.separator();
+
+            ui.horizontal(|ui| {
+                ui.label(t!("Project Number:"));
+                let project_response = ui.add(
+                    egui::TextEdit::singleline(&mut self.config.project_number)
+                        .desired_width(fonts::scaled(ui, 150.0))
+                        .hint_text(t!("e.g., P12345"))
+                );
+                if project_response.changed() {
+                    let _ = self.config.save();
+                }
+                self.render_recent_projects(ui);
+            });
+        });
+
+        ui.add_space(16.0);
+
+        // Status and progress
+        if self.is_extracting {
+            ui.group(|ui| {
+                ui.label(t!("🚀 Extraction in Progress"));
+                ui.separator();
+
+                let progress_bar = egui::ProgressBar::new(self.progress)
+                    .desired_width(fonts::scaled(ui, 280.0))
+                    .text(format!("{:.0}%", self.progress * 100.0));
+                ui.add(progress_bar);
+
+                ui.label(&self.status_message);
+
+                if ui.button(t!("⏹ Stop Extraction")).clicked() {
+                    self.stop_extraction();
+                }
+            });
+        } else {
+            // Validation and extract button
+            let validation_errors = self.config.validate();
+            let can_extract = validation_errors.is_empty();
+
+            if !validation_errors.is_empty() {
+                ui.group(|ui| {
+                    ui.label(t!("⚠️ Configuration Issues"));
+                    ui.separator();
+                    for error in &validation_errors {
+                        ui.colored_label(egui::Color32::from_rgb(244, 67, 54), format!("• {}", error));
+                    }
+                });
+                ui.add_space(8.0);
+            }
+
+            // Keyboard shortcuts section
+            ui.group(|ui| {
+                ui.label(t!("⌨️ Keyboard Shortcuts"));
+                ui.separator();
+
+                ui.horizontal(|ui| {
+                    ui.label(t!("Ctrl+E:"));
+                    ui.weak(t!("Start Extraction"));
+                });
+                ui.horizontal(|ui| {
+                    ui.label(t!("Ctrl+S:"));
+                    ui.weak(t!("Save Settings"));
+                });
+                ui.horizontal(|ui| {
+                    ui.label(t!("F5:"));
+                    ui.weak(t!("Restart Extraction"));
+                });
+                ui.horizontal(|ui| {
+                    ui.label(t!("Esc:"));
+                    ui.weak(t!("Cancel/Main Tab"));
+                });
+            });
+
+            ui.add_space(12.0);
+
+            ui.checkbox(&mut self.config.dry_run, t!("🧪 Dry run"))
+                .on_hover_text(t!("Only log in, open the project and count the PLC pages, without extracting anything"));
+
+            let extract_btn = ui.add_sized(
+                egui::Vec2::new(fonts::scaled(ui, 280.0), fonts::scaled(ui, 40.0)),
+                egui::Button::new(t!("🚀 Start Extraction"))
+                    .fill(if can_extract {
+                        egui::Color32::from_rgb(16, 124, 16)
+                    } else {
+                        egui::Color32::from_rgb(100, 100, 100)
+                    })
+            )
+            .on_hover_text(
+                if can_extract {
+                    t!("Start extracting PLC tables from eView")
                 } else {
-                    "Please fix configuration issues first"
+                    t!("Please fix configuration issues first")
+                }
+            );
+
+            if extract_btn.clicked() && can_extract {
+                self.start_extraction();
+            }
+        }
+    }
+
+    fn render_log_panel(&mut self, ui: &mut egui::Ui) {
+        let mut jump_to = None;
+        ui.horizontal(|ui| {
+            ui.heading(t!("📋 Logs"));
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                // Clear logs button
+                if ui.button(t!("🗑 Clear")).clicked() {
+                    self.log_messages.clear();
+                    self.log_selection.clear();
+                }
+
+                if ui.button(t!("📂 Open log folder"))
+                    .on_hover_text(t!("The daily log files with every message, also those no longer shown here"))
+                    .clicked()
+                {
+                    self.open_log_folder();
+                }
+
+                // Save logs button
+                if ui.button(t!("💾 Save"))
+                    .on_hover_text(t!("Save today's log file"))
+                    .clicked()
+                {
+                    self.save_logs_to_file(false);
+                }
+                if ui.button(t!("🧾 Save as JSON Lines"))
+                    .on_hover_text(t!("One record per log entry with timestamp, level and extraction phase, after a header with the app version and settings"))
+                    .clicked()
+                {
+                    self.save_logs_to_file(true);
+                }
+
+                // Copy all logs button
+                if ui.button(t!("📋 Copy All")).clicked() {
+                    ui.output_mut(|o| o.copied_text = self.log_text());
+                    self.toast_queue.push(Toast::info(t!("Logs copied to clipboard")));
+                }
+                if ui.add_enabled(!self.log_selection.ids.is_empty(), egui::Button::new(t!("📋 Copy Selected")))
+                    .on_hover_text(t!("Click lines to select them, Ctrl+click and Shift+click to add more (Ctrl+C)"))
+                    .clicked()
+                {
+                    self.copy_selected_log_lines(ui.ctx());
+                }
+
+                // Auto-scroll toggle
+                if ui.selectable_label(self.log_auto_scroll, t!("📍 Auto-scroll")).clicked() {
+                    self.log_auto_scroll = !self.log_auto_scroll;
+                }
+
+                // Timestamps toggle
+                if ui.selectable_label(self.show_timestamps, t!("⏰ Timestamps")).clicked() {
+                    self.show_timestamps = !self.show_timestamps;
+                }
+            });
+        });
+
+        ui.separator();
+
+        // Log level filter
+        ui.horizontal(|ui| {
+            ui.label(t!("Filter:"));
+
+            let current_filter = self.log_filter_level.clone();
+            let mut selected_text = format!("{} {}", current_filter.icon(), t!(current_filter.name()));
+            if self.log_browser_console_only {
+                selected_text = format!("{} · 🌐 {}", selected_text, t!("Browser Console"));
+            }
+            egui::ComboBox::from_label("")
+                .selected_text(selected_text)
+                .show_ui(ui, |ui| {
+                    for level in [LogLevel::Debug, LogLevel::Info, LogLevel::Success, LogLevel::Warning, LogLevel::Error] {
+                        let text = format!("{} {}", level.icon(), t!(level.name()));
+                        ui.selectable_value(&mut self.log_filter_level, level.clone(), text);
+                    }
+                    ui.separator();
+                    ui.checkbox(&mut self.log_browser_console_only, format!("🌐 {}", t!("Browser Console")))
+                        .on_hover_text(t!("Only show messages from the web page's JavaScript console"));
+                });
+
+            ui.separator();
+            let invalid_regex = self.log_search_matcher.as_ref().err().map(ToString::to_string);
+            let mut search_edit = egui::TextEdit::singleline(&mut self.log_search)
+                .desired_width(200.0)
+                .hint_text(t!("Search logs..."));
+            if invalid_regex.is_some() {
+                search_edit = search_edit.text_color(self.palette.status.error);
+            }
+            let search_response = ui.add(search_edit);
+            if let Some(error) = &invalid_regex {
+                search_response.on_hover_text(error);
+            }
+            if !self.log_search.is_empty() && ui.small_button("✕").on_hover_text(t!("Clear search")).clicked() {
+                self.log_search.clear();
+            }
+            ui.toggle_value(&mut self.log_search_regex, ".*")
+                .on_hover_text(t!("Regular expression"));
+            self.update_log_matcher();
+            ui.toggle_value(&mut self.log_search_filter, t!("Only matches"))
+                .on_hover_text(t!("Hide the lines without a match. Off, all lines stay visible and the matches are highlighted."));
+
+            // Match counter and F3 / Shift+F3 navigation
+            let matches = self.log_match_ids();
+            if self.log_matcher().is_some() {
+                let position = self.log_search_current
+                    .and_then(|current| matches.iter().position(|&id| id == current));
+                match position {
+                    Some(index) => ui.label(t!("{} of {} matches", index + 1, matches.len())),
+                    None => ui.label(t!("{} matches", matches.len())),
+                };
+                let previous = ui.add_enabled(!matches.is_empty(), egui::Button::new("⬆").small())
+                    .on_hover_text(t!("Previous match (Shift+F3)"))
+                    .clicked();
+                let next = ui.add_enabled(!matches.is_empty(), egui::Button::new("⬇").small())
+                    .on_hover_text(t!("Next match (F3)"))
+                    .clicked();
+                let (f3, shift) = ui.input_mut(|i| (i.consume_key(egui::Modifiers::NONE, egui::Key::F3), i.consume_key(egui::Modifiers::SHIFT, egui::Key::F3)));
+                if next || previous || f3 || shift {
+                    jump_to = step_log_match(&matches, self.log_search_current, next || f3);
+                }
+            }
+
+            ui.separator();
+            let visible = self.visible_log_entries().count();
+            if visible == self.log_messages.len() {
+                ui.label(t!("{} entries", visible));
+            } else {
+                ui.label(t!("{} of {} entries", visible, self.log_messages.len()));
+            }
+        });
+
+        ui.separator();
+
+        if jump_to.is_some() {
+            self.log_search_current = jump_to;
+            self.log_auto_scroll = false;
+        }
+
+        // Enhanced resizable log area
+        let available_height = ui.available_height() - 50.0; // Leave room for status bar
+        let log_height = self.log_panel_height.min(available_height).max(100.0);
+
+        ui.vertical(|ui| {
+            // Resizable log list, one colored line per entry. Only the rows in
+            // view are laid out, and sticking to the bottom stops once the user scrolls up.
+            let show_timestamps = self.show_timestamps;
+            let matcher = self.log_matcher();
+            let highlight = ui.visuals().selection.bg_fill;
+            let current_match = self.log_search_current;
+            let visible: Vec<&LogEntry> = self.visible_log_entries().collect();
+            let visible_ids: Vec<u64> = visible.iter().map(|entry| entry.id).collect();
+            let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+            let mut clicked = None;
+            let mut log_area = egui::ScrollArea::both()
+                .id_salt("log_lines")
+                .max_height(log_height)
+                .auto_shrink([false, false])
+                .stick_to_bottom(self.log_auto_scroll);
+
+            // Center the line jumped to, so the lines around it stay in view
+            if let Some(row) = jump_to.and_then(|id| visible_ids.iter().position(|&line| line == id)) {
+                let row_pitch = row_height + ui.spacing().item_spacing.y;
+                let offset = row as f32 * row_pitch - (log_height - row_height) / 2.0;
+                log_area = log_area.vertical_scroll_offset(offset.max(0.0));
+            }
+
+            let log_area = log_area
+                .show_rows(ui, row_height, visible.len(), |ui, rows| {
+                    for entry in &visible[rows] {
+                        let background = ui.painter().add(egui::Shape::Noop);
+                        let job = log_line_layout(ui, entry, show_timestamps, matcher, highlight);
+                        let mut response = ui.add(egui::Label::new(job).extend().sense(egui::Sense::click()));
+                        if first_line(&entry.message).1 {
+                            response = response.on_hover_text(&entry.message);
+                        }
+                        let row = response.rect.with_max_x(ui.clip_rect().right());
+                        if self.log_selection.contains(entry.id) {
+                            ui.painter().set(background, egui::Shape::rect_filled(row, 2.0, highlight));
+                        }
+                        if current_match == Some(entry.id) {
+                            ui.painter().rect_stroke(row, 2.0, egui::Stroke::new(1.0, ui.visuals().selection.stroke.color));
+                        }
+                        if response.clicked() {
+                            clicked = Some(entry.id);
+                        }
+                    }
+                });
+
+            if let Some(id) = clicked {
+                let modifiers = ui.input(|i| i.modifiers);
+                self.log_selection.click(id, &visible_ids, modifiers.command, modifiers.shift);
+            }
+
+            // Ctrl+A and Ctrl+C on the lines, unless a text field has the keyboard
+            if ui.memory(|memory| memory.focused().is_none()) {
+                if ui.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::A)) {
+                    self.log_selection.ids.extend(&visible_ids);
                 }
+                if !self.log_selection.ids.is_empty() && ui.input(|i| i.events.contains(&egui::Event::Copy)) {
+                    self.copy_selected_log_lines(ui.ctx());
+                }
+            }
+
+            // Handle resize drag
+            let resize_handle_rect = egui::Rect::from_min_size(
+                egui::pos2(ui.min_rect().left(), log_area.inner_rect.bottom()),
+                egui::vec2(ui.available_width(), 8.0)
+            );
+
+            let resize_response = ui.allocate_rect(resize_handle_rect, egui::Sense::drag());
+            if resize_response.dragged() {
+                self.log_panel_height = (self.log_panel_height + resize_response.drag_delta().y)
+                    .clamp(100.0, 600.0);
+            }
+
+            // Visual resize handle
+            if resize_response.hovered() {
+                ui.ctx().set_cursor_icon(egui::CursorIcon::ResizeVertical);
+            }
+
+            ui.painter().hline(
+                resize_handle_rect.x_range(),
+                resize_handle_rect.center().y,
+                egui::Stroke::new(2.0, if resize_response.hovered() {
+                    egui::Color32::WHITE
+                } else {
+                    egui::Color32::GRAY
+                })
             );
+        });
+
+        // Keyboard shortcuts info
+        if ui.input(|i| i.key_pressed(egui::Key::F1)) {
+            self.toast_queue.push(Toast::info(t!("Keyboard shortcuts: Ctrl+A (Select All), Ctrl+C (Copy Selected), F3/Shift+F3 (Next/Previous Match), F1 (Help)")));
+        }
+    }
+
+    /// Table an export of `scope` writes, in the shown order, with the number
+    /// of symbol names cleaned up
+    fn table_to_export(&self, scope: ExportScope) -> (PlcTable, usize) {
+        let mut table = match scope {
+            ExportScope::All => self.table_view.ordered_table(&self.plc_table, false),
+            ExportScope::Filtered => self.table_view.ordered_table(&self.plc_table, true),
+        };
+
+        let changed = if self.config.normalize_names_on_export {
+            table.normalize_names(&self.config.name_normalization)
+        } else {
+            0
+        };
+        (table, changed)
+    }
+
+    /// Path from the file name template in the last export folder
+    fn default_export_path(&self, format: ExportFormat, entries: usize) -> PathBuf {
+        let filename = export::export_file_name(
+            &self.config.filename_template,
+            &self.plc_table.project_name,
+            format,
+            entries,
+            chrono::Local::now(),
+        );
+        match &self.config.last_export_path {
+            Some(dir) => std::path::Path::new(dir).join(&filename),
+            None => PathBuf::from(&filename),
+        }
+    }
+
+    /// Show the export preview for `format`, the file is written once it's confirmed
+    fn preview_export(&mut self, format: ExportFormat) {
+        if self.running_export.is_some() {
+            return;
+        }
+
+        let (table, _) = self.table_to_export(self.export_scope);
+        let path = self.default_export_path(format, table.entries.len());
+        self.export_preview.excel_options = self.config.excel;
+        self.export_preview.json_layout = self.config.json_layout;
+        self.export_preview.columns = self.config.columns;
+        self.export_preview.csv_delimiter = self.config.csv_delimiter;
+        self.export_preview.open(&table, self.plc_table.entries.len(), format, &path);
+    }
+
+    fn export_table(&mut self, format: ExportFormat, path: PathBuf, scope: ExportScope, filter: ExportFilter) {
+        if self.running_export.is_some() {
+            return;
+        }
+
+        let (table, changed) = self.table_to_export(scope);
+        if changed > 0 {
+            self.log(format!("Cleaned up {} symbol names for export", changed), LogLevel::Info);
+        }
+
+        let exporter = format.exporter();
+        let exported = filter.apply(&table);
+        for warning in exporter.warnings(&exported) {
+            self.log(format!("{} export: {}", format.name(), warning), LogLevel::Warning);
+        }
+
+        let entries = exported.entries.len();
+        let complete = scope == ExportScope::All && filter == ExportFilter::default();
+        let handle = export::async_export::export_async(
+            exporter,
+            table,
+            filter,
+            path.to_string_lossy().to_string(),
+            self.background_tx.clone(),
+        );
+        self.running_export = Some(RunningExport {
+            handle,
+            format,
+            entries,
+            exit_when_done: false,
+            complete,
+            revision: self.plc_table.revision,
+            edited: false,
+        });
+    }
+
+    /// Export buttons are disabled while an export is still writing or it
+    /// would write no rows
+    fn can_export(&self) -> bool {
+        let filter = ExportFilter { selected_only: self.export_preview.selected_only, ..ExportFilter::default() };
+        self.can_export_rows(self.export_scope, &filter)
+    }
+
+    /// No export is writing and `scope` and `filter` leave rows to write
+    fn can_export_rows(&self, scope: ExportScope, filter: &ExportFilter) -> bool {
+        self.running_export.is_none()
+            && self.plc_table.entries.iter().any(|entry| {
+                filter.matches(entry) && (scope == ExportScope::All || self.table_view.filter().matches(entry))
+            })
+    }
+
+    fn copy_selected_log_lines(&mut self, ctx: &egui::Context) {
+        let lines = self.visible_log_entries().filter(|entry| self.log_selection.contains(entry.id)).count();
+        ctx.output_mut(|o| o.copied_text = self.selected_log_text());
+        self.status_message = t!("Copied {} log lines", lines);
+    }
+
+    /// Copy the selected rows in the shown order and columns, or all rows
+    /// passing the filter when none are selected
+    fn copy_rows(&mut self, ctx: &egui::Context, format: ClipboardFormat) {
+        let any_selected = self.plc_table.entries.iter().any(|entry| entry.selected);
+        let rows: Vec<PlcEntry> = self
+            .table_view
+            .ordered_table(&self.plc_table, !any_selected)
+            .entries
+            .into_iter()
+            .filter(|entry| entry.selected || !any_selected)
+            .collect();
+
+        let columns = self.table_view.columns().visible();
+        let text = export::clipboard::copy_text(&rows, &columns, format, self.config.csv_delimiter);
+        ctx.output_mut(|o| o.copied_text = text);
+        self.status_message = t!("Copied {} rows as {}", rows.len(), format.name());
+    }
+
+    fn finish_export(&mut self, ctx: &egui::Context, path: PathBuf, ok: bool) {
+        let Some(export) = self.running_export.take() else {
+            return;
+        };
+
+        if ok {
+            self.toast_queue.push(Toast::success(t!("Exported {} entries to {}", export.entries, path.display())));
+
+            // Edits made while the file was written are not in it
+            if export.edited || export.revision != self.plc_table.revision {
+                self.log(
+                    "The results were edited during the export - the file does not contain these edits".to_string(),
+                    LogLevel::Warning,
+                );
+                return;
+            }
+            // A subset leaves the rest of the results unsaved
+            if export.complete {
+                self.plc_table.dirty = false;
+                self.extraction_exported = true;
+            }
+            if export.exit_when_done {
+                self.exit_confirmed = true;
+                ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+            }
+        } else {
+            // Stay open, the error is in the log
+            self.status_message = t!("{} export failed - check log for details", export.format.name());
+        }
+    }
+
+    fn save_session(&mut self) {
+        match Session::from_table(&self.plc_table).save() {
+            Ok(_) => {
+                self.plc_table.dirty = false;
+                self.toast_queue.push(Toast::success(t!("Session saved ({} entries)", self.plc_table.entries.len())));
+            }
+            Err(e) => self.log(format!("Failed to save session: {}", e), LogLevel::Error),
+        }
+    }
+
+    fn render_export_preview(&mut self, ctx: &egui::Context) {
+        if let Some((format, path, filter)) = self.export_preview.show(ctx) {
+            self.export_table(format, path, self.export_scope, filter);
+        }
+    }
+
+    fn render_bulk_edit_dialog(&mut self, ctx: &egui::Context) {
+        let Some((operation, target)) = self.bulk_edit.show(ctx, &self.plc_table, self.table_view.filter()) else {
+            return;
+        };
+
+        let snapshot = self.plc_table.clone();
+        match self.plc_table.apply_bulk_edit(&operation, target) {
+            Ok(changed) => {
+                self.toast_queue.push(Toast::success(t!("Bulk edit changed {} entries", changed)));
+                self.push_undo(operation.to_string(), snapshot);
+                self.log(format!("✏️ Bulk edit: {} ({} entries changed)", operation, changed), LogLevel::Success);
+            }
+            Err(e) => self.log(format!("Bulk edit failed: {}", e), LogLevel::Error),
+        }
+    }
+
+    fn push_undo(&mut self, description: String, table: PlcTable) {
+        self.bulk_undo.push(BulkUndo { description, table });
+        if self.bulk_undo.len() > MAX_BULK_UNDO {
+            self.bulk_undo.remove(0);
+        }
+    }
+
+    fn undo_bulk_edit(&mut self) {
+        if let Some(undo) = self.bulk_undo.pop() {
+            let dirty = undo.table.dirty;
+            self.plc_table = undo.table;
+            self.plc_table.mark_changed();
+            self.plc_table.dirty = dirty;
+            self.log(format!("↶ Undid {}", undo.description), LogLevel::Info);
+        }
+    }
+
+    /// Load a table exported as JSON. A table of another project than the
+    /// configured one is only loaded after confirmation.
+    fn import_json(&mut self) {
+        let Some(path) = rfd::FileDialog::new().add_filter("JSON", &["json", "ndjson"]).pick_file() else {
+            return;
+        };
+
+        match PlcTable::from_json_file(&path) {
+            Ok(table) => {
+                let project = self.config.project_number.trim();
+                if !project.is_empty() && table.project_name.trim() != project {
+                    self.pending_json_import = Some((table, path));
+                } else {
+                    self.apply_json_import(table, &path);
+                }
+            }
+            Err(e) => self.log(format!("Import of {} failed: {}", path.display(), e), LogLevel::Error),
+        }
+    }
+
+    fn apply_json_import(&mut self, table: PlcTable, path: &std::path::Path) {
+        let file_name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        let previous = std::mem::replace(&mut self.plc_table, table);
+        self.push_undo(format!("import of {}", file_name), previous);
+        self.session_banner = None;
+        self.extraction_exported = true; // Already on disk
+
+        self.log(
+            format!("📂 Loaded {} entries of {} from {}", self.plc_table.entries.len(), self.plc_table.project_name, path.display()),
+            LogLevel::Success,
+        );
+        self.toast_queue.push(Toast::success(t!("Imported {} entries", self.plc_table.entries.len())));
+    }
+
+    fn render_json_import_dialog(&mut self, ctx: &egui::Context) {
+        let Some((table, _)) = &self.pending_json_import else {
+            return;
+        };
+        let project_name = table.project_name.clone();
 
-            if extract_btn.clicked() && can_extract {
-                self.start_extraction();
+        let mut choice = None;
+
+        egui::Window::new(t!("Different project"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(t!(
+                    "The file belongs to project \"{}\", the configured project is \"{}\".",
+                    project_name,
+                    self.config.project_number
+                ));
+                ui.label(t!("Load it anyway? Undo brings back the current table."));
+                ui.add_space(8.0);
+
+                ui.horizontal(|ui| {
+                    if ui.button(t!("Load")).clicked() {
+                        choice = Some(true);
+                    }
+                    if ui.button(t!("Cancel")).clicked() {
+                        choice = Some(false);
+                    }
+                });
+            });
+
+        match choice {
+            Some(true) => {
+                if let Some((table, path)) = self.pending_json_import.take() {
+                    self.apply_json_import(table, &path);
+                }
             }
+            Some(false) => self.pending_json_import = None,
+            None => {}
         }
     }
 
-    fn render_log_panel(&mut self, ui: &mut egui::Ui) {
-        ui.horizontal(|ui| {
-            ui.heading("📋 Logs");
-            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                // Clear logs button
-                if ui.button("🗑 Clear").clicked() {
-                    self.log_messages.clear();
-                    self.update_log_buffer();
-                }
+    /// Asks whether a finished extraction replaces the edited results or is merged into them
+    fn render_merge_dialog(&mut self, ctx: &egui::Context) {
+        let Some(new_count) = self.pending_merge.as_ref().map(|table| table.entries.len()) else {
+            return;
+        };
 
-                // Save logs button
-                if ui.button("💾 Save").clicked() {
-                    self.save_logs_to_file();
-                }
+        let mut choice = None;
 
-                // Copy all logs button
-                if ui.button("📋 Copy All").clicked() {
-                    ui.output_mut(|o| o.copied_text = self.log_text_buffer.clone());
-                    self.log("Logs copied to clipboard".to_string(), LogLevel::Success);
-                }
+        egui::Window::new(t!("Merge extraction"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(t!("The results were edited and have not been exported or saved."));
+                ui.label(t!("The new extraction has {} entries. Merging keeps your comments and selection.", new_count));
+                ui.add_space(4.0);
 
-                // Auto-scroll toggle
-                if ui.selectable_label(self.log_auto_scroll, "📍 Auto-scroll").clicked() {
-                    self.log_auto_scroll = !self.log_auto_scroll;
+                if ui.checkbox(&mut self.config.merge_keep_removed, t!("Keep removed addresses (flagged)")).changed() {
+                    let _ = self.config.save();
                 }
+                ui.add_space(8.0);
 
-                // Timestamps toggle
-                if ui.selectable_label(self.show_timestamps, "⏰ Timestamps").clicked() {
-                    self.show_timestamps = !self.show_timestamps;
-                    self.update_log_buffer();
+                ui.horizontal(|ui| {
+                    if ui.button(t!("🔀 Merge")).clicked() {
+                        choice = Some(true);
+                    }
+                    if ui.button(t!("Replace")).clicked() {
+                        choice = Some(false);
+                    }
+                    if ui.button(t!("Keep current table")).clicked() {
+                        self.pending_merge = None;
+                        self.log("New extraction discarded, the edited results were kept".to_string(), LogLevel::Info);
+                    }
+                });
+            });
+
+        let Some(merge) = choice else {
+            return;
+        };
+        let Some(table) = self.pending_merge.take() else {
+            return;
+        };
+
+        if merge {
+            let (merged, summary) = self.plc_table.merge(table, self.config.merge_keep_removed);
+            self.load_extraction(merged, false);
+            self.status_message = t!(
+                "Merged extraction - {} added, {} updated, {} removed",
+                summary.added,
+                summary.updated,
+                summary.removed
+            );
+            self.log(
+                format!("🔀 Merged extraction: {} added, {} updated, {} removed", summary.added, summary.updated, summary.removed),
+                LogLevel::Success,
+            );
+        } else {
+            self.load_extraction(table, false);
+        }
+    }
+
+    /// Apply tray menu commands, update the status icon and hide the
+    /// window to the tray when it gets minimised
+    fn handle_tray(&mut self, ctx: &egui::Context) {
+        let Some(tray) = &mut self.tray else {
+            return;
+        };
+
+        tray.update(ctx, &self.app_status, self.progress);
+
+        for command in tray.commands() {
+            match command {
+                TrayCommand::Open => self.restore_from_tray(ctx),
+                TrayCommand::StartExtraction => {
+                    if !self.is_extracting {
+                        self.start_extraction();
+                    }
+                }
+                TrayCommand::CancelExtraction => {
+                    if self.is_extracting {
+                        self.stop_extraction();
+                    }
                 }
+                TrayCommand::ShowLastResult => {
+                    self.restore_from_tray(ctx);
+                    self.current_tab = AppTab::Results;
+                }
+                TrayCommand::Quit => {
+                    // Visible so a pending exit confirmation can be answered
+                    self.restore_from_tray(ctx);
+                    self.stop_and_close(ctx);
+                }
+            }
+        }
+
+        let minimized = ctx.input(|i| i.viewport().minimized) == Some(true);
+        if minimized && self.config.minimize_to_tray && !self.hidden_to_tray {
+            self.hide_to_tray(ctx);
+        }
+    }
+
+    /// Hide the window, the extraction goes on and the tray icon shows it again
+    fn hide_to_tray(&mut self, ctx: &egui::Context) {
+        ctx.send_viewport_cmd(egui::ViewportCommand::Visible(false));
+        self.hidden_to_tray = true;
+    }
+
+    /// Stop a running extraction and the ChromeDriver it started, then close
+    /// the window, see `finish_closing`
+    fn stop_and_close(&mut self, ctx: &egui::Context) {
+        if self.is_extracting {
+            self.stop_extraction();
+        }
+        self.close_deadline = Some(std::time::Instant::now() + CLOSE_TIMEOUT);
+        self.finish_closing(ctx);
+    }
+
+    /// Close the window once the cancelled task quit its browser through the
+    /// driver, or when it takes too long. The UI keeps drawing meanwhile.
+    fn finish_closing(&mut self, ctx: &egui::Context) {
+        let Some(deadline) = self.close_deadline else {
+            return;
+        };
+
+        let stopped = self.stopping_extraction.as_ref().is_none_or(|handle| handle.is_finished());
+        if !stopped && std::time::Instant::now() < deadline {
+            ctx.request_repaint_after(std::time::Duration::from_millis(50));
+            return;
+        }
+
+        self.close_deadline = None;
+        self.stopping_extraction = None;
+        self.chromedriver_manager.kill_driver();
+        ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+    }
+
+    fn restore_from_tray(&mut self, ctx: &egui::Context) {
+        ctx.send_viewport_cmd(egui::ViewportCommand::Visible(true));
+        ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(false));
+        ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+        self.hidden_to_tray = false;
+    }
+
+    /// Keep the comments of the loaded table in a new extraction of the same project
+    fn carry_comments(&mut self, table: &mut PlcTable) {
+        if self.plc_table.entries.is_empty() || self.plc_table.project_name != table.project_name {
+            return;
+        }
+
+        let carry = table.carry_comments(&self.plc_table);
+        if carry.preserved == 0 && carry.dropped == 0 {
+            return;
+        }
+        let level = if carry.dropped > 0 { LogLevel::Warning } else { LogLevel::Info };
+        self.log(
+            format!(
+                "💬 Kept {} edited comments from the previous extraction, dropped {} whose address and symbol name are gone",
+                carry.preserved, carry.dropped
+            ),
+            level,
+        );
+    }
+
+    /// Show a finished extraction as the current results, with `carry_comments`
+    /// taking over the comments edited in the previous one
+    fn load_extraction(&mut self, mut table: PlcTable, carry_comments: bool) {
+        // Highlight what changed since the last extraction of the same project
+        if !self.plc_table.entries.is_empty() && self.plc_table.project_name == table.project_name {
+            table.mark_status(&self.plc_table);
+        }
+        if carry_comments {
+            self.carry_comments(&mut table);
+        }
+        self.plc_table = table;
+        self.extraction_exported = false;
+        self.bulk_undo.clear();
+        self.session_banner = None;
+    }
+
+    fn render_close_extraction_dialog(&mut self, ctx: &egui::Context) {
+        if !self.show_close_extraction_dialog {
+            return;
+        }
+        if !self.is_extracting {
+            // Finished while the dialog was open
+            self.show_close_extraction_dialog = false;
+            return;
+        }
+
+        egui::Window::new(t!("Extraction Running"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(t!("An extraction is still running."));
+                ui.add_space(8.0);
+
+                ui.horizontal(|ui| {
+                    if ui.button(t!("Cancel extraction and exit")).clicked() {
+                        self.show_close_extraction_dialog = false;
+                        self.stop_and_close(ctx);
+                    }
+                    if self.tray.is_some() && ui.button(t!("Keep running in tray")).clicked() {
+                        self.show_close_extraction_dialog = false;
+                        self.hide_to_tray(ctx);
+                    }
+                    if ui.button(t!("Cancel")).clicked() {
+                        self.show_close_extraction_dialog = false;
+                    }
+                });
             });
-        });
+    }
 
-        ui.separator();
+    fn render_exit_dialog(&mut self, ctx: &egui::Context) {
+        if !self.show_exit_dialog {
+            return;
+        }
 
-        // Log level filter
+        egui::Window::new(t!("Unsaved Data"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(t!("The extracted data has not been exported yet."));
+                ui.label(t!("It will be lost when the window is closed."));
+                ui.add_space(8.0);
+
+                ui.horizontal(|ui| {
+                    // Always the whole table, whatever scope the export bar has
+                    if ui.add_enabled(self.can_export_rows(ExportScope::All, &ExportFilter::default()), egui::Button::new(t!("Export and Exit"))).clicked() {
+                        let format = ExportFormat::Excel(self.config.excel, self.config.columns);
+                        let path = self.default_export_path(format, self.plc_table.entries.len());
+                        self.export_table(format, path, ExportScope::All, ExportFilter::default());
+                        // Closes once the file is written, stays open if the export fails
+                        if let Some(export) = &mut self.running_export {
+                            export.exit_when_done = true;
+                        }
+                        self.show_exit_dialog = false;
+                    }
+                    if ui.button(t!("Exit without saving")).clicked() {
+                        self.exit_confirmed = true;
+                        self.show_exit_dialog = false;
+                        ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+                    }
+                    if ui.button(t!("Cancel")).clicked() {
+                        self.show_exit_dialog = false;
+                    }
+                });
+            });
+    }
+
+    fn render_session_banner(&mut self, ctx: &egui::Context) {
+        let Some(banner) = self.session_banner.clone() else {
+            return;
+        };
+
+        egui::TopBottomPanel::top("session_banner")
+            .frame(egui::Frame {
+                fill: self.palette.tab_bar,
+                inner_margin: egui::Margin::symmetric(12.0, 4.0),
+                ..Default::default()
+            })
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(format!("💾 {}", banner));
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui.small_button("✕").clicked() {
+                            self.session_banner = None;
+                        }
+                    });
+                });
+            });
+    }
+
+    /// Save the visible log as text, or all entries as a JSON array
+    fn save_logs_to_file(&mut self, as_jsonl: bool) {
+        let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+        let filename = format!("eview_scraper_logs_{}.{}", timestamp, if as_jsonl { "jsonl" } else { "txt" });
+
+        // The log file has the lines the capped list already dropped
+        let log_file = logging::log_dir().ok().and_then(|dir| logging::current_file(&dir));
+        let contents = if as_jsonl {
+            match log_json_lines(&self.log_messages, &self.config) {
+                Ok(json) => json,
+                Err(e) => {
+                    self.log(format!("Failed to save logs: {}", e), LogLevel::Error);
+                    return;
+                }
+            }
+        } else if let Some(text) = log_file.and_then(|path| std::fs::read_to_string(path).ok()) {
+            text
+        } else {
+            self.log_text()
+        };
+
+        match std::fs::write(&filename, contents) {
+            Ok(_) => {
+                self.log(format!("Logs saved to {}", filename), LogLevel::Success);
+            }
+            Err(e) => {
+                self.log(format!("Failed to save logs: {}", e), LogLevel::Error);
+            }
+        }
+    }
+
+    fn open_log_folder(&mut self) {
+        let opened = logging::log_dir().and_then(|dir| {
+            std::fs::create_dir_all(&dir)?;
+            open_folder(&dir)?;
+            Ok(())
+        });
+        if let Err(e) = opened {
+            self.log(format!("Failed to open the log folder: {}", e), LogLevel::Error);
+        }
+    }
+
+    fn render_status_bar(&mut self, ui: &mut egui::Ui) {
         ui.horizontal(|ui| {
-            ui.label("Filter:");
+            ui.label(&self.status_message);
 
-            let current_filter = self.log_filter_level.clone();
-            egui::ComboBox::from_label("")
-                .selected_text(format!("{} {}", current_filter.icon(), current_filter.name()))
+            // Progress bar if extracting
+            if self.is_extracting {
+                ui.add(egui::ProgressBar::new(self.progress)
+                    .desired_width(200.0)
+                    .animate(true));
+            }
+
+            if let Some(export) = &self.running_export {
+                ui.separator();
+                ui.spinner();
+                ui.label(t!("Exporting {}…", export.format.name()));
+            }
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                // Add small right margin to prevent text cutoff
+                ui.add_space(10.0);
+                ui.label(t!(
+                    "v{} | {} entries loaded",
+                    env!("CARGO_PKG_VERSION"),
+                    self.plc_table.entries.len()
+                ));
+                ui.separator();
+                ui.label(format!("👤 {}", self.config.profile))
+                    .on_hover_text(t!("Active profile, {}", self.config.base_url));
+            });
+        });
+    }
+
+    /// Combo box switching to another saved profile
+    fn render_profile_selector(&mut self, ctx: &egui::Context, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(t!("Profile:"));
+            let mut switch_to = None;
+            egui::ComboBox::from_id_salt("profile_selector")
+                .selected_text(&self.config.profile)
                 .show_ui(ui, |ui| {
-                    for level in [LogLevel::Debug, LogLevel::Info, LogLevel::Success, LogLevel::Warning, LogLevel::Error] {
-                        let text = format!("{} {}", level.icon(), level.name());
-                        if ui.selectable_value(&mut self.log_filter_level, level.clone(), text).clicked() {
-                            self.update_log_buffer();
+                    for name in &self.profile_names {
+                        if ui.selectable_label(*name == self.config.profile, name).clicked() {
+                            switch_to = Some(name.clone());
                         }
                     }
                 });
-
-            ui.separator();
-            ui.label(format!("{} entries", self.log_messages.len()));
+            if let Some(name) = switch_to.filter(|name| *name != self.config.profile) {
+                self.switch_profile(ctx, &name);
+            }
         });
+    }
 
-        ui.separator();
+    /// Save the current profile and load `name` in its place, the command
+    /// line and environment values still win
+    fn switch_profile(&mut self, ctx: &egui::Context, name: &str) {
+        if let Err(e) = self.config.save() {
+            self.log(format!("Failed to save profile '{}': {}", self.config.profile, e), LogLevel::Error);
+        }
 
-        // Enhanced resizable log area
-        let available_height = ui.available_height() - 50.0; // Leave room for status bar
-        let log_height = self.log_panel_height.min(available_height).max(100.0);
+        match AppConfig::load_profile(name) {
+            Ok(mut config) => {
+                config.apply_overrides(self.config.overrides());
+                self.use_profile(ctx, config);
+            }
+            Err(e) => self.log(format!("Failed to load profile '{}': {}", name, e), LogLevel::Error),
+        }
+    }
+
+    /// Create a profile named after `new_profile_name`, empty or as a copy of the current one
+    fn create_profile(&mut self, ctx: &egui::Context, duplicate: bool) {
+        let name = match Profiles::new().and_then(|profiles| profiles.check_new_name(&self.new_profile_name)) {
+            Ok(name) => name,
+            Err(e) => {
+                self.log(format!("Can't create the profile: {}", e), LogLevel::Error);
+                return;
+            }
+        };
+
+        if let Err(e) = self.config.save() {
+            self.log(format!("Failed to save profile '{}': {}", self.config.profile, e), LogLevel::Error);
+        }
+
+        let config = if duplicate {
+            self.config.duplicate(&name)
+        } else {
+            let mut config = AppConfig::default();
+            config.profile = name;
+            config.apply_overrides(self.config.overrides());
+            config
+        };
+        if let Err(e) = config.save() {
+            self.log(format!("Failed to save profile '{}': {}", config.profile, e), LogLevel::Error);
+            return;
+        }
+
+        self.new_profile_name.clear();
+        self.use_profile(ctx, config);
+    }
+
+    /// Rename the current profile to `new_profile_name`
+    fn rename_profile(&mut self) {
+        if let Err(e) = self.config.save() {
+            self.log(format!("Failed to save profile '{}': {}", self.config.profile, e), LogLevel::Error);
+            return;
+        }
+
+        let old_name = self.config.profile.clone();
+        match Profiles::new().and_then(|profiles| profiles.rename(&old_name, &self.new_profile_name)) {
+            Ok(name) => {
+                self.config.profile = name;
+                self.new_profile_name.clear();
+                self.profile_names = Profiles::new().map(|profiles| profiles.list()).unwrap_or_default();
+                self.log(format!("Renamed profile '{}' to '{}'", old_name, self.config.profile), LogLevel::Info);
+            }
+            Err(e) => self.log(format!("Can't rename the profile: {}", e), LogLevel::Error),
+        }
+    }
+
+    /// Delete the current profile and switch to the first remaining one
+    fn delete_profile(&mut self, ctx: &egui::Context) {
+        let name = self.config.profile.clone();
+        if let Err(e) = Profiles::new().and_then(|profiles| profiles.delete(&name)) {
+            self.log(format!("Can't delete the profile: {}", e), LogLevel::Error);
+            return;
+        }
+
+        let next = Profiles::new()
+            .ok()
+            .and_then(|profiles| profiles.list().into_iter().next())
+            .unwrap_or_else(|| crate::config::DEFAULT_PROFILE.to_string());
+        match AppConfig::load_profile(&next) {
+            Ok(mut config) => {
+                config.apply_overrides(self.config.overrides());
+                self.use_profile(ctx, config);
+                self.log(format!("Deleted profile '{}'", name), LogLevel::Info);
+            }
+            Err(e) => self.log(format!("Failed to load profile '{}': {}", next, e), LogLevel::Error),
+        }
+    }
+
+    fn export_profile(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("JSON", &["json"])
+            .set_file_name(format!("{}.json", self.config.profile))
+            .save_file()
+        else {
+            return;
+        };
+
+        match self.config.export_profile(&path) {
+            Ok(_) => self.log(format!("📤 Profile '{}' exported to {}", self.config.profile, path.display()), LogLevel::Success),
+            Err(e) => self.log(format!("Failed to export the profile: {}", e), LogLevel::Error),
+        }
+    }
+
+    fn import_profile(&mut self, ctx: &egui::Context) {
+        let Some(path) = rfd::FileDialog::new().add_filter("JSON", &["json"]).pick_file() else {
+            return;
+        };
+
+        if let Err(e) = self.config.save() {
+            self.log(format!("Failed to save profile '{}': {}", self.config.profile, e), LogLevel::Error);
+        }
+        match AppConfig::import_profile(&path) {
+            Ok(mut config) => {
+                config.apply_overrides(self.config.overrides());
+                self.use_profile(ctx, config);
+                self.log("📥 Profile imported, enter the password to use it".to_string(), LogLevel::Success);
+            }
+            Err(e) => self.log(format!("Failed to import the profile: {}", e), LogLevel::Error),
+        }
+    }
+
+    /// Put a loaded or new profile in place of the current config
+    fn use_profile(&mut self, ctx: &egui::Context, config: AppConfig) {
+        self.config = config;
+        self.password_buffer = self.config.password().to_string();
+        self.user_agent_buffer = self.config.user_agent.clone().unwrap_or_default();
+        self.filename_template_buffer = self.config.filename_template.clone();
+        self.extraction_history = self.config.extraction_history.clone();
+        self.tags_color = self
+            .config
+            .tag_colors
+            .iter()
+            .map(|(tag, [r, g, b])| (tag.clone(), egui::Color32::from_rgb(*r, *g, *b)))
+            .collect();
+        self.table_view.set_columns(&self.config.columns);
+        i18n::set_language(&self.config.language);
+
+        if self.config.show_tray_icon != self.tray.is_some() {
+            self.tray = if self.config.show_tray_icon { create_tray(ctx) } else { None };
+        }
+
+        if let Err(e) = Profiles::new().and_then(|profiles| profiles.set_active(&self.config.profile)) {
+            self.log(format!("Failed to remember the profile: {}", e), LogLevel::Warning);
+        }
+        self.profile_names = Profiles::new().map(|profiles| profiles.list()).unwrap_or_default();
+        self.log(format!("Switched to profile '{}'", self.config.profile), LogLevel::Info);
+    }
+
+    fn create_selectors_file(&mut self) {
+        match Selectors::path(&self.config.profile) {
+            Ok(path) if path.exists() => {
+                self.log(format!("selectors.json already exists: {}", path.display()), LogLevel::Info);
+            }
+            Ok(_) => match Selectors::write_defaults(&self.config.profile) {
+                Ok(path) => self.log(format!("Default selectors written to {}", path.display()), LogLevel::Success),
+                Err(e) => self.log(format!("Failed to write selectors.json: {}", e), LogLevel::Error),
+            },
+            Err(e) => self.log(format!("Failed to locate selectors.json: {}", e), LogLevel::Error),
+        }
+    }
 
-        ui.vertical(|ui| {
-            // Resizable text area
-            let text_response = ui.add_sized(
-                [ui.available_width(), log_height],
-                egui::TextEdit::multiline(&mut self.log_text_buffer)
-                    .font(egui::TextStyle::Monospace)
-                    .desired_rows(10)
-                    .desired_width(f32::INFINITY)
-                    .interactive(true) // Allow text selection
-            );
+    /// Remember the window's size, position and maximized state for the next
+    /// start. A maximized or minimised window keeps its last normal geometry.
+    /// Move a window opened at the saved position back onto the monitor,
+    /// once the monitor size is known
+    fn keep_window_on_screen(&mut self, ctx: &egui::Context) {
+        if self.window_pos_checked {
+            return;
+        }
+        let (outer_rect, monitor_size) = ctx.input(|i| (i.viewport().outer_rect, i.viewport().monitor_size));
+        let (Some(outer_rect), Some(monitor_size)) = (outer_rect, monitor_size) else {
+            return;
+        };
 
-            // Handle resize drag
-            let resize_handle_rect = egui::Rect::from_min_size(
-                egui::pos2(ui.min_rect().left(), text_response.rect.bottom()),
-                egui::vec2(ui.available_width(), 8.0)
-            );
+        self.window_pos_checked = true;
+        if let Some([x, y]) = crate::config::clamp_window_pos([outer_rect.min.x, outer_rect.min.y], [monitor_size.x, monitor_size.y]) {
+            ctx.send_viewport_cmd(egui::ViewportCommand::OuterPosition(egui::pos2(x, y)));
+        }
+    }
 
-            let resize_response = ui.allocate_rect(resize_handle_rect, egui::Sense::drag());
-            if resize_response.dragged() {
-                self.log_panel_height = (self.log_panel_height + resize_response.drag_delta().y)
-                    .clamp(100.0, 600.0);
+    fn track_window_geometry(&mut self, ctx: &egui::Context) {
+        let ui_state = &mut self.config.ui_state;
+        ctx.input(|i| {
+            let viewport = i.viewport();
+            if viewport.minimized == Some(true) || self.hidden_to_tray {
+                return;
             }
 
-            // Visual resize handle
-            if resize_response.hovered() {
-                ui.ctx().set_cursor_icon(egui::CursorIcon::ResizeVertical);
+            ui_state.maximized = viewport.maximized == Some(true);
+            if ui_state.maximized || viewport.fullscreen == Some(true) {
+                return;
             }
+            if let Some(rect) = viewport.inner_rect {
+                ui_state.window_size = Some([rect.width(), rect.height()]);
+            }
+            if let Some(rect) = viewport.outer_rect {
+                ui_state.window_pos = Some([rect.min.x, rect.min.y]);
+            }
+        });
+    }
 
-            ui.painter().hline(
-                resize_handle_rect.x_range(),
-                resize_handle_rect.center().y,
-                egui::Stroke::new(2.0, if resize_response.hovered() {
-                    egui::Color32::WHITE
-                } else {
-                    egui::Color32::GRAY
-                })
-            );
-
-            // Auto-scroll to bottom if enabled
-            if self.log_auto_scroll && text_response.changed() {
-                text_response.scroll_to_me(Some(egui::Align::BOTTOM));
+    fn test_connection(&mut self) {
+        let url = self.config.base_url.trim().to_string();
+        self.log(format!("Testing connection to {}", url), LogLevel::Info);
+        let tx = self.background_tx.clone();
+
+        tokio::spawn(async move {
+            let result = async {
+                let client = reqwest::Client::builder()
+                    .timeout(std::time::Duration::from_secs(10))
+                    .build()?;
+                client.head(&url).send().await
             }
+            .await;
+
+            let update = match result {
+                Ok(response) if response.status().is_success() || response.status().is_redirection() => {
+                    ProgressUpdate::Log(format!("✅ {} answered with HTTP {}", url, response.status()), LogLevel::Success)
+                }
+                Ok(response) => {
+                    ProgressUpdate::Log(format!("⚠️ {} answered with HTTP {}", url, response.status()), LogLevel::Warning)
+                }
+                Err(e) => ProgressUpdate::Log(format!("❌ Could not reach {}: {}", url, e), LogLevel::Error),
+            };
+            let _ = tx.send(update);
         });
+    }
 
-        // Keyboard shortcuts info
-        if ui.input(|i| i.key_pressed(egui::Key::F1)) {
-            self.log("Keyboard shortcuts: Ctrl+A (Select All), Ctrl+C (Copy Selected), F1 (Help)".to_string(), LogLevel::Info);
-        }
+    fn test_webhook(&mut self) {
+        let Some(url) = self.config.webhook_url.clone() else {
+            return;
+        };
+
+        self.log(format!("Sending test payload to {}", url), LogLevel::Info);
+        let event = WebhookEvent::sample(&self.config.project_number);
+        let proxy = self.config.proxy.clone();
+        let tx = self.background_tx.clone();
+
+        tokio::spawn(async move {
+            let update = match notifications::send_webhook(&url, proxy.as_deref(), event).await {
+                Ok(_) => ProgressUpdate::Log("✅ Webhook test succeeded".to_string(), LogLevel::Success),
+                Err(e) => ProgressUpdate::Log(format!("❌ Webhook test failed: {:#}", e), LogLevel::Error),
+            };
+            let _ = tx.send(update);
+        });
     }
 
-    fn save_logs_to_file(&mut self) {
-        let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
-        let filename = format!("eview_scraper_logs_{}.txt", timestamp);
+    async fn notify_webhook(
+        webhook_url: Option<&str>,
+        proxy: Option<&str>,
+        event: WebhookEvent,
+        progress_tx: &mpsc::UnboundedSender<ProgressUpdate>,
+    ) {
+        let Some(url) = webhook_url else {
+            return;
+        };
 
-        match std::fs::write(&filename, &self.log_text_buffer) {
+        match notifications::send_webhook(url, proxy, event).await {
             Ok(_) => {
-                self.log(format!("Logs saved to {}", filename), LogLevel::Success);
+                let _ = progress_tx.send(ProgressUpdate::Log(
+                    "🔔 Webhook notification sent".to_string(),
+                    LogLevel::Info,
+                ));
             }
             Err(e) => {
-                self.log(format!("Failed to save logs: {}", e), LogLevel::Error);
+                let _ = progress_tx.send(ProgressUpdate::Log(
+                    format!("⚠️ Webhook notification failed: {:#}", e),
+                    LogLevel::Warning,
+                ));
             }
         }
     }
 
-    fn render_status_bar(&mut self, ui: &mut egui::Ui) {
-        ui.horizontal(|ui| {
-            ui.label(&self.status_message);
-
-            // Progress bar if extracting
-            if self.is_extracting {
-                ui.add(egui::ProgressBar::new(self.progress)
-                    .desired_width(200.0)
-                    .animate(true));
-            }
-
-            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                // Add small right margin to prevent text cutoff
-                ui.add_space(10.0);
-                ui.label(format!(
-                    "v{} | {} entries loaded",
-                    env!("CARGO_PKG_VERSION"),
-                    self.plc_table.entries.len()
-                ));
-            });
-        });
-    }
-
     fn start_extraction(&mut self) {
         // Validate config
         let errors = self.config.validate();
@@ -1048,14 +3748,12 @@ impl EviewApp {
             return;
         }
 
-        // Cancel any previous extraction task
-        if let Some(handle) = self.extraction_handle.take() {
-            handle.abort();
-        }
+        // Cancel any previous extraction task, it closes its browser in the background
+        self.cancel_extraction();
         self.progress_rx = None;
 
         self.is_extracting = true;
-        self.status_message = "Starting extraction...".to_string();
+        self.status_message = t!("Starting extraction...");
         self.progress = 0.0;
         self.app_status = AppStatus::Connecting;
         self.log("Starting EPLAN eVIEW extraction".to_string(), LogLevel::Info);
@@ -1067,20 +3765,38 @@ impl EviewApp {
         // Clone config and chromedriver manager for the async task
         let config = self.config.clone();
         let chromedriver_manager = self.chromedriver_manager.clone();
+        let cancel = CancellationToken::new();
+        self.extraction_cancel = Some(cancel.clone());
 
         // Spawn async extraction task - simplified without panic handling
         let handle = tokio::spawn(async move {
-            Self::run_extraction_async(config, chromedriver_manager, progress_tx).await
+            Self::run_extraction_async(config, chromedriver_manager, progress_tx, cancel).await
         });
 
         self.extraction_handle = Some(handle);
     }
 
+    /// Ask the running extraction to stop. The task is not aborted, it returns
+    /// at the next step and quits its browser on its own.
+    fn cancel_extraction(&mut self) {
+        if let Some(cancel) = self.extraction_cancel.take() {
+            cancel.cancel();
+        }
+        if let Some(handle) = self.extraction_handle.take() {
+            self.stopping_extraction = Some(handle);
+        }
+    }
+
     async fn run_extraction_async(
         config: AppConfig,
         chromedriver_manager: Arc<ChromeDriverManager>,
         progress_tx: mpsc::UnboundedSender<ProgressUpdate>,
+        cancel: CancellationToken,
     ) {
+        let started = std::time::Instant::now();
+        let started_at = chrono::Local::now();
+        let webhook_url = config.webhook_url.clone();
+
         let _ = progress_tx.send(ProgressUpdate::StatusChange(AppStatus::Connecting));
         let _ = progress_tx.send(ProgressUpdate::Log(
             "🚀 Starting extraction process...".to_string(),
@@ -1118,12 +3834,41 @@ impl EviewApp {
             LogLevel::Info,
         ));
 
+        let selectors = match Selectors::load(&config.profile) {
+            Ok(selectors) => selectors,
+            Err(e) => {
+                let _ = progress_tx.send(ProgressUpdate::Log(
+                    format!("⚠️ Could not read selectors.json, using built-in selectors: {}", e),
+                    LogLevel::Warning,
+                ));
+                None
+            }
+        };
+
         let scraper_config = ScraperConfig {
-            base_url: "https://eview.eplan.com/".to_string(),
+            base_url: config.base_url.trim().to_string(),
+            organization: config.organization.trim().to_string(),
             username: config.email.clone(),
             password: config.password().to_string(),
             project_number: config.project_number.clone(),
             headless: config.headless_mode,
+            proxy: config.proxy.clone(),
+            user_agent: config.user_agent.as_deref().map(|ua| ua.trim().to_string()),
+            external_chromedriver_url: config.external_chromedriver_url.as_deref().map(|url| url.trim().to_string()),
+            window_width: config.browser_width,
+            window_height: config.browser_height,
+            login_attempts: config.login_attempts.max(1),
+            attempt_interval_ms: config.login_attempt_interval_ms,
+            page_category_keywords: config.active_page_keywords(),
+            selectors: selectors.unwrap_or_default(),
+            dry_run: config.dry_run,
+            native_login: config.native_login,
+            session_cookies: if config.reuse_session {
+                Session::cookies_path(&config.profile, &config.base_url, &config.email).ok()
+            } else {
+                None
+            },
+            wait_timeouts: config.wait_timeouts,
         };
 
         let debug_mode = config.debug_mode;
@@ -1135,14 +3880,23 @@ impl EviewApp {
 
         impl crate::scraper::Logger for UiLogger {
             fn log(&self, message: String, level: crate::scraper::LogLevel) {
-                let ui_level = match level {
-                    crate::scraper::LogLevel::Info => LogLevel::Info,
-                    crate::scraper::LogLevel::Warning => LogLevel::Warning,
-                    crate::scraper::LogLevel::Error => LogLevel::Error,
-                    crate::scraper::LogLevel::Success => LogLevel::Success,
-                    crate::scraper::LogLevel::Debug => LogLevel::Info,
-                };
-                let _ = self.tx.send(ProgressUpdate::Log(message, ui_level));
+                let _ = self.tx.send(ProgressUpdate::Log(message, level.into()));
+            }
+
+            fn progress(&self, fraction: f32) {
+                let _ = self.tx.send(ProgressUpdate::Progress(fraction));
+            }
+
+            fn status(&self, text: String) {
+                let _ = self.tx.send(ProgressUpdate::Status(text));
+            }
+
+            fn browser_console(&self, message: String, level: crate::scraper::LogLevel) {
+                let _ = self.tx.send(ProgressUpdate::BrowserConsole(message, level.into()));
+            }
+
+            fn log_in_phase(&self, message: String, level: crate::scraper::LogLevel, phase: crate::scraper::LogPhase) {
+                let _ = self.tx.send(ProgressUpdate::PhaseLog(message, level.into(), phase.to_string()));
             }
         }
 
@@ -1158,7 +3912,7 @@ impl EviewApp {
         let scraper_result = match ScraperEngine::new(scraper_config, logger, chromedriver_manager).await {
             Ok(scraper) => {
                 let _ = progress_tx.send(ProgressUpdate::Progress(0.3));
-                let _ = progress_tx.send(ProgressUpdate::Status("🌐 Browser connected successfully".to_string()));
+                let _ = progress_tx.send(ProgressUpdate::Status(t!("🌐 Browser connected successfully")));
                 let _ = progress_tx.send(ProgressUpdate::Log(
                     "✅ Scraper engine created successfully".to_string(),
                     LogLevel::Success,
@@ -1166,7 +3920,19 @@ impl EviewApp {
                 Ok(scraper)
             }
             Err(e) => {
+                let _ = progress_tx.send(ProgressUpdate::History(ExtractionHistoryEntry::failed(
+                    &config.project_number,
+                    started_at,
+                    started.elapsed(),
+                    e.to_string(),
+                )));
                 let _ = progress_tx.send(ProgressUpdate::Error(format!("❌ Failed to initialize scraper: {}", e)));
+                Self::notify_webhook(
+                    webhook_url.as_deref(),
+                    config.proxy.as_deref(),
+                    WebhookEvent::failed(&config.project_number, e.to_string(), started.elapsed()),
+                    &progress_tx,
+                ).await;
                 let _ = progress_tx.send(ProgressUpdate::Log(
                     format!("❌ Scraper initialization failed: {}", e),
                     LogLevel::Error,
@@ -1191,23 +3957,76 @@ impl EviewApp {
                 LogLevel::Info,
             ));
 
+            scraper.set_cancellation_token(cancel.clone());
+            let extraction = scraper.run_extraction().await;
+
+            // Stopped by the user: nobody listens anymore, just release the browser
+            if cancel.is_cancelled() {
+                scraper.close_cancelled().await;
+                return;
+            }
+
             // Wrap extraction in detailed error handling
-            let extraction_result = match scraper.run_extraction().await {
+            let extraction_result = match extraction {
+                Ok(_) if config.dry_run => {
+                    let _ = progress_tx.send(ProgressUpdate::StatusChange(AppStatus::Completed));
+                    let _ = progress_tx.send(ProgressUpdate::DryRunComplete);
+                    Ok(())
+                }
                 Ok(table) => {
                     let _ = progress_tx.send(ProgressUpdate::StatusChange(AppStatus::Processing));
                     let _ = progress_tx.send(ProgressUpdate::Progress(1.0));
-                    let _ = progress_tx.send(ProgressUpdate::Status("🎉 Extraction complete!".to_string()));
+                    let _ = progress_tx.send(ProgressUpdate::Status(t!("🎉 Extraction complete!")));
                     let _ = progress_tx.send(ProgressUpdate::Log(
                         format!("✅ Extraction completed! Found {} entries", table.entries.len()),
                         LogLevel::Success,
                     ));
-                    let _ = progress_tx.send(ProgressUpdate::Complete(table));
+                    let entry_count = table.entries.len();
+                    let mut history_entry = ExtractionHistoryEntry::succeeded(
+                        &config.project_number,
+                        started_at,
+                        started.elapsed(),
+                        entry_count,
+                    );
+                    history_entry.session_path = Self::save_history_session(&table, started_at, &progress_tx);
+                    let _ = progress_tx.send(ProgressUpdate::History(history_entry));
+                    let _ = progress_tx.send(ProgressUpdate::Complete(table.clone()));
+                    if config.auto_export {
+                        match Self::auto_export_folder(&config) {
+                            Some(dir) => {
+                                // File writing stays off the async workers
+                                let (config, tx) = (config.clone(), progress_tx.clone());
+                                let _ = tokio::task::spawn_blocking(move || Self::auto_export(&config, table, &dir, &tx)).await;
+                            }
+                            None => {
+                                let _ = progress_tx.send(ProgressUpdate::AutoExportNeedsFolder(table));
+                            }
+                        }
+                    }
+                    Self::notify_webhook(
+                        webhook_url.as_deref(),
+                        config.proxy.as_deref(),
+                        WebhookEvent::completed(&config.project_number, entry_count, started.elapsed()),
+                        &progress_tx,
+                    ).await;
                     Ok(())
                 }
                 Err(e) => {
                     // More detailed error analysis
                     let error_msg = format!("{}", e);
+                    let _ = progress_tx.send(ProgressUpdate::History(ExtractionHistoryEntry::failed(
+                        &config.project_number,
+                        started_at,
+                        started.elapsed(),
+                        error_msg.clone(),
+                    )));
                     let _ = progress_tx.send(ProgressUpdate::Error(format!("❌ Extraction failed: {}", error_msg)));
+                    Self::notify_webhook(
+                        webhook_url.as_deref(),
+                        config.proxy.as_deref(),
+                        WebhookEvent::failed(&config.project_number, error_msg.clone(), started.elapsed()),
+                        &progress_tx,
+                    ).await;
 
                     // Provide specific troubleshooting based on error type
                     if error_msg.contains("Microsoft login") || error_msg.contains("login") {
@@ -1292,20 +4111,127 @@ impl EviewApp {
         ));
     }
 
+    /// Keep a copy of the extracted table so the run can be reopened from the history
+    fn save_history_session(
+        table: &PlcTable,
+        started_at: chrono::DateTime<chrono::Local>,
+        progress_tx: &mpsc::UnboundedSender<ProgressUpdate>,
+    ) -> Option<std::path::PathBuf> {
+        let result = Session::history_path(&table.project_name, started_at)
+            .and_then(|path| Session::from_table(table).save_to(&path).map(|_| path));
+
+        match result {
+            Ok(path) => Some(path),
+            Err(e) => {
+                let _ = progress_tx.send(ProgressUpdate::Log(
+                    format!("⚠️ Could not save the table for the extraction history: {}", e),
+                    LogLevel::Warning,
+                ));
+                None
+            }
+        }
+    }
+
+    /// Folder auto-export writes to, the last export folder if none is set
+    fn auto_export_folder(config: &AppConfig) -> Option<PathBuf> {
+        config.auto_export_dir.clone().or_else(|| config.last_export_path.as_ref().map(PathBuf::from))
+    }
+
+    /// Write every enabled export format to `dir`
+    fn auto_export(config: &AppConfig, mut table: PlcTable, dir: &std::path::Path, progress_tx: &mpsc::UnboundedSender<ProgressUpdate>) {
+        if let Err(e) = std::fs::create_dir_all(dir) {
+            let _ = progress_tx.send(ProgressUpdate::Log(
+                format!("❌ Auto-export skipped, cannot create {}: {}", dir.display(), e),
+                LogLevel::Error,
+            ));
+            return;
+        }
+
+        if config.normalize_names_on_export {
+            table.normalize_names(&config.name_normalization);
+        }
+
+        let formats = [
+            (config.export_excel, ExportFormat::Excel(config.excel, config.columns)),
+            (config.export_csv, ExportFormat::Csv(config.columns, config.csv_delimiter)),
+            (config.export_json, ExportFormat::Json(config.json_layout)),
+            (config.export_twincat, ExportFormat::TwinCat),
+            (config.export_step7, ExportFormat::Step7(config.step7_symbol_format)),
+            (config.export_markdown, ExportFormat::Markdown(config.report)),
+            (config.export_html, ExportFormat::Html(config.report)),
+            (config.export_opcua, ExportFormat::OpcUa),
+        ];
+        let now = chrono::Local::now();
+        let mut written = Vec::new();
+
+        for format in formats.into_iter().filter_map(|(enabled, format)| enabled.then_some(format)) {
+            let path = export::available_path(
+                dir.join(export::export_file_name(
+                    &config.filename_template,
+                    &table.project_name,
+                    format,
+                    table.entries.len(),
+                    now,
+                )),
+                config.auto_export_existing,
+            );
+            let exporter = format.exporter();
+            match exporter.export(&table, &path.to_string_lossy()) {
+                Ok(_) => {
+                    for warning in exporter.warnings(&table) {
+                        let _ = progress_tx.send(ProgressUpdate::Log(
+                            format!("⚠️ Auto-export: {}: {}", format.name(), warning),
+                            LogLevel::Warning,
+                        ));
+                    }
+                    let _ = progress_tx.send(ProgressUpdate::Log(
+                        format!("💾 Auto-export: {} saved to {}", format.name(), path.display()),
+                        LogLevel::Success,
+                    ));
+                    written.push(path);
+                }
+                Err(e) => {
+                    let _ = progress_tx.send(ProgressUpdate::Log(
+                        format!("❌ Auto-export: {} export failed: {}", format.name(), e),
+                        LogLevel::Error,
+                    ));
+                }
+            }
+        }
+
+        if !written.is_empty() {
+            let _ = progress_tx.send(ProgressUpdate::AutoExportComplete(written));
+        }
+    }
+
+    /// Ask for the auto-export folder when none is configured, the choice is
+    /// saved so this happens once, then export `table` there
+    fn choose_auto_export_folder(&mut self, table: PlcTable) {
+        let Some(dir) = rfd::FileDialog::new().set_title(t!("Choose the auto-export folder")).pick_folder() else {
+            self.log("Auto-export skipped, no output folder chosen".to_string(), LogLevel::Warning);
+            return;
+        };
+
+        self.config.auto_export_dir = Some(dir.clone());
+        let _ = self.config.save();
+
+        let config = self.config.clone();
+        let tx = self.background_tx.clone();
+        tokio::task::spawn_blocking(move || Self::auto_export(&config, table, &dir, &tx));
+    }
+
     fn stop_extraction(&mut self) {
         // Cancel the extraction task if running
-        if let Some(handle) = self.extraction_handle.take() {
-            handle.abort();
-        }
+        self.cancel_extraction();
 
         self.is_extracting = false;
-        self.status_message = "Extraction stopped".to_string();
+        self.status_message = t!("Extraction stopped");
         self.progress = 0.0;
         self.progress_rx = None;
         self.log("Extraction stopped by user".to_string(), LogLevel::Warning);
     }
 
-    fn process_progress_updates(&mut self) {
+    fn process_progress_updates(&mut self, ctx: &egui::Context) {
         let mut updates_to_process = Vec::new();
 
         // Collect all updates first
@@ -1314,6 +4240,9 @@ impl EviewApp {
                 updates_to_process.push(update);
             }
         }
+        while let Ok(update) = self.background_rx.try_recv() {
+            updates_to_process.push(update);
+        }
 
         // Process all collected updates
         for update in updates_to_process {
@@ -1321,27 +4250,60 @@ impl EviewApp {
                 ProgressUpdate::Log(message, level) => {
                     self.log(message, level);
                 }
+                ProgressUpdate::PhaseLog(message, level, phase) => {
+                    self.push_log(message, level, Some(phase), false);
+                }
+                ProgressUpdate::BrowserConsole(message, level) => {
+                    self.push_log(message, level, None, true);
+                }
                 ProgressUpdate::Progress(progress) => {
                     self.progress = progress;
                 }
                 ProgressUpdate::Status(status) => {
                     self.status_message = status;
                 }
+                ProgressUpdate::History(entry) => self.record_history(entry),
                 ProgressUpdate::Complete(table) => {
-                    self.plc_table = table;
+                    // The receiver stays until the task ends, auto-export and
+                    // the webhook still report after Complete
                     self.is_extracting = false;
-                    self.progress_rx = None;
                     self.extraction_handle = None;
-                    self.status_message = format!("Extraction complete - {} entries loaded", self.plc_table.entries.len());
+                    self.extraction_cancel = None;
+                    self.status_message = t!("Extraction complete - {} entries loaded", table.entries.len());
+
+                    // Edited results are not replaced without asking
+                    if self.plc_table.dirty && !self.plc_table.entries.is_empty() {
+                        self.pending_merge = Some(table);
+                    } else {
+                        self.load_extraction(table, true);
+                    }
                     self.progress = 0.0;
                     self.app_status = AppStatus::Completed;
                 }
+                ProgressUpdate::AutoExportComplete(paths) => {
+                    // An extraction waiting for the merge prompt is on disk, the edited table is not
+                    if self.pending_merge.is_none() {
+                        self.extraction_exported = true;
+                    }
+                    let folder = paths[0].parent().map(|dir| dir.display().to_string()).unwrap_or_default();
+                    self.toast_queue.push(Toast::success(t!("Auto-exported {} files to {}", paths.len(), folder)));
+                }
+                ProgressUpdate::AutoExportNeedsFolder(table) => self.choose_auto_export_folder(table),
+                ProgressUpdate::ExportFinished { path, ok } => self.finish_export(ctx, path, ok),
+                ProgressUpdate::DryRunComplete => {
+                    self.is_extracting = false;
+                    self.extraction_handle = None;
+                    self.extraction_cancel = None;
+                    self.status_message = t!("Dry run complete - the results were not changed");
+                    self.progress = 0.0;
+                }
                 ProgressUpdate::Error(error) => {
                     self.log(format!("💥 Error: {}", error), LogLevel::Error);
                     self.is_extracting = false;
                     self.progress_rx = None;
                     self.extraction_handle = None;
-                    self.status_message = "❌ Extraction failed - check log for details".to_string();
+                    self.extraction_cancel = None;
+                    self.status_message = t!("❌ Extraction failed - check log for details");
                     self.progress = 0.0;
                     self.app_status = AppStatus::Error(error);
                     // Keep GUI open and responsive for user to see errors and retry
@@ -1382,9 +4344,7 @@ impl EviewApp {
         if input.key_pressed(egui::Key::Escape) {
             if self.is_extracting {
                 // Cancel extraction
-                if let Some(handle) = self.extraction_handle.take() {
-                    handle.abort();
-                }
+                self.cancel_extraction();
                 self.is_extracting = false;
                 self.progress_rx = None;
                 self.app_status = AppStatus::Ready;
@@ -1406,36 +4366,58 @@ impl EviewApp {
 
 impl eframe::App for EviewApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        // Ask before closing the window during an extraction or with an unexported one
+        if ctx.input(|i| i.viewport().close_requested()) {
+            if self.is_extracting {
+                ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+                self.show_close_extraction_dialog = true;
+            } else if exit_needs_confirmation(!self.plc_table.entries.is_empty(), self.extraction_exported, self.exit_confirmed) {
+                ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+                self.show_exit_dialog = true;
+            }
+        }
+
         // Handle keyboard shortcuts
         self.handle_keyboard_shortcuts(ctx);
 
+        self.handle_tray(ctx);
+        self.finish_closing(ctx);
+
         // Process progress updates from async extraction
-        self.process_progress_updates();
+        self.process_progress_updates(ctx);
 
         // Request repaint if extracting to ensure UI updates
         if self.is_extracting {
             ctx.request_repaint();
         }
 
-        // Apply professional theme (light or dark)
-        self.apply_professional_theme(ctx);
+        // Restyle only when the theme setting changed
+        if self.applied_theme != self.config.theme {
+            self.palette = themes::apply_theme(ctx, &self.config.theme);
+            self.applied_theme = self.config.theme.clone();
+        }
+
+        // Rescale text when the font size setting changed
+        if self.applied_font_size != Some(self.config.ui_font_size) {
+            fonts::apply_font_sizes(ctx, self.config.ui_font_size);
+            self.applied_font_size = Some(self.config.ui_font_size);
+        }
+        if self.applied_ui_scale != Some(self.config.ui_scale) {
+            fonts::apply_ui_scale(ctx, self.config.ui_scale);
+            self.applied_ui_scale = Some(self.config.ui_scale);
+        }
 
-        // Get theme-based colors
-        let (toolbar_bg, tab_bg, _content_bg) = self.get_panel_colors();
-        let border_color = self.get_border_color();
+        let palette = self.palette;
 
         // Top toolbar with theme-based styling
         egui::TopBottomPanel::top("toolbar")
             .frame(egui::Frame {
-                fill: toolbar_bg,
+                fill: palette.toolbar,
                 shadow: egui::epaint::Shadow {
                     offset: egui::Vec2::new(0.0, 2.0),
                     blur: 8.0,
                     spread: 0.0,
-                    color: match self.config.theme {
-                        crate::config::Theme::Dark => egui::Color32::from_black_alpha(80),
-                        crate::config::Theme::Light => egui::Color32::from_black_alpha(20),
-                    },
+                    color: palette.shadow,
                 },
                 ..Default::default()
             })
@@ -1448,8 +4430,8 @@ impl eframe::App for EviewApp {
         // Tab bar with theme-based styling
         egui::TopBottomPanel::top("tab_bar")
             .frame(egui::Frame {
-                fill: tab_bg,
-                stroke: egui::Stroke::new(1.0, border_color),
+                fill: palette.tab_bar,
+                stroke: egui::Stroke::new(1.0, palette.border),
                 ..Default::default()
             })
             .show(ctx, |ui| {
@@ -1458,11 +4440,20 @@ impl eframe::App for EviewApp {
                 ui.add_space(4.0);
             });
 
+        self.render_session_banner(ctx);
+        self.render_merge_dialog(ctx);
+        self.render_json_import_dialog(ctx);
+        self.render_combine_dialog(ctx);
+        self.render_close_extraction_dialog(ctx);
+        self.render_exit_dialog(ctx);
+        self.render_bulk_edit_dialog(ctx);
+        self.render_export_preview(ctx);
+
         // Status bar with theme-based styling
         egui::TopBottomPanel::bottom("status_bar")
             .frame(egui::Frame {
-                fill: toolbar_bg,
-                stroke: egui::Stroke::new(1.0, border_color),
+                fill: palette.panel,
+                stroke: egui::Stroke::new(1.0, palette.border),
                 ..Default::default()
             })
             .show(ctx, |ui| {
@@ -1476,9 +4467,298 @@ impl eframe::App for EviewApp {
             AppTab::Main => self.render_main_tab(ctx),
             AppTab::Logs => self.render_logs_tab(ctx),
             AppTab::Results => self.render_results_tab(ctx),
+            AppTab::Diff => self.render_diff_tab(ctx),
+            AppTab::Statistics => self.render_statistics_tab(ctx),
             AppTab::Settings => self.render_settings_tab(ctx),
         }
 
+        self.toast_queue.render(ctx);
+        self.keep_window_on_screen(ctx);
+        self.track_window_geometry(ctx);
+
         // All UI is now handled through tabs - no separate dialogs needed
     }
+
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        // Let a running export finish its file instead of leaving it half written
+        if let Some(export) = &self.running_export {
+            let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+            while !export.handle.is_finished() && std::time::Instant::now() < deadline {
+                std::thread::sleep(std::time::Duration::from_millis(50));
+            }
+        }
+
+        if self.config.auto_save_session && !self.plc_table.entries.is_empty() {
+            if let Err(e) = Session::from_table(&self.plc_table).save() {
+                eprintln!("Failed to save session on exit: {}", e);
+            }
+        }
+
+        let (sort_column, sort_ascending) = self.table_view.sort();
+        let ui_state = &mut self.config.ui_state;
+        ui_state.tab = self.current_tab.clone();
+        ui_state.log_panel_height = self.log_panel_height;
+        ui_state.sort_column = sort_column;
+        ui_state.sort_ascending = sort_ascending;
+        ui_state.show_timestamps = self.show_timestamps;
+        ui_state.log_filter_level = self.log_filter_level.clone();
+        if let Err(e) = self.config.save() {
+            eprintln!("Failed to save the window layout on exit: {}", e);
+        }
+    }
+}
+
+/// Tray icon for the `show_tray_icon` setting, `None` if the system has no tray
+fn create_tray(ctx: &egui::Context) -> Option<Tray> {
+    match Tray::new(ctx) {
+        Ok(tray) => Some(tray),
+        Err(e) => {
+            eprintln!("Warning: Failed to create the tray icon: {}", e);
+            None
+        }
+    }
+}
+
+/// Show `dir` in the file manager
+fn open_folder(dir: &std::path::Path) -> std::io::Result<()> {
+    let program = if cfg!(windows) {
+        "explorer"
+    } else if cfg!(target_os = "macos") {
+        "open"
+    } else {
+        "xdg-open"
+    };
+    std::process::Command::new(program).arg(dir).spawn().map(|_| ())
+}
+
+/// Icon and message of a log line in its level color, with the search matches highlighted
+fn log_line_layout(ui: &egui::Ui, entry: &LogEntry, show_timestamp: bool, matcher: Option<&LogMatcher>, highlight: egui::Color32) -> egui::text::LayoutJob {
+    let format = egui::TextFormat {
+        font_id: egui::TextStyle::Monospace.resolve(ui.style()),
+        color: entry.level.color(),
+        ..Default::default()
+    };
+    let matched = egui::TextFormat { background: highlight, ..format.clone() };
+
+    let mut job = egui::text::LayoutJob::default();
+    if show_timestamp {
+        let weak = egui::TextFormat { color: ui.visuals().weak_text_color(), ..format.clone() };
+        job.append(&format!("[{}] ", entry.timestamp.format("%H:%M:%S")), 0.0, weak);
+    }
+    job.append(&format!("{} ", entry.level.icon()), 0.0, format.clone());
+
+    let (text, more_lines) = first_line(&entry.message);
+    let mut last = 0;
+    let matches = matcher.map(|matcher| matcher.find(text)).unwrap_or_default();
+    for range in matches {
+        job.append(&text[last..range.start], 0.0, format.clone());
+        job.append(&text[range.clone()], 0.0, matched.clone());
+        last = range.end;
+    }
+    job.append(&text[last..], 0.0, format.clone());
+    if more_lines {
+        job.append(" …", 0.0, format);
+    }
+    job
+}
+
+/// First line of a log message and whether more follow, so every entry
+/// takes one row of the log panel
+fn first_line(message: &str) -> (&str, bool) {
+    match message.split_once('\n') {
+        Some((first, _)) => (first.trim_end_matches('\r'), true),
+        None => (message, false),
+    }
+}
+
+/// Statistics of `table`, counted again when its revision changed
+fn cached_statistics<'a>(cache: &'a mut Option<(u64, TableStatistics)>, table: &PlcTable) -> &'a TableStatistics {
+    if !matches!(cache, Some((revision, _)) if *revision == table.revision) {
+        *cache = None;
+    }
+    let (_, stats) = cache.get_or_insert_with(|| (table.revision, table.statistics()));
+    stats
+}
+
+/// Closing the window needs a confirmation while an extraction has not been
+/// exported and the user hasn't already chosen to exit
+fn exit_needs_confirmation(has_entries: bool, exported: bool, exit_confirmed: bool) -> bool {
+    has_entries && !exported && !exit_confirmed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exit_confirmation_flag() {
+        assert!(exit_needs_confirmation(true, false, false));
+
+        assert!(!exit_needs_confirmation(false, false, false)); // Nothing extracted
+        assert!(!exit_needs_confirmation(true, true, false)); // Exported
+        assert!(!exit_needs_confirmation(true, false, true)); // "Exit without saving" chosen
+    }
+
+    #[test]
+    fn test_multi_line_log_messages_take_one_row() {
+        assert_eq!(first_line("Login successful!"), ("Login successful!", false));
+        assert_eq!(first_line("Stack trace:\r\n  at main.js:12"), ("Stack trace:", true));
+    }
+
+    #[test]
+    fn test_statistics_are_counted_per_revision() {
+        let mut table = PlcTable::new("Stats".to_string());
+        table.add_entry(PlcEntry::new("I0.0".to_string(), "Start".to_string(), "1".to_string()));
+        let mut cache = None;
+        assert_eq!(cached_statistics(&mut cache, &table).total, 1);
+
+        // Same revision, the cached count
+        table.entries.push(PlcEntry::new("I0.1".to_string(), "Stop".to_string(), "1".to_string()));
+        assert_eq!(cached_statistics(&mut cache, &table).total, 1);
+
+        table.mark_changed();
+        assert_eq!(cached_statistics(&mut cache, &table).total, 2);
+    }
+
+    #[test]
+    fn test_scraper_debug_logs_stay_debug() {
+        assert_eq!(LogLevel::from(crate::scraper::LogLevel::Debug), LogLevel::Debug);
+        assert_eq!(LogLevel::from(crate::scraper::LogLevel::Success), LogLevel::Success);
+
+        // The default Info filter hides them, the Debug filter shows everything
+        assert!(!LogLevel::Info.shows(&LogLevel::Debug));
+        assert!(LogLevel::Info.shows(&LogLevel::Info));
+        assert!(LogLevel::Debug.shows(&LogLevel::Debug));
+        assert!(LogLevel::Debug.shows(&LogLevel::Error));
+        assert!(!LogLevel::Warning.shows(&LogLevel::Debug));
+        assert!(!LogLevel::Error.shows(&LogLevel::Warning));
+    }
+
+    #[test]
+    fn test_log_entry_line() {
+        use chrono::TimeZone;
+        let entry = LogEntry {
+            timestamp: chrono::Local.with_ymd_and_hms(2024, 3, 5, 14, 7, 9).unwrap(),
+            message: "Export complete".to_string(),
+            level: LogLevel::Success,
+            phase: None,
+            browser_console: false,
+            id: 0,
+        };
+
+        assert_eq!(entry.line(true), "[14:07:09] ✅ Export complete");
+        assert_eq!(entry.line(false), "✅ Export complete");
+    }
+
+    #[test]
+    fn test_log_entries_as_json_lines() {
+        use chrono::TimeZone;
+        let entry = |message: &str, phase: Option<&str>| LogEntry {
+            timestamp: chrono::Local.with_ymd_and_hms(2024, 3, 5, 14, 7, 9).unwrap(),
+            message: message.to_string(),
+            level: LogLevel::Warning,
+            phase: phase.map(str::to_string),
+            browser_console: true,
+            id: 0,
+        };
+        let entries = vec![entry("Timeout \"page 3\"\nretrying", Some("page:3")), entry("Done", None)];
+        let mut config = AppConfig::default();
+        config.email = "user@example.com".to_string();
+
+        let text = log_json_lines(&entries, &config).unwrap();
+        let records: Vec<serde_json::Value> = text.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        assert_eq!(records.len(), 3);
+
+        assert_eq!(records[0]["type"], "header");
+        assert_eq!(records[0]["app_version"], env!("CARGO_PKG_VERSION"));
+        assert!(!text.contains("user@example.com"));
+
+        assert_eq!(records[1]["level"], "Warning");
+        assert_eq!(records[1]["message"], "Timeout \"page 3\"\nretrying");
+        assert_eq!(records[1]["phase"], "page:3");
+        assert!(records[1]["ts"].as_str().unwrap().starts_with("2024-03-05T14:07:09"));
+        assert!(records[1].get("browser_console").is_none());
+        assert!(records[2].get("phase").is_none());
+    }
+
+    #[test]
+    fn test_log_selection_clicks() {
+        let visible = [3, 4, 5, 6, 7];
+        let mut selection = LogSelection::default();
+
+        selection.click(4, &visible, false, false);
+        selection.click(6, &visible, false, true); // Shift: 4 to 6
+        assert_eq!(selection.ids.iter().copied().collect::<Vec<_>>(), [4, 5, 6]);
+
+        selection.click(5, &visible, true, false); // Ctrl: toggle off
+        assert_eq!(selection.ids.iter().copied().collect::<Vec<_>>(), [4, 6]);
+
+        selection.click(7, &visible, false, false);
+        assert_eq!(selection.ids.iter().copied().collect::<Vec<_>>(), [7]);
+
+        selection.click(3, &visible, false, true); // Backwards from the anchor
+        assert_eq!(selection.ids.len(), 5);
+    }
+
+    #[test]
+    fn test_browser_console_entries_are_capped() {
+        let entry = |browser_console| LogEntry {
+            timestamp: chrono::Local::now(),
+            message: String::new(),
+            level: LogLevel::Info,
+            phase: None,
+            browser_console,
+            id: 0,
+        };
+
+        let mut messages = vec![entry(false)];
+        for _ in 0..MAX_BROWSER_CONSOLE_ENTRIES + 10 {
+            push_capped(&mut messages, entry(true));
+        }
+        assert_eq!(messages.len(), MAX_BROWSER_CONSOLE_ENTRIES + 1);
+        assert!(!messages[0].browser_console); // Our own line survives
+
+        for _ in 0..MAX_LOG_ENTRIES {
+            push_capped(&mut messages, entry(false));
+        }
+        assert_eq!(messages.len(), MAX_LOG_ENTRIES);
+    }
+
+    #[test]
+    fn test_log_matcher_plain_and_regex() {
+        let plain = LogMatcher::new("  project ", false).unwrap().unwrap();
+        assert!(plain.is_match("Project 'A1' not found"));
+        assert_eq!(plain.find("Open project"), vec![5..12]);
+
+        let regex = LogMatcher::new(r"page #\d+", true).unwrap().unwrap();
+        assert!(regex.is_match("Failed to click PLC PAGE #12"));
+        assert_eq!(regex.find("page #3 and page #14"), vec![0..7, 12..20]);
+        assert!(regex.find("x").is_empty());
+
+        assert!(LogMatcher::new("", true).unwrap().is_none());
+        assert!(LogMatcher::new("page (", true).is_err());
+        assert!(LogMatcher::new("page (", false).is_ok());
+    }
+
+    #[test]
+    fn test_step_log_match_wraps_around() {
+        let matches = [3, 8, 20];
+        assert_eq!(step_log_match(&matches, None, true), Some(3));
+        assert_eq!(step_log_match(&matches, None, false), Some(20));
+        assert_eq!(step_log_match(&matches, Some(8), true), Some(20));
+        assert_eq!(step_log_match(&matches, Some(20), true), Some(3));
+        assert_eq!(step_log_match(&matches, Some(3), false), Some(20));
+
+        // The line jumped to was filtered out meanwhile
+        assert_eq!(step_log_match(&matches, Some(10), false), Some(8));
+        assert_eq!(step_log_match(&[], Some(10), true), None);
+    }
+
+    #[test]
+    fn test_find_matches_ignores_case() {
+        assert_eq!(find_matches("Timeout after Timeout", "timeout"), vec![0..7, 14..21]);
+        assert_eq!(find_matches("Prüfung PRÜFUNG", "prüfung"), vec![0..8, 9..17]);
+        assert!(find_matches("Export complete", "").is_empty());
+        assert!(find_matches("Export", "exports").is_empty());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,216 @@
+use crate::models::{BulkOperation, BulkTarget, PlcTable, TableFilter};
+use crate::t;
+use eframe::egui;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BulkAction {
+    SetComment,
+    AppendComment,
+    AddCommentPrefix,
+    ReplaceInComments,
+    AddPrefix,
+    AddSuffix,
+    ReplaceInNames,
+    SetPage,
+}
+
+impl BulkAction {
+    const ALL: [Self; 8] = [
+        Self::SetComment,
+        Self::AppendComment,
+        Self::AddCommentPrefix,
+        Self::ReplaceInComments,
+        Self::AddPrefix,
+        Self::AddSuffix,
+        Self::ReplaceInNames,
+        Self::SetPage,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::SetComment => "Set comment",
+            Self::AppendComment => "Append to comment",
+            Self::AddCommentPrefix => "Add prefix to comments",
+            Self::ReplaceInComments => "Find and replace in comments",
+            Self::AddPrefix => "Add prefix to names",
+            Self::AddSuffix => "Add suffix to names",
+            Self::ReplaceInNames => "Find and replace in names",
+            Self::SetPage => "Set page",
+        }
+    }
+
+    fn value_label(&self) -> &'static str {
+        match self {
+            Self::SetComment => "Comment:",
+            Self::AppendComment => "Text:",
+            Self::AddCommentPrefix | Self::AddPrefix => "Prefix:",
+            Self::AddSuffix => "Suffix:",
+            Self::ReplaceInComments | Self::ReplaceInNames => "Find:",
+            Self::SetPage => "Page:",
+        }
+    }
+
+    fn is_replace(&self) -> bool {
+        matches!(self, Self::ReplaceInComments | Self::ReplaceInNames)
+    }
+}
+
+/// Entries the dialog edits
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BulkScope {
+    Selected,
+    Filtered,
+}
+
+/// Popup for editing all selected or filtered entries at once
+pub struct BulkEditDialog {
+    pub open: bool,
+    action: BulkAction,
+    scope: BulkScope,
+    value: String,
+    replacement: String,
+    use_regex: bool,
+}
+
+impl BulkEditDialog {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            action: BulkAction::SetComment,
+            scope: BulkScope::Selected,
+            value: String::new(),
+            replacement: String::new(),
+            use_regex: false,
+        }
+    }
+
+    /// Open the dialog on the selection, or on the filtered rows when nothing is selected
+    pub fn open_for(&mut self, has_selection: bool) {
+        self.scope = if has_selection { BulkScope::Selected } else { BulkScope::Filtered };
+        self.open = true;
+    }
+
+    fn target<'a>(&self, filter: &'a TableFilter) -> BulkTarget<'a> {
+        match self.scope {
+            BulkScope::Selected => BulkTarget::Selected,
+            BulkScope::Filtered => BulkTarget::Filtered(filter),
+        }
+    }
+
+    fn operation(&self) -> BulkOperation {
+        let value = self.value.clone();
+        match self.action {
+            BulkAction::SetComment => BulkOperation::SetComment(value),
+            BulkAction::AppendComment => BulkOperation::AppendComment(value),
+            BulkAction::AddCommentPrefix => BulkOperation::AddCommentPrefix(value),
+            BulkAction::ReplaceInComments => BulkOperation::ReplaceInComments {
+                find: value,
+                replace: self.replacement.clone(),
+                regex: self.use_regex,
+            },
+            BulkAction::AddPrefix => BulkOperation::AddPrefix(value),
+            BulkAction::AddSuffix => BulkOperation::AddSuffix(value),
+            BulkAction::ReplaceInNames => BulkOperation::ReplaceInNames {
+                find: value,
+                replace: self.replacement.clone(),
+                regex: self.use_regex,
+            },
+            BulkAction::SetPage => BulkOperation::SetPage(value),
+        }
+    }
+
+    /// Draw the dialog, returns the operation and its target once the user
+    /// applies it. `filter` is the filter the table currently shows.
+    pub fn show<'a>(
+        &mut self,
+        ctx: &egui::Context,
+        table: &PlcTable,
+        filter: &'a TableFilter,
+    ) -> Option<(BulkOperation, BulkTarget<'a>)> {
+        if !self.open {
+            return None;
+        }
+
+        let mut open = true;
+        let mut close = false;
+        let mut applied = None;
+
+        egui::Window::new(t!("Bulk edit"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let selected = table.get_selected().len();
+                let filtered = table.entries.iter().filter(|entry| filter.matches(entry)).count();
+
+                ui.horizontal(|ui| {
+                    ui.radio_value(&mut self.scope, BulkScope::Selected, t!("{} entries selected", selected));
+                    ui.radio_value(&mut self.scope, BulkScope::Filtered, t!("{} filtered entries", filtered));
+                });
+                ui.separator();
+
+                egui::ComboBox::from_id_salt("bulk_edit_action")
+                    .selected_text(t!(self.action.name()))
+                    .show_ui(ui, |ui| {
+                        for action in BulkAction::ALL {
+                            ui.selectable_value(&mut self.action, action, t!(action.name()));
+                        }
+                    });
+
+                ui.add_space(4.0);
+
+                egui::Grid::new("bulk_edit_fields")
+                    .num_columns(2)
+                    .spacing([8.0, 6.0])
+                    .show(ui, |ui| {
+                        ui.label(t!(self.action.value_label()));
+                        ui.text_edit_singleline(&mut self.value);
+                        ui.end_row();
+
+                        if self.action.is_replace() {
+                            ui.label(t!("Replace with:"));
+                            ui.text_edit_singleline(&mut self.replacement);
+                            ui.end_row();
+                        }
+                    });
+
+                if self.action.is_replace() {
+                    ui.checkbox(&mut self.use_regex, t!("Regular expression"));
+                }
+
+                ui.add_space(8.0);
+
+                let operation = self.operation();
+                let target = self.target(filter);
+                let preview = table.preview_bulk_edit(&operation, target);
+                let changes = match &preview {
+                    Ok(count) => {
+                        ui.label(match self.scope {
+                            BulkScope::Selected => t!("Will change {} of {} selected entries", count, selected),
+                            BulkScope::Filtered => t!("Will change {} of {} filtered entries", count, filtered),
+                        });
+                        *count
+                    }
+                    Err(e) => {
+                        ui.colored_label(ui.visuals().error_fg_color, t!("Invalid pattern: {}", e));
+                        0
+                    }
+                };
+
+                ui.add_space(8.0);
+
+                ui.horizontal(|ui| {
+                    if ui.add_enabled(changes > 0, egui::Button::new(t!("Apply"))).clicked() {
+                        applied = Some((operation, target));
+                        close = true;
+                    }
+                    if ui.button(t!("Cancel")).clicked() {
+                        close = true;
+                    }
+                });
+            });
+
+        self.open = open && !close;
+        applied
+    }
+}
\ No newline at end of file
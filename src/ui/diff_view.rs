@@ -0,0 +1,141 @@
+use crate::models::{PlcEntry, TableDiff};
+use crate::t;
+use super::{fonts, themes};
+use egui_extras::{Column, TableBuilder};
+use eframe::egui;
+
+const ADDED_COLOR: egui::Color32 = egui::Color32::from_rgb(76, 175, 80);
+const REMOVED_COLOR: egui::Color32 = egui::Color32::from_rgb(244, 67, 54);
+const MODIFIED_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 193, 7);
+
+pub fn render(ui: &mut egui::Ui, diff: &TableDiff) {
+    ui.horizontal(|ui| {
+        ui.colored_label(ADDED_COLOR, t!("+{} added", diff.added.len()));
+        ui.separator();
+        ui.colored_label(REMOVED_COLOR, t!("−{} removed", diff.removed.len()));
+        ui.separator();
+        ui.colored_label(MODIFIED_COLOR, t!("~{} modified", diff.modified.len()));
+    });
+
+    ui.add_space(8.0);
+
+    if diff.is_empty() {
+        ui.label(t!("No differences found - both extractions are identical."));
+        return;
+    }
+
+    egui::ScrollArea::vertical().show(ui, |ui| {
+        egui::CollapsingHeader::new(
+            egui::RichText::new(t!("Added ({})", diff.added.len())).color(ADDED_COLOR),
+        )
+        .id_salt("diff_added")
+        .default_open(!diff.added.is_empty())
+        .show(ui, |ui| {
+            render_entries(ui, "diff_added_table", &diff.added, ADDED_COLOR);
+        });
+
+        egui::CollapsingHeader::new(
+            egui::RichText::new(t!("Removed ({})", diff.removed.len())).color(REMOVED_COLOR),
+        )
+        .id_salt("diff_removed")
+        .default_open(!diff.removed.is_empty())
+        .show(ui, |ui| {
+            render_entries(ui, "diff_removed_table", &diff.removed, REMOVED_COLOR);
+        });
+
+        egui::CollapsingHeader::new(
+            egui::RichText::new(t!("Modified ({})", diff.modified.len())).color(MODIFIED_COLOR),
+        )
+        .id_salt("diff_modified")
+        .default_open(!diff.modified.is_empty())
+        .show(ui, |ui| {
+            render_modified(ui, &diff.modified);
+        });
+    });
+}
+
+fn render_entries(ui: &mut egui::Ui, id: &str, entries: &[PlcEntry], color: egui::Color32) {
+    if entries.is_empty() {
+        ui.weak(t!("None"));
+        return;
+    }
+
+    let row_height = fonts::scaled(ui, 22.0);
+    TableBuilder::new(ui)
+        .id_salt(id)
+        .striped(true)
+        .vscroll(false)
+        .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+        .column(Column::initial(100.0).at_least(80.0))  // Address
+        .column(Column::initial(250.0).at_least(150.0)) // Symbol Name
+        .column(Column::initial(80.0).at_least(60.0))   // Type
+        .column(Column::remainder().at_least(200.0))    // Comment
+        .column(Column::initial(80.0).at_least(60.0))   // Page
+        .header(row_height, |mut header| {
+            header.col(|ui| { ui.strong(t!("Address")); });
+            header.col(|ui| { ui.strong(t!("Symbol Name")); });
+            header.col(|ui| { ui.strong(t!("Type")); });
+            header.col(|ui| { ui.strong(t!("Comment")); });
+            header.col(|ui| { ui.strong(t!("Page")); });
+        })
+        .body(|mut body| {
+            for entry in entries {
+                body.row(row_height, |mut row| {
+                    row.col(|ui| { ui.colored_label(color, &entry.address); });
+                    row.col(|ui| { ui.label(&entry.symbol_name); });
+                    row.col(|ui| { ui.colored_label(themes::data_type_color(&entry.data_type), entry.data_type.to_string()); });
+                    row.col(|ui| { ui.label(&entry.comment); });
+                    row.col(|ui| { ui.label(&entry.page); });
+                });
+            }
+        });
+}
+
+fn render_modified(ui: &mut egui::Ui, modified: &[(PlcEntry, PlcEntry)]) {
+    if modified.is_empty() {
+        ui.weak(t!("None"));
+        return;
+    }
+
+    // Highlight only the values that actually changed
+    let changed_text = |ui: &mut egui::Ui, text: &str, changed: bool, color: egui::Color32| {
+        if changed {
+            ui.colored_label(color, text);
+        } else {
+            ui.label(text);
+        }
+    };
+
+    let row_height = fonts::scaled(ui, 22.0);
+    TableBuilder::new(ui)
+        .id_salt("diff_modified_table")
+        .striped(true)
+        .vscroll(false)
+        .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+        .column(Column::initial(100.0).at_least(80.0))  // Address
+        .column(Column::initial(200.0).at_least(120.0)) // Old name
+        .column(Column::initial(200.0).at_least(120.0)) // New name
+        .column(Column::initial(200.0).at_least(120.0)) // Old comment
+        .column(Column::remainder().at_least(120.0))    // New comment
+        .header(row_height, |mut header| {
+            header.col(|ui| { ui.strong(t!("Address")); });
+            header.col(|ui| { ui.strong(t!("Old Symbol Name")); });
+            header.col(|ui| { ui.strong(t!("New Symbol Name")); });
+            header.col(|ui| { ui.strong(t!("Old Comment")); });
+            header.col(|ui| { ui.strong(t!("New Comment")); });
+        })
+        .body(|mut body| {
+            for (old, new) in modified {
+                let name_changed = old.symbol_name != new.symbol_name;
+                let comment_changed = old.comment != new.comment;
+
+                body.row(row_height, |mut row| {
+                    row.col(|ui| { ui.colored_label(MODIFIED_COLOR, &new.address); });
+                    row.col(|ui| { changed_text(ui, &old.symbol_name, name_changed, REMOVED_COLOR); });
+                    row.col(|ui| { changed_text(ui, &new.symbol_name, name_changed, ADDED_COLOR); });
+                    row.col(|ui| { changed_text(ui, &old.comment, comment_changed, REMOVED_COLOR); });
+                    row.col(|ui| { changed_text(ui, &new.comment, comment_changed, ADDED_COLOR); });
+                });
+            }
+        });
+}
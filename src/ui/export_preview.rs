@@ -0,0 +1,277 @@
+use crate::export::csv::CsvDelimiter;
+use crate::export::excel::ExcelOptions;
+use crate::export::json::JsonLayout;
+use crate::export::{ExportFilter, ExportFormat};
+use crate::models::{ColumnId, ColumnSet, PlcEntry, PlcTable};
+use crate::t;
+use crate::ui::themes;
+use eframe::egui;
+use std::path::{Path, PathBuf};
+
+/// Rows shown before the user confirms an export
+const PREVIEW_ROWS: usize = 10;
+
+/// Shows what an export will write before the file is saved, so a forgotten
+/// filter or the wrong format is noticed before the file is handed on
+pub struct ExportPreviewDialog {
+    pub visible: bool,
+    pub preview_rows: Vec<PlcEntry>,
+    pub format: ExportFormat,
+    pub proposed_path: String,
+    pub excel_options: ExcelOptions, // Used when switching to Excel
+    pub json_layout: JsonLayout,     // Used when switching to JSON
+    pub columns: ColumnSet,          // Used when switching to Excel or CSV
+    pub csv_delimiter: CsvDelimiter, // Used when switching to CSV
+    source: PlcTable,   // Entries of the chosen scope, before the options below
+    entries: usize,     // Entries the export will write
+    all_entries: usize, // Entries in the results table
+    pub selected_only: bool, // Also set with "Selected only" in the export bar
+    by_page: bool,
+    pages: String, // e.g. "3, 5-7"
+    by_prefix: bool,
+    address_prefix: String,
+}
+
+impl ExportPreviewDialog {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            preview_rows: Vec::new(),
+            format: ExportFormat::Excel(ExcelOptions::default(), ColumnSet::default()),
+            proposed_path: String::new(),
+            excel_options: ExcelOptions::default(),
+            json_layout: JsonLayout::default(),
+            columns: ColumnSet::default(),
+            csv_delimiter: CsvDelimiter::default(),
+            source: PlcTable::new(String::new()),
+            entries: 0,
+            all_entries: 0,
+            selected_only: false,
+            by_page: false,
+            pages: String::new(),
+            by_prefix: false,
+            address_prefix: String::new(),
+        }
+    }
+
+    /// Open the dialog for exporting `table`, which holds the entries of the
+    /// chosen scope out of `all_entries`
+    pub fn open(&mut self, table: &PlcTable, all_entries: usize, format: ExportFormat, path: &Path) {
+        self.visible = true;
+        self.source = table.clone();
+        self.format = format;
+        self.proposed_path = path.to_string_lossy().to_string();
+        self.all_entries = all_entries;
+        self.refresh();
+    }
+
+    /// Subset chosen with the options, unfinished fields filter nothing
+    pub fn filter(&self) -> ExportFilter {
+        ExportFilter {
+            pages: if self.by_page { ExportFilter::parse_pages(&self.pages) } else { None },
+            address_prefix: Some(self.address_prefix.trim().to_string())
+                .filter(|prefix| self.by_prefix && !prefix.is_empty()),
+            selected_only: self.selected_only,
+        }
+    }
+
+    /// Update the preview rows after the options changed
+    fn refresh(&mut self) {
+        let filter = self.filter();
+        let mut matching = self.source.entries.iter().filter(|entry| filter.matches(entry));
+        self.preview_rows = matching.by_ref().take(PREVIEW_ROWS).cloned().collect();
+        self.entries = self.preview_rows.len() + matching.count();
+    }
+
+    /// Switch to the next of Excel, CSV and JSON and follow with the file extension
+    fn change_format(&mut self) {
+        self.format = match self.format {
+            ExportFormat::Excel(..) => ExportFormat::Csv(self.columns, self.csv_delimiter),
+            ExportFormat::Csv(..) => ExportFormat::Json(self.json_layout),
+            _ => ExportFormat::Excel(self.excel_options, self.columns),
+        };
+        self.proposed_path = Path::new(&self.proposed_path)
+            .with_extension(self.format.extension())
+            .to_string_lossy()
+            .to_string();
+    }
+
+    /// Draw the dialog, returns the format, path and subset once the user confirms
+    pub fn show(&mut self, ctx: &egui::Context) -> Option<(ExportFormat, PathBuf, ExportFilter)> {
+        if !self.visible {
+            return None;
+        }
+
+        let mut open = true;
+        let mut close = false;
+        let mut confirmed = None;
+
+        egui::Window::new(t!("Export Preview"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(t!("Format: {}", self.format.name()));
+                ui.add_space(4.0);
+
+                let mut changed = false;
+                changed |= ui.checkbox(&mut self.selected_only, t!("Export selected only")).changed();
+                ui.horizontal(|ui| {
+                    changed |= ui.checkbox(&mut self.by_page, t!("Export page...")).changed();
+                    changed |= ui.add_enabled(
+                        self.by_page,
+                        egui::TextEdit::singleline(&mut self.pages)
+                            .desired_width(160.0)
+                            .hint_text(t!("e.g. 3, 5-7")),
+                    ).changed();
+                });
+                ui.horizontal(|ui| {
+                    changed |= ui.checkbox(&mut self.by_prefix, t!("Export by address prefix...")).changed();
+                    changed |= ui.add_enabled(
+                        self.by_prefix,
+                        egui::TextEdit::singleline(&mut self.address_prefix)
+                            .desired_width(80.0)
+                            .hint_text(t!("e.g. Q")),
+                    ).changed();
+                });
+                if changed {
+                    self.refresh();
+                }
+
+                ui.add_space(4.0);
+
+                if self.entries == 0 {
+                    ui.colored_label(
+                        ui.visuals().error_fg_color,
+                        t!("Nothing to export, no entries match the filter and options."),
+                    );
+                } else if self.entries < self.all_entries {
+                    ui.colored_label(
+                        ui.visuals().warn_fg_color,
+                        t!("A filter is active: {} of {} entries will be exported.", self.entries, self.all_entries),
+                    );
+                } else {
+                    ui.label(t!("{} entries will be exported.", self.entries));
+                }
+
+                ui.add_space(4.0);
+
+                if !self.preview_rows.is_empty() {
+                    // Excel and CSV write the configured columns
+                    let columns = match self.format {
+                        ExportFormat::Excel(_, columns) | ExportFormat::Csv(columns, _) => columns.visible(),
+                        _ => ColumnSet::default().visible(),
+                    };
+
+                    egui::Grid::new("export_preview_rows")
+                        .num_columns(columns.len())
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for column in &columns {
+                                ui.strong(t!(column.title()));
+                            }
+                            ui.end_row();
+
+                            for entry in &self.preview_rows {
+                                for column in &columns {
+                                    match column {
+                                        ColumnId::Address => ui.monospace(&entry.address),
+                                        ColumnId::Type => ui.colored_label(themes::data_type_color(&entry.data_type), entry.data_type.to_string()),
+                                        column => ui.add(egui::Label::new(column.value(entry)).truncate()),
+                                    };
+                                }
+                                ui.end_row();
+                            }
+                        });
+
+                    if self.entries > self.preview_rows.len() {
+                        ui.weak(t!("… and {} more", self.entries - self.preview_rows.len()));
+                    }
+                }
+
+                ui.add_space(8.0);
+
+                ui.horizontal(|ui| {
+                    ui.label(t!("File:"));
+                    ui.add(egui::TextEdit::singleline(&mut self.proposed_path).desired_width(360.0));
+                });
+
+                ui.add_space(8.0);
+
+                ui.horizontal(|ui| {
+                    let can_export = self.entries > 0 && !self.proposed_path.trim().is_empty();
+                    if ui.add_enabled(can_export, egui::Button::new(t!("Export"))).clicked() {
+                        confirmed = Some((self.format, PathBuf::from(self.proposed_path.trim()), self.filter()));
+                        close = true;
+                    }
+                    if ui.button(t!("Change Format"))
+                        .on_hover_text(t!("Switch between Excel, CSV and JSON"))
+                        .clicked()
+                    {
+                        self.change_format();
+                    }
+                    if ui.button(t!("Cancel")).clicked() {
+                        close = true;
+                    }
+                });
+            });
+
+        self.visible = open && !close;
+        confirmed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_open_and_change_format() {
+        let mut table = PlcTable::new("P-100".to_string());
+        for i in 0..12 {
+            table.add_entry(PlcEntry::new(format!("I0.{}", i % 8), format!("Input {}", i), "1".to_string()));
+        }
+
+        let mut dialog = ExportPreviewDialog::new();
+        dialog.excel_options.sheet_per_page = true;
+        dialog.open(&table, 20, ExportFormat::Excel(dialog.excel_options, dialog.columns), Path::new("exports/P-100.xlsx"));
+        assert!(dialog.visible);
+        assert_eq!(dialog.preview_rows.len(), PREVIEW_ROWS);
+        assert_eq!(dialog.entries, 12);
+
+        dialog.change_format();
+        assert_eq!(dialog.format, ExportFormat::Csv(dialog.columns, dialog.csv_delimiter));
+        assert!(dialog.proposed_path.ends_with("P-100.csv"));
+
+        dialog.change_format();
+        dialog.change_format();
+        assert_eq!(dialog.format, ExportFormat::Excel(dialog.excel_options, dialog.columns));
+        assert!(dialog.proposed_path.ends_with("P-100.xlsx"));
+    }
+
+    #[test]
+    fn test_options_update_preview() {
+        let mut table = PlcTable::new("P-100".to_string());
+        for (address, page) in [("I0.0", "1"), ("Q0.0", "2"), ("Q0.1", "3")] {
+            table.add_entry(PlcEntry::new(address.to_string(), String::new(), page.to_string()));
+        }
+
+        let mut dialog = ExportPreviewDialog::new();
+        dialog.open(&table, 3, ExportFormat::Csv(ColumnSet::default(), CsvDelimiter::default()), Path::new("P-100.csv"));
+        dialog.by_prefix = true;
+        dialog.address_prefix = "q".to_string();
+        dialog.refresh();
+        assert_eq!(dialog.entries, 2);
+
+        dialog.by_page = true;
+        dialog.pages = "3".to_string();
+        dialog.refresh();
+        assert_eq!(dialog.entries, 1);
+        assert_eq!(dialog.preview_rows[0].address, "Q0.1");
+
+        // An empty prefix field doesn't filter
+        dialog.address_prefix.clear();
+        assert_eq!(dialog.filter().address_prefix, None);
+    }
+}
\ No newline at end of file
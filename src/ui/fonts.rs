@@ -0,0 +1,92 @@
+use eframe::egui;
+
+pub const MIN_FONT_SIZE: f32 = 10.0;
+pub const MAX_FONT_SIZE: f32 = 24.0;
+pub const DEFAULT_FONT_SIZE: f32 = 14.0;
+
+pub const MIN_UI_SCALE: f32 = 0.8;
+pub const MAX_UI_SCALE: f32 = 2.0;
+
+/// Zoom the whole UI on top of the OS scaling, e.g. 1.5 on a 4K laptop
+pub fn apply_ui_scale(ctx: &egui::Context, scale: f32) {
+    ctx.set_zoom_factor(scale.clamp(MIN_UI_SCALE, MAX_UI_SCALE));
+}
+
+/// A fixed size in points laid out for the default font size, grown or shrunk
+/// with the body text so labels and rows don't clip
+pub fn scaled(ui: &egui::Ui, size: f32) -> f32 {
+    let body = ui.style().text_styles.get(&egui::TextStyle::Body).map_or(DEFAULT_FONT_SIZE, |font| font.size);
+    size * body / DEFAULT_FONT_SIZE
+}
+
+/// Scale all text styles from a single base size (body and button text)
+pub fn apply_font_sizes(ctx: &egui::Context, base_size: f32) {
+    let base_size = base_size.clamp(MIN_FONT_SIZE, MAX_FONT_SIZE);
+    let mut style = (*ctx.style()).clone();
+
+    style.text_styles.insert(
+        egui::TextStyle::Body,
+        egui::FontId::proportional(base_size),
+    );
+    style.text_styles.insert(
+        egui::TextStyle::Button,
+        egui::FontId::proportional(base_size),
+    );
+    style.text_styles.insert(
+        egui::TextStyle::Heading,
+        egui::FontId::proportional(base_size * 1.3),
+    );
+    style.text_styles.insert(
+        egui::TextStyle::Monospace,
+        egui::FontId::monospace(base_size),
+    );
+    style.text_styles.insert(
+        egui::TextStyle::Small,
+        egui::FontId::proportional(base_size * 0.75),
+    );
+
+    ctx.set_style(style);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_font_sizes() {
+        let ctx = egui::Context::default();
+
+        for size in [MIN_FONT_SIZE, DEFAULT_FONT_SIZE, 18.0, MAX_FONT_SIZE] {
+            apply_font_sizes(&ctx, size);
+            let style = ctx.style();
+
+            assert_eq!(style.text_styles[&egui::TextStyle::Body].size, size);
+            assert_eq!(style.text_styles[&egui::TextStyle::Button].size, size);
+            assert_eq!(style.text_styles[&egui::TextStyle::Monospace].size, size);
+            assert!((style.text_styles[&egui::TextStyle::Heading].size - size * 1.3).abs() < f32::EPSILON * 32.0);
+        }
+    }
+
+    #[test]
+    fn test_font_size_is_clamped() {
+        let ctx = egui::Context::default();
+
+        apply_font_sizes(&ctx, 100.0);
+        assert_eq!(ctx.style().text_styles[&egui::TextStyle::Body].size, MAX_FONT_SIZE);
+
+        apply_font_sizes(&ctx, 2.0);
+        assert_eq!(ctx.style().text_styles[&egui::TextStyle::Body].size, MIN_FONT_SIZE);
+    }
+
+    #[test]
+    fn test_scaled_sizes_follow_font_size() {
+        let ctx = egui::Context::default();
+        apply_font_sizes(&ctx, 21.0);
+
+        let _ = ctx.run(egui::RawInput::default(), |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                assert_eq!(scaled(ui, 22.0), 33.0);
+            });
+        });
+    }
+}
@@ -0,0 +1,129 @@
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Number of past runs kept in the config
+pub const MAX_HISTORY_ENTRIES: usize = 100;
+
+/// Number of project numbers offered in the Recent Projects dropdown
+pub const MAX_RECENT_PROJECTS: usize = 10;
+
+/// Outcome of one extraction run
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ExtractionHistoryEntry {
+    pub project: String,
+    pub started_at: DateTime<Local>,
+    pub duration_secs: f64,
+    pub entry_count: u32,
+    pub success: bool,
+    pub error: Option<String>,
+    #[serde(default)]
+    pub session_path: Option<PathBuf>, // Saved table of a successful run
+}
+
+impl ExtractionHistoryEntry {
+    pub fn succeeded(project: &str, started_at: DateTime<Local>, duration: Duration, entry_count: usize) -> Self {
+        Self {
+            project: project.to_string(),
+            started_at,
+            duration_secs: duration.as_secs_f64(),
+            entry_count: entry_count as u32,
+            success: true,
+            error: None,
+            session_path: None,
+        }
+    }
+
+    pub fn failed(project: &str, started_at: DateTime<Local>, duration: Duration, error: String) -> Self {
+        Self {
+            project: project.to_string(),
+            started_at,
+            duration_secs: duration.as_secs_f64(),
+            entry_count: 0,
+            success: false,
+            error: Some(error),
+            session_path: None,
+        }
+    }
+
+    /// Duration as "42s" or "3m 05s"
+    pub fn duration_text(&self) -> String {
+        let secs = self.duration_secs.round() as u64;
+        if secs < 60 {
+            format!("{}s", secs)
+        } else {
+            format!("{}m {:02}s", secs / 60, secs % 60)
+        }
+    }
+}
+
+/// Append `entry` as the newest run and drop the oldest ones beyond
+/// `MAX_HISTORY_ENTRIES`. Returns the dropped entries so their saved
+/// sessions can be cleaned up.
+pub fn push_history(history: &mut Vec<ExtractionHistoryEntry>, entry: ExtractionHistoryEntry) -> Vec<ExtractionHistoryEntry> {
+    history.push(entry);
+    let excess = history.len().saturating_sub(MAX_HISTORY_ENTRIES);
+    history.drain(..excess).collect()
+}
+
+/// Move `project` to the front of the recent projects, dropping the oldest
+/// ones beyond `MAX_RECENT_PROJECTS`
+pub fn push_recent_project(recent: &mut Vec<String>, project: &str) {
+    let project = project.trim();
+    if project.is_empty() {
+        return;
+    }
+
+    recent.retain(|p| p != project);
+    recent.insert(0, project.to_string());
+    recent.truncate(MAX_RECENT_PROJECTS);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(project: &str) -> ExtractionHistoryEntry {
+        ExtractionHistoryEntry::succeeded(project, Local::now(), Duration::from_secs(5), 10)
+    }
+
+    #[test]
+    fn test_push_history_caps_length() {
+        let mut history = Vec::new();
+        for i in 0..MAX_HISTORY_ENTRIES {
+            assert!(push_history(&mut history, entry(&i.to_string())).is_empty());
+        }
+
+        let dropped = push_history(&mut history, entry("newest"));
+        assert_eq!(dropped.len(), 1);
+        assert_eq!(dropped[0].project, "0");
+        assert_eq!(history.len(), MAX_HISTORY_ENTRIES);
+        assert_eq!(history.last().unwrap().project, "newest");
+    }
+
+    #[test]
+    fn test_recent_projects_are_deduplicated() {
+        let mut recent = Vec::new();
+        for i in 0..MAX_RECENT_PROJECTS + 2 {
+            push_recent_project(&mut recent, &format!("P-{}", i));
+        }
+        assert_eq!(recent.len(), MAX_RECENT_PROJECTS);
+        assert_eq!(recent[0], "P-11");
+
+        push_recent_project(&mut recent, " P-5 ");
+        push_recent_project(&mut recent, "");
+        assert_eq!(recent.len(), MAX_RECENT_PROJECTS);
+        assert_eq!(recent[0], "P-5");
+        assert_eq!(recent.iter().filter(|p| *p == "P-5").count(), 1);
+    }
+
+    #[test]
+    fn test_duration_text() {
+        let mut entry = entry("P1");
+        assert_eq!(entry.duration_text(), "5s");
+
+        entry.duration_secs = 185.4;
+        assert_eq!(entry.duration_text(), "3m 05s");
+    }
+}
\ No newline at end of file
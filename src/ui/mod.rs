@@ -1,5 +1,14 @@
 pub mod app;
 pub mod table_view;
 pub mod themes;
+pub mod session;
+pub mod diff_view;
+pub mod bulk_edit;
+pub mod export_preview;
+pub mod fonts;
+pub mod history;
+pub mod statistics_view;
+pub mod toast;
+pub mod tray;
 
 pub use app::EviewApp;
\ No newline at end of file
@@ -0,0 +1,131 @@
+use anyhow::Result;
+use chrono::{DateTime, Local};
+use directories::ProjectDirs;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::models::PlcTable;
+use crate::t;
+
+/// Snapshot of the last extraction, persisted between launches
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Session {
+    pub project_name: String,
+    pub extraction_date: DateTime<Local>,
+    pub table: PlcTable,
+}
+
+impl Session {
+    pub fn from_table(table: &PlcTable) -> Self {
+        Self {
+            project_name: table.project_name.clone(),
+            extraction_date: table.extraction_date,
+            table: table.clone(),
+        }
+    }
+
+    /// Load the last session, returns `None` if no session has been saved yet
+    pub fn load() -> Result<Option<Self>> {
+        let path = Self::session_path()?;
+
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        Ok(Some(Self::load_from(&path)?))
+    }
+
+    pub fn save(&self) -> Result<()> {
+        self.save_to(&Self::session_path()?)
+    }
+
+    pub fn load_from(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let content = serde_json::to_string(self)?;
+        fs::write(path, content)?;
+
+        Ok(())
+    }
+
+    pub fn session_path() -> Result<PathBuf> {
+        Ok(Self::data_dir()?.join("last_session.json"))
+    }
+
+    /// Where the table of a run from the extraction history is kept
+    pub fn history_path(project_name: &str, started_at: DateTime<Local>) -> Result<PathBuf> {
+        let project: String = project_name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+            .collect();
+        let file_name = format!("{}_{}.json", started_at.format("%Y%m%d_%H%M%S"), project);
+
+        Ok(Self::data_dir()?.join("history").join(file_name))
+    }
+
+    /// Where the cookies of the last login are kept when `AppConfig::reuse_session`
+    /// is on. One file per profile, tenant and account, so switching to another
+    /// of them logs in again instead of reusing someone else's session.
+    pub fn cookies_path(profile: &str, base_url: &str, email: &str) -> Result<PathBuf> {
+        Ok(Self::cookies_dir()?.join(cookies_file_name(profile, base_url, email)))
+    }
+
+    /// Folder of all `cookies_path` files
+    pub fn cookies_dir() -> Result<PathBuf> {
+        Ok(Self::data_dir()?.join("session_cookies"))
+    }
+
+    fn data_dir() -> Result<PathBuf> {
+        let proj_dirs = ProjectDirs::from("com", "eplan", "eview-scraper")
+            .ok_or_else(|| anyhow::anyhow!("Could not determine data directory"))?;
+
+        Ok(proj_dirs.data_dir().to_path_buf())
+    }
+
+    /// Banner text shown after restoring the session on startup
+    pub fn summary(&self) -> String {
+        t!(
+            "Loaded session from {} — {} ({} entries)",
+            self.extraction_date.format("%Y-%m-%d %H:%M"),
+            self.project_name,
+            self.table.entries.len()
+        )
+    }
+}
+
+/// Profile name and a hash of the tenant and account, e.g. `default_3f9a1c0b2e7d4a56.json`
+fn cookies_file_name(profile: &str, base_url: &str, email: &str) -> String {
+    use sha2::{Digest, Sha256};
+
+    let profile: String = profile
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    let account = format!("{}\n{}", base_url.trim().trim_end_matches('/').to_lowercase(), email.trim().to_lowercase());
+    let hash: String = Sha256::digest(account.as_bytes()).iter().take(8).map(|byte| format!("{:02x}", byte)).collect();
+
+    format!("{}_{}.json", profile, hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cookies_file_per_profile_and_account() {
+        let name = cookies_file_name("Site A", "https://eview.eplan.com/", "Max@Example.com");
+        assert!(name.starts_with("Site_A_"));
+        assert_eq!(name, cookies_file_name("Site A", " https://EVIEW.eplan.com", "max@example.com "));
+
+        assert_ne!(name, cookies_file_name("Site B", "https://eview.eplan.com/", "max@example.com"));
+        assert_ne!(name, cookies_file_name("Site A", "https://other.eview.eplan.com/", "max@example.com"));
+        assert_ne!(name, cookies_file_name("Site A", "https://eview.eplan.com/", "erika@example.com"));
+    }
+}
@@ -0,0 +1,160 @@
+use crate::models::statistics::AreaCoverage;
+use crate::models::{IssueSeverity, PlcDataType, TableStatistics};
+use crate::t;
+use crate::ui::themes;
+use eframe::egui;
+
+const BAR_WIDTH: f32 = 240.0;
+const BAR_HEIGHT: f32 = 14.0;
+const BIT_SIZE: f32 = 12.0;
+const BYTES_PER_ROW: usize = 8;
+const MAX_GRID_BYTES: usize = 256; // Larger areas only list their gaps
+
+pub fn render(ui: &mut egui::Ui, stats: &TableStatistics) {
+    ui.strong(t!("Entries by Type"));
+    let max = stats.by_type.iter().map(|(_, count)| *count).max().unwrap_or(0);
+    egui::Grid::new("stats_by_type").num_columns(3).show(ui, |ui| {
+        for (data_type, count) in &stats.by_type {
+            ui.label(data_type.to_string());
+            bar(ui, *count, max, themes::data_type_color(data_type));
+            ui.label(count.to_string());
+            ui.end_row();
+        }
+    });
+
+    ui.add_space(4.0);
+    ui.label(t!(
+        "{} pages, {} entries with comment, {} without, {} duplicate addresses",
+        stats.entries_per_page.len(),
+        stats.with_comment,
+        stats.total - stats.with_comment,
+        stats.duplicate_addresses
+    ));
+
+    ui.add_space(8.0);
+
+    for (name, area, data_type) in [
+        (t!("Inputs"), &stats.inputs, PlcDataType::Input),
+        (t!("Outputs"), &stats.outputs, PlcDataType::Output),
+    ] {
+        let gaps = area.gaps();
+        let double_assigned = area.double_assigned();
+
+        let title = if area.bytes.is_empty() {
+            t!("{}: no bit addresses", name)
+        } else {
+            t!(
+                "{}: {} of {} bits used, {} gaps, {} double-assigned",
+                name,
+                area.used_bits(),
+                area.bytes.len() * 8,
+                gaps.len(),
+                double_assigned.len()
+            )
+        };
+
+        egui::CollapsingHeader::new(egui::RichText::new(title).strong())
+            .id_salt(("stats_area", area.prefix))
+            .default_open(!area.bytes.is_empty())
+            .show(ui, |ui| {
+                if area.bytes.is_empty() {
+                    return;
+                }
+
+                if area.bytes.len() <= MAX_GRID_BYTES {
+                    render_bit_grid(ui, area, themes::data_type_color(&data_type));
+                } else {
+                    ui.weak(t!("The address area is too large for a bit grid ({} bytes).", area.bytes.len()));
+                }
+
+                if !double_assigned.is_empty() {
+                    ui.add_space(4.0);
+                    ui.colored_label(
+                        themes::severity_color(IssueSeverity::Error),
+                        t!("Double-assigned: {}", double_assigned.join(", ")),
+                    );
+                }
+
+                if !gaps.is_empty() {
+                    ui.add_space(4.0);
+                    let labels: Vec<String> = gaps.iter().map(|gap| gap.label()).collect();
+                    ui.label(t!("Gaps: {}", labels.join(", ")));
+                }
+            });
+    }
+
+    ui.add_space(8.0);
+
+    egui::CollapsingHeader::new(egui::RichText::new(t!("Entries per Page ({})", stats.entries_per_page.len())).strong())
+        .id_salt("stats_pages")
+        .show(ui, |ui| {
+            let max = stats.entries_per_page.iter().map(|(_, count)| *count).max().unwrap_or(0);
+            let color = ui.visuals().selection.stroke.color;
+            egui::Grid::new("stats_pages_grid").num_columns(3).show(ui, |ui| {
+                for (page, count) in &stats.entries_per_page {
+                    ui.label(if page.is_empty() { t!("No page") } else { t!("Page {}", page) });
+                    bar(ui, *count, max, color);
+                    ui.label(count.to_string());
+                    ui.end_row();
+                }
+            });
+        });
+}
+
+/// Horizontal bar scaled against `max`
+fn bar(ui: &mut egui::Ui, value: usize, max: usize, color: egui::Color32) {
+    let (rect, _) = ui.allocate_exact_size(egui::vec2(BAR_WIDTH, BAR_HEIGHT), egui::Sense::hover());
+    let fraction = if max == 0 { 0.0 } else { value as f32 / max as f32 };
+
+    let painter = ui.painter();
+    painter.rect_filled(rect, 2.0, ui.visuals().faint_bg_color);
+    let filled = egui::Rect::from_min_size(rect.min, egui::vec2(rect.width() * fraction, rect.height()));
+    painter.rect_filled(filled, 2.0, color);
+}
+
+/// One row of bit cells per byte group, bit 0 on the left. Used bits take the
+/// type color and double-assigned bits the error color.
+fn render_bit_grid(ui: &mut egui::Ui, area: &AreaCoverage, color: egui::Color32) {
+    let empty = ui.visuals().faint_bg_color;
+    let double = themes::severity_color(IssueSeverity::Error);
+
+    for (row, bytes) in area.bytes.chunks(BYTES_PER_ROW).enumerate() {
+        ui.horizontal(|ui| {
+            for (offset, bits) in bytes.iter().enumerate() {
+                let byte = row * BYTES_PER_ROW + offset;
+
+                ui.add_sized([48.0, BIT_SIZE], egui::Label::new(
+                    egui::RichText::new(format!("{}B{}", area.prefix, byte)).small().monospace(),
+                ));
+
+                let (rect, response) = ui.allocate_exact_size(egui::vec2(BIT_SIZE * 8.0, BIT_SIZE), egui::Sense::hover());
+                let painter = ui.painter();
+                for (bit, &symbols) in bits.iter().enumerate() {
+                    let cell = egui::Rect::from_min_size(
+                        rect.min + egui::vec2(bit as f32 * BIT_SIZE, 0.0),
+                        egui::vec2(BIT_SIZE, BIT_SIZE),
+                    )
+                    .shrink(1.0);
+                    let fill = match symbols {
+                        0 => empty,
+                        1 => color,
+                        _ => double,
+                    };
+                    painter.rect_filled(cell, 1.0, fill);
+                }
+
+                if let Some(pos) = response.hover_pos() {
+                    let bit = (((pos.x - rect.min.x) / BIT_SIZE) as usize).min(7);
+                    let text = match bits[bit] {
+                        0 => t!("{} unused", area.bit_name(byte, bit)),
+                        1 => area.bit_name(byte, bit),
+                        symbols => t!("{} — {} symbols", area.bit_name(byte, bit), symbols),
+                    };
+                    response.on_hover_text(text);
+                }
+
+                ui.add_space(6.0);
+            }
+        });
+    }
+}
\ No newline at end of file
@@ -1,247 +1,1371 @@
-use crate::models::{PlcEntry, PlcTable};
+use crate::models::{
+    natural_sort, next_revision, ColumnId, ColumnSet, EntryStatus, PlcDataType, PlcEntry, PlcTable, TableFilter,
+    LOW_CONFIDENCE,
+};
+use crate::t;
+use super::{fonts, themes};
 use egui_extras::{Column, TableBuilder};
 use eframe::egui;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+/// Sideways drag distance after which a dragged header swaps with its neighbour
+const COLUMN_DRAG_THRESHOLD: f32 = 40.0;
+
+/// Seconds the filter must stay unchanged before the rows are filtered again
+const FILTER_DEBOUNCE: f64 = 0.15;
+
+const ROW_HEIGHT: f32 = 22.0;
+
+/// Saved column widths outside this range are ignored
+const MIN_SAVED_WIDTH: f32 = 50.0;
+const MAX_SAVED_WIDTH: f32 = 800.0;
 
 pub struct TableView {
-    sort_column: SortColumn,
+    sort_column: Option<ColumnId>, // Unsorted shows the entries in extraction order
     sort_ascending: bool,
+    filter: TableFilter,
+    columns: ColumnSet,
+    column_drag: f32, // Horizontal drag distance since the last swap
+    column_widths: HashMap<String, f32>, // Current widths by column key, read back after each frame
+    reset_widths: bool,
+    selected_row_index: Option<usize>, // Keyboard cursor, index into `table.entries`
+    anchor_row: Option<usize>, // Last row clicked or moved to without Shift, start of a Shift range
+    shift_range: Vec<usize>, // Rows the current Shift range selected, replaced when it grows or shrinks
+    scroll_to_selected: bool,
+    detail_row: Option<usize>,
+    rows: RowCache,
+    filter_edited_at: f64, // egui time of the last filter change
+    pending_filter: TableFilter,
+    group_by: GroupBy,
+    collapsed: HashSet<String>, // Labels of folded groups
+    tag_popup: Option<TagPopup>,
+}
+
+/// "Add tag…" popup for one row
+struct TagPopup {
+    row: usize, // Index into `table.entries`
+    tag: String,
+    color: egui::Color32,
+}
+
+/// Badge color of tags without a saved color
+const DEFAULT_TAG_COLOR: egui::Color32 = egui::Color32::from_rgb(96, 125, 139);
+
+/// Filtered and sorted row indices into `table.entries`. Rebuilt only when the
+/// table revision, the sort, the grouping or the (debounced) filter changes, so
+/// large tables aren't filtered every frame. Cell edits keep the cached rows,
+/// the edited row stays in place until the next rebuild.
+#[derive(Default)]
+struct RowCache {
+    key: Option<(u64, Option<ColumnId>, bool, GroupBy)>, // Table revision, sort and grouping the indices were built for
+    filter: TableFilter,
+    indices: Vec<usize>, // All matching entries in display order
+    groups: Vec<RowGroup>, // Empty without grouping
+    rows: Vec<DisplayRow>, // Rows of the table body, without the members of collapsed groups
+    visible: Vec<usize>, // Entries among `rows`, for keyboard navigation and range selection
+    deleted: Vec<usize>, // Matching rows of `table.deleted`, shown after all others
+    has_changes: bool, // Some entry has a status from `PlcTable::mark_status`, which bumps the revision
+}
+
+/// A run of `RowCache::indices` sharing one group value
+#[derive(Debug, PartialEq)]
+struct RowGroup {
+    label: String,
+    start: usize,
+    len: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DisplayRow {
+    Group(usize), // Index into `RowCache::groups`
+    Entry(usize), // Index into `table.entries`
+    Deleted(usize), // Index into `table.deleted`
+}
+
+impl RowCache {
+    fn members(&self, group: usize) -> &[usize] {
+        let group = &self.groups[group];
+        &self.indices[group.start..group.start + group.len]
+    }
+
+    /// Lay out the body rows, leaving out the entries of collapsed groups
+    fn build_rows(&mut self, collapsed: &HashSet<String>) {
+        self.rows.clear();
+        self.visible.clear();
+
+        if self.groups.is_empty() {
+            self.rows.extend(self.indices.iter().map(|&index| DisplayRow::Entry(index)));
+            self.visible.extend_from_slice(&self.indices);
+        }
+
+        for (position, group) in self.groups.iter().enumerate() {
+            self.rows.push(DisplayRow::Group(position));
+            if !collapsed.contains(&group.label) {
+                let members = &self.indices[group.start..group.start + group.len];
+                self.rows.extend(members.iter().map(|&index| DisplayRow::Entry(index)));
+                self.visible.extend_from_slice(members);
+            }
+        }
+
+        self.rows.extend(self.deleted.iter().map(|&index| DisplayRow::Deleted(index)));
+    }
 }
 
-#[derive(Debug, Clone, PartialEq)]
-enum SortColumn {
+/// Column the results are folded by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupBy {
     None,
-    Address,
-    Name,
-    Type,
-    Comment,
     Page,
+    Type,
+    Module, // Hardware module, for cross-checks against the BOM
+}
+
+impl GroupBy {
+    pub const ALL: [Self; 4] = [Self::None, Self::Page, Self::Type, Self::Module];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::None => "None",
+            Self::Page => "Page",
+            Self::Type => "Type",
+            Self::Module => "Module",
+        }
+    }
+
+    fn value(&self, entry: &PlcEntry) -> String {
+        match self {
+            Self::None => String::new(),
+            Self::Page => entry.page.clone(),
+            Self::Type => entry.data_type.to_string(),
+            Self::Module => entry.module.clone().unwrap_or_default(),
+        }
+    }
+
+    /// Group header text, e.g. "Page 12"
+    fn header(&self, value: &str) -> String {
+        match self {
+            Self::Page if value.is_empty() => t!("No page"),
+            Self::Page => t!("Page {}", value),
+            Self::Module if value.is_empty() => t!("No module"),
+            Self::None | Self::Type | Self::Module => value.to_string(),
+        }
+    }
+}
+
+/// Row operations picked from the context menu, applied after the table is drawn
+#[derive(Debug, Clone)]
+enum RowAction {
+    Delete(usize),
+    Duplicate(usize),
+    InsertAbove(usize),
+    AddTag(usize),
+    RemoveTag(usize, String),
+}
+
+/// Key of a column in the saved widths
+fn width_key(column: ColumnId) -> String {
+    format!("{:?}", column)
+}
+
+/// Column sizing, starting from the saved width if it's plausible.
+/// The comment column always takes the remaining space.
+fn column_width(column: ColumnId, saved: &HashMap<String, f32>) -> Column {
+    let initial = |default: f32| {
+        saved
+            .get(&width_key(column))
+            .copied()
+            .filter(|width| (MIN_SAVED_WIDTH..=MAX_SAVED_WIDTH).contains(width))
+            .unwrap_or(default)
+    };
+
+    match column {
+        ColumnId::Address => Column::initial(initial(100.0)).at_least(80.0),
+        ColumnId::Name | ColumnId::FunctionText => Column::initial(initial(250.0)).at_least(150.0),
+        ColumnId::Comment => Column::remainder().at_least(200.0),
+        ColumnId::Tags => Column::initial(initial(150.0)).at_least(80.0),
+        ColumnId::Module => Column::initial(initial(200.0)).at_least(80.0),
+        ColumnId::DeviceTag => Column::initial(initial(100.0)).at_least(60.0),
+        ColumnId::Type | ColumnId::Page | ColumnId::Confidence => Column::initial(initial(80.0)).at_least(60.0),
+    }
+}
+
+fn column_filter_mut(column: ColumnId, filter: &mut TableFilter) -> Option<&mut String> {
+    match column {
+        ColumnId::Address => Some(&mut filter.address),
+        ColumnId::Name => Some(&mut filter.symbol_name),
+        ColumnId::Comment => Some(&mut filter.comment),
+        ColumnId::Page => Some(&mut filter.page),
+        _ => None,
+    }
 }
 
 impl TableView {
-    pub fn new() -> Self {
+    pub fn new(columns: &ColumnSet, column_widths: &HashMap<String, f32>) -> Self {
         Self {
-            sort_column: SortColumn::None,
+            sort_column: None,
             sort_ascending: true,
+            filter: TableFilter::default(),
+            columns: *columns,
+            column_drag: 0.0,
+            column_widths: column_widths.clone(),
+            reset_widths: false,
+            selected_row_index: None,
+            anchor_row: None,
+            shift_range: Vec::new(),
+            scroll_to_selected: false,
+            detail_row: None,
+            rows: RowCache::default(),
+            filter_edited_at: 0.0,
+            pending_filter: TableFilter::default(),
+            group_by: GroupBy::None,
+            collapsed: HashSet::new(),
+            tag_popup: None,
+        }
+    }
+
+    /// Filter currently applied to the table, combining the search text,
+    /// column filters and data type chips
+    pub fn filter(&self) -> &TableFilter {
+        &self.filter
+    }
+
+    /// Order and visibility of the columns, also used by the clipboard and the exports
+    pub fn columns(&self) -> &ColumnSet {
+        &self.columns
+    }
+
+    /// Take the columns of another profile
+    pub fn set_columns(&mut self, columns: &ColumnSet) {
+        self.columns = *columns;
+    }
+
+    /// Sort column and direction, saved with the UI state
+    pub fn sort(&self) -> (Option<ColumnId>, bool) {
+        (self.sort_column, self.sort_ascending)
+    }
+
+    pub fn set_sort(&mut self, column: Option<ColumnId>, ascending: bool) {
+        self.sort_column = column;
+        self.sort_ascending = ascending;
+    }
+
+    pub fn column_widths(&self) -> &HashMap<String, f32> {
+        &self.column_widths
+    }
+
+    /// Update the search text right away, so parse errors show in the same frame
+    pub fn set_filter_text(&mut self, text: &str) {
+        self.filter.set_text(text);
+    }
+
+    pub fn group_by(&self) -> GroupBy {
+        self.group_by
+    }
+
+    pub fn set_group_by(&mut self, group_by: GroupBy) {
+        if self.group_by != group_by {
+            self.group_by = group_by;
+            self.collapsed.clear();
+        }
+    }
+
+    /// Copy of `table` in display order: sorted, and group after group while
+    /// grouped, so exports match what the table shows. With `filtered` only the
+    /// entries matching the current filter are kept.
+    pub fn ordered_table(&self, table: &PlcTable, filtered: bool) -> PlcTable {
+        let source = if filtered { self.filter.apply(table) } else { table.clone() };
+        let (indices, _) = arrange(
+            (0..source.entries.len()).collect(),
+            &source.entries,
+            self.sort_column,
+            self.sort_ascending,
+            self.group_by,
+        );
+
+        PlcTable {
+            entries: indices.iter().map(|&index| source.entries[index].clone()).collect(),
+            project_name: source.project_name,
+            extraction_date: source.extraction_date,
+            dirty: false,
+            revision: next_revision(),
+            deleted: Vec::new(),
         }
     }
 
-    pub fn render(&mut self, ui: &mut egui::Ui, table: &mut PlcTable, filter: &str) {
+    /// Draw the table. Tags added with "Add tag…" store their color in `tag_colors`.
+    /// Returns whether a cell was edited, which keeps the table revision.
+    pub fn render(
+        &mut self,
+        ui: &mut egui::Ui,
+        table: &mut PlcTable,
+        filter: &str,
+        tag_colors: &mut HashMap<String, egui::Color32>,
+    ) -> bool {
+        self.filter.set_text(filter);
+        self.refresh_rows(ui.ctx(), table);
+
         // Header with table title and actions
         ui.horizontal(|ui| {
-            ui.heading("SPS Table");
+            ui.heading(t!("SPS Table"));
             ui.separator();
 
-            let filtered_count = table.get_filtered(filter).len();
+            let filtered_count = self.rows.indices.len();
             let total_count = table.entries.len();
 
-            if !filter.is_empty() {
-                ui.label(format!("Showing {} of {} entries", filtered_count, total_count));
+            if self.filter.is_active() {
+                ui.label(t!("Showing {} of {} entries", filtered_count, total_count));
             } else {
-                ui.label(format!("{} entries", total_count));
+                ui.label(t!("{} entries", total_count));
             }
 
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                ui.menu_button(t!("⚙ Columns…"), |ui| {
+                    self.show_columns_menu(ui);
+                });
+
                 // Select all/none buttons
-                if ui.button("Select All").clicked() {
-                    for entry in &mut table.entries {
-                        if entry.matches_filter(filter) {
-                            entry.selected = true;
-                        }
+                if ui.button(t!("Select All")).clicked() {
+                    for &index in &self.rows.indices {
+                        table.entries[index].selected = true;
                     }
                 }
 
-                if ui.button("Select None").clicked() {
+                if ui.button(t!("Select None")).clicked() {
                     table.select_all(false);
                 }
             });
         });
 
+        // Quick filter chips per data type
+        ui.horizontal(|ui| {
+            ui.label(t!("Type:"));
+            for (data_type, label) in [
+                (PlcDataType::Input, "Inputs"),
+                (PlcDataType::Output, "Outputs"),
+                (PlcDataType::Memory, "Memory"),
+                (PlcDataType::Unknown, "Unknown"),
+            ] {
+                let active = self.filter.data_types.contains(&data_type);
+                let text = egui::RichText::new(format!("● {}", t!(label))).color(themes::data_type_color(&data_type));
+                if ui.selectable_label(active, text).clicked() {
+                    self.filter.toggle_data_type(data_type);
+                }
+            }
+
+            // Only once a baseline marked the entries
+            if self.filter.changed_only || self.rows.has_changes {
+                ui.separator();
+                ui.label(t!("Show:"));
+                ui.selectable_value(&mut self.filter.changed_only, false, t!("All"));
+                ui.selectable_value(&mut self.filter.changed_only, true, t!("Changed only"));
+            }
+
+            ui.separator();
+            let mut low_confidence = self.filter.below_confidence.is_some();
+            if ui.toggle_value(&mut low_confidence, t!("Low confidence"))
+                .on_hover_text(t!("Only entries whose symbol name the parser was unsure about"))
+                .changed()
+            {
+                self.filter.below_confidence = low_confidence.then_some(LOW_CONFIDENCE);
+            }
+            if let Some(threshold) = &mut self.filter.below_confidence {
+                ui.label(t!("below"));
+                ui.add(egui::DragValue::new(threshold).range(0.05..=1.0).speed(0.01).fixed_decimals(2));
+            }
+
+            if self.filter.has_column_filters() && ui.small_button(t!("Clear column filters")).clicked() {
+                self.filter.clear_columns();
+            }
+        });
+
         ui.separator();
 
+        // Header buttons may have changed the filter or sort
+        self.refresh_rows(ui.ctx(), table);
+        let cache = std::mem::take(&mut self.rows);
+
+        self.handle_keyboard(ui, table, &cache);
+
         // The actual table
         let available_height = ui.available_height();
+        let mut row_action = None;
+        let mut clicked_row = None;
+        let mut clicked_checkbox = None;
+        let mut clicked_group = None;
+        let mut group_checkbox = None;
+        let mut cell_edited = false;
+        let modifiers = ui.input(|i| i.modifiers);
+        let focused_row = self.selected_row_index;
+        let scroll_to_selected = std::mem::take(&mut self.scroll_to_selected);
+        let focused_position = focused_row
+            .filter(|_| scroll_to_selected)
+            .and_then(|focused| cache.rows.iter().position(|row| *row == DisplayRow::Entry(focused)));
+
+        let order = self.columns.visible();
+        let row_height = fonts::scaled(ui, ROW_HEIGHT);
+        let header_height = fonts::scaled(ui, 48.0);
 
-        TableBuilder::new(ui)
+        let mut builder = TableBuilder::new(ui)
+            .id_salt(&order) // Fresh widths when the order changes, they are kept per position
             .striped(true)
             .resizable(true)
+            .sense(egui::Sense::click())
             .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
-            .column(Column::exact(40.0))  // Checkbox
-            .column(Column::initial(100.0).at_least(80.0))  // Address
-            .column(Column::initial(250.0).at_least(150.0)) // Symbol Name
-            .column(Column::initial(80.0).at_least(60.0))   // Type
-            .column(Column::remainder().at_least(200.0))    // Comment
-            .column(Column::initial(80.0).at_least(60.0))   // Page
+            .column(Column::exact(40.0)); // Checkbox
+        for column in &order {
+            builder = builder.column(column_width(*column, &self.column_widths));
+        }
+        if std::mem::take(&mut self.reset_widths) {
+            builder.reset();
+        }
+        // Keep the keyboard cursor visible
+        if let Some(position) = focused_position {
+            builder = builder.scroll_to_row(position, None);
+        }
+
+        builder
             .max_scroll_height(available_height)
-            .header(25.0, |mut header| {
+            .header(header_height, |mut header| {
                 // Checkbox header
                 header.col(|ui| {
                     ui.strong("✓");
                 });
 
-                // Address header
-                header.col(|ui| {
-                    let response = ui.button("Address");
-                    if response.clicked() {
-                        self.toggle_sort(SortColumn::Address, table);
-                    }
-                    self.show_sort_indicator(ui, SortColumn::Address);
-                });
+                for &column in &order {
+                    header.col(|ui| {
+                        if column != ColumnId::Comment {
+                            self.column_widths.insert(width_key(column), ui.max_rect().width().round());
+                        }
 
-                // Symbol Name header
-                header.col(|ui| {
-                    let response = ui.button("Symbol Name");
-                    if response.clicked() {
-                        self.toggle_sort(SortColumn::Name, table);
-                    }
-                    self.show_sort_indicator(ui, SortColumn::Name);
-                });
+                        ui.vertical(|ui| {
+                            ui.horizontal(|ui| {
+                                self.column_drag_handle(ui, column);
+                                if ui.button(t!(column.title())).clicked() {
+                                    self.toggle_sort(column);
+                                }
+                                self.show_sort_indicator(ui, column);
+                            });
+                            if let Some(value) = column_filter_mut(column, &mut self.filter) {
+                                column_filter(ui, value);
+                            }
+                        });
+                    });
+                }
+            })
+            .body(|body| {
+                // Only the visible rows are laid out
+                body.rows(row_height, cache.rows.len(), |mut row| {
+                    let index = match cache.rows[row.index()] {
+                        DisplayRow::Entry(index) => index,
+                        DisplayRow::Deleted(index) => {
+                            // Only in the baseline, struck through and not editable
+                            let entry = &table.deleted[index];
+                            row.col(|_| {});
+                            for &column in &order {
+                                row.col(|ui| deleted_cell(ui, column, entry));
+                            }
+                            return;
+                        }
+                        DisplayRow::Group(group) => {
+                            let members = cache.members(group);
+                            let selected = members.iter().filter(|&&index| table.entries[index].selected).count();
 
-                // Type header
-                header.col(|ui| {
-                    let response = ui.button("Type");
-                    if response.clicked() {
-                        self.toggle_sort(SortColumn::Type, table);
+                            // Group checkbox selects everything inside
+                            row.col(|ui| {
+                                let mut checked = selected == members.len();
+                                let checkbox = egui::Checkbox::without_text(&mut checked)
+                                    .indeterminate(selected > 0 && selected < members.len());
+                                if ui.add(checkbox).clicked() {
+                                    group_checkbox = Some((group, checked));
+                                }
+                            });
+                            row.col(|ui| {
+                                let label = &cache.groups[group].label;
+                                let arrow = if self.collapsed.contains(label) { "▶" } else { "▼" };
+                                ui.strong(format!("{} {} ({})", arrow, self.group_by.header(label), members.len()));
+                            });
+
+                            if row.response().clicked() {
+                                clicked_group = Some(group);
+                            }
+                            return;
+                        }
+                    };
+                    let entry = &mut table.entries[index];
+                    let mut edited = false;
+                    row.set_selected(focused_row == Some(index));
+
+                    // Checkbox
+                    row.col(|ui| {
+                        if ui.checkbox(&mut entry.selected, "").clicked() {
+                            clicked_checkbox = Some(index);
+                        }
+                    });
+
+                    let dimmed = entry.confidence < LOW_CONFIDENCE;
+                    for &column in &order {
+                        row.col(|ui| {
+                            // Symbol name guessed without function text nearby
+                            if dimmed {
+                                ui.visuals_mut().override_text_color = Some(ui.visuals().weak_text_color());
+                            }
+                            edited |= body_cell(ui, column, entry, tag_colors);
+                        });
                     }
-                    self.show_sort_indicator(ui, SortColumn::Type);
-                });
 
-                // Comment header
-                header.col(|ui| {
-                    let response = ui.button("Comment");
-                    if response.clicked() {
-                        self.toggle_sort(SortColumn::Comment, table);
+                    if edited {
+                        table.dirty = true;
+                        cell_edited = true;
                     }
-                    self.show_sort_indicator(ui, SortColumn::Comment);
-                });
 
-                // Page header
-                header.col(|ui| {
-                    let response = ui.button("Page");
+                    let response = row.response();
                     if response.clicked() {
-                        self.toggle_sort(SortColumn::Page, table);
+                        clicked_row = Some(index);
                     }
-                    self.show_sort_indicator(ui, SortColumn::Page);
+
+                    response.context_menu(|ui| {
+                        if ui.button(t!("Insert row above")).clicked() {
+                            row_action = Some(RowAction::InsertAbove(index));
+                            ui.close_menu();
+                        }
+                        if ui.button(t!("Duplicate row")).clicked() {
+                            row_action = Some(RowAction::Duplicate(index));
+                            ui.close_menu();
+                        }
+                        if ui.button(t!("Delete row")).clicked() {
+                            row_action = Some(RowAction::Delete(index));
+                            ui.close_menu();
+                        }
+                        ui.separator();
+                        if ui.button(t!("Add tag…")).clicked() {
+                            row_action = Some(RowAction::AddTag(index));
+                            ui.close_menu();
+                        }
+                        if !entry.tags.is_empty() {
+                            ui.menu_button(t!("Remove tag"), |ui| {
+                                for tag in &entry.tags {
+                                    if ui.button(tag).clicked() {
+                                        row_action = Some(RowAction::RemoveTag(index, tag.clone()));
+                                        ui.close_menu();
+                                    }
+                                }
+                            });
+                        }
+                    });
                 });
+            });
+
+        // Shift+click selects a range, Ctrl+click toggles a single row
+        if let Some(index) = clicked_row {
+            if modifiers.shift {
+                select_range(&mut table.entries, &cache.visible, self.anchor_row, index, &mut self.shift_range);
+            } else {
+                if modifiers.command {
+                    let entry = &mut table.entries[index];
+                    entry.selected = !entry.selected;
+                }
+                self.anchor_row = Some(index);
+                self.shift_range.clear();
+            }
+            self.selected_row_index = Some(index);
+            ui.memory_mut(|memory| memory.request_focus(table_nav_id()));
+        }
+        if let Some(index) = clicked_checkbox {
+            if modifiers.shift {
+                select_range(&mut table.entries, &cache.visible, self.anchor_row, index, &mut self.shift_range);
+            } else {
+                self.anchor_row = Some(index);
+                self.shift_range.clear();
+            }
+        }
+
+        if let Some((group, state)) = group_checkbox {
+            for &index in cache.members(group) {
+                table.entries[index].selected = state;
+            }
+        }
+
+        self.rows = cache;
+
+        // Fold or unfold a clicked group header
+        if let Some(group) = clicked_group {
+            let label = self.rows.groups[group].label.clone();
+            if !self.collapsed.remove(&label) {
+                self.collapsed.insert(label);
+            }
+            self.rows.build_rows(&self.collapsed);
+        }
+
+        // Row indices move after these
+        let rows_changed = matches!(
+            row_action,
+            Some(RowAction::Delete(_) | RowAction::Duplicate(_) | RowAction::InsertAbove(_))
+        );
+
+        match row_action {
+            Some(RowAction::Delete(index)) => {
+                table.remove_entry(index);
+            }
+            Some(RowAction::Duplicate(index)) => table.duplicate_entry(index),
+            Some(RowAction::InsertAbove(index)) => {
+                table.insert_entry(index, PlcEntry::new(String::new(), String::new(), String::new()));
+            }
+            Some(RowAction::AddTag(row)) => {
+                self.tag_popup = Some(TagPopup { row, tag: String::new(), color: DEFAULT_TAG_COLOR });
+            }
+            Some(RowAction::RemoveTag(index, tag)) => {
+                table.entries[index].remove_tag(&tag);
+                table.mark_changed();
+            }
+            None => {}
+        }
+
+        if rows_changed {
+            self.selected_row_index = None;
+            self.anchor_row = None;
+            self.shift_range.clear();
+            self.detail_row = None;
+            self.tag_popup = None;
+        }
+
+        self.show_detail_popup(ui.ctx(), table);
+        self.show_tag_popup(ui.ctx(), table, tag_colors);
+        cell_edited
+    }
+
+    /// Rebuild the cached rows when the table or sort changed, or when the
+    /// filter has been left alone for `FILTER_DEBOUNCE` seconds
+    fn refresh_rows(&mut self, ctx: &egui::Context, table: &PlcTable) {
+        let now = ctx.input(|i| i.time);
+        if self.filter != self.pending_filter {
+            self.pending_filter = self.filter.clone();
+            self.filter_edited_at = now;
+        }
+
+        let key = (table.revision, self.sort_column, self.sort_ascending, self.group_by);
+        let table_changed = self.rows.key != Some(key);
+        if !table_changed && self.rows.filter == self.filter {
+            return;
+        }
+
+        let waited = now - self.filter_edited_at;
+        if !table_changed && waited < FILTER_DEBOUNCE {
+            ctx.request_repaint_after(Duration::from_secs_f64(FILTER_DEBOUNCE - waited));
+            return;
+        }
+
+        let indices: Vec<usize> = table.entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| self.filter.matches(entry))
+            .map(|(index, _)| index)
+            .collect();
+        let (indices, groups) = arrange(indices, &table.entries, self.sort_column, self.sort_ascending, self.group_by);
+
+        let deleted = table.deleted
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| self.filter.matches(entry))
+            .map(|(index, _)| index)
+            .collect();
+
+        self.rows = RowCache {
+            key: Some(key),
+            filter: self.filter.clone(),
+            indices,
+            groups,
+            deleted,
+            has_changes: !table.deleted.is_empty() || table.entries.iter().any(|entry| entry.entry_status.is_changed()),
+            ..Default::default()
+        };
+        self.rows.build_rows(&self.collapsed);
+    }
+
+    /// Arrow keys and Home/End (with Shift selecting the rows passed), Space,
+    /// Enter and Ctrl+A while the table has keyboard focus
+    fn handle_keyboard(&mut self, ui: &mut egui::Ui, table: &mut PlcTable, cache: &RowCache) {
+        let nav_id = table_nav_id();
+        ui.memory_mut(|memory| memory.interested_in_focus(nav_id));
+
+        if !ui.memory(|memory| memory.has_focus(nav_id)) {
+            return;
+        }
+
+        // Arrow keys would otherwise move egui focus to the next widget
+        ui.memory_mut(|memory| {
+            memory.set_focus_lock_filter(nav_id, egui::EventFilter {
+                vertical_arrows: true,
+                ..Default::default()
             })
-            .body(|mut body| {
-                // Filter entries
-                let entries: Vec<&mut PlcEntry> = table.entries
-                    .iter_mut()
-                    .filter(|entry| entry.matches_filter(filter))
-                    .collect();
-
-                for entry in entries {
-                    let row_height = 22.0;
-                    let data_type_color = entry.data_type.color();
-
-                    body.row(row_height, |mut row| {
-                        // Checkbox
-                        row.col(|ui| {
-                            ui.checkbox(&mut entry.selected, "");
-                        });
+        });
 
-                        // Address with color indicator
-                        row.col(|ui| {
-                            ui.horizontal(|ui| {
-                                // Color indicator dot
-                                let (response, painter) = ui.allocate_painter(egui::vec2(8.0, 8.0), egui::Sense::hover());
-                                painter.circle_filled(
-                                    response.rect.center(),
-                                    4.0,
-                                    data_type_color,
-                                );
-
-                                ui.label(&entry.address);
-                            });
-                        });
+        // Select all filtered rows
+        if ui.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::A)) {
+            for &index in &cache.indices {
+                table.entries[index].selected = true;
+            }
+            ui.ctx().request_repaint();
+        }
 
-                        // Symbol Name
-                        row.col(|ui| {
-                            ui.label(&entry.symbol_name);
-                        });
+        for key in [egui::Key::ArrowDown, egui::Key::ArrowUp, egui::Key::Home, egui::Key::End] {
+            // Before the plain key, which also matches with Shift held
+            if ui.input_mut(|i| i.consume_key(egui::Modifiers::SHIFT, key)) {
+                let anchor = self.anchor_row.or(self.selected_row_index);
+                self.selected_row_index = next_row(&cache.visible, self.selected_row_index, key);
+                if let Some(index) = self.selected_row_index {
+                    select_range(&mut table.entries, &cache.visible, anchor, index, &mut self.shift_range);
+                    self.anchor_row = anchor;
+                }
+                self.scroll_to_selected = true;
+                ui.ctx().request_repaint();
+            } else if ui.input_mut(|i| i.consume_key(egui::Modifiers::NONE, key)) {
+                self.selected_row_index = next_row(&cache.visible, self.selected_row_index, key);
+                self.anchor_row = self.selected_row_index; // Shift+arrow extends from here
+                self.shift_range.clear();
+                self.scroll_to_selected = true;
+                ui.ctx().request_repaint();
+            }
+        }
 
-                        // Type
-                        row.col(|ui| {
-                            ui.colored_label(data_type_color, entry.data_type.to_string());
-                        });
+        let Some(index) = self.selected_row_index.filter(|index| cache.visible.contains(index)) else {
+            return;
+        };
 
-                        // Comment (editable)
-                        row.col(|ui| {
-                            ui.text_edit_singleline(&mut entry.comment);
-                        });
+        if ui.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::Space)) {
+            let entry = &mut table.entries[index];
+            entry.selected = !entry.selected;
+            ui.ctx().request_repaint();
+        }
 
-                        // Page
-                        row.col(|ui| {
-                            ui.label(&entry.page);
-                        });
+        if ui.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::Enter)) {
+            self.detail_row = Some(index);
+            ui.ctx().request_repaint();
+        }
+    }
+
+    fn show_detail_popup(&mut self, ctx: &egui::Context, table: &PlcTable) {
+        let Some(entry) = self.detail_row.and_then(|index| table.entries.get(index)) else {
+            self.detail_row = None;
+            return;
+        };
+
+        let mut open = true;
+
+        egui::Window::new(t!("Entry details"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                egui::Grid::new("entry_details")
+                    .num_columns(2)
+                    .spacing([8.0, 6.0])
+                    .show(ui, |ui| {
+                        for (label, value) in [
+                            ("Address", entry.address.clone()),
+                            ("Symbol Name", entry.symbol_name.clone()),
+                            ("Type", entry.data_type.to_string()),
+                            ("Comment", entry.comment.clone()),
+                            ("Page", entry.page.clone()),
+                            ("Tags", entry.tags_text()),
+                        ] {
+                            ui.strong(t!(label));
+                            ui.label(value);
+                            ui.end_row();
+                        }
+                    });
+            });
+
+        if !open {
+            self.detail_row = None;
+        }
+    }
+
+    /// Name and color of a new tag, a known tag name brings its saved color
+    fn show_tag_popup(
+        &mut self,
+        ctx: &egui::Context,
+        table: &mut PlcTable,
+        tag_colors: &mut HashMap<String, egui::Color32>,
+    ) {
+        let Some(popup) = self.tag_popup.as_mut().filter(|popup| popup.row < table.entries.len()) else {
+            self.tag_popup = None;
+            return;
+        };
+
+        let mut open = true;
+        let mut close = false;
+
+        egui::Window::new(t!("Add tag"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    let response = ui.add(
+                        egui::TextEdit::singleline(&mut popup.tag)
+                            .desired_width(160.0)
+                            .hint_text(t!("e.g. Critical")),
+                    );
+                    if response.changed() {
+                        if let Some(color) = tag_colors.get(popup.tag.trim()) {
+                            popup.color = *color;
+                        }
+                    }
+                    egui::color_picker::color_edit_button_srgba(
+                        ui,
+                        &mut popup.color,
+                        egui::color_picker::Alpha::Opaque,
+                    );
+                });
+
+                // Known tags are one click away
+                if !tag_colors.is_empty() {
+                    let mut known: Vec<&String> = tag_colors.keys().collect();
+                    known.sort();
+                    ui.horizontal_wrapped(|ui| {
+                        for tag in known {
+                            if tag_badge(ui, tag, tag_colors[tag]).clicked() {
+                                popup.tag = tag.clone();
+                                popup.color = tag_colors[tag];
+                            }
+                        }
                     });
                 }
+
+                ui.add_space(4.0);
+                ui.horizontal(|ui| {
+                    let tag = popup.tag.trim();
+                    if ui.add_enabled(!tag.is_empty(), egui::Button::new(t!("Add"))).clicked() {
+                        tag_colors.insert(tag.to_string(), popup.color);
+                        if table.entries[popup.row].add_tag(tag) {
+                            table.mark_changed();
+                        }
+                        close = true;
+                    }
+                    if ui.button(t!("Cancel")).clicked() {
+                        close = true;
+                    }
+                });
             });
+
+        if !open || close {
+            self.tag_popup = None;
+        }
     }
 
-    fn toggle_sort(&mut self, column: SortColumn, table: &mut PlcTable) {
-        if self.sort_column == column {
+    fn toggle_sort(&mut self, column: ColumnId) {
+        if self.sort_column == Some(column) {
             self.sort_ascending = !self.sort_ascending;
         } else {
-            self.sort_column = column.clone();
+            self.sort_column = Some(column);
             self.sort_ascending = true;
         }
-
-        self.apply_sort(table);
     }
 
-    fn apply_sort(&self, table: &mut PlcTable) {
-        match self.sort_column {
-            SortColumn::Address => {
-                if self.sort_ascending {
-                    table.sort_by_address();
-                } else {
-                    table.sort_by_address();
-                    table.entries.reverse();
+    /// Checkbox and up/down buttons per column, hidden ones included
+    fn show_columns_menu(&mut self, ui: &mut egui::Ui) {
+        ui.label(egui::RichText::new(t!("Shown in the table, copied rows and CSV/Excel exports")).weak());
+
+        let last = self.columns.settings().len() - 1;
+        let mut moved = None;
+        for (position, setting) in self.columns.settings().to_vec().into_iter().enumerate() {
+            ui.horizontal(|ui| {
+                if ui.add_enabled(position > 0, egui::Button::new("⬆").small()).clicked() {
+                    moved = Some((position, true));
+                }
+                if ui.add_enabled(position < last, egui::Button::new("⬇").small()).clicked() {
+                    moved = Some((position, false));
                 }
+
+                let mut visible = setting.visible;
+                let checkbox = ui.add_enabled(
+                    setting.column != ColumnId::Address,
+                    egui::Checkbox::new(&mut visible, t!(setting.column.title())),
+                );
+                if checkbox.changed() {
+                    self.columns.set_visible(setting.column, visible);
+                }
+            });
+        }
+        if let Some((position, up)) = moved {
+            self.columns.move_column(position, up);
+        }
+
+        ui.separator();
+        if ui.button(t!("Reset columns")).clicked() {
+            self.columns = ColumnSet::default();
+            ui.close_menu();
+        }
+        if ui.button(t!("Reset column widths")).clicked() {
+            self.column_widths.clear();
+            self.reset_widths = true;
+            ui.close_menu();
+        }
+    }
+
+    /// Grip in front of a header label, dragging it sideways moves the column
+    fn column_drag_handle(&mut self, ui: &mut egui::Ui, column: ColumnId) {
+        let (rect, _) = ui.allocate_exact_size(egui::vec2(12.0, ui.spacing().interact_size.y), egui::Sense::hover());
+        // Keyed by column so the drag continues after the header moved
+        let response = ui
+            .interact(rect, egui::Id::new(("table_column_drag", column)), egui::Sense::drag())
+            .on_hover_cursor(egui::CursorIcon::Grab)
+            .on_hover_text(t!("Drag to move the column"));
+
+        ui.painter().text(
+            rect.center(),
+            egui::Align2::CENTER_CENTER,
+            "↔",
+            egui::TextStyle::Body.resolve(ui.style()),
+            ui.visuals().weak_text_color(),
+        );
+
+        if response.drag_started() {
+            self.column_drag = 0.0;
+        }
+        if !response.dragged() {
+            return;
+        }
+
+        self.column_drag += response.drag_delta().x;
+        if self.column_drag.abs() < COLUMN_DRAG_THRESHOLD {
+            return;
+        }
+
+        // Hidden columns in between keep their place
+        let shown = self.columns.visible();
+        if let Some(position) = shown.iter().position(|c| *c == column) {
+            let target = if self.column_drag > 0.0 { position + 1 } else { position.wrapping_sub(1) };
+            if let Some(&neighbour) = shown.get(target) {
+                self.columns.swap(column, neighbour);
             }
-            SortColumn::Name => {
-                if self.sort_ascending {
-                    table.sort_by_name();
+        }
+        self.column_drag = 0.0;
+        ui.ctx().request_repaint();
+    }
+
+    fn show_sort_indicator(&self, ui: &mut egui::Ui, column: ColumnId) {
+        if self.sort_column == Some(column) {
+            let arrow = if self.sort_ascending { "▲" } else { "▼" };
+            ui.label(arrow);
+        }
+    }
+}
+
+/// Contents of one data cell, returns whether the entry was edited
+fn body_cell(
+    ui: &mut egui::Ui,
+    column: ColumnId,
+    entry: &mut PlcEntry,
+    tag_colors: &HashMap<String, egui::Color32>,
+) -> bool {
+    let data_type_color = themes::data_type_color(&entry.data_type);
+
+    match column {
+        // Address (editable) with color indicator
+        ColumnId::Address => {
+            ui.horizontal(|ui| {
+                // Color indicator dot, hollow for entries a merge kept although they were removed
+                let (response, painter) = ui.allocate_painter(egui::vec2(8.0, 8.0), egui::Sense::hover());
+                if entry.removed {
+                    painter.circle_stroke(response.rect.center(), 3.5, egui::Stroke::new(1.0, data_type_color));
+                    response.on_hover_text(t!("No longer in the latest extraction"));
                 } else {
-                    table.sort_by_name();
-                    table.entries.reverse();
+                    painter.circle_filled(
+                        response.rect.center(),
+                        4.0,
+                        data_type_color,
+                    );
                 }
-            }
-            SortColumn::Type => {
-                if self.sort_ascending {
-                    table.sort_by_type();
+                status_dot(ui, &entry.entry_status);
+
+                let mut address = entry.address.clone();
+                if ui.text_edit_singleline(&mut address).changed() {
+                    entry.set_address(address);
+                    true
                 } else {
-                    table.sort_by_type();
-                    table.entries.reverse();
+                    false
                 }
+            }).inner
+        }
+        ColumnId::Name => {
+            let edited = ui.text_edit_singleline(&mut entry.symbol_name).changed();
+            if edited {
+                entry.confidence = 1.0; // Typed by hand, no longer dimmed
             }
-            SortColumn::Comment => {
-                table.entries.sort_by(|a, b| {
-                    if self.sort_ascending {
-                        a.comment.cmp(&b.comment)
-                    } else {
-                        b.comment.cmp(&a.comment)
-                    }
-                });
+            edited
+        }
+        ColumnId::Type => {
+            ui.colored_label(data_type_color, entry.data_type.to_string());
+            false
+        }
+        ColumnId::Comment => {
+            let edited = ui.text_edit_singleline(&mut entry.comment).changed();
+            entry.comment_edited |= edited;
+            edited
+        }
+        ColumnId::Page => {
+            ui.label(&entry.page);
+            false
+        }
+        ColumnId::Tags => {
+            for tag in &entry.tags {
+                tag_badge(ui, tag, tag_colors.get(tag).copied().unwrap_or(DEFAULT_TAG_COLOR));
             }
-            SortColumn::Page => {
-                table.entries.sort_by(|a, b| {
-                    if self.sort_ascending {
-                        a.page.cmp(&b.page)
-                    } else {
-                        b.page.cmp(&a.page)
-                    }
-                });
+            false
+        }
+        // Derived from the symbol name or set by the parser
+        ColumnId::Module | ColumnId::DeviceTag | ColumnId::FunctionText | ColumnId::Confidence => {
+            ui.label(column.value(entry));
+            false
+        }
+    }
+}
+
+/// Contents of one cell of a row from `PlcTable::deleted`
+fn deleted_cell(ui: &mut egui::Ui, column: ColumnId, entry: &PlcEntry) {
+    ui.horizontal(|ui| {
+        if column == ColumnId::Address {
+            status_dot(ui, &entry.entry_status);
+        }
+        let text = column.value(entry);
+        if !text.is_empty() {
+            ui.label(egui::RichText::new(text).strikethrough().weak());
+        }
+    });
+}
+
+/// Green, orange or gray dot for entries new, modified or deleted since the baseline
+fn status_dot(ui: &mut egui::Ui, status: &EntryStatus) {
+    let Some(color) = status.color() else {
+        return;
+    };
+
+    let (response, painter) = ui.allocate_painter(egui::vec2(8.0, 8.0), egui::Sense::hover());
+    painter.circle_filled(response.rect.center(), 3.0, color);
+    let hover = match status {
+        EntryStatus::Modified(old_name) => t!("Modified, was \"{}\"", old_name),
+        status => t!(status.label()),
+    };
+    response.on_hover_text(hover);
+}
+
+/// Small rounded label in the tag's color, with dark or light text for contrast
+fn tag_badge(ui: &mut egui::Ui, tag: &str, color: egui::Color32) -> egui::Response {
+    let text_color = if badge_text_is_dark(color) { egui::Color32::BLACK } else { egui::Color32::WHITE };
+    egui::Frame::none()
+        .fill(color)
+        .rounding(6.0)
+        .inner_margin(egui::Margin::symmetric(5.0, 1.0))
+        .show(ui, |ui| {
+            ui.add(egui::Label::new(egui::RichText::new(tag).small().color(text_color)).selectable(false))
+        })
+        .inner
+        .interact(egui::Sense::click())
+}
+
+/// Dark text on light badge colors (perceived brightness above the middle)
+fn badge_text_is_dark(color: egui::Color32) -> bool {
+    let brightness = 0.299 * color.r() as f32 + 0.587 * color.g() as f32 + 0.114 * color.b() as f32;
+    brightness > 150.0
+}
+
+/// Sort and group `indices`, returning them in display order with the groups
+fn arrange(
+    mut indices: Vec<usize>,
+    entries: &[PlcEntry],
+    column: Option<ColumnId>,
+    ascending: bool,
+    group_by: GroupBy,
+) -> (Vec<usize>, Vec<RowGroup>) {
+    sort_indices(&mut indices, entries, column, ascending);
+    if group_by == GroupBy::None {
+        return (indices, Vec::new());
+    }
+    group_indices(&indices, entries, group_by)
+}
+
+/// Split sorted indices into groups ordered by their value (pages naturally,
+/// "2" before "10"), keeping the sort within each group
+fn group_indices(indices: &[usize], entries: &[PlcEntry], group_by: GroupBy) -> (Vec<usize>, Vec<RowGroup>) {
+    let mut members: Vec<(String, Vec<usize>)> = Vec::new();
+    let mut positions: HashMap<String, usize> = HashMap::new();
+
+    for &index in indices {
+        let value = group_by.value(&entries[index]);
+        let position = match positions.get(&value) {
+            Some(&position) => position,
+            None => {
+                positions.insert(value.clone(), members.len());
+                members.push((value, Vec::new()));
+                members.len() - 1
             }
-            SortColumn::None => {}
+        };
+        members[position].1.push(index);
+    }
+
+    members.sort_by(|a, b| natural_sort(&a.0, &b.0).then_with(|| a.0.cmp(&b.0)));
+
+    let mut ordered = Vec::with_capacity(indices.len());
+    let mut groups = Vec::with_capacity(members.len());
+    for (label, group) in members {
+        groups.push(RowGroup { label, start: ordered.len(), len: group.len() });
+        ordered.extend(group);
+    }
+
+    (ordered, groups)
+}
+
+/// Sort row indices by `column`, the entries themselves keep their order
+fn sort_indices(indices: &mut [usize], entries: &[PlcEntry], column: Option<ColumnId>, ascending: bool) {
+    let Some(column) = column else {
+        return;
+    };
+
+    let compare = |a: &PlcEntry, b: &PlcEntry| -> Ordering {
+        match column {
+            ColumnId::Address => natural_sort(&a.address, &b.address),
+            ColumnId::Module => natural_sort(&a.module_text(), &b.module_text()),
+            column => column.value(a).cmp(&column.value(b)),
+        }
+    };
+
+    indices.sort_by(|&a, &b| {
+        let ordering = compare(&entries[a], &entries[b]);
+        if ascending { ordering } else { ordering.reverse() }
+    });
+}
+
+/// Select the rows from `anchor` to `index` in display order, both included,
+/// in place of `previous`, the rows the last range from this anchor selected.
+/// Without a visible anchor only `index` is selected.
+fn select_range(entries: &mut [PlcEntry], indices: &[usize], anchor: Option<usize>, index: usize, previous: &mut Vec<usize>) {
+    let position = |row: usize| indices.iter().position(|&i| i == row);
+
+    for row in previous.drain(..) {
+        if let Some(entry) = entries.get_mut(row) {
+            entry.selected = false;
         }
     }
 
-    fn show_sort_indicator(&self, ui: &mut egui::Ui, column: SortColumn) {
-        if self.sort_column == column {
-            let arrow = if self.sort_ascending { "▲" } else { "▼" };
-            ui.label(arrow);
+    let range = match (anchor.and_then(position), position(index)) {
+        (Some(a), Some(b)) => &indices[a.min(b)..=a.max(b)],
+        _ => std::slice::from_ref(&index),
+    };
+
+    for &row in range {
+        entries[row].selected = true;
+    }
+    previous.extend_from_slice(range);
+}
+
+/// Focus id of the table body, which receives the navigation keys
+fn table_nav_id() -> egui::Id {
+    egui::Id::new("plc_table_keyboard_nav")
+}
+
+/// Row the keyboard cursor moves to, as an index into `table.entries`.
+/// `indices` are the visible (filtered) rows in display order.
+fn next_row(indices: &[usize], current: Option<usize>, key: egui::Key) -> Option<usize> {
+    let position = current.and_then(|current| indices.iter().position(|&index| index == current));
+
+    let next = match (key, position) {
+        (egui::Key::Home, _) | (egui::Key::ArrowDown | egui::Key::ArrowUp, None) => 0,
+        (egui::Key::End, _) => indices.len().saturating_sub(1),
+        (egui::Key::ArrowDown, Some(position)) => (position + 1).min(indices.len().saturating_sub(1)),
+        (egui::Key::ArrowUp, Some(position)) => position.saturating_sub(1),
+        (_, position) => position?,
+    };
+
+    indices.get(next).copied()
+}
+
+/// Small search field shown under a column header
+fn column_filter(ui: &mut egui::Ui, value: &mut String) {
+    ui.add(
+        egui::TextEdit::singleline(value)
+            .hint_text(t!("Filter..."))
+            .desired_width(f32::INFINITY)
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use egui::Key;
+
+    #[test]
+    fn test_next_row_moves_through_filtered_rows() {
+        let indices = [2, 5, 7];
+
+        assert_eq!(next_row(&indices, None, Key::ArrowDown), Some(2));
+        assert_eq!(next_row(&indices, Some(2), Key::ArrowDown), Some(5));
+        assert_eq!(next_row(&indices, Some(7), Key::ArrowDown), Some(7));
+        assert_eq!(next_row(&indices, Some(5), Key::ArrowUp), Some(2));
+        assert_eq!(next_row(&indices, Some(2), Key::ArrowUp), Some(2));
+        assert_eq!(next_row(&indices, Some(5), Key::End), Some(7));
+        assert_eq!(next_row(&indices, Some(7), Key::Home), Some(2));
+    }
+
+    #[test]
+    fn test_sort_indices_keeps_entries() {
+        let entries: Vec<PlcEntry> = ["I10.0", "I2.0", "I1.5"]
+            .iter()
+            .map(|address| PlcEntry::new(address.to_string(), String::new(), String::new()))
+            .collect();
+
+        let mut indices = vec![0, 1, 2];
+        sort_indices(&mut indices, &entries, Some(ColumnId::Address), true);
+        assert_eq!(indices, vec![2, 1, 0]);
+
+        sort_indices(&mut indices, &entries, Some(ColumnId::Address), false);
+        assert_eq!(indices, vec![0, 1, 2]);
+
+        // Filtered subsets sort the same way
+        let mut subset = vec![0, 2];
+        sort_indices(&mut subset, &entries, Some(ColumnId::Address), true);
+        assert_eq!(subset, vec![2, 0]);
+    }
+
+    #[test]
+    fn test_select_range_follows_display_order() {
+        let mut entries: Vec<PlcEntry> = (0..6)
+            .map(|i| PlcEntry::new(format!("I0.{}", i), String::new(), String::new()))
+            .collect();
+        let indices = [5, 3, 1, 0]; // Sorted and filtered, 2 and 4 hidden
+
+        select_range(&mut entries, &indices, Some(1), 5, &mut Vec::new());
+        let selected: Vec<usize> = (0..6).filter(|&i| entries[i].selected).collect();
+        assert_eq!(selected, vec![1, 3, 5]);
+
+        // Hidden anchor selects only the clicked row
+        entries.iter_mut().for_each(|entry| entry.selected = false);
+        select_range(&mut entries, &indices, Some(2), 0, &mut Vec::new());
+        let selected: Vec<usize> = (0..6).filter(|&i| entries[i].selected).collect();
+        assert_eq!(selected, vec![0]);
+    }
+
+    #[test]
+    fn test_grouping_keeps_sort_within_groups() {
+        let entries: Vec<PlcEntry> = [("I0.2", "10"), ("I0.1", "2"), ("I0.0", "10"), ("I0.3", "2")]
+            .iter()
+            .map(|(address, page)| PlcEntry::new(address.to_string(), String::new(), page.to_string()))
+            .collect();
+
+        let (indices, groups) = arrange(vec![0, 1, 2, 3], &entries, Some(ColumnId::Address), true, GroupBy::Page);
+        assert_eq!(indices, vec![1, 3, 2, 0]); // Page 2 before page 10, addresses ascending inside
+        assert_eq!(groups[0], RowGroup { label: "2".to_string(), start: 0, len: 2 });
+        assert_eq!(groups[1], RowGroup { label: "10".to_string(), start: 2, len: 2 });
+
+        let mut cache = RowCache { indices, groups, ..Default::default() };
+        cache.build_rows(&HashSet::from(["2".to_string()]));
+        assert_eq!(cache.rows, vec![
+            DisplayRow::Group(0),
+            DisplayRow::Group(1),
+            DisplayRow::Entry(2),
+            DisplayRow::Entry(0),
+        ]);
+        assert_eq!(cache.visible, vec![2, 0]);
+    }
+
+    #[test]
+    fn test_header_drag_skips_hidden_columns() {
+        let mut columns = ColumnSet::default();
+        columns.set_visible(ColumnId::Comment, false);
+        let mut view = TableView::new(&columns, &HashMap::new());
+
+        // One frame per batch of pointer events, returns where the Type grip was drawn
+        let ctx = egui::Context::default();
+        let frame = |view: &mut TableView, events: Vec<egui::Event>| {
+            let mut grip = egui::Pos2::ZERO;
+            let input = egui::RawInput { events, time: Some(ctx.input(|i| i.time) + 0.1), ..Default::default() };
+            let _ = ctx.run(input, |ctx| {
+                egui::CentralPanel::default().show(ctx, |ui| {
+                    grip = ui.cursor().min + egui::vec2(6.0, ui.spacing().interact_size.y / 2.0);
+                    view.column_drag_handle(ui, ColumnId::Type);
+                });
+            });
+            grip
+        };
+
+        // Press on the grip and drag it right past the threshold
+        let grip = frame(&mut view, Vec::new());
+        let press = egui::Event::PointerButton {
+            pos: grip,
+            button: egui::PointerButton::Primary,
+            pressed: true,
+            modifiers: Default::default(),
+        };
+        frame(&mut view, vec![egui::Event::PointerMoved(grip)]);
+        frame(&mut view, vec![press]);
+        frame(&mut view, vec![egui::Event::PointerMoved(grip + egui::vec2(COLUMN_DRAG_THRESHOLD + 10.0, 0.0))]);
+
+        // Type and Page are neighbours in the table, the hidden Comment stays between them
+        assert_eq!(view.columns().visible()[..4], [ColumnId::Address, ColumnId::Name, ColumnId::Page, ColumnId::Type]);
+        assert_eq!(view.columns().settings()[3].column, ColumnId::Comment);
+    }
+
+    #[test]
+    fn test_badge_text_contrast() {
+        assert!(badge_text_is_dark(egui::Color32::from_rgb(255, 193, 7)));
+        assert!(!badge_text_is_dark(DEFAULT_TAG_COLOR));
+    }
+
+    #[test]
+    fn test_saved_width_key() {
+        assert_eq!(width_key(ColumnId::Name), "Name");
+        assert_eq!(width_key(ColumnId::Address), "Address");
+    }
+
+    #[test]
+    fn test_shift_arrows_grow_and_shrink_the_range() {
+        let mut table = PlcTable::new("Keys".to_string());
+        for bit in 0..6 {
+            table.add_entry(PlcEntry::new(format!("I0.{}", bit), String::new(), String::new()));
+        }
+        table.entries[5].selected = true; // Ctrl+clicked before, outside the range
+        let mut view = TableView::new(&ColumnSet::default(), &HashMap::new());
+
+        // One frame per key press, with the table focused
+        let ctx = egui::Context::default();
+        let press = |view: &mut TableView, table: &mut PlcTable, key: Key, modifiers: egui::Modifiers| {
+            let event = egui::Event::Key { key, physical_key: None, pressed: true, repeat: false, modifiers };
+            let input = egui::RawInput { events: vec![event], modifiers, ..Default::default() };
+            let _ = ctx.run(input, |ctx| {
+                egui::CentralPanel::default().show(ctx, |ui| {
+                    ui.memory_mut(|memory| memory.request_focus(table_nav_id()));
+                    view.refresh_rows(ui.ctx(), table);
+                    let cache = std::mem::take(&mut view.rows);
+                    view.handle_keyboard(ui, table, &cache);
+                    view.rows = cache;
+                });
+            });
+        };
+        let selected = |table: &PlcTable| -> Vec<usize> { (0..6).filter(|&i| table.entries[i].selected).collect() };
+
+        press(&mut view, &mut table, Key::ArrowDown, egui::Modifiers::NONE); // Cursor on the first row
+        for _ in 0..3 {
+            press(&mut view, &mut table, Key::ArrowDown, egui::Modifiers::SHIFT);
         }
+        assert_eq!(selected(&table), [0, 1, 2, 3, 5]);
+
+        for _ in 0..2 {
+            press(&mut view, &mut table, Key::ArrowUp, egui::Modifiers::SHIFT);
+        }
+        assert_eq!(selected(&table), [0, 1, 5]);
+
+        // Above the anchor the range turns around
+        press(&mut view, &mut table, Key::ArrowUp, egui::Modifiers::SHIFT);
+        press(&mut view, &mut table, Key::ArrowUp, egui::Modifiers::SHIFT);
+        assert_eq!(selected(&table), [0, 5]);
+    }
+
+    #[test]
+    fn test_next_row_with_hidden_or_no_rows() {
+        // Cursor on a row the filter hides starts over at the top
+        assert_eq!(next_row(&[2, 5], Some(3), Key::ArrowDown), Some(2));
+        assert_eq!(next_row(&[], None, Key::End), None);
     }
 }
\ No newline at end of file
@@ -1,67 +1,277 @@
 use eframe::egui;
+use std::sync::atomic::{AtomicBool, Ordering};
 use crate::config::Theme;
+use crate::models::{IssueSeverity, PlcDataType};
 
-pub fn apply_theme(ctx: &egui::Context, theme: &Theme) {
-    match theme {
-        Theme::Dark => apply_dark_theme(ctx),
-        Theme::Light => apply_light_theme(ctx),
+/// Set while the high-contrast theme is active, so the color helpers (log
+/// levels, data types, issue severities) can pick accessible colors
+pub static HIGH_CONTRAST: AtomicBool = AtomicBool::new(false);
+
+pub fn is_high_contrast() -> bool {
+    HIGH_CONTRAST.load(Ordering::Relaxed)
+}
+
+pub fn set_high_contrast(enabled: bool) {
+    HIGH_CONTRAST.store(enabled, Ordering::Relaxed);
+}
+
+/// Color of a data type in the tables and charts
+pub fn data_type_color(data_type: &PlcDataType) -> egui::Color32 {
+    if is_high_contrast() {
+        return match data_type {
+            PlcDataType::Input => egui::Color32::from_rgb(0, 255, 0),
+            PlcDataType::Output => egui::Color32::from_rgb(0, 255, 255),
+            PlcDataType::Memory => egui::Color32::from_rgb(255, 255, 0),
+            PlcDataType::Unknown => egui::Color32::WHITE,
+        };
     }
+
+    data_type.standard_color()
 }
 
-fn apply_dark_theme(ctx: &egui::Context) {
-    let mut style = (*ctx.style()).clone();
+/// Color of a validation issue severity
+pub fn severity_color(severity: IssueSeverity) -> egui::Color32 {
+    if is_high_contrast() {
+        return match severity {
+            IssueSeverity::Warning => egui::Color32::from_rgb(255, 255, 0),
+            IssueSeverity::Error => egui::Color32::from_rgb(255, 102, 102),
+        };
+    }
 
-    // Dark color scheme
-    style.visuals.dark_mode = true;
-    style.visuals.override_text_color = Some(egui::Color32::from_rgb(220, 220, 220));
+    match severity {
+        IssueSeverity::Warning => egui::Color32::from_rgb(255, 193, 7), // Amber
+        IssueSeverity::Error => egui::Color32::from_rgb(244, 67, 54),   // Red
+    }
+}
 
-    // Window and panel backgrounds
-    style.visuals.window_fill = egui::Color32::from_rgb(35, 35, 35);
-    style.visuals.panel_fill = egui::Color32::from_rgb(27, 27, 27);
-    style.visuals.faint_bg_color = egui::Color32::from_rgb(45, 45, 45);
+/// Every color the UI paints with, derived once per `Theme` so the egui
+/// visuals, the panel frames and the tab bar can't drift apart
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThemePalette {
+    pub panel: egui::Color32,         // Sidebar and status bar
+    pub toolbar: egui::Color32,       // Top toolbar, also dialogs and popups
+    pub tab_bar: egui::Color32,
+    pub content: egui::Color32,       // Central panel of every tab
+    pub border: egui::Color32,        // Panel edges and inactive tabs
+    pub widget: egui::Color32,        // Inactive buttons and tabs
+    pub widget_hovered: egui::Color32,
+    pub text: egui::Color32,
+    pub text_hovered: egui::Color32,
+    pub extreme: egui::Color32,       // Text edits and scroll bars
+    pub accent: egui::Color32,        // Active tab, pressed buttons, selection
+    pub accent_border: egui::Color32, // Outline of the active tab
+    pub selection: egui::Color32,     // Accent with some transparency
+    pub shadow: egui::Color32,        // Below the toolbar
+    pub status: StatusColors,
+}
 
-    // Button styling
-    style.visuals.widgets.inactive.weak_bg_fill = egui::Color32::from_rgb(50, 50, 50);
-    style.visuals.widgets.inactive.bg_fill = egui::Color32::from_rgb(60, 60, 60);
-    style.visuals.widgets.hovered.bg_fill = egui::Color32::from_rgb(70, 70, 70);
-    style.visuals.widgets.active.bg_fill = egui::Color32::from_rgb(55, 55, 55);
+/// Colors of the status badge in the toolbar
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StatusColors {
+    pub ready: egui::Color32, // Also used for "Completed"
+    pub connecting: egui::Color32,
+    pub extracting: egui::Color32,
+    pub processing: egui::Color32,
+    pub error: egui::Color32,
+}
 
-    // Selection color
-    style.visuals.selection.bg_fill = egui::Color32::from_rgb(64, 128, 255);
+const ACCENT: egui::Color32 = egui::Color32::from_rgb(26, 115, 232);
+const ACCENT_BORDER: egui::Color32 = egui::Color32::from_rgb(66, 135, 252);
 
-    // Spacing
-    style.spacing.item_spacing = egui::vec2(8.0, 6.0);
-    style.spacing.button_padding = egui::vec2(8.0, 4.0);
-    style.spacing.indent = 20.0;
+const STATUS_COLORS: StatusColors = StatusColors {
+    ready: egui::Color32::from_rgb(76, 175, 80),
+    connecting: egui::Color32::from_rgb(255, 193, 7),
+    extracting: egui::Color32::from_rgb(33, 150, 243),
+    processing: egui::Color32::from_rgb(156, 39, 176),
+    error: egui::Color32::from_rgb(244, 67, 54),
+};
+
+impl ThemePalette {
+    pub fn for_theme(theme: &Theme) -> Self {
+        match theme {
+            Theme::Dark => Self {
+                panel: egui::Color32::from_rgb(32, 33, 36),
+                toolbar: egui::Color32::from_rgb(32, 33, 36),
+                tab_bar: egui::Color32::from_rgb(40, 41, 44),
+                content: egui::Color32::from_rgb(24, 25, 26),
+                border: egui::Color32::from_rgb(60, 61, 64),
+                widget: egui::Color32::from_rgb(48, 49, 52),
+                widget_hovered: egui::Color32::from_rgb(64, 65, 68),
+                text: egui::Color32::from_rgb(200, 200, 200),
+                text_hovered: egui::Color32::WHITE,
+                extreme: egui::Color32::from_rgb(16, 17, 18),
+                accent: ACCENT,
+                accent_border: ACCENT_BORDER,
+                selection: egui::Color32::from_rgba_unmultiplied(26, 115, 232, 80),
+                shadow: egui::Color32::from_black_alpha(80),
+                status: STATUS_COLORS,
+            },
+            Theme::Light => Self {
+                panel: egui::Color32::from_rgb(248, 249, 250),
+                toolbar: egui::Color32::from_rgb(248, 249, 250),
+                tab_bar: egui::Color32::from_rgb(241, 243, 244),
+                content: egui::Color32::WHITE,
+                border: egui::Color32::from_rgb(218, 220, 224),
+                widget: egui::Color32::WHITE,
+                widget_hovered: egui::Color32::from_rgb(241, 243, 244),
+                text: egui::Color32::from_rgb(60, 64, 67),
+                text_hovered: egui::Color32::from_rgb(32, 33, 36),
+                extreme: egui::Color32::from_rgb(248, 249, 250),
+                accent: ACCENT,
+                accent_border: ACCENT_BORDER,
+                selection: egui::Color32::from_rgba_unmultiplied(26, 115, 232, 40),
+                shadow: egui::Color32::from_black_alpha(20),
+                status: STATUS_COLORS,
+            },
+            // At least 4.5:1 against black (WCAG AA), like the log level colors
+            Theme::HighContrast => Self {
+                panel: egui::Color32::BLACK,
+                toolbar: egui::Color32::BLACK,
+                tab_bar: egui::Color32::BLACK,
+                content: egui::Color32::BLACK,
+                border: egui::Color32::WHITE,
+                widget: egui::Color32::BLACK,
+                widget_hovered: egui::Color32::BLACK,
+                text: egui::Color32::WHITE,
+                text_hovered: egui::Color32::from_rgb(255, 255, 0),
+                extreme: egui::Color32::BLACK,
+                accent: egui::Color32::from_rgb(0, 70, 180), // White text stays readable on it
+                accent_border: egui::Color32::from_rgb(255, 255, 0),
+                selection: egui::Color32::from_rgb(0, 70, 180),
+                shadow: egui::Color32::TRANSPARENT,
+                status: StatusColors {
+                    ready: egui::Color32::from_rgb(0, 255, 0),
+                    connecting: egui::Color32::from_rgb(255, 255, 0),
+                    extracting: egui::Color32::from_rgb(0, 255, 255),
+                    processing: egui::Color32::from_rgb(255, 128, 255),
+                    error: egui::Color32::from_rgb(255, 102, 102),
+                },
+            },
+        }
+    }
+
+    /// egui visuals of the dark and light themes
+    fn visuals(&self, dark_mode: bool) -> egui::Visuals {
+        let mut v = if dark_mode { egui::Visuals::dark() } else { egui::Visuals::light() };
+
+        v.widgets.inactive.bg_fill = self.widget;
+        v.widgets.hovered.bg_fill = self.widget_hovered;
+        v.widgets.active.bg_fill = self.accent;
+        v.widgets.inactive.fg_stroke = egui::Stroke::new(1.0, self.text);
+        v.widgets.hovered.fg_stroke = egui::Stroke::new(1.0, self.text_hovered);
+
+        v.panel_fill = self.content;
+        v.window_fill = self.toolbar;
+        v.extreme_bg_color = self.extreme;
 
-    ctx.set_style(style);
+        v.selection.bg_fill = self.selection;
+        v.selection.stroke = egui::Stroke::new(1.0, self.accent);
+
+        v
+    }
 }
 
-fn apply_light_theme(ctx: &egui::Context) {
+/// Set the visuals and spacing of `theme` and return its palette. Rebuilding
+/// the style is not free, so the app only calls this when the theme changed.
+pub fn apply_theme(ctx: &egui::Context, theme: &Theme) -> ThemePalette {
+    set_high_contrast(*theme == Theme::HighContrast);
+    let palette = ThemePalette::for_theme(theme);
+
+    // Fonts and scale come along from the style in use
     let mut style = (*ctx.style()).clone();
+    match theme {
+        Theme::Dark | Theme::Light => {
+            style.visuals = palette.visuals(*theme == Theme::Dark);
+            style.spacing.item_spacing = egui::vec2(8.0, 6.0);
+            style.spacing.button_padding = egui::vec2(8.0, 4.0);
+        }
+        Theme::HighContrast => {
+            style.visuals = high_contrast_visuals();
+            // Slightly larger so thick borders don't crowd the content
+            style.spacing.item_spacing = egui::vec2(10.0, 8.0);
+            style.spacing.button_padding = egui::vec2(10.0, 6.0);
+        }
+    }
+    style.spacing.indent = 20.0;
 
-    // Light color scheme
-    style.visuals.dark_mode = false;
-    style.visuals.override_text_color = Some(egui::Color32::from_rgb(40, 40, 40));
+    // Pinned, so a change of the system theme doesn't switch to egui's other style
+    let egui_theme = if *theme == Theme::Light { egui::Theme::Light } else { egui::Theme::Dark };
+    ctx.set_theme(egui_theme);
+    ctx.set_style_of(egui_theme, style);
 
-    // Window and panel backgrounds
-    style.visuals.window_fill = egui::Color32::from_rgb(248, 248, 248);
-    style.visuals.panel_fill = egui::Color32::from_rgb(255, 255, 255);
-    style.visuals.faint_bg_color = egui::Color32::from_rgb(240, 240, 240);
+    palette
+}
 
-    // Button styling
-    style.visuals.widgets.inactive.weak_bg_fill = egui::Color32::from_rgb(230, 230, 230);
-    style.visuals.widgets.inactive.bg_fill = egui::Color32::from_rgb(220, 220, 220);
-    style.visuals.widgets.hovered.bg_fill = egui::Color32::from_rgb(210, 210, 210);
-    style.visuals.widgets.active.bg_fill = egui::Color32::from_rgb(200, 200, 200);
+/// Pure white on pure black with thick borders on every interactive element
+fn high_contrast_visuals() -> egui::Visuals {
+    let mut v = egui::Visuals::dark();
 
-    // Selection color
-    style.visuals.selection.bg_fill = egui::Color32::from_rgb(64, 128, 255);
+    let white = egui::Color32::WHITE;
+    let black = egui::Color32::BLACK;
+    let yellow = egui::Color32::from_rgb(255, 255, 0);
 
-    // Spacing
-    style.spacing.item_spacing = egui::vec2(8.0, 6.0);
-    style.spacing.button_padding = egui::vec2(8.0, 4.0);
-    style.spacing.indent = 20.0;
+    v.override_text_color = Some(white);
+    v.panel_fill = black;
+    v.window_fill = black;
+    v.extreme_bg_color = black;
+    v.faint_bg_color = egui::Color32::from_rgb(40, 40, 40); // Striped rows only
+    v.code_bg_color = black;
+    v.window_stroke = egui::Stroke::new(2.0, white);
+    v.hyperlink_color = egui::Color32::from_rgb(0, 255, 255);
+    v.warn_fg_color = yellow;
+    v.error_fg_color = egui::Color32::from_rgb(255, 102, 102);
+
+    // Borders and text on every widget state, hover and focus in yellow
+    let widgets = &mut v.widgets;
+    for (state, stroke_color, width) in [
+        (&mut widgets.noninteractive, white, 1.0),
+        (&mut widgets.inactive, white, 2.0),
+        (&mut widgets.hovered, yellow, 3.0),
+        (&mut widgets.active, yellow, 3.0),
+        (&mut widgets.open, yellow, 2.0),
+    ] {
+        state.bg_fill = black;
+        state.weak_bg_fill = black;
+        state.bg_stroke = egui::Stroke::new(width, stroke_color);
+        state.fg_stroke = egui::Stroke::new(width.min(2.0), stroke_color);
+    }
+    widgets.noninteractive.fg_stroke = egui::Stroke::new(1.0, white);
 
-    ctx.set_style(style);
-}
\ No newline at end of file
+    v.selection.bg_fill = egui::Color32::from_rgb(0, 70, 180);
+    v.selection.stroke = egui::Stroke::new(2.0, yellow);
+
+    v
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_visuals_follow_the_palette() {
+        for theme in [Theme::Dark, Theme::Light] {
+            let palette = ThemePalette::for_theme(&theme);
+            let visuals = palette.visuals(theme == Theme::Dark);
+            assert_eq!(visuals.panel_fill, palette.content);
+            assert_eq!(visuals.widgets.inactive.bg_fill, palette.widget);
+            assert_eq!(visuals.widgets.active.bg_fill, palette.accent);
+        }
+
+        // Tabs and selection share the accent the high-contrast visuals use
+        let palette = ThemePalette::for_theme(&Theme::HighContrast);
+        assert_eq!(high_contrast_visuals().selection.bg_fill, palette.accent);
+    }
+
+    #[test]
+    fn test_apply_theme_pins_the_egui_theme() {
+        let ctx = egui::Context::default();
+        crate::ui::fonts::apply_font_sizes(&ctx, 18.0);
+
+        for theme in [Theme::Light, Theme::Dark, Theme::HighContrast] {
+            apply_theme(&ctx, &theme);
+            assert_eq!(ctx.options(|options| options.theme_preference), egui::ThemePreference::from(ctx.theme()));
+            assert_eq!(ctx.theme() == egui::Theme::Light, theme == Theme::Light);
+            assert_eq!(ctx.style().visuals.dark_mode, theme != Theme::Light);
+            assert_eq!(ctx.style().text_styles[&egui::TextStyle::Body].size, 18.0);
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use crate::ui::app::LogLevel;
+use eframe::egui;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// How long a toast stays on screen
+const DEFAULT_DURATION_MS: u64 = 3000;
+
+/// Older toasts are dropped once more than this many are queued
+const MAX_TOASTS: usize = 5;
+
+/// Brief acknowledgement shown in the bottom-right corner, e.g. after a copy or save
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub message: String,
+    pub level: LogLevel,
+    pub created_at: Instant,
+    pub duration_ms: u64,
+}
+
+impl Toast {
+    pub fn new(message: impl Into<String>, level: LogLevel) -> Self {
+        Self {
+            message: message.into(),
+            level,
+            created_at: Instant::now(),
+            duration_ms: DEFAULT_DURATION_MS,
+        }
+    }
+
+    pub fn info(message: impl Into<String>) -> Self {
+        Self::new(message, LogLevel::Info)
+    }
+
+    pub fn success(message: impl Into<String>) -> Self {
+        Self::new(message, LogLevel::Success)
+    }
+
+    fn remaining(&self, now: Instant) -> Duration {
+        Duration::from_millis(self.duration_ms).saturating_sub(now.saturating_duration_since(self.created_at))
+    }
+
+    /// Opacity fading from 1 to 0 over the toast's lifetime
+    fn alpha(&self, now: Instant) -> f32 {
+        if self.duration_ms == 0 {
+            return 0.0;
+        }
+        (self.remaining(now).as_secs_f32() / Duration::from_millis(self.duration_ms).as_secs_f32()).clamp(0.0, 1.0)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ToastQueue {
+    toasts: VecDeque<Toast>,
+}
+
+impl ToastQueue {
+    pub fn push(&mut self, toast: Toast) {
+        self.toasts.push_back(toast);
+        while self.toasts.len() > MAX_TOASTS {
+            self.toasts.pop_front();
+        }
+    }
+
+    /// Drop the toasts whose time is up
+    fn prune(&mut self, now: Instant) {
+        self.toasts.retain(|toast| !toast.remaining(now).is_zero());
+    }
+
+    /// Draw the queue as a floating column above the status bar, newest at the bottom
+    pub fn render(&mut self, ctx: &egui::Context) {
+        let now = Instant::now();
+        self.prune(now);
+
+        if self.toasts.is_empty() {
+            return;
+        }
+
+        egui::Area::new(egui::Id::new("toast_queue"))
+            .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-16.0, -40.0))
+            .order(egui::Order::Foreground)
+            .interactable(false)
+            .show(ctx, |ui| {
+                for toast in &self.toasts {
+                    ui.scope(|ui| {
+                        ui.set_opacity(toast.alpha(now));
+                        egui::Frame::popup(ui.style()).show(ui, |ui| {
+                            ui.colored_label(toast.level.color(), format!("{} {}", toast.level.icon(), toast.message));
+                        });
+                    });
+                    ui.add_space(4.0);
+                }
+            });
+
+        // Keep fading while toasts are visible
+        ctx.request_repaint_after(Duration::from_millis(16));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toasts_fade_and_expire() {
+        let mut queue = ToastQueue::default();
+        let toast = Toast::info("Copied");
+        let start = toast.created_at;
+        queue.push(toast);
+        queue.push(Toast { duration_ms: 500, ..Toast::success("Saved") });
+
+        let halfway = start + Duration::from_millis(1500);
+        assert!((queue.toasts[0].alpha(halfway) - 0.5).abs() < 0.01);
+
+        queue.prune(halfway);
+        assert_eq!(queue.toasts.len(), 1);
+        assert_eq!(queue.toasts[0].message, "Copied");
+
+        queue.prune(start + Duration::from_millis(DEFAULT_DURATION_MS));
+        assert!(queue.toasts.is_empty());
+    }
+
+    #[test]
+    fn test_queue_keeps_newest_toasts() {
+        let mut queue = ToastQueue::default();
+        for i in 0..MAX_TOASTS + 2 {
+            queue.push(Toast::info(i.to_string()));
+        }
+
+        assert_eq!(queue.toasts.len(), MAX_TOASTS);
+        assert_eq!(queue.toasts[0].message, "2");
+    }
+}
\ No newline at end of file
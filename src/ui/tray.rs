@@ -0,0 +1,337 @@
+// Only Windows and macOS have the native tray, the icon drawing is built everywhere
+#![cfg_attr(not(any(windows, target_os = "macos")), allow(dead_code))]
+
+use crate::t;
+use crate::ui::app::AppStatus;
+use anyhow::Result;
+use eframe::egui;
+use image::{Rgba, RgbaImage};
+use std::time::Duration;
+#[cfg(any(windows, target_os = "macos"))]
+use image::imageops::FilterType;
+#[cfg(any(windows, target_os = "macos"))]
+use std::{sync::mpsc, time::Instant};
+#[cfg(any(windows, target_os = "macos"))]
+use tray_icon::menu::{Menu, MenuEvent, MenuItem, PredefinedMenuItem};
+#[cfg(any(windows, target_os = "macos"))]
+use tray_icon::{Icon, TrayIcon, TrayIconBuilder, TrayIconEvent};
+
+/// The system has a tray `Tray` can put its icon in
+pub const SUPPORTED: bool = cfg!(any(windows, target_os = "macos"));
+
+/// Edge length of the rendered tray icon in pixels
+const ICON_SIZE: u32 = 32;
+
+/// The extraction spinner turns one dot every frame
+const SPINNER_DOTS: usize = 8;
+const SPINNER_FRAME: Duration = Duration::from_millis(125);
+
+const SUCCESS_COLOR: Rgba<u8> = Rgba([76, 175, 80, 255]);
+const ERROR_COLOR: Rgba<u8> = Rgba([244, 67, 54, 255]);
+const BADGE_COLOR: Rgba<u8> = Rgba([32, 33, 36, 255]);
+const MARK_COLOR: Rgba<u8> = Rgba([255, 255, 255, 255]);
+
+/// Actions picked from the tray menu or by double-clicking the icon
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TrayCommand {
+    Open,
+    StartExtraction,
+    CancelExtraction,
+    ShowLastResult,
+    Quit,
+}
+
+/// What the tray icon shows
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TrayState {
+    Idle,
+    Extracting,
+    Succeeded,
+    Failed,
+}
+
+impl TrayState {
+    pub fn from_status(status: &AppStatus) -> Self {
+        match status {
+            AppStatus::Ready => Self::Idle,
+            AppStatus::Connecting | AppStatus::Extracting | AppStatus::Processing => Self::Extracting,
+            AppStatus::Completed => Self::Succeeded,
+            AppStatus::Error(_) => Self::Failed,
+        }
+    }
+
+    fn tooltip(&self) -> String {
+        match self {
+            Self::Idle => "EPLAN eVIEW Extractor".to_string(),
+            Self::Extracting => t!("EPLAN eVIEW Extractor - extracting…"),
+            Self::Succeeded => t!("EPLAN eVIEW Extractor - extraction complete"),
+            Self::Failed => t!("EPLAN eVIEW Extractor - extraction failed"),
+        }
+    }
+}
+
+/// First line of the tray menu, e.g. "Extracting – 42%"
+fn status_text(status: &AppStatus, progress: f32) -> String {
+    let label = match status {
+        AppStatus::Ready => "Ready",
+        AppStatus::Connecting => "Connecting",
+        AppStatus::Extracting => "Extracting",
+        AppStatus::Processing => "Processing",
+        AppStatus::Completed => "Completed",
+        AppStatus::Error(_) => "Error",
+    };
+
+    match TrayState::from_status(status) {
+        TrayState::Extracting => format!("{} – {:.0}%", t!(label), progress.clamp(0.0, 1.0) * 100.0),
+        _ => t!(label),
+    }
+}
+
+/// System tray icon with a status badge and a small menu. Menu clicks arrive
+/// from the tray's own event handler and are picked up by `commands`.
+#[cfg(any(windows, target_os = "macos"))]
+pub struct Tray {
+    icon: TrayIcon,
+    base: RgbaImage, // App icon at tray size
+    shown: (TrayState, usize), // State and spinner frame currently set on the icon
+    status_item: MenuItem, // Disabled, shows `status_text`
+    shown_status: String,
+    cancel_item: MenuItem, // Enabled while an extraction runs
+    started: Instant,
+    commands: mpsc::Receiver<TrayCommand>,
+}
+
+#[cfg(any(windows, target_os = "macos"))]
+impl Tray {
+    pub fn new(ctx: &egui::Context) -> Result<Self> {
+        let base = image::load_from_memory(include_bytes!("../../assets/icon.png"))?
+            .resize_exact(ICON_SIZE, ICON_SIZE, FilterType::Lanczos3)
+            .to_rgba8();
+
+        let shown_status = status_text(&AppStatus::Ready, 0.0);
+        let status_item = MenuItem::new(&shown_status, false, None);
+        let open = MenuItem::new(t!("Show window"), true, None);
+        let start = MenuItem::new(t!("Start Extraction"), true, None);
+        let cancel_item = MenuItem::new(t!("Cancel extraction"), false, None);
+        let last_result = MenuItem::new(t!("Show Last Result"), true, None);
+        let quit = MenuItem::new(t!("Quit"), true, None);
+
+        let menu = Menu::new();
+        menu.append_items(&[
+            &status_item,
+            &PredefinedMenuItem::separator(),
+            &open,
+            &start,
+            &cancel_item,
+            &last_result,
+            &PredefinedMenuItem::separator(),
+            &quit,
+        ])?;
+
+        let items = [
+            (open.id().clone(), TrayCommand::Open),
+            (start.id().clone(), TrayCommand::StartExtraction),
+            (cancel_item.id().clone(), TrayCommand::CancelExtraction),
+            (last_result.id().clone(), TrayCommand::ShowLastResult),
+            (quit.id().clone(), TrayCommand::Quit),
+        ];
+
+        // The handlers also run while the window is hidden, so they wake the UI
+        let (sender, commands) = mpsc::channel();
+        let menu_sender = sender.clone();
+        let menu_ctx = ctx.clone();
+        MenuEvent::set_event_handler(Some(move |event: MenuEvent| {
+            if let Some((_, command)) = items.iter().find(|(id, _)| *id == event.id) {
+                let _ = menu_sender.send(*command);
+                menu_ctx.request_repaint();
+            }
+        }));
+
+        let click_ctx = ctx.clone();
+        TrayIconEvent::set_event_handler(Some(move |event: TrayIconEvent| {
+            if matches!(event, TrayIconEvent::DoubleClick { .. }) {
+                let _ = sender.send(TrayCommand::Open);
+                click_ctx.request_repaint();
+            }
+        }));
+
+        let icon = TrayIconBuilder::new()
+            .with_menu(Box::new(menu))
+            .with_tooltip(TrayState::Idle.tooltip())
+            .with_icon(to_icon(base.clone())?)
+            .build()?;
+
+        Ok(Self {
+            icon,
+            base,
+            shown: (TrayState::Idle, 0),
+            status_item,
+            shown_status,
+            cancel_item,
+            started: Instant::now(),
+            commands,
+        })
+    }
+
+    /// Commands picked since the last frame
+    pub fn commands(&self) -> Vec<TrayCommand> {
+        self.commands.try_iter().collect()
+    }
+
+    /// Show `status` and the `progress` (0.0 to 1.0) on the icon and in the
+    /// menu, turning the spinner while an extraction runs
+    pub fn update(&mut self, ctx: &egui::Context, status: &AppStatus, progress: f32) {
+        let state = TrayState::from_status(status);
+
+        let text = status_text(status, progress);
+        if self.shown_status != text {
+            self.status_item.set_text(&text);
+            self.shown_status = text;
+        }
+
+        let frame = match state {
+            TrayState::Extracting => {
+                ctx.request_repaint_after(SPINNER_FRAME);
+                (self.started.elapsed().as_millis() / SPINNER_FRAME.as_millis()) as usize % SPINNER_DOTS
+            }
+            _ => 0,
+        };
+
+        if self.shown == (state, frame) {
+            return;
+        }
+
+        if self.shown.0 != state {
+            let _ = self.icon.set_tooltip(Some(state.tooltip()));
+            self.cancel_item.set_enabled(state == TrayState::Extracting);
+        }
+        if let Ok(icon) = to_icon(render_icon(&self.base, state, frame)) {
+            let _ = self.icon.set_icon(Some(icon));
+        }
+        self.shown = (state, frame);
+    }
+}
+
+/// Without a tray `new` always fails, so the app never holds one
+#[cfg(not(any(windows, target_os = "macos")))]
+pub struct Tray;
+
+#[cfg(not(any(windows, target_os = "macos")))]
+impl Tray {
+    pub fn new(_ctx: &egui::Context) -> Result<Self> {
+        anyhow::bail!("the system tray is only supported on Windows and macOS")
+    }
+
+    pub fn commands(&self) -> Vec<TrayCommand> {
+        Vec::new()
+    }
+
+    pub fn update(&mut self, _ctx: &egui::Context, _status: &AppStatus, _progress: f32) {}
+}
+
+#[cfg(any(windows, target_os = "macos"))]
+fn to_icon(image: RgbaImage) -> Result<Icon> {
+    let (width, height) = image.dimensions();
+    Ok(Icon::from_rgba(image.into_raw(), width, height)?)
+}
+
+/// App icon with a badge in the lower right corner: spinner dots while
+/// extracting, a check mark on success and a cross on failure
+fn render_icon(base: &RgbaImage, state: TrayState, frame: usize) -> RgbaImage {
+    let mut image = base.clone();
+    let size = image.width() as f32;
+    let center = (size * 0.7, size * 0.7);
+    let radius = size * 0.3;
+
+    match state {
+        TrayState::Idle => {}
+        TrayState::Extracting => {
+            fill_circle(&mut image, center, radius, BADGE_COLOR);
+            for dot in 0..SPINNER_DOTS {
+                let angle = dot as f32 / SPINNER_DOTS as f32 * std::f32::consts::TAU;
+                let position = (center.0 + angle.sin() * radius * 0.65, center.1 - angle.cos() * radius * 0.65);
+                let color = if dot == frame { MARK_COLOR } else { Rgba([120, 120, 120, 255]) };
+                fill_circle(&mut image, position, radius * 0.18, color);
+            }
+        }
+        TrayState::Succeeded => {
+            fill_circle(&mut image, center, radius, SUCCESS_COLOR);
+            let width = radius * 0.25;
+            let (x, y) = center;
+            draw_line(&mut image, (x - radius * 0.5, y), (x - radius * 0.15, y + radius * 0.4), width, MARK_COLOR);
+            draw_line(&mut image, (x - radius * 0.15, y + radius * 0.4), (x + radius * 0.5, y - radius * 0.4), width, MARK_COLOR);
+        }
+        TrayState::Failed => {
+            fill_circle(&mut image, center, radius, ERROR_COLOR);
+            let width = radius * 0.25;
+            let arm = radius * 0.45;
+            let (x, y) = center;
+            draw_line(&mut image, (x - arm, y - arm), (x + arm, y + arm), width, MARK_COLOR);
+            draw_line(&mut image, (x - arm, y + arm), (x + arm, y - arm), width, MARK_COLOR);
+        }
+    }
+
+    image
+}
+
+fn fill_circle(image: &mut RgbaImage, center: (f32, f32), radius: f32, color: Rgba<u8>) {
+    paint(image, color, |x, y| (x - center.0).hypot(y - center.1) <= radius);
+}
+
+fn draw_line(image: &mut RgbaImage, from: (f32, f32), to: (f32, f32), width: f32, color: Rgba<u8>) {
+    let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+    let length_squared = dx * dx + dy * dy;
+
+    paint(image, color, |x, y| {
+        // Distance from the pixel to the closest point on the segment
+        let t = (((x - from.0) * dx + (y - from.1) * dy) / length_squared).clamp(0.0, 1.0);
+        (x - (from.0 + t * dx)).hypot(y - (from.1 + t * dy)) <= width / 2.0
+    });
+}
+
+/// Set every pixel whose center passes `inside`
+fn paint(image: &mut RgbaImage, color: Rgba<u8>, inside: impl Fn(f32, f32) -> bool) {
+    for (x, y, pixel) in image.enumerate_pixels_mut() {
+        if inside(x as f32 + 0.5, y as f32 + 0.5) {
+            *pixel = color;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_state_from_status() {
+        assert_eq!(TrayState::from_status(&AppStatus::Ready), TrayState::Idle);
+        assert_eq!(TrayState::from_status(&AppStatus::Connecting), TrayState::Extracting);
+        assert_eq!(TrayState::from_status(&AppStatus::Processing), TrayState::Extracting);
+        assert_eq!(TrayState::from_status(&AppStatus::Completed), TrayState::Succeeded);
+        assert_eq!(TrayState::from_status(&AppStatus::Error("timeout".to_string())), TrayState::Failed);
+    }
+
+    #[test]
+    fn test_status_text() {
+        assert_eq!(status_text(&AppStatus::Extracting, 0.424), "Extracting – 42%");
+        assert_eq!(status_text(&AppStatus::Connecting, 1.5), "Connecting – 100%");
+        assert_eq!(status_text(&AppStatus::Completed, 1.0), "Completed");
+        assert_eq!(status_text(&AppStatus::Error("timeout".to_string()), 0.3), "Error");
+    }
+
+    #[test]
+    fn test_badges() {
+        let base = RgbaImage::from_pixel(ICON_SIZE, ICON_SIZE, Rgba([0, 0, 0, 0]));
+        assert_eq!(render_icon(&base, TrayState::Idle, 0), base);
+
+        // The badge edge takes the state color, the mark sits in its middle
+        let failed = render_icon(&base, TrayState::Failed, 0);
+        assert_eq!(*failed.get_pixel(31, 22), ERROR_COLOR);
+        assert_eq!(*failed.get_pixel(22, 22), MARK_COLOR);
+        assert_eq!(*failed.get_pixel(2, 2), Rgba([0, 0, 0, 0]));
+
+        let first = render_icon(&base, TrayState::Extracting, 0);
+        let second = render_icon(&base, TrayState::Extracting, 1);
+        assert_ne!(first, second);
+    }
+}
\ No newline at end of file